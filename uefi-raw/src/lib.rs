@@ -6,6 +6,13 @@
 //! For creating UEFI applications and drivers, consider using the [`uefi`]
 //! crate instead of `uefi-raw`.
 //!
+//! # Optional Cargo crate features
+//!
+//! - `core-net`: Conversions between the network address types and the
+//!   `core::net` types, `Display` and `FromStr` impls for the IP address
+//!   types, and `core::error::Error` impls. This requires Rust 1.81, newer
+//!   than the MSRV of this crate.
+//!
 //! [`uefi`]: https://crates.io/crates/uefi
 
 #![no_std]
@@ -32,7 +39,10 @@ pub mod time;
 mod status;
 
 use core::ffi::c_void;
-use core::fmt::{self, Debug, Formatter};
+use core::fmt::{self, Debug, Display, Formatter};
+#[cfg(feature = "core-net")]
+use core::net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
 pub use status::Status;
 pub use uguid::{guid, Guid};
 
@@ -70,11 +80,77 @@ pub type VirtualAddress = u64;
 #[repr(transparent)]
 pub struct Ipv4Address(pub [u8; 4]);
 
+#[cfg(feature = "core-net")]
+impl From<Ipv4Addr> for Ipv4Address {
+    fn from(addr: Ipv4Addr) -> Self {
+        Self(addr.octets())
+    }
+}
+
+#[cfg(feature = "core-net")]
+impl From<Ipv4Address> for Ipv4Addr {
+    fn from(addr: Ipv4Address) -> Self {
+        Self::from(addr.0)
+    }
+}
+
+/// Formats the address in dotted-decimal notation, e.g. `192.168.0.1`.
+#[cfg(feature = "core-net")]
+impl Display for Ipv4Address {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&Ipv4Addr::from(*self), f)
+    }
+}
+
+/// Parses an address in dotted-decimal notation, e.g. `192.168.0.1`.
+#[cfg(feature = "core-net")]
+impl FromStr for Ipv4Address {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ipv4Addr::from_str(s).map(Self::from)
+    }
+}
+
 /// An IPv6 internet protocol address.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(transparent)]
 pub struct Ipv6Address(pub [u8; 16]);
 
+#[cfg(feature = "core-net")]
+impl From<Ipv6Addr> for Ipv6Address {
+    fn from(addr: Ipv6Addr) -> Self {
+        Self(addr.octets())
+    }
+}
+
+#[cfg(feature = "core-net")]
+impl From<Ipv6Address> for Ipv6Addr {
+    fn from(addr: Ipv6Address) -> Self {
+        Self::from(addr.0)
+    }
+}
+
+/// Formats the address in the canonical text representation of RFC 5952,
+/// e.g. `fe80::1`.
+#[cfg(feature = "core-net")]
+impl Display for Ipv6Address {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&Ipv6Addr::from(*self), f)
+    }
+}
+
+/// Parses an address in any of the text representations of RFC 4291, e.g.
+/// `fe80::1`.
+#[cfg(feature = "core-net")]
+impl FromStr for Ipv6Address {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ipv6Addr::from_str(s).map(Self::from)
+    }
+}
+
 /// An IPv4 or IPv6 internet protocol address.
 ///
 /// Corresponds to the `EFI_IP_ADDRESS` type in the UEFI specification. This
@@ -129,7 +205,156 @@ impl Default for IpAddress {
     }
 }
 
+#[cfg(feature = "core-net")]
+impl From<IpAddr> for IpAddress {
+    fn from(addr: IpAddr) -> Self {
+        match addr {
+            IpAddr::V4(addr) => Self::new_v4(addr.octets()),
+            IpAddr::V6(addr) => Self::new_v6(addr.octets()),
+        }
+    }
+}
+
 /// A Media Access Control (MAC) address.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(transparent)]
 pub struct MacAddress(pub [u8; 32]);
+
+impl MacAddress {
+    /// Length of the common 48-bit MAC addresses, e.g. Ethernet addresses.
+    const ETHERNET_LEN: usize = 6;
+}
+
+impl From<[u8; 6]> for MacAddress {
+    fn from(addr: [u8; 6]) -> Self {
+        let mut mac = [0; 32];
+        mac[..6].copy_from_slice(&addr);
+        Self(mac)
+    }
+}
+
+/// Formats the address as colon-separated hexadecimal bytes, e.g.
+/// `52:54:00:12:34:56`.
+///
+/// The actual length of the address is not known, so only the first six
+/// bytes are formatted if all other bytes are zero, as is the case for
+/// Ethernet addresses. Otherwise all 32 bytes are formatted.
+impl Display for MacAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let len = if self.0[Self::ETHERNET_LEN..].iter().all(|b| *b == 0) {
+            Self::ETHERNET_LEN
+        } else {
+            self.0.len()
+        };
+        for (i, byte) in self.0[..len].iter().enumerate() {
+            if i != 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses colon-separated hexadecimal bytes, e.g. `52:54:00:12:34:56`.
+/// Between one and 32 bytes are accepted; the remaining bytes of the address
+/// are set to zero.
+impl FromStr for MacAddress {
+    type Err = MacAddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mac = [0; 32];
+        for (i, part) in s.split(':').enumerate() {
+            if i == mac.len() || part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(MacAddressParseError);
+            }
+            mac[i] = u8::from_str_radix(part, 16).map_err(|_| MacAddressParseError)?;
+        }
+        Ok(Self(mac))
+    }
+}
+
+/// Error returned when parsing a [`MacAddress`] fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MacAddressParseError;
+
+impl Display for MacAddressParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid MAC address syntax")
+    }
+}
+
+#[cfg(feature = "core-net")]
+impl core::error::Error for MacAddressParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::string::ToString;
+
+    #[test]
+    #[cfg(feature = "core-net")]
+    fn test_ipv4_address() {
+        let addr = Ipv4Address([192, 168, 0, 1]);
+        assert_eq!(Ipv4Addr::from(addr), Ipv4Addr::new(192, 168, 0, 1));
+        assert_eq!(Ipv4Address::from(Ipv4Addr::new(192, 168, 0, 1)), addr);
+
+        assert_eq!(addr.to_string(), "192.168.0.1");
+        assert_eq!("192.168.0.1".parse::<Ipv4Address>(), Ok(addr));
+        assert!("192.168.0".parse::<Ipv4Address>().is_err());
+        assert!("192.168.0.256".parse::<Ipv4Address>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "core-net")]
+    fn test_ipv6_address() {
+        let addr = Ipv6Address([0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        let core_addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+        assert_eq!(Ipv6Addr::from(addr), core_addr);
+        assert_eq!(Ipv6Address::from(core_addr), addr);
+
+        assert_eq!(addr.to_string(), "fe80::1");
+        assert_eq!("fe80::1".parse::<Ipv6Address>(), Ok(addr));
+        assert_eq!("fe80:0:0:0:0:0:0:1".parse::<Ipv6Address>(), Ok(addr));
+        assert!("fe80::1::1".parse::<Ipv6Address>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "core-net")]
+    fn test_ip_address() {
+        let addr = IpAddress::from(IpAddr::V4(Ipv4Addr::new(10, 0, 2, 15)));
+        assert_eq!(unsafe { addr.v4 }, Ipv4Address([10, 0, 2, 15]));
+
+        let addr = IpAddress::from(IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(unsafe { addr.v6 }, Ipv6Address::from(Ipv6Addr::LOCALHOST));
+    }
+
+    #[test]
+    fn test_mac_address() {
+        let addr = MacAddress::from([0x52, 0x54, 0x00, 0x12, 0x34, 0xab]);
+        assert_eq!(addr.to_string(), "52:54:00:12:34:ab");
+        assert_eq!("52:54:00:12:34:ab".parse::<MacAddress>(), Ok(addr));
+        assert_eq!("52:54:00:12:34:AB".parse::<MacAddress>(), Ok(addr));
+
+        let mut long = MacAddress([0; 32]);
+        long.0[31] = 0xff;
+        let s = long.to_string();
+        assert_eq!(s.len(), 32 * 3 - 1);
+        assert!(s.ends_with(":00:ff"));
+        assert_eq!(s.parse::<MacAddress>(), Ok(long));
+
+        for invalid in [
+            "",
+            "52:54:00:12:34:5",
+            "52-54-00-12-34-56",
+            "+1:00",
+            "52::54",
+        ] {
+            assert_eq!(invalid.parse::<MacAddress>(), Err(MacAddressParseError));
+        }
+        let too_long = s + ":00";
+        assert_eq!(too_long.parse::<MacAddress>(), Err(MacAddressParseError));
+    }
+}
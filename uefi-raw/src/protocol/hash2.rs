@@ -0,0 +1,76 @@
+//! `Hash2` protocol.
+
+use crate::{guid, Guid, Status};
+
+newtype_enum! {
+    /// Hash algorithms that may be supported by [`Hash2Protocol`].
+    pub enum HashAlgorithm: Guid => {
+        /// MD5. Not recommended for new code.
+        MD5 = guid!("0af7c79c-65b5-4319-b0ae-44ec484e4ad7"),
+
+        /// SHA-1. Not recommended for new code.
+        SHA1 = guid!("2ae9d80f-3fb2-4095-b7b1-e93157b946b6"),
+
+        /// SHA-224.
+        SHA224 = guid!("8df01a06-9bd5-4bf7-b021-db4fd9ccf45b"),
+
+        /// SHA-256.
+        SHA256 = guid!("51aa59de-fdf2-4ea3-bc63-875fb7842ee9"),
+
+        /// SHA-384.
+        SHA384 = guid!("efa96432-de33-4dd2-aee6-328c33df777a"),
+
+        /// SHA-512.
+        SHA512 = guid!("caa4381e-750c-4770-b870-7a23b4e42130"),
+    }
+}
+
+/// Output buffer for a hash computed by [`Hash2Protocol`].
+///
+/// Corresponds to the `EFI_HASH2_OUTPUT` union in the C API. The union's
+/// largest member is the 64-byte SHA-512 hash; smaller hashes are written
+/// to the start of the buffer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct Hash2Output(pub [u8; 64]);
+
+impl Default for Hash2Output {
+    fn default() -> Self {
+        Self([0; 64])
+    }
+}
+
+/// Hash2 protocol.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Hash2Protocol {
+    pub get_hash_size: unsafe extern "efiapi" fn(
+        this: *const Self,
+        hash_algorithm: *const HashAlgorithm,
+        hash_size: *mut usize,
+    ) -> Status,
+
+    pub hash: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        hash_algorithm: *const HashAlgorithm,
+        message: *const u8,
+        message_size: usize,
+        hash: *mut Hash2Output,
+    ) -> Status,
+
+    pub hash_init:
+        unsafe extern "efiapi" fn(this: *mut Self, hash_algorithm: *const HashAlgorithm) -> Status,
+
+    pub hash_update: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        message: *const u8,
+        message_size: usize,
+    ) -> Status,
+
+    pub hash_final: unsafe extern "efiapi" fn(this: *mut Self, hash: *mut Hash2Output) -> Status,
+}
+
+impl Hash2Protocol {
+    pub const GUID: Guid = guid!("55b1d734-c5e1-49db-9647-b16afb0e305b");
+    pub const SERVICE_BINDING_GUID: Guid = guid!("da836f8d-217f-4ca0-99c2-1ca4e16077ea");
+}
@@ -1,3 +1,4 @@
+use crate::protocol::device_path::DevicePathProtocol;
 use crate::table::runtime;
 use crate::{guid, Guid, Status};
 
@@ -45,3 +46,31 @@ pub type ResetSystemFn = unsafe extern "efiapi" fn(
     data_size: usize,
     data: *const u8,
 );
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct BootManagerPolicyProtocol {
+    pub revision: u64,
+    pub connect_device_path: unsafe extern "efiapi" fn(
+        this: *const Self,
+        device_path: *const DevicePathProtocol,
+        recursive: bool,
+    ) -> Status,
+    pub connect_device_class:
+        unsafe extern "efiapi" fn(this: *const Self, class: *const Guid) -> Status,
+}
+
+impl BootManagerPolicyProtocol {
+    pub const GUID: Guid = guid!("fedf8e0c-e147-11e3-9903-b8e8562cbafa");
+
+    pub const REVISION: u64 = 0x00010000;
+
+    /// Connect all console devices.
+    pub const CONSOLE_GUID: Guid = guid!("cab0e94c-e15f-11e3-918d-b8e8562cbafa");
+
+    /// Connect all network devices.
+    pub const NETWORK_GUID: Guid = guid!("d04159dc-e15f-11e3-b261-b8e8562cbafa");
+
+    /// Connect all devices.
+    pub const CONNECT_ALL_GUID: Guid = guid!("113b2126-fc8a-11e3-bd6c-b8e8562cbafa");
+}
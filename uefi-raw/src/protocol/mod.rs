@@ -11,12 +11,15 @@ pub mod disk;
 pub mod driver;
 pub mod file_system;
 pub mod firmware_volume;
+pub mod hash2;
 pub mod loaded_image;
 pub mod media;
 pub mod memory_protection;
 pub mod misc;
 pub mod network;
 pub mod rng;
+pub mod security;
 pub mod shell_params;
 pub mod string;
 pub mod tcg;
+pub mod usb;
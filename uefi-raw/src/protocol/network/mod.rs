@@ -2,4 +2,7 @@ pub mod dhcp4;
 pub mod http;
 pub mod ip4;
 pub mod ip4_config2;
+pub mod mnp;
+pub mod tcp4;
 pub mod tls;
+pub mod udp4;
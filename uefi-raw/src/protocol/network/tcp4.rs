@@ -0,0 +1,191 @@
+use crate::{guid, Event, Guid, Handle, Ipv4Address, Status};
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Formatter};
+
+newtype_enum! {
+    pub enum Tcp4ConnectionState: i32 => {
+        CLOSED       = 0,
+        LISTEN       = 1,
+        SYN_SENT     = 2,
+        SYN_RECEIVED = 3,
+        ESTABLISHED  = 4,
+        FIN_WAIT1    = 5,
+        FIN_WAIT2    = 6,
+        CLOSING      = 7,
+        TIME_WAIT    = 8,
+        CLOSE_WAIT   = 9,
+        LAST_ACK     = 10,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(C)]
+pub struct Tcp4AccessPoint {
+    pub use_default_address: bool,
+    pub station_address: Ipv4Address,
+    pub subnet_mask: Ipv4Address,
+    pub station_port: u16,
+    pub remote_address: Ipv4Address,
+    pub remote_port: u16,
+    pub active_flag: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(C)]
+pub struct Tcp4Option {
+    pub receive_buffer_size: u32,
+    pub send_buffer_size: u32,
+    pub max_syn_back_log: u32,
+    pub connection_timeout: u32,
+    pub data_retries: u32,
+    pub fin_timeout: u32,
+    pub time_wait_timeout: u32,
+    pub keep_alive_probes: u32,
+    pub keep_alive_time: u32,
+    pub keep_alive_interval: u32,
+    pub enable_nagle: bool,
+    pub enable_time_stamp: bool,
+    pub enable_window_scaling: bool,
+    pub enable_selective_ack: bool,
+    pub enable_path_mtu_discovery: bool,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4ConfigData {
+    pub type_of_service: u8,
+    pub time_to_live: u8,
+    pub access_point: Tcp4AccessPoint,
+    pub control_option: *mut Tcp4Option,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4CompletionToken {
+    pub event: Event,
+    pub status: Status,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4ConnectionToken {
+    pub completion_token: Tcp4CompletionToken,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4ListenToken {
+    pub completion_token: Tcp4CompletionToken,
+    pub new_child_handle: Handle,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4CloseToken {
+    pub completion_token: Tcp4CompletionToken,
+    pub abort_on_close: bool,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4IoToken {
+    pub completion_token: Tcp4CompletionToken,
+    pub packet: Tcp4Packet,
+}
+
+#[repr(C)]
+pub union Tcp4Packet {
+    pub rx_data: *mut Tcp4ReceiveData,
+    pub tx_data: *mut Tcp4TransmitData,
+}
+
+impl Debug for Tcp4Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // This is a union type, so we can't access the internal data.
+        f.debug_struct("Tcp4Packet").finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct Tcp4FragmentData {
+    pub fragment_length: u32,
+    pub fragment_buffer: *mut c_void,
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4ReceiveData {
+    pub urgent_flag: bool,
+    pub data_length: u32,
+    pub fragment_count: u32,
+
+    /// Start of the fragment table.
+    ///
+    /// Note that this field is actually a variable-length array with
+    /// `fragment_count` entries.
+    pub fragment_table: [Tcp4FragmentData; 1],
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4TransmitData {
+    pub push: bool,
+    pub urgent: bool,
+    pub data_length: u32,
+    pub fragment_count: u32,
+
+    /// Start of the fragment table.
+    ///
+    /// Note that this field is actually a variable-length array with
+    /// `fragment_count` entries.
+    pub fragment_table: [Tcp4FragmentData; 1],
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Tcp4Protocol {
+    /// Get the current operational status.
+    ///
+    /// The `ip4_mode_data`, `mnp_config_data`, and `snp_mode_data` parameters
+    /// point to an `EFI_IP4_MODE_DATA`, `EFI_MANAGED_NETWORK_CONFIG_DATA`,
+    /// and `EFI_SIMPLE_NETWORK_MODE` respectively. Any of the output
+    /// parameters may be null.
+    pub get_mode_data: unsafe extern "efiapi" fn(
+        this: *const Self,
+        tcp4_state: *mut Tcp4ConnectionState,
+        tcp4_config_data: *mut Tcp4ConfigData,
+        ip4_mode_data: *mut c_void,
+        mnp_config_data: *mut c_void,
+        snp_mode_data: *mut c_void,
+    ) -> Status,
+    pub configure: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        tcp_config_data: *const Tcp4ConfigData,
+    ) -> Status,
+    pub routes: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        delete_route: bool,
+        subnet_address: *const Ipv4Address,
+        subnet_mask: *const Ipv4Address,
+        gateway_address: *const Ipv4Address,
+    ) -> Status,
+    pub connect: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        connection_token: *mut Tcp4ConnectionToken,
+    ) -> Status,
+    pub accept:
+        unsafe extern "efiapi" fn(this: *mut Self, listen_token: *mut Tcp4ListenToken) -> Status,
+    pub transmit: unsafe extern "efiapi" fn(this: *mut Self, token: *mut Tcp4IoToken) -> Status,
+    pub receive: unsafe extern "efiapi" fn(this: *mut Self, token: *mut Tcp4IoToken) -> Status,
+    pub close:
+        unsafe extern "efiapi" fn(this: *mut Self, close_token: *mut Tcp4CloseToken) -> Status,
+    pub cancel:
+        unsafe extern "efiapi" fn(this: *mut Self, token: *mut Tcp4CompletionToken) -> Status,
+    pub poll: unsafe extern "efiapi" fn(this: *mut Self) -> Status,
+}
+
+impl Tcp4Protocol {
+    pub const GUID: Guid = guid!("65530bc7-a359-410f-b010-5aadc7ec2b62");
+    pub const SERVICE_BINDING_GUID: Guid = guid!("00720665-67eb-4a99-baf7-d3c33a1c7cc9");
+}
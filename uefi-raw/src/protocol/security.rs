@@ -0,0 +1,36 @@
+//! Security architectural protocols from the Platform Initialization
+//! specification.
+
+use crate::protocol::device_path::DevicePathProtocol;
+use crate::{guid, Guid, Status};
+use core::ffi::c_void;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SecurityArchProtocol {
+    pub file_authentication_state: unsafe extern "efiapi" fn(
+        this: *const Self,
+        authentication_status: u32,
+        file: *const DevicePathProtocol,
+    ) -> Status,
+}
+
+impl SecurityArchProtocol {
+    pub const GUID: Guid = guid!("a46423e3-4617-49f1-b9ff-d1bfa9115839");
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Security2ArchProtocol {
+    pub file_authentication: unsafe extern "efiapi" fn(
+        this: *const Self,
+        device_path: *const DevicePathProtocol,
+        file_buffer: *mut c_void,
+        file_size: usize,
+        boot_policy: bool,
+    ) -> Status,
+}
+
+impl Security2ArchProtocol {
+    pub const GUID: Guid = guid!("94ab2f58-1438-4ef1-9152-18941a3a0e68");
+}
@@ -0,0 +1,217 @@
+//! USB I/O protocol and the USB types it uses.
+
+use crate::{guid, Char16, Guid, Status};
+use core::ffi::c_void;
+
+newtype_enum! {
+    /// Direction of the data stage of a USB control transfer.
+    pub enum UsbDataDirection: u32 => {
+        /// Data is transferred from the device to the host.
+        DATA_IN = 0,
+        /// Data is transferred from the host to the device.
+        DATA_OUT = 1,
+        /// The transfer has no data stage.
+        NO_DATA = 2,
+    }
+}
+
+bitflags::bitflags! {
+    /// USB-level result of a transfer, as reported by the host controller.
+    ///
+    /// An empty set means the transfer completed without errors.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct UsbTransferStatus: u32 {
+        const NOT_EXECUTE = 0x0001;
+        const STALL = 0x0002;
+        const BUFFER = 0x0004;
+        const BABBLE = 0x0008;
+        const NAK = 0x0010;
+        const CRC = 0x0020;
+        const TIMEOUT = 0x0040;
+        const BIT_STUFF = 0x0080;
+        const SYSTEM = 0x0100;
+    }
+}
+
+/// Setup packet of a USB control transfer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct DeviceRequest {
+    pub request_type: u8,
+    pub request: u8,
+    pub value: u16,
+    pub index: u16,
+    pub length: u16,
+}
+
+/// Standard USB device descriptor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C, packed)]
+pub struct DeviceDescriptor {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub bcd_usb: u16,
+    pub device_class: u8,
+    pub device_sub_class: u8,
+    pub device_protocol: u8,
+    pub max_packet_size0: u8,
+    pub id_vendor: u16,
+    pub id_product: u16,
+    pub bcd_device: u16,
+    pub str_manufacturer: u8,
+    pub str_product: u8,
+    pub str_serial_number: u8,
+    pub num_configurations: u8,
+}
+
+/// Standard USB configuration descriptor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C, packed)]
+pub struct ConfigDescriptor {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub total_length: u16,
+    pub num_interfaces: u8,
+    pub configuration_value: u8,
+    pub configuration: u8,
+    pub attributes: u8,
+    pub max_power: u8,
+}
+
+/// Standard USB interface descriptor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C, packed)]
+pub struct InterfaceDescriptor {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub interface_number: u8,
+    pub alternate_setting: u8,
+    pub num_endpoints: u8,
+    pub interface_class: u8,
+    pub interface_sub_class: u8,
+    pub interface_protocol: u8,
+    pub interface: u8,
+}
+
+/// Standard USB endpoint descriptor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C, packed)]
+pub struct EndpointDescriptor {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub endpoint_address: u8,
+    pub attributes: u8,
+    pub max_packet_size: u16,
+    pub interval: u8,
+}
+
+/// Callback for asynchronous interrupt and isochronous transfers.
+pub type AsyncUsbTransferCallback = unsafe extern "efiapi" fn(
+    data: *mut c_void,
+    data_length: usize,
+    context: *mut c_void,
+    status: UsbTransferStatus,
+) -> Status;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct UsbIoProtocol {
+    pub control_transfer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        request: *mut DeviceRequest,
+        direction: UsbDataDirection,
+        timeout: u32,
+        data: *mut c_void,
+        data_length: usize,
+        status: *mut UsbTransferStatus,
+    ) -> Status,
+    pub bulk_transfer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        device_endpoint: u8,
+        data: *mut c_void,
+        data_length: *mut usize,
+        timeout: usize,
+        status: *mut UsbTransferStatus,
+    ) -> Status,
+    pub async_interrupt_transfer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        device_endpoint: u8,
+        is_new_transfer: bool,
+        polling_interval: usize,
+        data_length: usize,
+        interrupt_callback: Option<AsyncUsbTransferCallback>,
+        context: *mut c_void,
+    ) -> Status,
+    pub sync_interrupt_transfer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        device_endpoint: u8,
+        data: *mut c_void,
+        data_length: *mut usize,
+        timeout: usize,
+        status: *mut UsbTransferStatus,
+    ) -> Status,
+    pub isochronous_transfer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        device_endpoint: u8,
+        data: *mut c_void,
+        data_length: usize,
+        status: *mut UsbTransferStatus,
+    ) -> Status,
+    pub async_isochronous_transfer: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        device_endpoint: u8,
+        data: *mut c_void,
+        data_length: usize,
+        isochronous_callback: AsyncUsbTransferCallback,
+        context: *mut c_void,
+    ) -> Status,
+    pub get_device_descriptor: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        device_descriptor: *mut DeviceDescriptor,
+    ) -> Status,
+    pub get_config_descriptor: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        config_descriptor: *mut ConfigDescriptor,
+    ) -> Status,
+    pub get_interface_descriptor: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        interface_descriptor: *mut InterfaceDescriptor,
+    ) -> Status,
+    pub get_endpoint_descriptor: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        endpoint_index: u8,
+        endpoint_descriptor: *mut EndpointDescriptor,
+    ) -> Status,
+    pub get_string_descriptor: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        lang_id: u16,
+        string_id: u8,
+        string: *mut *mut Char16,
+    ) -> Status,
+    pub get_supported_languages: unsafe extern "efiapi" fn(
+        this: *mut Self,
+        lang_id_table: *mut *mut u16,
+        table_size: *mut u16,
+    ) -> Status,
+    pub port_reset: unsafe extern "efiapi" fn(this: *mut Self) -> Status,
+}
+
+impl UsbIoProtocol {
+    pub const GUID: Guid = guid!("2b2f68d6-0cd2-44cf-8e8b-bba20b1b5b75");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::size_of;
+
+    #[test]
+    fn test_descriptor_sizes() {
+        assert_eq!(size_of::<DeviceRequest>(), 8);
+        assert_eq!(size_of::<DeviceDescriptor>(), 18);
+        assert_eq!(size_of::<ConfigDescriptor>(), 9);
+        assert_eq!(size_of::<InterfaceDescriptor>(), 9);
+        assert_eq!(size_of::<EndpointDescriptor>(), 7);
+    }
+}
@@ -94,6 +94,20 @@ pub enum Status: usize => {
     IP_ADDRESS_CONFLICT     = Self::ERROR_BIT | 34,
     /// A HTTP error occurred during the network operation.
     HTTP_ERROR              = Self::ERROR_BIT | 35,
+    /// The network is unreachable.
+    NETWORK_UNREACHABLE     = Self::ERROR_BIT | 100,
+    /// The host is unreachable.
+    HOST_UNREACHABLE        = Self::ERROR_BIT | 101,
+    /// The protocol is unreachable.
+    PROTOCOL_UNREACHABLE    = Self::ERROR_BIT | 102,
+    /// The port is unreachable.
+    PORT_UNREACHABLE        = Self::ERROR_BIT | 103,
+    /// The peer has closed the connection.
+    CONNECTION_FIN          = Self::ERROR_BIT | 104,
+    /// The connection was reset by the peer.
+    CONNECTION_RESET        = Self::ERROR_BIT | 105,
+    /// The connection was refused by the peer.
+    CONNECTION_REFUSED      = Self::ERROR_BIT | 106,
 }}
 
 impl Status {
@@ -443,6 +443,58 @@ impl MemoryType {
         assert!(value >= 0x80000000);
         Self(value)
     }
+
+    /// Whether the memory type is in the [`RESERVED_FOR_OEM`] range.
+    ///
+    /// [`RESERVED_FOR_OEM`]: Self::RESERVED_FOR_OEM
+    #[must_use]
+    pub const fn is_reserved_for_oem(self) -> bool {
+        self.0 >= *Self::RESERVED_FOR_OEM.start() && self.0 <= *Self::RESERVED_FOR_OEM.end()
+    }
+
+    /// Whether the memory type is in the [`RESERVED_FOR_OS_LOADER`] range,
+    /// e.g. a type created with [`MemoryType::custom`].
+    ///
+    /// [`RESERVED_FOR_OS_LOADER`]: Self::RESERVED_FOR_OS_LOADER
+    #[must_use]
+    pub const fn is_reserved_for_os_loader(self) -> bool {
+        self.0 >= *Self::RESERVED_FOR_OS_LOADER.start()
+    }
+
+    /// Whether memory of this type is available for general use by the OS
+    /// after exiting boot services.
+    ///
+    /// This is true for [`CONVENTIONAL`] memory, for memory used by boot
+    /// services ([`BOOT_SERVICES_CODE`] and [`BOOT_SERVICES_DATA`]), and for
+    /// memory of the loader ([`LOADER_CODE`] and [`LOADER_DATA`]). Note that
+    /// loader memory still contains the running loader as well as anything
+    /// it allocated, such as the final memory map, so it must only be reused
+    /// once that data is no longer needed.
+    ///
+    /// [`ACPI_RECLAIM`] memory is not included since it only becomes
+    /// available after the ACPI tables have been parsed. Neither is
+    /// [`PERSISTENT_MEMORY`], whose contents are expected to be preserved,
+    /// nor [`UNACCEPTED`] memory, which must be accepted before use.
+    ///
+    /// [`CONVENTIONAL`]: Self::CONVENTIONAL
+    /// [`BOOT_SERVICES_CODE`]: Self::BOOT_SERVICES_CODE
+    /// [`BOOT_SERVICES_DATA`]: Self::BOOT_SERVICES_DATA
+    /// [`LOADER_CODE`]: Self::LOADER_CODE
+    /// [`LOADER_DATA`]: Self::LOADER_DATA
+    /// [`ACPI_RECLAIM`]: Self::ACPI_RECLAIM
+    /// [`PERSISTENT_MEMORY`]: Self::PERSISTENT_MEMORY
+    /// [`UNACCEPTED`]: Self::UNACCEPTED
+    #[must_use]
+    pub const fn is_usable_after_exit_boot_services(self) -> bool {
+        matches!(
+            self,
+            Self::CONVENTIONAL
+                | Self::BOOT_SERVICES_CODE
+                | Self::BOOT_SERVICES_DATA
+                | Self::LOADER_CODE
+                | Self::LOADER_DATA
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -484,3 +536,41 @@ pub enum Tpl: usize => {
 /// Note that this is not necessarily the processor's page size. The UEFI page
 /// size is always 4 KiB.
 pub const PAGE_SIZE: usize = 4096;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_type_ranges() {
+        assert!(!MemoryType::CONVENTIONAL.is_reserved_for_oem());
+        assert!(!MemoryType::CONVENTIONAL.is_reserved_for_os_loader());
+        assert!(!MemoryType(0x6fff_ffff).is_reserved_for_oem());
+        assert!(MemoryType(0x7000_0000).is_reserved_for_oem());
+        assert!(MemoryType(0x7fff_ffff).is_reserved_for_oem());
+        assert!(!MemoryType(0x7fff_ffff).is_reserved_for_os_loader());
+        assert!(!MemoryType(0x8000_0000).is_reserved_for_oem());
+        assert!(MemoryType::custom(0x8000_0000).is_reserved_for_os_loader());
+        assert!(MemoryType::custom(0xffff_ffff).is_reserved_for_os_loader());
+    }
+
+    #[test]
+    fn test_memory_type_usable_after_exit_boot_services() {
+        let usable = [
+            MemoryType::LOADER_CODE,
+            MemoryType::LOADER_DATA,
+            MemoryType::BOOT_SERVICES_CODE,
+            MemoryType::BOOT_SERVICES_DATA,
+            MemoryType::CONVENTIONAL,
+        ];
+        for ty in (0..MemoryType::MAX.0).map(MemoryType) {
+            assert_eq!(
+                ty.is_usable_after_exit_boot_services(),
+                usable.contains(&ty),
+                "{ty:?}"
+            );
+        }
+        assert!(!MemoryType(0x7000_0000).is_usable_after_exit_boot_services());
+        assert!(!MemoryType::custom(0x8000_0000).is_usable_after_exit_boot_services());
+    }
+}
@@ -29,6 +29,7 @@ use core::fmt;
 /// assert_eq!(Revision::EFI_2_30.to_string(), "2.3");
 /// assert_eq!(Revision::EFI_2_31.to_string(), "2.3.1");
 /// assert_eq!(Revision::EFI_2_100.to_string(), "2.10");
+/// assert_eq!(Revision::EFI_2_110.to_string(), "2.11");
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -52,6 +53,7 @@ impl Revision {
     pub const EFI_2_80: Self = Self::new(2, 80);
     pub const EFI_2_90: Self = Self::new(2, 90);
     pub const EFI_2_100: Self = Self::new(2, 100);
+    pub const EFI_2_110: Self = Self::new(2, 110);
 }
 
 impl Revision {
@@ -75,6 +77,22 @@ impl Revision {
     pub const fn minor(self) -> u16 {
         self.0 as u16
     }
+
+    /// Returns true if this revision is `required` or later.
+    ///
+    /// This is intended for guarding calls to functions that were added in
+    /// a later revision of the specification:
+    ///
+    /// ```
+    /// # use uefi_raw::table::Revision;
+    /// let rev = Revision::EFI_2_70;
+    /// assert!(rev.supports_at_least(Revision::EFI_2_00));
+    /// assert!(!rev.supports_at_least(Revision::EFI_2_80));
+    /// ```
+    #[must_use]
+    pub const fn supports_at_least(self, required: Self) -> bool {
+        self.0 >= required.0
+    }
 }
 
 impl fmt::Display for Revision {
@@ -107,4 +125,36 @@ mod tests {
 
         assert!(Revision::EFI_1_10 < Revision::EFI_2_00);
     }
+
+    #[test]
+    fn test_revision_ordering() {
+        let revisions = [
+            Revision::EFI_1_02,
+            Revision::EFI_1_10,
+            Revision::EFI_2_00,
+            Revision::EFI_2_10,
+            Revision::EFI_2_20,
+            Revision::EFI_2_30,
+            Revision::EFI_2_31,
+            Revision::EFI_2_40,
+            Revision::EFI_2_50,
+            Revision::EFI_2_60,
+            Revision::EFI_2_70,
+            Revision::EFI_2_80,
+            Revision::EFI_2_90,
+            Revision::EFI_2_100,
+            Revision::EFI_2_110,
+        ];
+        for pair in revisions.windows(2) {
+            assert!(pair[0] < pair[1], "{} >= {}", pair[0], pair[1]);
+            assert!(pair[1].supports_at_least(pair[0]));
+            assert!(!pair[0].supports_at_least(pair[1]));
+        }
+        for rev in revisions {
+            assert!(rev.supports_at_least(rev));
+        }
+
+        // The minor revision is compared numerically, not as a string.
+        assert!(Revision::EFI_2_100 > Revision::EFI_2_90);
+    }
 }
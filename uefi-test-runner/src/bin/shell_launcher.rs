@@ -64,12 +64,11 @@ fn efi_main() -> Status {
     let mut shell_loaded_image = boot::open_protocol_exclusive::<LoadedImage>(shell_image_handle)
         .expect("failed to open LoadedImage protocol");
     let load_options = cstr16!(r"shell.efi test_runner.efi arg1 arg2");
-    unsafe {
-        shell_loaded_image.set_load_options(
-            load_options.as_ptr().cast(),
-            load_options.num_bytes() as u32,
-        );
-    }
+    shell_loaded_image.set_load_options_cstr16(load_options);
+    assert_eq!(
+        shell_loaded_image.load_options_as_cstr16(),
+        Ok(load_options)
+    );
 
     info!("launching the shell app");
     boot::start_image(shell_image_handle).expect("failed to launch the shell app");
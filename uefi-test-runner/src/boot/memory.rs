@@ -6,12 +6,16 @@ pub fn test() {
     info!("Testing memory functions");
 
     test_allocate_pages();
+    test_allocate_pages_owned();
     test_allocate_pool();
 
     vec_alloc();
     alloc_alignment();
+    #[cfg(feature = "unstable")]
+    pool_allocator();
 
     test_memory_map();
+    test_memory_map_coalesce();
 }
 
 fn test_allocate_pages() {
@@ -31,6 +35,34 @@ fn test_allocate_pages() {
     unsafe { boot::free_pages(ptr, num_pages) }.unwrap();
 }
 
+fn test_allocate_pages_owned() {
+    let mut pages =
+        boot::allocate_pages_owned(AllocateType::AnyPages, MemoryType::LOADER_DATA, 2).unwrap();
+    assert_eq!(pages.page_count(), 2);
+    assert_eq!(pages.len(), 2 * boot::PAGE_SIZE);
+    assert_eq!(
+        pages.as_ptr().as_ptr() as usize % boot::PAGE_SIZE,
+        0,
+        "Page pointer is not page-aligned"
+    );
+
+    // The pages are zeroed and can be written to.
+    assert!(pages.iter().all(|b| *b == 0));
+    pages.fill(0xff);
+    let addr = pages.as_ptr();
+    drop(pages);
+
+    // Allocating at the address that was just freed succeeds, and records
+    // that address.
+    let pages = boot::allocate_pages_owned(
+        AllocateType::Address(addr.as_ptr() as u64),
+        MemoryType::LOADER_DATA,
+        1,
+    )
+    .unwrap();
+    assert_eq!(pages.as_ptr(), addr);
+}
+
 fn test_allocate_pool() {
     let ptr = boot::allocate_pool(MemoryType::LOADER_DATA, 10).unwrap();
 
@@ -69,6 +101,39 @@ fn alloc_alignment() {
     assert_eq!(value.as_ptr() as usize % 0x100, 0, "Wrong alignment");
 }
 
+// Test that `PoolAllocator` allocates memory of the requested type.
+#[cfg(feature = "unstable")]
+fn pool_allocator() {
+    use uefi::allocator::PoolAllocator;
+
+    info!("Allocating a vector with `PoolAllocator`");
+
+    let allocator = PoolAllocator::new(MemoryType::RUNTIME_SERVICES_DATA);
+    let mut values = Vec::new_in(allocator);
+    values.extend_from_slice(&[1u32, 2, 3]);
+    let addr = values.as_ptr() as u64;
+
+    let memory_map = boot::memory_map(MemoryType::LOADER_DATA).unwrap();
+    let desc = memory_map
+        .entries()
+        .find(|desc| {
+            let end = desc.phys_start + desc.page_count * boot::PAGE_SIZE as u64;
+            (desc.phys_start..end).contains(&addr)
+        })
+        .expect("allocation is not in the memory map");
+    assert_eq!(desc.ty, MemoryType::RUNTIME_SERVICES_DATA);
+
+    // Over-aligned allocations are supported too.
+    #[repr(align(0x100))]
+    struct Block(#[allow(dead_code)] [u8; 0x100]);
+    let block = alloc::boxed::Box::new_in(Block([1; 0x100]), allocator);
+    assert_eq!(
+        &*block as *const Block as usize % 0x100,
+        0,
+        "Wrong alignment"
+    );
+}
+
 fn test_memory_map() {
     info!("Testing memory map functions");
 
@@ -105,3 +170,27 @@ fn test_memory_map() {
     let page_count = first_desc.page_count;
     assert!(page_count != 0, "Memory map entry has size zero");
 }
+
+fn test_memory_map_coalesce() {
+    info!("Testing memory map coalescing");
+
+    let memory_map =
+        boot::memory_map(MemoryType::LOADER_DATA).expect("Failed to retrieve UEFI memory map");
+    let coalesced = memory_map.coalesce().unwrap();
+
+    assert!(!coalesced.is_empty());
+    assert!(coalesced.len() <= memory_map.len());
+
+    // The total number of pages of each type must be unchanged.
+    for ty in [MemoryType::CONVENTIONAL, MemoryType::LOADER_CODE] {
+        let before: u64 = memory_map.entries_of_type(ty).map(|d| d.page_count).sum();
+        let after: u64 = coalesced.entries_of_type(ty).map(|d| d.page_count).sum();
+        assert_eq!(before, after);
+    }
+
+    let largest = coalesced.largest_free_region().expect("no free memory");
+    assert_eq!(largest.ty, MemoryType::CONVENTIONAL);
+    assert!(coalesced
+        .entries_of_type(MemoryType::CONVENTIONAL)
+        .all(|desc| desc.page_count <= largest.page_count));
+}
@@ -1,27 +1,41 @@
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
 use core::ffi::c_void;
 use core::ptr::{self, NonNull};
 
+use core::time::Duration;
+use uefi::boot::events::{WaitOutcome, WaitSet};
 use uefi::boot::{
     EventType, OpenProtocolAttributes, OpenProtocolParams, SearchType, TimerTrigger, Tpl,
 };
 use uefi::mem::memory_map::MemoryType;
 use uefi::proto::unsafe_protocol;
-use uefi::{boot, guid, system, Event, Guid, Identify};
+use uefi::{boot, guid, system, Event, Guid, Identify, Status};
 
 pub fn test() {
     test_tpl();
     info!("Testing timer...");
     test_timer();
+    test_timer_helpers();
     info!("Testing events...");
+    test_wait_set();
     test_check_event();
     test_callback_with_ctx();
     info!("Testing watchdog...");
     test_watchdog();
+    info!("Testing monotonic counter...");
+    test_monotonic_count();
+    info!("Testing CRC32 calculation...");
+    test_calculate_crc32();
     info!("Testing protocol handler services...");
     test_register_protocol_notify();
+    test_watch_protocol_installs();
     test_install_protocol_interface();
+    test_open_protocol_dynamic();
     test_reinstall_protocol_interface();
     test_uninstall_protocol_interface();
+    test_install_multiple_protocol_interfaces();
     test_install_configuration_table();
 }
 
@@ -55,9 +69,53 @@ fn test_timer() {
     boot::set_timer(&timer_event, TimerTrigger::Relative(5_0 /*00 ns */)).unwrap();
     assert_eq!(boot::wait_for_event(&mut events).unwrap(), 0);
 
+    let trigger = TimerTrigger::relative(Duration::from_micros(5)).unwrap();
+    assert_eq!(trigger, TimerTrigger::Relative(50));
+    boot::set_timer(&timer_event, trigger).unwrap();
+    assert_eq!(boot::wait_for_event(&mut events).unwrap(), 0);
+
     boot::close_event(timer_event).unwrap();
 }
 
+fn test_timer_helpers() {
+    let timer = boot::create_oneshot_timer(Duration::from_micros(5)).unwrap();
+    let mut events = unsafe { [timer.unsafe_clone()] };
+    assert_eq!(boot::wait_for_event(&mut events).unwrap(), 0);
+    boot::close_event(timer).unwrap();
+
+    // A periodic timer fires repeatedly.
+    let timer = boot::create_periodic_timer(Duration::from_micros(5)).unwrap();
+    let mut events = unsafe { [timer.unsafe_clone()] };
+    for _ in 0..3 {
+        assert_eq!(boot::wait_for_event(&mut events).unwrap(), 0);
+    }
+    boot::close_event(timer).unwrap();
+
+    // A very long delay saturates rather than failing, and doesn't fire.
+    let timer = boot::create_oneshot_timer(Duration::MAX).unwrap();
+    assert!(!boot::check_event(unsafe { timer.unsafe_clone() }).unwrap());
+    boot::close_event(timer).unwrap();
+}
+
+fn test_wait_set() {
+    let event = unsafe { boot::create_event(EventType::TIMER, Tpl::CALLBACK, None, None) }.unwrap();
+
+    // The event is not signaled, so the timer fires.
+    let mut wait_set = WaitSet::new()
+        .add(&event)
+        .add_timer(Duration::from_millis(1))
+        .unwrap();
+    assert_eq!(wait_set.wait().unwrap(), WaitOutcome::Timeout);
+
+    // The event fires long before the timeout.
+    let mut wait_set = wait_set.add_timer(Duration::from_secs(10)).unwrap();
+    boot::set_timer(&event, TimerTrigger::Relative(0)).unwrap();
+    assert_eq!(wait_set.wait().unwrap(), WaitOutcome::Event(0));
+    drop(wait_set);
+
+    boot::close_event(event).unwrap();
+}
+
 fn test_callback_with_ctx() {
     let mut data = 123u32;
 
@@ -103,6 +161,16 @@ struct TestProtocol {
     data: u32,
 }
 
+fn test_monotonic_count() {
+    let count1 = boot::get_next_monotonic_count().unwrap();
+    let count2 = boot::get_next_monotonic_count().unwrap();
+    assert!(count2 > count1);
+}
+
+fn test_calculate_crc32() {
+    assert_eq!(boot::calculate_crc32(b"123456789").unwrap(), 0xcbf4_3926);
+}
+
 fn test_register_protocol_notify() {
     unsafe extern "efiapi" fn callback(_event: Event, _context: Option<NonNull<c_void>>) {
         info!("in callback for test_register_protocol_notify")
@@ -117,6 +185,44 @@ fn test_register_protocol_notify() {
         .expect("Failed to register protocol notify fn");
 }
 
+fn test_watch_protocol_installs() {
+    info!("Testing watch_protocol_installs");
+
+    /// Protocol that is only installed by this test.
+    #[unsafe_protocol("3d0c6a4f-6a33-4d2c-9c1c-6f4e1bdfb2b1")]
+    struct WatchedProtocol;
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let watch = {
+        let seen = seen.clone();
+        boot::watch_protocol_installs::<WatchedProtocol, _>(move |handle| {
+            seen.borrow_mut().push(handle);
+        })
+        .expect("Failed to watch protocol installs")
+    };
+
+    let install = || unsafe {
+        boot::install_protocol_interface(None, &WatchedProtocol::GUID, ptr::null())
+            .expect("Failed to install protocol interface")
+    };
+    let uninstall = |handle| unsafe {
+        boot::uninstall_protocol_interface(handle, &WatchedProtocol::GUID, ptr::null())
+            .expect("Failed to uninstall protocol interface")
+    };
+
+    // The callback runs as soon as the interface is installed.
+    let handle = install();
+    assert_eq!(*seen.borrow(), [handle]);
+
+    // No more callbacks after the watch is dropped.
+    drop(watch);
+    let other_handle = install();
+    assert_eq!(*seen.borrow(), [handle]);
+
+    uninstall(handle);
+    uninstall(other_handle);
+}
+
 fn test_install_protocol_interface() {
     info!("Installing TestProtocol");
 
@@ -136,6 +242,28 @@ fn test_install_protocol_interface() {
         .expect("Failed to find protocol after it was installed");
 }
 
+fn test_open_protocol_dynamic() {
+    info!("Opening TestProtocol by GUID");
+    let handle = boot::locate_handle_buffer(SearchType::from_proto::<TestProtocol>())
+        .expect("Failed to find protocol")[0];
+
+    {
+        let sp = boot::open_protocol_dynamic(handle, TestProtocol::GUID)
+            .expect("Failed to open protocol by GUID");
+        assert_eq!(sp.guid(), TestProtocol::GUID);
+        let interface = sp.interface().unwrap().cast::<TestProtocol>();
+        assert_eq!(unsafe { interface.as_ref() }.data, 123);
+    }
+
+    // Opening a protocol that isn't installed on the handle fails.
+    assert_eq!(
+        boot::open_protocol_dynamic(handle, guid!("0d3a2b6c-1c38-4d1e-9b5f-5a4c2c1e7f01"))
+            .unwrap_err()
+            .status(),
+        Status::UNSUPPORTED
+    );
+}
+
 fn test_reinstall_protocol_interface() {
     info!("Reinstalling TestProtocol");
     let handle = boot::locate_handle_buffer(SearchType::from_proto::<TestProtocol>())
@@ -182,6 +310,52 @@ fn test_uninstall_protocol_interface() {
     }
 }
 
+fn test_install_multiple_protocol_interfaces() {
+    info!("Installing multiple protocol interfaces");
+
+    const GUID_A: Guid = guid!("0c5ee1a1-0bd4-4b07-9c4a-3ac39b8a3c5e");
+    const GUID_B: Guid = guid!("d1e1f6d2-1b8b-4b0f-9a41-7f0a0f2d0c4b");
+    const GUID_C: Guid = guid!("5b5d0b4c-9c3e-4d0a-8f1c-2a8b6f3e7d19");
+    static DATA: [u32; 3] = [1, 2, 3];
+    let interfaces = [
+        (GUID_A, ptr::from_ref(&DATA[0]).cast::<c_void>()),
+        (GUID_B, ptr::from_ref(&DATA[1]).cast::<c_void>()),
+    ];
+
+    let handle = unsafe { boot::install_multiple_protocol_interfaces(None, &interfaces) }
+        .expect("Failed to install multiple protocol interfaces");
+    let protocols = boot::protocols_per_handle(handle).unwrap();
+    assert!(protocols.iter().any(|guid| **guid == GUID_A));
+    assert!(protocols.iter().any(|guid| **guid == GUID_B));
+    drop(protocols);
+
+    // Installing GUID_A again fails, and GUID_C must be rolled back.
+    let duplicate = [
+        (GUID_C, ptr::from_ref(&DATA[2]).cast::<c_void>()),
+        (GUID_A, ptr::from_ref(&DATA[0]).cast::<c_void>()),
+    ];
+    assert_eq!(
+        unsafe { boot::install_multiple_protocol_interfaces(Some(handle), &duplicate) }
+            .unwrap_err()
+            .status(),
+        Status::INVALID_PARAMETER
+    );
+    let protocols = boot::protocols_per_handle(handle).unwrap();
+    assert!(!protocols.iter().any(|guid| **guid == GUID_C));
+    drop(protocols);
+
+    // An empty list is rejected.
+    assert_eq!(
+        unsafe { boot::install_multiple_protocol_interfaces(Some(handle), &[]) }
+            .unwrap_err()
+            .status(),
+        Status::INVALID_PARAMETER
+    );
+
+    unsafe { boot::uninstall_multiple_protocol_interfaces(handle, &interfaces) }
+        .expect("Failed to uninstall multiple protocol interfaces");
+}
+
 fn test_install_configuration_table() {
     // Get the current number of entries.
     let count = system::with_config_table(|t| t.len());
@@ -204,9 +378,14 @@ fn test_install_configuration_table() {
         let config_entry = t.iter().find(|ct| ct.guid == ID).unwrap();
         assert_eq!(unsafe { *(config_entry.address as *const u8) }, 42);
     });
+    assert_eq!(
+        system::find_config_table(&ID),
+        Some(config.cast_const().cast())
+    );
 
     // Uninstall the table.
     unsafe {
         boot::install_configuration_table(&ID, ptr::null()).unwrap();
     }
+    assert_eq!(system::find_config_table(&ID), None);
 }
@@ -4,8 +4,9 @@ use uefi::fs::FileSystem;
 use uefi::proto::console::text::Output;
 use uefi::proto::device_path::media::FilePath;
 use uefi::proto::device_path::{DevicePath, LoadedImageDevicePath};
+use uefi::proto::media::fs::SimpleFileSystem;
 use uefi::proto::BootPolicy;
-use uefi::{boot, CString16, Identify};
+use uefi::{boot, CString16, Identify, Status};
 
 mod memory;
 mod misc;
@@ -42,6 +43,17 @@ fn test_locate_handles() {
         let handles_vec = boot::find_handles::<Output>().unwrap();
         assert_eq!(*handles, handles_vec);
     }
+
+    {
+        // All file system handles have a device path.
+        let handles = boot::find_handles::<SimpleFileSystem>().unwrap();
+        let handles_with_paths = boot::find_handles_with_paths::<SimpleFileSystem>().unwrap();
+        assert_eq!(handles_with_paths.len(), handles.len());
+        for (handle, path) in &handles_with_paths {
+            assert!(handles.contains(handle));
+            assert_ne!(path.node_iter().count(), 0);
+        }
+    }
 }
 
 /// This test loads the "self image" again into memory using the `load_image`
@@ -77,6 +89,17 @@ fn test_load_image() {
 
     assert_eq!(image_device_path_file_path.as_str(), LOADED_IMAGE_PATH);
 
+    // Check that the helpers return the same paths.
+    let image_file_path =
+        boot::get_image_file_path(boot::image_handle()).expect("should get image file path");
+    assert_eq!(&*image_file_path, image_device_path);
+    let image_file_name =
+        boot::get_image_file_name(boot::image_handle()).expect("should get image file name");
+    assert_eq!(
+        image_file_name.to_string().to_uppercase(),
+        LOADED_IMAGE_PATH
+    );
+
     // Variant A: FromBuffer
     {
         let fs =
@@ -111,4 +134,26 @@ fn test_load_image() {
 
         log::debug!("load_image with FromFilePath strategy works");
     }
+    // Variant C: the `load_image_from_path` and `load_image_from_file`
+    // helpers
+    {
+        let image = boot::load_image_from_path(boot::image_handle(), image_device_path)
+            .expect("should load image");
+        boot::unload_image(image).unwrap();
+
+        let path = CString16::try_from(LOADED_IMAGE_PATH).unwrap();
+        let image =
+            boot::load_image_from_file(boot::image_handle(), &path).expect("should load image");
+        boot::unload_image(image).unwrap();
+
+        let path = CString16::try_from(r"\EFI\BOOT\MISSING.EFI").unwrap();
+        assert_eq!(
+            boot::load_image_from_file(boot::image_handle(), &path)
+                .unwrap_err()
+                .status(),
+            Status::NOT_FOUND
+        );
+
+        log::debug!("load_image_from_path and load_image_from_file work");
+    }
 }
@@ -2,15 +2,20 @@
 
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use uefi::boot::ScopedProtocol;
 use uefi::fs::{FileSystem, IoError, IoErrorContext, PathBuf};
-use uefi::proto::media::fs::SimpleFileSystem;
-use uefi::{cstr16, fs, Status};
+use uefi::{boot, cstr16, fs, Handle, Status};
 
 /// Tests functionality from the `uefi::fs` module. This test relies on a
 /// working File System Protocol, which is tested at a dedicated place.
-pub fn test(sfs: ScopedProtocol<SimpleFileSystem>) -> Result<(), fs::Error> {
-    let mut fs = FileSystem::new(sfs);
+pub fn test(handle: Handle) -> Result<(), fs::Error> {
+    // A handle without the file system protocol is reported distinctly from
+    // a volume that can't be opened.
+    match FileSystem::from_handle(boot::image_handle()) {
+        Err(fs::Error::Io(err)) => assert_eq!(err.context, IoErrorContext::CantOpenProtocol),
+        _ => panic!("expected an error opening the image handle as a file system"),
+    }
+
+    let mut fs = FileSystem::from_handle(handle)?;
 
     // test create dir
     fs.create_dir(cstr16!("foo_dir"))?;
@@ -24,6 +29,11 @@ pub fn test(sfs: ScopedProtocol<SimpleFileSystem>) -> Result<(), fs::Error> {
     let read = String::from_utf8(read).expect("Should be valid utf8");
     assert_eq!(read.as_str(), data_to_write);
 
+    // test reading an empty file
+    fs.write(cstr16!("foo_dir\\empty"), "")?;
+    assert!(fs.read(cstr16!("foo_dir\\empty"))?.is_empty());
+    fs.remove_file(cstr16!("foo_dir\\empty"))?;
+
     // test rename file + path buf replaces / with \
     fs.rename(
         PathBuf::from(cstr16!("/foo_dir/foo_cpy")),
@@ -58,6 +68,9 @@ pub fn test(sfs: ScopedProtocol<SimpleFileSystem>) -> Result<(), fs::Error> {
     test_copy_error(&mut fs)?;
     test_copy_success(&mut fs)?;
     test_copy_success_chunks(&mut fs)?;
+    test_copy_preserving_metadata(&mut fs)?;
+    test_write_atomic(&mut fs)?;
+    test_copy_dir_recursive(&mut fs)?;
 
     Ok(())
 }
@@ -104,6 +117,20 @@ fn test_copy_error(fs: &mut FileSystem) -> Result<(), fs::Error> {
     );
     assert_eq!(fs.try_exists(dir_path), Ok(true));
 
+    // Test that a non-empty directory is not removed by `remove_dir`.
+    let nested_path = cstr16!("dir\\nested");
+    fs.write(nested_path, "nested")?;
+    assert_eq!(
+        fs.remove_dir(dir_path),
+        Err(fs::Error::Io(IoError {
+            path: PathBuf::from(dir_path),
+            context: IoErrorContext::DirectoryNotEmpty,
+            uefi_error: uefi::Error::new(Status::ACCESS_DENIED, ()),
+        }))
+    );
+    assert_eq!(fs.try_exists(nested_path), Ok(true));
+    fs.remove_file(nested_path)?;
+
     // Clean up temporary files.
     fs.remove_file(file1_path)?;
     fs.remove_dir(dir_path)?;
@@ -168,3 +195,77 @@ fn test_copy_success_chunks(fs: &mut FileSystem) -> Result<(), fs::Error> {
 
     Ok(())
 }
+
+fn test_copy_preserving_metadata(fs: &mut FileSystem) -> Result<(), fs::Error> {
+    let file1_path = cstr16!("file1");
+    let file2_path = cstr16!("file2");
+
+    fs.write(file1_path, "data1")?;
+    fs.copy_preserving_metadata(file1_path, file2_path)?;
+    assert_eq!(fs.read(file2_path)?, b"data1");
+
+    let src_info = fs.metadata(file1_path)?;
+    let dest_info = fs.metadata(file2_path)?;
+    assert_eq!(dest_info.create_time(), src_info.create_time());
+    assert_eq!(dest_info.modification_time(), src_info.modification_time());
+    assert_eq!(dest_info.attribute(), src_info.attribute());
+
+    // Clean up temporary files.
+    fs.remove_file(file1_path)?;
+    fs.remove_file(file2_path)?;
+
+    Ok(())
+}
+
+fn test_write_atomic(fs: &mut FileSystem) -> Result<(), fs::Error> {
+    let file1_path = cstr16!("atomic_dir\\file1");
+    fs.create_dir(cstr16!("atomic_dir"))?;
+
+    // Write a new file, then replace it.
+    fs.write_atomic(file1_path, "data1")?;
+    assert_eq!(fs.read(file1_path)?, b"data1");
+    fs.write_atomic(file1_path, "some longer data")?;
+    assert_eq!(fs.read(file1_path)?, b"some longer data");
+
+    // The temporary file is gone.
+    let entries = fs
+        .read_dir(cstr16!("atomic_dir"))?
+        .map(|entry| entry.expect("Should be valid").file_name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(&[".", "..", "file1"], entries.as_slice());
+
+    // Clean up temporary files.
+    fs.remove_dir_all(cstr16!("atomic_dir"))?;
+
+    Ok(())
+}
+
+fn test_copy_dir_recursive(fs: &mut FileSystem) -> Result<(), fs::Error> {
+    fs.create_dir_all(cstr16!("src_dir\\a\\b"))?;
+    fs.create_dir(cstr16!("src_dir\\empty"))?;
+    fs.write(cstr16!("src_dir\\file1"), "data1")?;
+    fs.write(cstr16!("src_dir\\a\\b\\file2"), "data2")?;
+
+    fs.copy_dir_recursive(cstr16!("src_dir"), cstr16!("dest_dir\\nested"))?;
+    assert_eq!(fs.read(cstr16!("dest_dir\\nested\\file1"))?, b"data1");
+    assert_eq!(fs.read(cstr16!("dest_dir\\nested\\a\\b\\file2"))?, b"data2");
+    assert!(fs
+        .metadata(cstr16!("dest_dir\\nested\\empty"))?
+        .is_directory());
+
+    // Copying a missing directory reports the source path.
+    assert_eq!(
+        fs.copy_dir_recursive(cstr16!("missing_dir"), cstr16!("dest_dir")),
+        Err(fs::Error::Io(IoError {
+            path: PathBuf::from(cstr16!("missing_dir")),
+            context: IoErrorContext::OpenError,
+            uefi_error: uefi::Error::new(Status::NOT_FOUND, ()),
+        }))
+    );
+
+    // Clean up temporary files.
+    fs.remove_dir_all(cstr16!("src_dir"))?;
+    fs.remove_dir_all(cstr16!("dest_dir"))?;
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 #![no_std]
 #![no_main]
+#![cfg_attr(feature = "unstable", feature(allocator_api))]
 
 #[macro_use]
 extern crate log;
@@ -8,11 +9,13 @@ extern crate alloc;
 
 use alloc::string::ToString;
 use alloc::vec::Vec;
+use core::time::Duration;
 use uefi::mem::memory_map::{MemoryMap, MemoryType};
 use uefi::prelude::*;
 use uefi::proto::console::serial::Serial;
 use uefi::proto::device_path::build::{self, DevicePathBuilder};
 use uefi::proto::device_path::messaging::Vendor;
+use uefi::table::Revision;
 use uefi::{print, println, system, Result};
 
 mod boot;
@@ -57,16 +60,17 @@ fn efi_main() -> Status {
     shutdown();
 }
 
-fn check_revision(rev: uefi::table::Revision) {
+fn check_revision(rev: Revision) {
     assert_eq!(system::uefi_revision(), rev);
 
-    let (major, minor) = (rev.major(), rev.minor());
+    info!("UEFI {rev}");
 
-    info!("UEFI {}.{}", major, minor / 10);
-
-    assert!(major >= 2, "Running on an old, unsupported version of UEFI");
     assert!(
-        minor >= 30,
+        rev.supports_at_least(Revision::EFI_2_00),
+        "Running on an old, unsupported version of UEFI"
+    );
+    assert!(
+        rev.supports_at_least(Revision::EFI_2_30),
         "Old version of UEFI 2, some features might not be available."
     );
 }
@@ -88,6 +92,44 @@ fn check_system() {
             .output_string(cstr16!("test system::with_stderr\n"))
             .unwrap()
     });
+
+    check_smbios();
+    check_acpi();
+}
+
+fn check_smbios() {
+    info!("Testing SMBIOS table");
+
+    let smbios = uefi::table::cfg::smbios3_table().expect("no SMBIOS 3.0 table");
+    assert_eq!(smbios.major_version(), 3);
+
+    // OVMF always provides BIOS information (type 0).
+    // Safety: the table was just found in the configuration table, and
+    // nothing modifies it.
+    let bios_info = unsafe { smbios.structures() }
+        .find(|s| s.header_type() == 0)
+        .expect("no BIOS information structure");
+    // The vendor string is referenced by the first byte after the header.
+    let vendor = bios_info.string(bios_info.formatted()[4]);
+    assert!(vendor.is_some_and(|v| !v.is_empty()));
+}
+
+fn check_acpi() {
+    info!("Testing ACPI RSDP");
+
+    let rsdp = uefi::table::cfg::acpi2_rsdp().expect("no ACPI 2.0 RSDP");
+    assert!(rsdp.revision() >= 2);
+
+    // Every ACPI system has a FADT, with signature "FACP".
+    // Safety: the RSDP was just found in the configuration table, and
+    // nothing modifies the XSDT.
+    let mut xsdt = unsafe { rsdp.xsdt() }.expect("invalid XSDT");
+    assert_ne!(xsdt.len(), 0);
+    let has_fadt = xsdt.any(|address| {
+        let signature = unsafe { core::slice::from_raw_parts(address as *const u8, 4) };
+        signature == b"FACP"
+    });
+    assert!(has_fadt);
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -108,9 +150,7 @@ fn send_request_helper(serial: &mut Serial, request: HostRequest) -> Result {
     };
 
     // Set a 10 second timeout for the read and write operations.
-    let mut io_mode = *serial.io_mode();
-    io_mode.timeout = 10_000_000;
-    serial.set_attributes(&io_mode)?;
+    serial.set_timeout(Duration::from_secs(10))?;
 
     // Send a screenshot request to the host.
     serial.write(request.as_bytes()).discard_errdata()?;
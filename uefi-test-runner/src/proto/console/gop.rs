@@ -1,6 +1,9 @@
 use crate::{send_request_to_host, HostRequest};
 use uefi::boot::{self, OpenProtocolAttributes, OpenProtocolParams};
-use uefi::proto::console::gop::{BltOp, BltPixel, FrameBuffer, GraphicsOutput, PixelFormat};
+use uefi::proto::console::gop::{
+    BackBuffer, BltOp, BltPixel, EdidActive, GraphicsOutput, PixelFormat,
+};
+use uefi::Status;
 
 pub unsafe fn test() {
     info!("Running graphics output protocol test");
@@ -19,7 +22,10 @@ pub unsafe fn test() {
     )
     .expect("failed to open Graphics Output Protocol");
 
+    enter_graphics_mode(gop);
     set_graphics_mode(gop);
+    check_edid();
+    draw_back_buffer(gop);
     fill_color(gop);
     draw_fb(gop);
 
@@ -29,6 +35,22 @@ pub unsafe fn test() {
     }
 }
 
+// Temporarily switch to another graphics mode and check that the original
+// mode is restored.
+fn enter_graphics_mode(gop: &mut GraphicsOutput) {
+    let original_info = gop.current_mode_info();
+    let mode = gop
+        .modes()
+        .find(|mode| *mode.info() != original_info)
+        .expect("no other graphics mode");
+
+    {
+        let guard = gop.enter_mode(mode.index()).expect("Failed to enter mode");
+        assert_eq!(guard.current_mode_info(), *mode.info());
+    }
+    assert_eq!(gop.current_mode_info(), original_info);
+}
+
 // Set a larger graphics mode.
 fn set_graphics_mode(gop: &mut GraphicsOutput) {
     // We know for sure QEMU has a 1024x768 mode.
@@ -43,6 +65,53 @@ fn set_graphics_mode(gop: &mut GraphicsOutput) {
     gop.set_mode(&mode).expect("Failed to set graphics mode");
 }
 
+// Check the EDID of the display, if the firmware provides it.
+fn check_edid() {
+    let Ok(handle) = boot::get_handle_for_protocol::<EdidActive>() else {
+        info!("EDID active protocol is not supported");
+        return;
+    };
+    let edid =
+        boot::open_protocol_exclusive::<EdidActive>(handle).expect("failed to open EDID protocol");
+    if let Some(raw_edid) = edid.raw_edid() {
+        // EDID data consists of one or more 128-byte blocks.
+        assert_eq!(raw_edid.len() % 128, 0);
+        info!("Preferred timing: {:?}", edid.parse_preferred_timing());
+    }
+}
+
+// Draw to an off-screen buffer and present it. The result is overwritten
+// by `fill_color`, so this doesn't affect the screenshot.
+fn draw_back_buffer(gop: &mut GraphicsOutput) {
+    if gop.current_mode_info().pixel_format() == PixelFormat::Bitmask {
+        info!("Bitmask pixel format is not supported by the back buffer");
+        return;
+    }
+
+    let mut buffer = BackBuffer::new(gop).expect("Failed to create back buffer");
+    assert_eq!(buffer.resolution(), (1024, 768));
+    assert!(buffer.pixel_mut(1024, 0).is_none());
+
+    buffer.clear(BltPixel::new(0, 0, 0));
+    buffer.fill_rect((100, 100), (200, 50), BltPixel::new(255, 0, 0));
+    *buffer.pixel_mut(0, 0).unwrap() = BltPixel::new(0, 255, 0);
+    buffer.present().expect("Failed to present back buffer");
+
+    // Read back what was drawn.
+    let rgb = |pixel: &BltPixel| (pixel.red, pixel.green, pixel.blue);
+    let region = gop.read_region((100, 100), (200, 50)).unwrap();
+    assert_eq!(region.len(), 200 * 50);
+    assert!(region.iter().all(|pixel| rgb(pixel) == (255, 0, 0)));
+    let (screen, resolution) = gop.capture_full().unwrap();
+    assert_eq!(resolution, (1024, 768));
+    assert_eq!(rgb(&screen[0]), (0, 255, 0));
+    assert_eq!(rgb(&screen[100 * 1024 + 100]), (255, 0, 0));
+    assert_eq!(
+        gop.read_region((1000, 0), (25, 1)).unwrap_err().status(),
+        Status::INVALID_PARAMETER
+    );
+}
+
 // Fill the screen with color.
 fn fill_color(gop: &mut GraphicsOutput) {
     let op = BltOp::VideoFill {
@@ -63,42 +132,19 @@ fn draw_fb(gop: &mut GraphicsOutput) {
         return;
     }
 
-    let mi = gop.current_mode_info();
-    let stride = mi.stride();
-    let (width, height) = mi.resolution();
-
     let mut fb = gop.frame_buffer();
-
-    type PixelWriter = unsafe fn(&mut FrameBuffer, usize, [u8; 3]);
-    unsafe fn write_pixel_rgb(fb: &mut FrameBuffer, pixel_base: usize, rgb: [u8; 3]) {
-        fb.write_value(pixel_base, rgb);
-    }
-    unsafe fn write_pixel_bgr(fb: &mut FrameBuffer, pixel_base: usize, rgb: [u8; 3]) {
-        fb.write_value(pixel_base, [rgb[2], rgb[1], rgb[0]]);
-    }
-    let write_pixel: PixelWriter = match mi.pixel_format() {
-        PixelFormat::Rgb => write_pixel_rgb,
-        PixelFormat::Bgr => write_pixel_bgr,
-        _ => {
-            info!("This pixel format is not supported by the drawing demo");
-            return;
-        }
-    };
+    let (width, height) = fb.info().resolution();
 
     let mut fill_rectangle = |(x1, y1), (x2, y2), color| {
         assert!((x1 < width) && (x2 < width), "Bad X coordinate");
         assert!((y1 < height) && (y2 < height), "Bad Y coordinate");
         for row in y1..y2 {
             for column in x1..x2 {
-                unsafe {
-                    let pixel_index = (row * stride) + column;
-                    let pixel_base = 4 * pixel_index;
-                    write_pixel(&mut fb, pixel_base, color);
-                }
+                fb.write_pixel(column, row, color);
             }
         }
     };
 
-    fill_rectangle((50, 30), (150, 600), [250, 128, 64]);
-    fill_rectangle((400, 120), (750, 450), [16, 128, 255]);
+    fill_rectangle((50, 30), (150, 600), BltPixel::new(250, 128, 64));
+    fill_rectangle((400, 120), (750, 450), BltPixel::new(16, 128, 255));
 }
@@ -0,0 +1,50 @@
+use uefi::boot::{self, OpenProtocolAttributes, OpenProtocolParams};
+use uefi::proto::console::text::{InputEx, Key, KeyData, KeyShiftState, KeyState, KeyToggleState};
+use uefi::{Char16, Status};
+
+unsafe extern "efiapi" fn key_notify(
+    _key_data: *mut uefi_raw::protocol::console::KeyData,
+) -> Status {
+    Status::SUCCESS
+}
+
+pub fn test() {
+    info!("Running extended text input protocol test");
+    let Ok(handle) = boot::get_handle_for_protocol::<InputEx>() else {
+        info!("InputEx protocol is not supported");
+        return;
+    };
+    // Don't open in exclusive mode, that would disconnect the console.
+    // Safety: the console is not disconnected while the protocol is in use.
+    let mut input = unsafe {
+        boot::open_protocol::<InputEx>(
+            OpenProtocolParams {
+                handle,
+                agent: boot::image_handle(),
+                controller: None,
+            },
+            OpenProtocolAttributes::GetProtocol,
+        )
+    }
+    .expect("failed to open InputEx protocol");
+
+    // No key has been pressed, but reading must not fail.
+    let key = input.read_key_stroke().expect("failed to read key stroke");
+    info!("Pending key stroke: {key:?}");
+    assert!(input.wait_for_key_event().is_some());
+
+    // Register and unregister a Ctrl+C hotkey.
+    let ctrl_c = KeyData {
+        key: Key::Printable(Char16::try_from('c').unwrap()),
+        state: KeyState {
+            key_shift_state: KeyShiftState::SHIFT_STATE_VALID | KeyShiftState::LEFT_CONTROL_PRESSED,
+            key_toggle_state: KeyToggleState::empty(),
+        },
+    };
+    let notify_handle = input
+        .register_key_notify(&ctrl_c, key_notify)
+        .expect("failed to register key notify");
+    input
+        .unregister_key_notify(notify_handle)
+        .expect("failed to unregister key notify");
+}
@@ -10,9 +10,11 @@ pub fn test() {
         gop::test();
     }
     pointer::test();
+    input_ex::test();
 }
 
 mod gop;
+mod input_ex;
 mod pointer;
 mod serial;
 mod stdout;
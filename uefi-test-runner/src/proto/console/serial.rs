@@ -1,4 +1,5 @@
 use crate::reconnect_serial_to_console;
+use core::time::Duration;
 use uefi::proto::console::serial::{ControlBits, Serial};
 use uefi::{boot, Result, ResultExt, Status};
 
@@ -20,6 +21,15 @@ fn serial_test_helper(serial: &mut Serial) -> Result {
 
     serial.set_control_bits(ctrl_bits)?;
 
+    // Enabling flow control again leaves the other bits unchanged.
+    serial.set_hardware_flow_control(true)?;
+    if !serial
+        .get_control_bits()?
+        .contains(ControlBits::SOFTWARE_LOOPBACK_ENABLE)
+    {
+        return Err(Status::ABORTED.into());
+    }
+
     // Keep this message short, we need it to fit in the FIFO.
     const OUTPUT: &[u8] = b"Hello world!";
     const MSG_LEN: usize = OUTPUT.len();
@@ -29,9 +39,31 @@ fn serial_test_helper(serial: &mut Serial) -> Result {
     let mut input = [0u8; MSG_LEN];
     serial.read(&mut input).discard_errdata()?;
 
+    // A non-blocking read returns the bytes that are available, which may
+    // be fewer than the size of the buffer.
+    serial.write(OUTPUT).discard_errdata()?;
+    let mut partial_input = [0u8; MSG_LEN + 4];
+    let len = serial.try_read(&mut partial_input)?;
+    if len != MSG_LEN || partial_input[..len] != *OUTPUT {
+        return Err(Status::ABORTED.into());
+    }
+
+    // Nothing left to read.
+    if serial.try_read(&mut partial_input)? != 0 {
+        return Err(Status::ABORTED.into());
+    }
+
+    let old_timeout = serial.io_mode().timeout;
+    serial.set_timeout(Duration::from_millis(1500))?;
+    if serial.io_mode().timeout != 1_500_000 {
+        return Err(Status::ABORTED.into());
+    }
+    serial.set_timeout(Duration::from_micros(old_timeout.into()))?;
+
     // Clean up after ourselves
     serial.reset()?;
-    serial.set_control_bits(old_ctrl_bits & ControlBits::SETTABLE)?;
+    // Read-only status bits are masked out by `set_control_bits`.
+    serial.set_control_bits(old_ctrl_bits)?;
 
     if OUTPUT == input {
         Ok(())
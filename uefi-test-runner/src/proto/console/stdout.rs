@@ -8,6 +8,8 @@ pub fn test(stdout: &mut Output) {
     change_text_mode(stdout);
     change_color(stdout);
     center_text(stdout);
+    overwrite_line(stdout);
+    output_str_lossy(stdout);
 
     // Print all modes.
     for (index, mode) in stdout.modes().enumerate() {
@@ -29,12 +31,16 @@ fn get_current_mode(stdout: &mut Output) {
     info!("UEFI standard output current mode: {:?}", current_mode);
 }
 
-// Switch to the maximum supported text mode.
+// Switch to the largest supported text mode.
 fn change_text_mode(stdout: &mut Output) {
-    let best_mode = stdout.modes().last().unwrap();
+    let best_mode = stdout.largest_mode().unwrap();
+    for mode in stdout.modes() {
+        assert!(mode.columns() * mode.rows() <= best_mode.columns() * best_mode.rows());
+    }
     stdout
-        .set_mode(best_mode)
+        .set_largest_mode()
         .expect("Failed to change text mode");
+    assert_eq!(stdout.current_mode().unwrap(), Some(best_mode));
 }
 
 // Set a new color, and paint the background with it.
@@ -66,3 +72,28 @@ fn center_text(stdout: &mut Output) {
             _ => panic!("Failed to hide cursor"),
         });
 }
+
+// Overwrite a line in place without moving the cursor.
+fn overwrite_line(stdout: &mut Output) {
+    let (column, row) = stdout.cursor_position();
+    stdout
+        .with_saved_cursor(|stdout| {
+            stdout.clear_line(0).expect("Failed to clear line");
+            assert_eq!(stdout.cursor_position(), (0, 0));
+        })
+        .expect("Failed to restore cursor");
+    assert_eq!(stdout.cursor_position(), (column, row));
+}
+
+// Print a Rust string containing characters that cannot be encoded in UCS-2.
+fn output_str_lossy(stdout: &mut Output) {
+    stdout
+        .output_str_lossy("test output_str_lossy: \u{1f600} \0 ok\n")
+        .expect("Failed to output string");
+
+    // Longer than the internal buffer.
+    let long = "0123456789\n".repeat(20);
+    stdout
+        .output_str_lossy(&long)
+        .expect("Failed to output long string");
+}
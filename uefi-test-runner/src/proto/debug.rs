@@ -172,5 +172,12 @@ extern "efiapi" fn periodic_callback(context: SystemContext) {
 
 extern "efiapi" fn exception_callback(exception_type: ExceptionType, context: SystemContext) {
     let _ = exception_type;
+
+    // Check that the registers are accessible. The callback is never
+    // invoked during the test, so nothing is done with them.
+    #[cfg(target_arch = "x86_64")]
+    let _ = unsafe { (*context.x64()).rip };
+    #[cfg(target_arch = "aarch64")]
+    let _ = unsafe { (*context.aarch64()).elr };
     let _ = context;
 }
@@ -1,8 +1,9 @@
+use alloc::format;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use uefi::boot;
 use uefi::proto::device_path::text::*;
-use uefi::proto::device_path::{DevicePath, LoadedImageDevicePath};
+use uefi::proto::device_path::{DevicePath, DevicePathNodeEnum, LoadedImageDevicePath};
 use uefi::proto::loaded_image::LoadedImage;
 use uefi::proto::media::disk::DiskIo;
 
@@ -44,12 +45,31 @@ pub fn test() {
             let text = &*text;
             info!("path name: {text}");
 
+            // The built-in formatting matches the firmware's for common nodes.
+            let builtin = format!("{path}");
+            info!("built-in path name: {builtin}");
+            if matches!(
+                path.as_enum(),
+                Ok(DevicePathNodeEnum::AcpiAcpi(_) | DevicePathNodeEnum::HardwarePci(_))
+            ) {
+                assert_eq!(builtin, text.to_string());
+            }
+
             let convert = device_path_from_text
                 .convert_text_to_device_node(text)
                 .expect("Failed to convert text to device path");
-            assert_eq!(path, convert);
+            assert_eq!(path, &*convert);
         }
 
+        // Test converting a whole device path from text.
+        let text = device_path_to_text
+            .convert_device_path_to_text(&device_path, DisplayOnly(false), AllowShortcuts(false))
+            .expect("Failed to convert device path to text");
+        let convert = device_path_from_text
+            .convert_text_to_device_path(&text)
+            .expect("Failed to convert text to device path");
+        assert_eq!(&*device_path, &*convert);
+
         // Get the `LoadedImageDevicePath`. Verify it start with the same nodes as
         // `device_path`.
         let loaded_image_device_path =
@@ -1,7 +1,11 @@
 use uefi::boot::{self, ScopedProtocol, SearchType};
 use uefi::prelude::*;
-use uefi::proto::driver::{ComponentName, ComponentName2, LanguageError, LanguageIter};
+use uefi::proto::driver::{
+    ComponentName, ComponentName2, DriverBinding, DriverBindingBuilder, LanguageError, LanguageIter,
+};
 use uefi::{CStr16, Result};
+use uefi_raw::protocol::device_path::DevicePathProtocol;
+use uefi_raw::protocol::driver::DriverBindingProtocol;
 
 #[allow(deprecated)]
 use uefi::proto::driver::ComponentName1;
@@ -116,6 +120,12 @@ fn test_component_name<C: ComponentNameInterface>(english: &str) {
         })
         .expect("failed to find FAT driver");
 
+    // An unsupported language is reported distinctly from a missing name.
+    assert_eq!(
+        component_name.driver_name("xx").unwrap_err().status(),
+        Status::INVALID_LANGUAGE
+    );
+
     // Now check that the FAT controller can be found by name.
     all_handles
         .iter()
@@ -133,6 +143,44 @@ fn test_component_name<C: ComponentNameInterface>(english: &str) {
         .expect("failed to find FAT controller");
 }
 
+unsafe extern "efiapi" fn unsupported(
+    _this: *const DriverBindingProtocol,
+    _controller_handle: uefi_raw::Handle,
+    _remaining_device_path: *const DevicePathProtocol,
+) -> uefi_raw::Status {
+    uefi_raw::Status::UNSUPPORTED
+}
+
+unsafe extern "efiapi" fn stop(
+    _this: *const DriverBindingProtocol,
+    _controller_handle: uefi_raw::Handle,
+    _number_of_children: usize,
+    _child_handle_buffer: *const uefi_raw::Handle,
+) -> uefi_raw::Status {
+    uefi_raw::Status::SUCCESS
+}
+
+fn test_driver_binding() {
+    info!("Running driver binding test");
+
+    // By default the protocol is installed on the image handle.
+    let handle = boot::image_handle();
+    let binding = DriverBindingBuilder::new(unsupported, unsupported, stop)
+        .version(0x1234)
+        .install()
+        .unwrap();
+
+    {
+        let opened = boot::open_protocol_exclusive::<DriverBinding>(handle).unwrap();
+        assert_eq!(opened.version(), 0x1234);
+        assert_eq!(opened.image_handle(), Some(boot::image_handle()));
+        assert_eq!(opened.driver_binding_handle(), Some(handle));
+    }
+
+    unsafe { DriverBinding::uninstall(binding) }.unwrap();
+    assert!(boot::open_protocol_exclusive::<DriverBinding>(handle).is_err());
+}
+
 pub fn test() {
     info!("Running component name test");
 
@@ -140,4 +188,6 @@ pub fn test() {
     test_component_name::<ScopedProtocol<ComponentName1>>("eng");
     test_component_name::<ScopedProtocol<ComponentName2>>("en");
     test_component_name::<ComponentName>("en");
+
+    test_driver_binding();
 }
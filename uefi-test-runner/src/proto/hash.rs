@@ -0,0 +1,51 @@
+use uefi::proto::hash::{Hash2, Hash2ServiceBinding, HashAlgorithm};
+use uefi::{boot, Status};
+
+/// SHA-256 hash of `b"abc"`.
+const SHA256_ABC: [u8; 32] = [
+    0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+    0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+];
+
+pub fn test() {
+    info!("Running hash2 protocol test");
+
+    let Ok(sb_handle) = boot::get_handle_for_protocol::<Hash2ServiceBinding>() else {
+        info!("Hash2 protocol is not supported");
+        return;
+    };
+    let mut sb = boot::open_protocol_exclusive::<Hash2ServiceBinding>(sb_handle)
+        .expect("failed to open hash2 service binding");
+    let handle = sb.create_child().expect("failed to create hash2 instance");
+
+    {
+        let mut hash =
+            boot::open_protocol_exclusive::<Hash2>(handle).expect("failed to open hash2 protocol");
+
+        assert_eq!(hash.get_hash_size(HashAlgorithm::SHA256), Ok(32));
+
+        // One-shot hash.
+        let mut out = [0; 64];
+        assert_eq!(hash.hash(HashAlgorithm::SHA256, b"abc", &mut out), Ok(32));
+        assert_eq!(out[..32], SHA256_ABC);
+
+        // Output buffer too small.
+        assert_eq!(
+            hash.hash(HashAlgorithm::SHA256, b"abc", &mut [0; 31])
+                .unwrap_err()
+                .status(),
+            Status::BUFFER_TOO_SMALL
+        );
+
+        // Incremental hash.
+        let mut out = [0; 32];
+        hash.hash_init(HashAlgorithm::SHA256).unwrap();
+        hash.hash_update(b"a").unwrap();
+        hash.hash_update(b"bc").unwrap();
+        assert_eq!(hash.hash_final(HashAlgorithm::SHA256, &mut out), Ok(32));
+        assert_eq!(out, SHA256_ABC);
+    }
+
+    sb.destroy_child(handle)
+        .expect("failed to destroy hash2 instance");
+}
@@ -108,6 +108,30 @@ pub fn test() {
     assert_eq!(load_data_msg, &loadfile_file_string);
     assert_eq!(load_data_msg, &loadfile2_file_string);
 
+    // Load without allocating: query the size first, then load into a
+    // buffer of that size.
+    let size = load_file_protocol
+        .load_file_into(dummy_dvp, BootPolicy::BootSelection, None)
+        .unwrap_err()
+        .data()
+        .unwrap();
+    assert_eq!(size, load_data_msg.len());
+    let mut buf = vec![0; size];
+    let len = load_file_protocol
+        .load_file_into(dummy_dvp, BootPolicy::BootSelection, Some(&mut buf))
+        .unwrap();
+    assert_eq!(&buf[..len], load_data_msg.as_bytes());
+
+    let err = load_file2_protocol
+        .load_file_into(dummy_dvp, Some(&mut buf[..4]))
+        .unwrap_err();
+    assert_eq!(err.status(), Status::BUFFER_TOO_SMALL);
+    assert_eq!(*err.data(), Some(load_data_msg.len()));
+    let len = load_file2_protocol
+        .load_file_into(dummy_dvp, Some(&mut buf))
+        .unwrap();
+    assert_eq!(&buf[..len], load_data_msg.as_bytes());
+
     // Cleanup: Uninstall protocols again.
     drop(load_file_protocol);
     drop(load_file2_protocol);
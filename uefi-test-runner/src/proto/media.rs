@@ -1,18 +1,19 @@
 use alloc::string::ToString;
+use alloc::vec::Vec;
 use core::cell::RefCell;
 use core::ptr::NonNull;
-use uefi::boot::{
-    self, EventType, OpenProtocolAttributes, OpenProtocolParams, ScopedProtocol, Tpl,
-};
+use uefi::boot::{self, EventType, ScopedProtocol, Tpl};
 use uefi::data_types::Align;
 use uefi::prelude::*;
 use uefi::proto::media::block::BlockIO;
 use uefi::proto::media::disk::{DiskIo, DiskIo2, DiskIo2Token};
 use uefi::proto::media::file::{
     Directory, File, FileAttribute, FileInfo, FileMode, FileSystemInfo, FileSystemVolumeLabel,
+    RemoveDirError, SeekFrom,
 };
 use uefi::proto::media::fs::SimpleFileSystem;
-use uefi::proto::media::partition::{MbrOsType, PartitionInfo};
+use uefi::proto::media::gpt::{self, GptHeader};
+use uefi::proto::media::partition::{MbrOsType, PartitionInfo, PartitionType};
 use uefi::runtime::{Daylight, Time, TimeParams};
 
 /// Test directory entry iteration.
@@ -75,6 +76,17 @@ fn test_existing_dir(directory: &mut Directory) {
         assert_eq!(&*entry_names, EXPECTED);
     };
 
+    // Reads the whole directory with an iterator.
+    let test_read_dir_iter = || {
+        let mut dir = dir.borrow_mut();
+        let mut entry_names = entry_names.borrow_mut();
+        for entry in dir.entries() {
+            let entry = entry.expect("failed to read directory");
+            entry_names.push(entry.file_name().to_string());
+        }
+        assert_eq!(&*entry_names, EXPECTED);
+    };
+
     // Tests all read dir test functions three times.
     for _ in 0..3 {
         entry_names.borrow_mut().clear();
@@ -84,6 +96,10 @@ fn test_existing_dir(directory: &mut Directory) {
         entry_names.borrow_mut().clear();
         dir.borrow_mut().reset_entry_readout().unwrap();
         test_read_dir_heap_mem();
+
+        entry_names.borrow_mut().clear();
+        dir.borrow_mut().reset_entry_readout().unwrap();
+        test_read_dir_iter();
     }
 }
 
@@ -123,6 +139,38 @@ fn test_existing_file(directory: &mut Directory) {
     info!("Successfully read {}", input_file_path);
     assert_eq!(buffer, b"test input data");
 
+    // Read the whole file again into a growing buffer.
+    file.set_position(0).unwrap();
+    let mut buffer = Vec::new();
+    assert_eq!(file.read_to_end(&mut buffer), Ok(15));
+    assert_eq!(buffer, b"test input data");
+
+    // Seek relative to the start, end, and current position.
+    let mut buffer = [0; 5];
+    assert_eq!(file.seek(SeekFrom::Start(5)), Ok(5));
+    assert_eq!(file.read(&mut buffer), Ok(5));
+    assert_eq!(&buffer, b"input");
+    assert_eq!(file.seek(SeekFrom::End(-4)), Ok(11));
+    assert_eq!(file.read(&mut buffer), Ok(4));
+    assert_eq!(&buffer[..4], b"data");
+    assert_eq!(file.seek(SeekFrom::Current(-10)), Ok(5));
+    assert_eq!(
+        file.seek(SeekFrom::Current(-6)),
+        Err(Status::INVALID_PARAMETER.into())
+    );
+    assert_eq!(file.get_position(), Ok(5));
+    assert_eq!(
+        file.seek(SeekFrom::End(-16)),
+        Err(Status::INVALID_PARAMETER.into())
+    );
+    assert_eq!(file.get_position(), Ok(5));
+    // Reads at the end of the file return no data.
+    assert_eq!(file.seek(SeekFrom::End(0)), Ok(15));
+    assert_eq!(file.read(&mut buffer), Ok(0));
+    // Seeking past the end of the file is allowed.
+    assert_eq!(file.seek(SeekFrom::End(10)), Ok(25));
+    assert_eq!(file.get_position(), Ok(25));
+
     // Check file metadata.
     let mut info_buffer = vec![0; 128];
     let info = file.get_info::<FileInfo>(&mut info_buffer).unwrap();
@@ -192,7 +240,58 @@ fn test_create_file(directory: &mut Directory) {
     assert!(file.is_regular_file().unwrap());
 
     let mut file = file.into_regular_file().expect("not a regular file");
-    file.write(b"test output data").unwrap();
+    file.write_all(b"test output data").unwrap();
+
+    // The file can be written, so it can be opened exclusively even though
+    // it is still open above. See `Directory::open_exclusive` for why that
+    // is not detected.
+    let file = directory
+        .open_exclusive(
+            cstr16!("new_test_file.txt"),
+            FileMode::Read,
+            FileAttribute::empty(),
+        )
+        .expect("failed to open file exclusively");
+    assert!(file.is_regular_file().unwrap());
+}
+
+/// Test renaming a file with `File::rename`.
+fn test_rename_file(directory: &mut Directory) {
+    info!("Testing file rename");
+
+    let mut file = directory
+        .open(
+            cstr16!("rename_src.txt"),
+            FileMode::CreateReadWrite,
+            FileAttribute::empty(),
+        )
+        .expect("failed to create file")
+        .into_regular_file()
+        .expect("not a regular file");
+    file.write(b"rename").unwrap();
+    file.rename(cstr16!("rename_dst.txt")).unwrap();
+    assert_eq!(
+        file.get_boxed_info::<FileInfo>().unwrap().file_name(),
+        cstr16!("rename_dst.txt")
+    );
+    file.close();
+
+    // The file is only reachable under its new name.
+    assert!(directory
+        .open(
+            cstr16!("rename_src.txt"),
+            FileMode::Read,
+            FileAttribute::empty()
+        )
+        .is_err());
+    let file = directory
+        .open(
+            cstr16!("rename_dst.txt"),
+            FileMode::ReadWrite,
+            FileAttribute::empty(),
+        )
+        .expect("failed to open renamed file");
+    file.delete().unwrap();
 }
 
 /// Test directory creation by
@@ -241,24 +340,69 @@ fn test_create_directory(root_dir: &mut Directory) {
     let read = &buf[0..read_bytes];
 
     assert_eq!(msg.as_bytes(), read);
+    drop(file);
+
+    // The directory can only be removed once it is empty.
+    assert!(!new_dir.is_empty().unwrap());
+    assert_eq!(
+        root_dir.remove_dir(cstr16!("created_dir")),
+        Err(RemoveDirError::NotEmpty)
+    );
+    assert_eq!(
+        root_dir.remove_dir(cstr16!("created_dir\\foobar")),
+        Err(RemoveDirError::NotADirectory)
+    );
+    new_dir
+        .open(
+            cstr16!("foobar"),
+            FileMode::ReadWrite,
+            FileAttribute::empty(),
+        )
+        .unwrap()
+        .delete()
+        .unwrap();
+    assert!(new_dir.is_empty().unwrap());
+    drop(new_dir);
+    root_dir.remove_dir(cstr16!("created_dir")).unwrap();
 }
 
 /// Get the media ID via the BlockIO protocol.
 fn get_block_media_id(handle: Handle) -> u32 {
     // This cannot be opened in `EXCLUSIVE` mode, as doing so
     // unregisters the `DiskIO` protocol from the handle.
-    unsafe {
-        let block_io = boot::open_protocol::<BlockIO>(
-            OpenProtocolParams {
-                handle,
-                agent: boot::image_handle(),
-                controller: None,
-            },
-            OpenProtocolAttributes::GetProtocol,
-        )
+    let block_io = unsafe { boot::open_protocol_get::<BlockIO>(handle) }
         .expect("Failed to get block I/O protocol");
-        block_io.media().media_id()
-    }
+    block_io.media().media_id()
+}
+
+/// Tests raw block I/O.
+fn test_raw_block_io(handle: Handle) {
+    info!("Testing raw block I/O");
+
+    // This cannot be opened in `EXCLUSIVE` mode, as doing so
+    // unregisters the `DiskIO` protocol from the handle.
+    let block_io = unsafe { boot::open_protocol_get::<BlockIO>(handle) }
+        .expect("Failed to get block I/O protocol");
+    let media = block_io.media();
+    let block_size = usize::try_from(media.block_size()).unwrap();
+
+    // Read the first block of the disk and verify the MBR signature.
+    let mut buf = vec![0; block_size];
+    block_io
+        .read_blocks(media.media_id(), 0, &mut buf)
+        .expect("Failed to read from disk");
+    assert_eq!(buf[510], 0x55);
+    assert_eq!(buf[511], 0xaa);
+
+    // A buffer that isn't a multiple of the block size is rejected.
+    let mut buf = vec![0; block_size - 1];
+    assert_eq!(
+        block_io
+            .read_blocks(media.media_id(), 0, &mut buf)
+            .unwrap_err()
+            .status(),
+        Status::INVALID_PARAMETER
+    );
 }
 
 /// Tests raw disk I/O.
@@ -281,6 +425,33 @@ fn test_raw_disk_io(handle: Handle) {
     assert_eq!(buf[510], 0x55);
     assert_eq!(buf[511], 0xaa);
 
+    // A range that overflows a `u64` is rejected.
+    assert_eq!(
+        disk_io
+            .read_disk(media_id, u64::MAX, &mut buf)
+            .unwrap_err()
+            .status(),
+        Status::INVALID_PARAMETER
+    );
+
+    // The test disk uses MBR, so there is no GPT header.
+    let block_size = unsafe { boot::open_protocol_get::<BlockIO>(handle) }
+        .expect("Failed to get block I/O protocol")
+        .media()
+        .block_size();
+    assert_eq!(
+        GptHeader::read(&disk_io, media_id, block_size)
+            .unwrap_err()
+            .status(),
+        Status::NOT_FOUND
+    );
+    assert_eq!(
+        gpt::partition_entries(&disk_io, media_id, block_size)
+            .unwrap_err()
+            .status(),
+        Status::NOT_FOUND
+    );
+
     info!("Raw disk I/O succeeded");
 }
 
@@ -345,6 +516,8 @@ fn test_partition_info(disk_handle: Handle) {
     let pi = boot::open_protocol_exclusive::<PartitionInfo>(disk_handle)
         .expect("Failed to get partition info");
 
+    assert_eq!({ pi.partition_type }, PartitionType::MBR);
+    assert!(pi.gpt_partition_entry().is_none());
     let mbr = pi.mbr_partition_record().expect("Not an MBR disk");
 
     info!("MBR partition: {:?}", mbr);
@@ -427,6 +600,7 @@ pub fn test() {
         test_delete_warning(&mut root_directory);
         test_existing_file(&mut root_directory);
         test_create_file(&mut root_directory);
+        test_rename_file(&mut root_directory);
         test_create_directory(&mut root_directory);
 
         test_partition_info(handle);
@@ -435,10 +609,12 @@ pub fn test() {
     // Invoke the fs test after the basic low-level file system protocol
     // tests succeeded.
 
-    // This will also drop the `SimpleFileSystem` protocol so that the raw disk
-    // tests work.
-    crate::fs::test(sfs).unwrap();
+    // Close the `SimpleFileSystem` protocol so that the fs test can open it
+    // again, and so that the raw disk tests work afterwards.
+    drop(sfs);
+    crate::fs::test(handle).unwrap();
 
+    test_raw_block_io(handle);
     test_raw_disk_io(handle);
     test_raw_disk_io2(handle);
 }
@@ -1,9 +1,45 @@
+use uefi::guid;
 use uefi::prelude::*;
-use uefi::proto::misc::ResetNotification;
+use uefi::proto::device_path::DevicePath;
+use uefi::proto::loaded_image::LoadedImage;
+use uefi::proto::misc::{BootManagerPolicy, ResetNotification};
 use uefi::runtime::ResetType;
 
 pub fn test() {
     test_reset_notification();
+    test_boot_manager_policy();
+}
+
+fn test_boot_manager_policy() {
+    info!("Running BootManagerPolicy protocol test");
+
+    let Ok(handle) = boot::get_handle_for_protocol::<BootManagerPolicy>() else {
+        info!("BootManagerPolicy protocol is not supported");
+        return;
+    };
+    let policy = boot::open_protocol_exclusive::<BootManagerPolicy>(handle)
+        .expect("failed to open BootManagerPolicy protocol");
+
+    policy
+        .connect_device_class(&BootManagerPolicy::CONSOLE)
+        .expect("failed to connect console devices");
+
+    // Unknown device classes are rejected.
+    assert_eq!(
+        policy.connect_device_class(&guid!("5e4b4ab5-4dd1-4f0a-b0bb-0b4d1c4f3c0e")),
+        Err(Status::NOT_FOUND.into())
+    );
+
+    // Connecting the device the image was loaded from succeeds, since it is
+    // already connected.
+    let loaded_image = boot::open_protocol_exclusive::<LoadedImage>(boot::image_handle())
+        .expect("failed to open LoadedImage protocol");
+    let device = loaded_image.device().expect("image has no device handle");
+    let device_path = boot::open_protocol_exclusive::<DevicePath>(device)
+        .expect("failed to open DevicePath protocol");
+    policy
+        .connect_device_path(&device_path, false)
+        .expect("failed to connect device path");
 }
 
 pub fn test_reset_notification() {
@@ -33,10 +69,30 @@ pub fn test_reset_notification() {
         "ResetNotification Protocol register efi_reset_fn test: {:?}",
         result
     );
+    if result.is_ok() {
+        // Registering the same function twice fails.
+        assert_eq!(
+            reset_notif_proto
+                .register_reset_notify(efi_reset_fn)
+                .unwrap_err()
+                .status(),
+            Status::ALREADY_STARTED
+        );
+    }
 
     let result = reset_notif_proto.unregister_reset_notify(efi_reset_fn);
     info!(
         "ResetNotification Protocol unregister efi_reset_fn test: {:?}",
         result
     );
+    if result.is_ok() {
+        // The function is no longer registered.
+        assert_eq!(
+            reset_notif_proto
+                .unregister_reset_notify(efi_reset_fn)
+                .unwrap_err()
+                .status(),
+            Status::INVALID_PARAMETER
+        );
+    }
 }
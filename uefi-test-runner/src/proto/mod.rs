@@ -1,6 +1,6 @@
-use uefi::boot::{self, OpenProtocolParams};
+use uefi::boot::{self, OpenProtocolAttributes, OpenProtocolParams};
 use uefi::proto::loaded_image::LoadedImage;
-use uefi::{proto, Identify};
+use uefi::{proto, Identify, Status};
 
 pub fn test() {
     info!("Testing various protocols");
@@ -9,17 +9,21 @@ pub fn test() {
 
     find_protocol();
     test_protocols_per_handle();
+    test_describe_handle();
     test_test_protocol();
+    test_open_protocol_by_driver();
 
     debug::test();
     device_path::test();
     driver::test();
+    hash::test();
     load::test();
     loaded_image::test();
     media::test();
     network::test();
     pi::test();
     rng::test();
+    security::test();
     shell_params::test();
     string::test();
     misc::test();
@@ -32,6 +36,7 @@ pub fn test() {
     ))]
     shim::test();
     tcg::test();
+    usb::test();
 }
 
 fn find_protocol() {
@@ -51,6 +56,52 @@ fn test_protocols_per_handle() {
     assert!(pph.iter().any(|guid| **guid == LoadedImage::GUID));
 }
 
+fn test_describe_handle() {
+    let description = boot::describe_handle(boot::image_handle()).unwrap();
+    info!("Image handle protocols: {description}");
+    assert_eq!(description.handle(), boot::image_handle());
+    assert!(description.protocols().contains(&LoadedImage::GUID));
+    assert!(description
+        .protocol_names()
+        .any(|name| name == Some("LoadedImage")));
+}
+
+fn test_open_protocol_by_driver() {
+    let image = boot::image_handle();
+
+    // ByDriver requires a controller handle.
+    let result = unsafe {
+        boot::open_protocol::<LoadedImage>(
+            OpenProtocolParams {
+                handle: image,
+                agent: image,
+                controller: None,
+            },
+            OpenProtocolAttributes::ByDriver,
+        )
+    };
+    assert_eq!(result.unwrap_err().status(), Status::INVALID_PARAMETER);
+
+    // Open the protocol, keep it open past the end of the `ScopedProtocol`,
+    // and close it explicitly.
+    let params = || OpenProtocolParams {
+        handle: image,
+        agent: image,
+        controller: Some(image),
+    };
+    let loaded_image =
+        unsafe { boot::open_protocol::<LoadedImage>(params(), OpenProtocolAttributes::ByDriver) }
+            .unwrap();
+    assert!(loaded_image.leak().is_some());
+    unsafe { boot::close_protocol::<LoadedImage>(params()) }.unwrap();
+    assert_eq!(
+        unsafe { boot::close_protocol::<LoadedImage>(params()) }
+            .unwrap_err()
+            .status(),
+        Status::NOT_FOUND
+    );
+}
+
 fn test_test_protocol() {
     assert!(boot::test_protocol::<LoadedImage>(OpenProtocolParams {
         handle: boot::image_handle(),
@@ -64,6 +115,7 @@ mod console;
 mod debug;
 mod device_path;
 mod driver;
+mod hash;
 mod load;
 mod loaded_image;
 mod media;
@@ -71,6 +123,7 @@ mod misc;
 mod network;
 mod pi;
 mod rng;
+mod security;
 mod shell_params;
 #[cfg(any(
     target_arch = "x86",
@@ -81,3 +134,4 @@ mod shell_params;
 mod shim;
 mod string;
 mod tcg;
+mod usb;
@@ -0,0 +1,47 @@
+use uefi::proto::network::dhcp4::{Dhcp4, Dhcp4ServiceBinding, Dhcp4State};
+use uefi::{boot, Status};
+
+pub fn test() {
+    info!("Testing the DHCPv4 protocol");
+
+    let handles = boot::find_handles::<Dhcp4ServiceBinding>().unwrap_or_default();
+    if handles.is_empty() {
+        info!("No DHCPv4 service binding found, skipping test");
+        return;
+    }
+
+    for handle in handles {
+        let mut service_binding =
+            boot::open_protocol_exclusive::<Dhcp4ServiceBinding>(handle).unwrap();
+        let child = service_binding
+            .create_child()
+            .expect("Failed to create DHCPv4 instance");
+
+        {
+            let mut dhcp = boot::open_protocol_exclusive::<Dhcp4>(child).unwrap();
+            assert_eq!(dhcp.mode_data().unwrap().state, Dhcp4State::STOPPED);
+
+            match dhcp.start() {
+                Ok(()) => {
+                    let lease = dhcp.mode_data().unwrap();
+                    info!("DHCPv4 lease: {:?}", lease);
+                    assert_eq!(lease.state, Dhcp4State::BOUND);
+                    // QEMU's user network is 192.168.17.0/24.
+                    assert_eq!(lease.client_address.0[..3], [192, 168, 17]);
+                    assert_eq!(lease.subnet_mask.0, [255, 255, 255, 0]);
+                    dhcp.stop().unwrap();
+                }
+                // Another DHCPv4 instance on the same device (e.g. the one
+                // used by PXE) may already be configured.
+                Err(err) if err.status() == Status::ACCESS_DENIED => {
+                    info!("DHCPv4 already in use, skipping test");
+                }
+                Err(err) => panic!("DHCPv4 handshake failed: {err:?}"),
+            }
+        }
+
+        service_binding
+            .destroy_child(child)
+            .expect("Failed to destroy DHCPv4 instance");
+    }
+}
@@ -0,0 +1,85 @@
+use core::time::Duration;
+use uefi::proto::network::http::{
+    Http, HttpAccessPoint, HttpConfigData, HttpServiceBinding, HttpV4AccessPoint, HttpVersion,
+};
+use uefi::proto::network::Ipv4Address;
+use uefi::{boot, cstr16, Status};
+
+pub fn test() {
+    info!("Testing the HTTP protocol");
+
+    let handles = boot::find_handles::<HttpServiceBinding>().unwrap_or_default();
+    if handles.is_empty() {
+        info!("No HTTP service binding found, skipping test");
+        return;
+    }
+
+    for handle in handles {
+        let mut service_binding =
+            boot::open_protocol_exclusive::<HttpServiceBinding>(handle).unwrap();
+        let child = service_binding
+            .create_child()
+            .expect("Failed to create HTTP instance");
+
+        {
+            let mut http = boot::open_protocol_exclusive::<Http>(child).unwrap();
+
+            // URLs without a scheme are rejected before calling the firmware.
+            assert_eq!(
+                http.request_get(cstr16!("example.com/"), Duration::from_secs(1))
+                    .unwrap_err()
+                    .status(),
+                Status::INVALID_PARAMETER
+            );
+
+            // Sending a request with an unconfigured instance fails
+            // immediately.
+            assert_eq!(
+                http.request_get(cstr16!("http://example.com/"), Duration::from_secs(1))
+                    .unwrap_err()
+                    .status(),
+                Status::NOT_STARTED
+            );
+
+            test_request_timeout(&mut http);
+        }
+
+        service_binding
+            .destroy_child(child)
+            .expect("Failed to destroy HTTP instance");
+    }
+}
+
+/// Check that a request that doesn't complete within the timeout is stopped
+/// and leaves the instance usable.
+fn test_request_timeout(http: &mut Http) {
+    let config = HttpConfigData {
+        http_version: HttpVersion::HTTP_VERSION_11,
+        timeout: Duration::from_secs(1),
+        access_point: HttpAccessPoint::Ipv4(HttpV4AccessPoint {
+            use_default_addr: true,
+            local_address: Ipv4Address([0; 4]),
+            local_subnet: Ipv4Address([0; 4]),
+            local_port: 0,
+        }),
+    };
+    match http.configure(Some(&config)) {
+        Ok(()) => {}
+        Err(err) if err.status() == Status::NO_MAPPING => {
+            info!("No IPv4 address yet, skipping HTTP timeout test");
+            return;
+        }
+        Err(err) => panic!("Failed to configure HTTP instance: {err:?}"),
+    }
+
+    // 192.0.2.0/24 is reserved for documentation, so the request can't
+    // complete. Depending on the implementation, it either times out or the
+    // firmware gives up on connecting first.
+    let err = http
+        .request_get(cstr16!("http://192.0.2.1/"), Duration::ZERO)
+        .unwrap_err();
+    info!("Request to unreachable host failed with {:?}", err.status());
+
+    // The stopped request must not leave anything pending in the instance.
+    http.configure(None).unwrap();
+}
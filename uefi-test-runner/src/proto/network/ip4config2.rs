@@ -0,0 +1,32 @@
+use uefi::proto::network::ip4config2::{Ip4Config2, Ip4Config2Policy};
+use uefi::proto::network::Ipv4Address;
+use uefi::{boot, Status};
+
+pub fn test() {
+    info!("Testing the IPv4 configuration protocol");
+
+    let handles = boot::find_handles::<Ip4Config2>().unwrap_or_default();
+    for handle in handles {
+        let mut config = boot::open_protocol_exclusive::<Ip4Config2>(handle).unwrap();
+
+        let info = config.get_interface_info().unwrap();
+        info!("Interface {}: {:?}", info.name, info.station_address);
+        assert_eq!(info.hw_address_size, 6);
+
+        let policy = config.policy().unwrap();
+        config.set_policy(policy).unwrap();
+        if policy == Ip4Config2Policy::DHCP {
+            // A manual address can't be set while DHCP is in use.
+            assert_eq!(
+                config
+                    .set_manual_address(
+                        Ipv4Address([192, 168, 17, 99]),
+                        Ipv4Address([255, 255, 255, 0])
+                    )
+                    .unwrap_err()
+                    .status(),
+                Status::ACCESS_DENIED
+            );
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use core::time::Duration;
+use uefi::proto::network::mnp::{ManagedNetwork, ManagedNetworkServiceBinding, MnpConfigData};
+use uefi::proto::network::MacAddress;
+use uefi::{boot, Status};
+
+/// Ethertype reserved for local experiments.
+const ETHERTYPE: u16 = 0x88b5;
+
+pub fn test() {
+    info!("Testing the Managed Network protocol");
+
+    let handles = boot::find_handles::<ManagedNetworkServiceBinding>().unwrap_or_default();
+    if handles.is_empty() {
+        info!("No Managed Network service binding found, skipping test");
+        return;
+    }
+
+    for handle in handles {
+        let mut service_binding =
+            boot::open_protocol_exclusive::<ManagedNetworkServiceBinding>(handle).unwrap();
+        let child = service_binding
+            .create_child()
+            .expect("Failed to create Managed Network instance");
+
+        {
+            let mut mnp = boot::open_protocol_exclusive::<ManagedNetwork>(child).unwrap();
+
+            // The device mode is available before the instance is configured.
+            let (_, mode) = mnp.get_mode_data().expect("Failed to get mode data");
+            info!(
+                "Managed Network MAC address: {:02x?}",
+                &mode.current_address.0[..6]
+            );
+            assert_ne!(mode.current_address.0, [0; 32]);
+
+            let broadcast = MacAddress([0xff; 32]);
+
+            // Sending from an unconfigured instance fails immediately.
+            assert_eq!(
+                mnp.transmit(b"test", &broadcast, ETHERTYPE, Duration::from_secs(1))
+                    .unwrap_err()
+                    .status(),
+                Status::NOT_STARTED
+            );
+
+            let config = MnpConfigData {
+                protocol_type_filter: ETHERTYPE,
+                enable_unicast_receive: true,
+                ..Default::default()
+            };
+            mnp.configure(Some(&config))
+                .expect("Failed to configure Managed Network instance");
+            assert_eq!(mnp.get_mode_data().unwrap().0, config);
+
+            mnp.transmit(b"test", &broadcast, ETHERTYPE, Duration::from_secs(1))
+                .expect("Failed to transmit frame");
+
+            // Nothing is sent to this address with this type, so receiving
+            // times out.
+            let mut buffer = [0; 64];
+            assert_eq!(
+                mnp.receive(&mut buffer, Duration::from_millis(100))
+                    .unwrap_err()
+                    .status(),
+                Status::TIMEOUT
+            );
+
+            mnp.configure(None)
+                .expect("Failed to reset Managed Network instance");
+        }
+
+        service_binding
+            .destroy_child(child)
+            .expect("Failed to destroy Managed Network instance");
+    }
+}
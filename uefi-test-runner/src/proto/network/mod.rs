@@ -2,8 +2,20 @@ pub fn test() {
     info!("Testing Network protocols");
 
     pxe::test();
+    dhcp4::test();
+    ip4config2::test();
+    mnp::test();
+    tcp::test();
+    udp::test();
+    http::test();
     snp::test();
 }
 
+mod dhcp4;
+mod http;
+mod ip4config2;
+mod mnp;
 mod pxe;
 mod snp;
+mod tcp;
+mod udp;
@@ -1,6 +1,6 @@
 use uefi::proto::network::pxe::{BaseCode, DhcpV4Packet, IpFilter, IpFilters, UdpOpFlags};
 use uefi::proto::network::IpAddress;
-use uefi::{boot, CStr8};
+use uefi::{boot, CStr8, Status};
 
 pub fn test() {
     // Skip the test if the `pxe` feature is not enabled.
@@ -45,6 +45,13 @@ pub fn test() {
         let len = usize::try_from(len).unwrap();
         assert_eq!(EXAMPLE_FILE_CONTENT, &buffer[..len]);
 
+        info!("Reading remote file into a too-small buffer");
+        let err = base_code
+            .tftp_read_file(&server_ip, example_file_name, Some(&mut buffer[..4]))
+            .unwrap_err();
+        assert_eq!(err.status(), Status::BUFFER_TOO_SMALL);
+        assert!(err.data().is_some());
+
         base_code
             .set_ip_filter(&IpFilter::new(IpFilters::STATION_IP, &[]))
             .expect("failed to set IP filter");
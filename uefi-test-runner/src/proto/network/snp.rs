@@ -1,6 +1,6 @@
+use uefi::boot;
 use uefi::proto::network::snp::{InterruptStatus, ReceiveFlags, SimpleNetwork};
 use uefi::proto::network::MacAddress;
-use uefi::{boot, Status};
 
 pub fn test() {
     info!("Testing the simple network protocol");
@@ -98,17 +98,26 @@ pub fn test() {
         let mut buffer = [0u8; 1500];
 
         info!("Waiting for the reception");
-        if simple_network.receive(&mut buffer, None, None, None, None)
-            == Err(Status::NOT_READY.into())
-        {
-            boot::stall(1_000_000);
-
-            simple_network
-                .receive(&mut buffer, None, None, None, None)
-                .unwrap();
-        }
+        let info = match simple_network.try_receive(&mut buffer).unwrap() {
+            Some(info) => info,
+            None => {
+                boot::stall(1_000_000);
+
+                simple_network
+                    .try_receive(&mut buffer)
+                    .unwrap()
+                    .expect("no packet received")
+            }
+        };
 
         assert_eq!(buffer[42..47], [4, 4, 3, 2, 1]);
+        assert_eq!(
+            info.header_size,
+            simple_network.mode().media_header_size as usize
+        );
+        // Only the first `hw_address_size` bytes of the address are set.
+        assert_eq!(info.dest_addr.0[..6], [0xff; 6]);
+        assert_eq!(info.protocol, 0x0800);
 
         // Get stats
         let stats = simple_network
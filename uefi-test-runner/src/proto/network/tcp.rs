@@ -0,0 +1,36 @@
+use core::time::Duration;
+use uefi::proto::network::tcp::{Tcp4, Tcp4ConnectionState, Tcp4ServiceBinding};
+use uefi::{boot, Status};
+
+pub fn test() {
+    info!("Testing the TCPv4 protocol");
+
+    let handles = boot::find_handles::<Tcp4ServiceBinding>().unwrap_or_default();
+    if handles.is_empty() {
+        info!("No TCPv4 service binding found, skipping test");
+        return;
+    }
+
+    for handle in handles {
+        let mut service_binding =
+            boot::open_protocol_exclusive::<Tcp4ServiceBinding>(handle).unwrap();
+        let child = service_binding
+            .create_child()
+            .expect("Failed to create TCPv4 instance");
+
+        {
+            let mut tcp = boot::open_protocol_exclusive::<Tcp4>(child).unwrap();
+            assert_eq!(tcp.state().unwrap(), Tcp4ConnectionState::CLOSED);
+
+            // Connecting an unconfigured instance fails immediately.
+            assert_eq!(
+                tcp.connect(Duration::from_secs(1)).unwrap_err().status(),
+                Status::NOT_STARTED
+            );
+        }
+
+        service_binding
+            .destroy_child(child)
+            .expect("Failed to destroy TCPv4 instance");
+    }
+}
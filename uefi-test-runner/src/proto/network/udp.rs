@@ -0,0 +1,69 @@
+use core::time::Duration;
+use uefi::proto::network::udp::{Udp4, Udp4ConfigData, Udp4ServiceBinding};
+use uefi::proto::network::Ipv4Address;
+use uefi::{boot, Status};
+
+pub fn test() {
+    info!("Testing the UDPv4 protocol");
+
+    let handles = boot::find_handles::<Udp4ServiceBinding>().unwrap_or_default();
+    if handles.is_empty() {
+        info!("No UDPv4 service binding found, skipping test");
+        return;
+    }
+
+    for handle in handles {
+        let mut service_binding =
+            boot::open_protocol_exclusive::<Udp4ServiceBinding>(handle).unwrap();
+        let child = service_binding
+            .create_child()
+            .expect("Failed to create UDPv4 instance");
+
+        {
+            let mut udp = boot::open_protocol_exclusive::<Udp4>(child).unwrap();
+
+            // Sending from an unconfigured instance fails immediately.
+            assert_eq!(
+                udp.transmit(
+                    b"test",
+                    Ipv4Address([10, 0, 2, 2]),
+                    9,
+                    Duration::from_secs(1)
+                )
+                .unwrap_err()
+                .status(),
+                Status::NOT_STARTED
+            );
+
+            // A zero station address uses the default address, which may not
+            // have been acquired yet.
+            let config = Udp4ConfigData {
+                time_to_live: 64,
+                station_port: 4321,
+                accept_any_port: true,
+                ..Default::default()
+            };
+            match udp.configure(Some(&config)) {
+                Ok(()) => {
+                    // Nothing is sent to this port, so receiving times out.
+                    let mut buffer = [0; 64];
+                    assert_eq!(
+                        udp.receive(&mut buffer, Duration::from_millis(100))
+                            .unwrap_err()
+                            .status(),
+                        Status::TIMEOUT
+                    );
+                    udp.configure(None).expect("Failed to reset UDPv4 instance");
+                }
+                Err(err) if err.status() == Status::NO_MAPPING => {
+                    info!("UDPv4 default address not yet available");
+                }
+                Err(err) => panic!("Failed to configure UDPv4 instance: {err:?}"),
+            }
+        }
+
+        service_binding
+            .destroy_child(child)
+            .expect("Failed to destroy UDPv4 instance");
+    }
+}
@@ -25,6 +25,7 @@ pub fn test() {
     test_get_number_of_processors(mp_support);
     test_get_processor_info(mp_support);
     test_startup_all_aps(mp_support);
+    test_run_on_all_aps(mp_support);
     test_startup_this_ap(mp_support);
     test_enable_disable_ap(mp_support);
     test_switch_bsp_and_who_am_i(mp_support);
@@ -74,7 +75,7 @@ extern "efiapi" fn proc_increment_atomic(arg: *mut c_void) {
 }
 
 extern "efiapi" fn proc_wait_100ms(_: *mut c_void) {
-    boot::stall(100_000);
+    boot::stall_for(Duration::from_millis(100));
 }
 
 fn test_startup_all_aps(mps: &MpServices) {
@@ -96,6 +97,35 @@ fn test_startup_all_aps(mps: &MpServices) {
     assert_eq!(ret.map_err(|err| err.status()), Err(Status::TIMEOUT));
 }
 
+fn test_run_on_all_aps(mps: &MpServices) {
+    // Ensure that the closure runs once on each AP with the right
+    // processor number.
+    let counter = AtomicUsize::new(0);
+    let processor_sum = AtomicUsize::new(0);
+    mps.run_on_all_aps(
+        |processor_number| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            processor_sum.fetch_add(processor_number, Ordering::Relaxed);
+        },
+        false,
+        None,
+    )
+    .unwrap();
+    assert_eq!(counter.load(Ordering::Relaxed), NUM_CPUS - 1);
+    assert_eq!(
+        processor_sum.load(Ordering::Relaxed),
+        (1..NUM_CPUS).sum::<usize>()
+    );
+
+    // Make sure that timeout works
+    let ret = mps.run_on_all_aps(
+        |_| boot::stall_for(Duration::from_millis(100)),
+        true,
+        Some(Duration::from_millis(50)),
+    );
+    assert_eq!(ret.map_err(|err| err.status()), Err(Status::TIMEOUT));
+}
+
 fn test_startup_this_ap(mps: &MpServices) {
     // Ensure that each AP starts up
     let counter = AtomicUsize::new(0);
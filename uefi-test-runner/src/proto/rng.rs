@@ -14,10 +14,20 @@ pub fn test() {
     let list = rng.get_info(&mut list).unwrap();
     info!("Supported rng algorithms : {:?}", list);
 
+    let boxed_list = rng.get_info_boxed().unwrap();
+    assert_eq!(&*boxed_list, list);
+
     let mut buf = [0u8; 4];
 
     rng.get_rng(Some(list[0]), &mut buf).unwrap();
 
     assert_ne!([0u8; 4], buf);
     info!("Random buffer : {:?}", buf);
+
+    let guid1 = rng.get_guid().unwrap();
+    let guid2 = rng.get_guid().unwrap();
+    info!("Random GUID : {guid1}");
+    assert_eq!(guid1.version(), 4);
+    assert_eq!(guid1.to_bytes()[8] & 0xc0, 0x80);
+    assert_ne!(guid1, guid2);
 }
@@ -0,0 +1,69 @@
+use uefi::boot::{AllocateType, MemoryType, PAGE_SIZE};
+use uefi::mem::memory_map::MemoryAttribute;
+use uefi::proto::device_path::LoadedImageDevicePath;
+use uefi::proto::security::{MemoryProtection, Security2};
+use uefi::{boot, Status};
+
+pub fn test() {
+    test_security2();
+    test_memory_protection();
+}
+
+fn test_security2() {
+    info!("Running Security2 protocol test");
+
+    let Ok(handle) = boot::get_handle_for_protocol::<Security2>() else {
+        info!("Security2 protocol is not supported");
+        return;
+    };
+    let security =
+        boot::open_protocol_exclusive::<Security2>(handle).expect("failed to open Security2");
+
+    // Check the policy for this image, which has already been loaded and
+    // started, so it must not be rejected outright.
+    let device_path = boot::open_protocol_exclusive::<LoadedImageDevicePath>(boot::image_handle())
+        .expect("failed to open LoadedImageDevicePath");
+    let result = security.file_authentication(&device_path, None, false);
+    info!("Security2 file authentication of this image: {result:?}");
+    assert_ne!(result, Err(Status::ACCESS_DENIED.into()));
+}
+
+fn test_memory_protection() {
+    info!("Running MemoryProtection protocol test");
+
+    let Ok(handle) = boot::get_handle_for_protocol::<MemoryProtection>() else {
+        info!("MemoryProtection protocol is not supported");
+        return;
+    };
+    let memory_protection = boot::open_protocol_exclusive::<MemoryProtection>(handle)
+        .expect("failed to open MemoryProtection");
+
+    let page = boot::allocate_pages(AllocateType::AnyPages, MemoryType::LOADER_DATA, 1)
+        .expect("failed to allocate page");
+    let start = page.as_ptr() as u64;
+    let region = start..start + PAGE_SIZE as u64;
+
+    // Unaligned regions are rejected without calling the firmware.
+    assert_eq!(
+        memory_protection.get_memory_attributes(start + 1..region.end),
+        Err(Status::INVALID_PARAMETER.into())
+    );
+
+    memory_protection
+        .set_memory_attributes(region.clone(), MemoryAttribute::READ_ONLY)
+        .expect("failed to set memory attributes");
+    let attrs = memory_protection
+        .get_memory_attributes(region.clone())
+        .expect("failed to get memory attributes");
+    assert!(attrs.contains(MemoryAttribute::READ_ONLY));
+
+    memory_protection
+        .clear_memory_attributes(region.clone(), MemoryAttribute::READ_ONLY)
+        .expect("failed to clear memory attributes");
+    let attrs = memory_protection
+        .get_memory_attributes(region)
+        .expect("failed to get memory attributes");
+    assert!(!attrs.contains(MemoryAttribute::READ_ONLY));
+
+    unsafe { boot::free_pages(page, 1) }.expect("failed to free page");
+}
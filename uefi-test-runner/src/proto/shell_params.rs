@@ -13,6 +13,7 @@ pub fn test() {
         .expect("Failed to open ShellParameters protocol");
 
     assert_eq!(shell_params.args_len(), 4);
+    assert_eq!(shell_params.args().len(), 4);
     assert_eq!(
         shell_params
             .args()
@@ -20,4 +21,10 @@ pub fn test() {
             .collect::<Vec<_>>(),
         &["shell.efi", "test_runner.efi", "arg1", "arg2"]
     );
+
+    // The shell always provides the standard streams, even if they are not
+    // redirected.
+    assert!(!shell_params.std_in().is_null());
+    assert!(!shell_params.std_out().is_null());
+    assert!(!shell_params.std_err().is_null());
 }
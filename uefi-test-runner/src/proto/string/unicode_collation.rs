@@ -54,6 +54,19 @@ pub fn test() {
             CStr16::from_str_with_buf("hello world!", &mut [0; 13]).unwrap()
         );
 
+        // A larger buffer only contains the converted string.
+        let mut buf2 = [0xffff; 30];
+        let lower_s = uc.str_lwr(s, &mut buf2).unwrap();
+        assert_eq!(
+            lower_s,
+            CStr16::from_str_with_buf("hello world!", &mut [0; 13]).unwrap()
+        );
+
+        let mut empty_buf = [0; 1];
+        let empty = CStr16::from_str_with_buf("", &mut empty_buf).unwrap();
+        let mut buf2 = [0xffff; 1];
+        assert_eq!(uc.str_lwr(empty, &mut buf2).unwrap(), empty);
+
         let mut buf = [0; 12];
         assert_eq!(
             uc.str_upr(s, &mut buf),
@@ -77,6 +90,10 @@ pub fn test() {
             uc.fat_to_str(s, &mut [0; 13]).unwrap(),
             CStr16::from_str_with_buf("Hello World!", &mut [0; 13]).unwrap()
         );
+        assert_eq!(
+            uc.fat_to_str(s, &mut [0xffff; 30]).unwrap(),
+            CStr16::from_str_with_buf("Hello World!", &mut [0; 13]).unwrap()
+        );
 
         let mut buf = [0; 13];
         let s = CStr16::from_str_with_buf("Hello World!", &mut buf).unwrap();
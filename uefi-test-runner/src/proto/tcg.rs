@@ -327,6 +327,9 @@ pub fn test_tcg_v2() {
     #[rustfmt::skip]
     assert_eq!(entry.digest(), expected_hash_sha1);
 
+    // The raw log ends with the data of the last event.
+    assert!(log.as_bytes().ends_with(&event_data));
+
     // Get the v2 log, and validate the last entry is the one we just added above.
     let log = tcg.get_event_log_v2().unwrap();
     assert!(!log.is_truncated());
@@ -343,6 +346,7 @@ pub fn test_tcg_v2() {
             (AlgorithmId::SHA512, expected_hash_sha512.as_slice()),
         ]
     );
+    assert!(log.as_bytes().ends_with(&event_data));
 
     // PCR 8 has been extended: `sha1([0; 20], sha1("some-data"))`.
     assert_eq!(
@@ -0,0 +1,58 @@
+use core::time::Duration;
+use uefi::boot::{self, OpenProtocolAttributes, OpenProtocolParams};
+use uefi::proto::usb::{DeviceRequest, UsbDataDirection, UsbIo};
+
+pub fn test() {
+    info!("Running USB I/O protocol test");
+
+    let handles = boot::find_handles::<UsbIo>().unwrap_or_default();
+    if handles.is_empty() {
+        info!("No USB I/O handles available, skipping test");
+        return;
+    }
+
+    for handle in handles {
+        // Open non-exclusively, so that drivers such as the USB keyboard
+        // driver are not disconnected.
+        let mut usb = unsafe {
+            boot::open_protocol::<UsbIo>(
+                OpenProtocolParams {
+                    handle,
+                    agent: boot::image_handle(),
+                    controller: None,
+                },
+                OpenProtocolAttributes::GetProtocol,
+            )
+        }
+        .expect("failed to open USB I/O protocol");
+
+        let device = usb.get_device_descriptor().unwrap();
+        assert_eq!(device.descriptor_type, 1);
+        let (vendor, product) = (device.id_vendor, device.id_product);
+        info!("USB device {vendor:04x}:{product:04x}");
+
+        let interface = usb.get_interface_descriptor().unwrap();
+        assert_eq!(interface.descriptor_type, 4);
+        for index in 0..interface.num_endpoints {
+            let endpoint = usb.get_endpoint_descriptor(index).unwrap();
+            assert_eq!(endpoint.descriptor_type, 5);
+        }
+
+        // GET_STATUS request to the device.
+        let request = DeviceRequest {
+            request_type: 0x80,
+            request: 0,
+            value: 0,
+            index: 0,
+            length: 2,
+        };
+        let mut status = [0; 2];
+        usb.control_transfer(
+            &request,
+            UsbDataDirection::DATA_IN,
+            Some(&mut status),
+            Duration::from_secs(1),
+        )
+        .unwrap();
+    }
+}
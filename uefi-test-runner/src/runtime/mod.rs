@@ -1,11 +1,19 @@
 mod vars;
 
 use uefi::runtime::{self, Daylight, Time, TimeParams};
+use uefi::Status;
 
 pub fn test() {
     info!("Testing runtime services");
     vars::test();
     test_time();
+    test_high_monotonic_count();
+}
+
+fn test_high_monotonic_count() {
+    let high1 = runtime::get_next_high_monotonic_count().unwrap();
+    let high2 = runtime::get_next_high_monotonic_count().unwrap();
+    assert_eq!(high2, high1 + 1);
 }
 
 fn test_time() {
@@ -15,6 +23,12 @@ fn test_time() {
         runtime::get_time_and_caps().unwrap()
     );
 
+    // Invalid times are rejected before calling the firmware.
+    assert_eq!(
+        unsafe { runtime::set_time(&Time::invalid()) }.map_err(|err| err.status()),
+        Err(Status::INVALID_PARAMETER)
+    );
+
     // Set the time.
     let time = Time::new(TimeParams {
         year: 2020,
@@ -34,4 +48,6 @@ fn test_time() {
     let now = runtime::get_time().unwrap();
     info!("After setting time: {}", now);
     assert_eq!(now.year(), 2020);
+    assert!(now >= time);
+    assert!(now.to_unix_secs().unwrap() >= time.to_unix_secs().unwrap());
 }
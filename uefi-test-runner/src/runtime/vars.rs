@@ -1,5 +1,7 @@
+use alloc::boxed::Box;
 use log::info;
 use uefi::prelude::*;
+use uefi::runtime::load_option::{self, LoadOption};
 use uefi::runtime::{VariableAttributes, VariableVendor};
 use uefi::{guid, runtime, CStr16, Error};
 
@@ -44,6 +46,16 @@ fn test_variables() {
     assert_eq!(&*data, VALUE);
     assert_eq!(attrs, ATTRS);
 
+    // Test `get_typed`.
+    let data: Box<[u8]> = runtime::get_typed(NAME, VENDOR).expect("failed to get variable");
+    assert_eq!(&*data, VALUE);
+    assert_eq!(
+        runtime::get_typed::<u32>(NAME, VENDOR)
+            .unwrap_err()
+            .status(),
+        Status::BAD_BUFFER_SIZE
+    );
+
     // Test that the variable is present in the `variable_keys` iterator.
     let find_by_key = || {
         runtime::variable_keys().any(|k| {
@@ -66,6 +78,18 @@ fn test_variables() {
     assert!(!find_by_key());
 }
 
+/// Test reading well-known global variables with `get_typed`.
+fn test_typed_global_variables() {
+    let vendor = &VariableVendor::GLOBAL_VARIABLE;
+    for name in [cstr16!("SecureBoot"), cstr16!("SetupMode")] {
+        match runtime::get_typed::<bool>(name, vendor) {
+            Ok(value) => info!("{name}: {value}"),
+            Err(err) if err.status() == Status::NOT_FOUND => info!("{name} is not set"),
+            Err(err) => panic!("failed to read {name}: {err:?}"),
+        }
+    }
+}
+
 fn test_variable_info() {
     let attr = VariableAttributes::BOOTSERVICE_ACCESS | VariableAttributes::NON_VOLATILE;
     let info = runtime::query_variable_info(attr).unwrap();
@@ -76,7 +100,24 @@ fn test_variable_info() {
     info!("Storage for volatile runtime variables: {info:?}");
 }
 
+/// Test reading the boot order and parsing each boot option.
+fn test_load_options() {
+    let order = load_option::boot_order().expect("failed to get boot order");
+    assert!(!order.is_empty());
+
+    for number in order {
+        let name = load_option::boot_option_name(number);
+        let (data, _) = runtime::get_variable_boxed(&name, &VariableVendor::GLOBAL_VARIABLE)
+            .expect("failed to get boot option");
+        let option = LoadOption::try_from(&*data).expect("failed to parse boot option");
+        info!("{name}: {}", option.description);
+        assert_eq!(option.to_bytes(), &*data);
+    }
+}
+
 pub fn test() {
     test_variable_info();
     test_variables();
+    test_typed_global_variables();
+    test_load_options();
 }
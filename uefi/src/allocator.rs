@@ -6,8 +6,13 @@
 //! This allocator can only be used while boot services are active. If boot
 //! services are not active, `alloc` will return a null pointer, and `dealloc`
 //! will panic.
+//!
+//! With the `unstable` feature, [`PoolAllocator`] can be used with the
+//! `allocator_api` to allocate memory of a specific [`MemoryType`].
 
 use core::alloc::{GlobalAlloc, Layout};
+#[cfg(all(feature = "unstable", feature = "alloc"))]
+use core::num::NonZeroUsize;
 use core::ptr::{self, NonNull};
 use core::sync::atomic::{AtomicU32, Ordering};
 
@@ -56,62 +61,200 @@ unsafe impl GlobalAlloc for Allocator {
             return ptr::null_mut();
         }
 
-        let size = layout.size();
-        let align = layout.align();
-        let memory_type = get_memory_type();
-
-        if align > 8 {
-            // The requested alignment is greater than 8, but `allocate_pool` is
-            // only guaranteed to provide eight-byte alignment. Allocate extra
-            // space so that we can return an appropriately-aligned pointer
-            // within the allocation.
-            let full_alloc_ptr = if let Ok(ptr) = boot::allocate_pool(memory_type, size + align) {
-                ptr.as_ptr()
-            } else {
-                return ptr::null_mut();
-            };
-
-            // Calculate the offset needed to get an aligned pointer within the
-            // full allocation. If that offset is zero, increase it to `align`
-            // so that we still have space to store the extra pointer described
-            // below.
-            let mut offset = full_alloc_ptr.align_offset(align);
-            if offset == 0 {
-                offset = align;
-            }
+        alloc_pool(get_memory_type(), layout)
+    }
 
-            // Before returning the aligned allocation, store a pointer to the
-            // full unaligned allocation in the bytes just before the aligned
-            // allocation. We know we have at least eight bytes there due to
-            // adding `align` to the memory allocation size. We also know the
-            // write is appropriately aligned for a `*mut u8` pointer because
-            // `align_ptr` is aligned, and alignments are always powers of two
-            // (as enforced by the `Layout` type).
-            let aligned_ptr = full_alloc_ptr.add(offset);
-            (aligned_ptr.cast::<*mut u8>()).sub(1).write(full_alloc_ptr);
-            aligned_ptr
-        } else {
-            // The requested alignment is less than or equal to eight, and
-            // `allocate_pool` always provides eight-byte alignment, so we can
-            // use `allocate_pool` directly.
-            boot::allocate_pool(memory_type, size)
-                .map(|ptr| ptr.as_ptr())
-                .unwrap_or(ptr::null_mut())
+    /// Deallocate memory using [`boot::free_pool`].
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        dealloc_pool(ptr, layout);
+    }
+}
+
+/// Allocator which uses the UEFI pool allocation functions with a
+/// caller-chosen [`MemoryType`].
+///
+/// Unlike [`Allocator`], this is not meant to be used as the global
+/// allocator. Instead, it can be passed to collections such as
+/// [`Vec::new_in`] and [`Box::new_in`] to place specific allocations in a
+/// particular type of memory, e.g. [`MemoryType::RUNTIME_SERVICES_DATA`] for
+/// data that a runtime driver needs after `SetVirtualAddressMap`.
+///
+/// Only valid for as long as the UEFI boot services are available.
+///
+/// [`Vec::new_in`]: alloc::vec::Vec::new_in
+/// [`Box::new_in`]: alloc::boxed::Box::new_in
+#[cfg(all(feature = "unstable", feature = "alloc"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PoolAllocator {
+    memory_type: MemoryType,
+}
+
+#[cfg(all(feature = "unstable", feature = "alloc"))]
+impl PoolAllocator {
+    /// Create an allocator for pool memory of type `memory_type`.
+    #[must_use]
+    pub const fn new(memory_type: MemoryType) -> Self {
+        Self { memory_type }
+    }
+
+    /// Type of the memory allocated by this allocator.
+    #[must_use]
+    pub const fn memory_type(&self) -> MemoryType {
+        self.memory_type
+    }
+}
+
+#[cfg(all(feature = "unstable", feature = "alloc"))]
+unsafe impl core::alloc::Allocator for PoolAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        if layout.size() == 0 {
+            // Zero-sized allocations don't need any memory, so return a
+            // dangling pointer with the requested alignment.
+            let align = NonZeroUsize::new(layout.align()).ok_or(core::alloc::AllocError)?;
+            // The `unstable` feature requires a nightly compiler anyway.
+            #[allow(clippy::incompatible_msrv)]
+            let dangling = NonNull::<u8>::dangling().with_addr(align);
+            return Ok(NonNull::slice_from_raw_parts(dangling, 0));
         }
+        if !boot::are_boot_services_active() {
+            return Err(core::alloc::AllocError);
+        }
+
+        let ptr = unsafe { alloc_pool(self.memory_type, layout) };
+        NonNull::new(ptr)
+            .map(|ptr| NonNull::slice_from_raw_parts(ptr, layout.size()))
+            .ok_or(core::alloc::AllocError)
     }
 
-    /// Deallocate memory using [`boot::free_pool`].
-    unsafe fn dealloc(&self, mut ptr: *mut u8, layout: Layout) {
-        if layout.align() > 8 {
-            // Retrieve the pointer to the full allocation that was packed right
-            // before the aligned allocation in `alloc`.
-            ptr = (ptr as *const *mut u8).sub(1).read();
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            dealloc_pool(ptr.as_ptr(), layout);
         }
+    }
+}
 
-        // OK to unwrap: `ptr` is required to be a valid allocation by the trait API.
-        let ptr = NonNull::new(ptr).unwrap();
+/// Allocate pool memory of type `memory_type` for `layout`, returning a null
+/// pointer on failure.
+///
+/// # Safety
+///
+/// Boot services must be active.
+unsafe fn alloc_pool(memory_type: MemoryType, layout: Layout) -> *mut u8 {
+    let size = layout.size();
+    let align = layout.align();
 
-        // Warning: this will panic after exiting boot services.
-        boot::free_pool(ptr).unwrap();
+    if align > 8 {
+        // The requested alignment is greater than 8, but `allocate_pool` is
+        // only guaranteed to provide eight-byte alignment. Allocate extra
+        // space so that we can return an appropriately-aligned pointer
+        // within the allocation.
+        let Some(full_size) = size.checked_add(align) else {
+            return ptr::null_mut();
+        };
+        let full_alloc_ptr = if let Ok(ptr) = boot::allocate_pool(memory_type, full_size) {
+            ptr.as_ptr()
+        } else {
+            return ptr::null_mut();
+        };
+        align_allocation(full_alloc_ptr, align)
+    } else {
+        // The requested alignment is less than or equal to eight, and
+        // `allocate_pool` always provides eight-byte alignment, so we can
+        // use `allocate_pool` directly.
+        boot::allocate_pool(memory_type, size)
+            .map(|ptr| ptr.as_ptr())
+            .unwrap_or(ptr::null_mut())
+    }
+}
+
+/// Free memory allocated by [`alloc_pool`].
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`alloc_pool`] with the same `layout`.
+unsafe fn dealloc_pool(mut ptr: *mut u8, layout: Layout) {
+    if layout.align() > 8 {
+        ptr = unalign_allocation(ptr);
+    }
+
+    // OK to unwrap: `ptr` is required to be a valid allocation by the trait API.
+    let ptr = NonNull::new(ptr).unwrap();
+
+    // Warning: this will panic after exiting boot services.
+    boot::free_pool(ptr).unwrap();
+}
+
+/// Get an `align`-aligned pointer within an allocation of `size + align`
+/// bytes starting at `full_alloc_ptr`, with a pointer to the start of the
+/// allocation stored just before it. The full allocation can be recovered
+/// with [`unalign_allocation`].
+///
+/// # Safety
+///
+/// `full_alloc_ptr` must be eight-byte aligned and valid for writes of
+/// `size + align` bytes, and `align` must be a power of two greater than
+/// eight.
+unsafe fn align_allocation(full_alloc_ptr: *mut u8, align: usize) -> *mut u8 {
+    // Calculate the offset needed to get an aligned pointer within the
+    // full allocation. If that offset is zero, increase it to `align`
+    // so that we still have space to store the extra pointer described
+    // below.
+    let mut offset = full_alloc_ptr.align_offset(align);
+    if offset == 0 {
+        offset = align;
+    }
+
+    // Before returning the aligned allocation, store a pointer to the
+    // full unaligned allocation in the bytes just before the aligned
+    // allocation. We know we have at least eight bytes there because the
+    // offset is a nonzero multiple of eight. We also know the write is
+    // appropriately aligned for a `*mut u8` pointer because `aligned_ptr`
+    // is aligned, and alignments are always powers of two (as enforced by
+    // the `Layout` type).
+    let aligned_ptr = full_alloc_ptr.add(offset);
+    (aligned_ptr.cast::<*mut u8>()).sub(1).write(full_alloc_ptr);
+    aligned_ptr
+}
+
+/// Retrieve the pointer to the full allocation that was packed right before
+/// the aligned allocation by [`align_allocation`].
+///
+/// # Safety
+///
+/// `aligned_ptr` must have been returned by [`align_allocation`].
+const unsafe fn unalign_allocation(aligned_ptr: *mut u8) -> *mut u8 {
+    (aligned_ptr as *const *mut u8).sub(1).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_align_allocation() {
+        const SIZE: usize = 24;
+
+        for align in [16, 32, 64, 4096] {
+            // Use `u64` elements to get the eight-byte alignment that
+            // `allocate_pool` guarantees. Try each eight-byte-aligned start
+            // position within one `align` period, so that both the zero and
+            // nonzero offset cases are covered.
+            let full_size = SIZE + align;
+            let mut buf = vec![0u64; (full_size + align) / 8];
+            for start in (0..align).step_by(8) {
+                let full_alloc_ptr = unsafe { buf.as_mut_ptr().cast::<u8>().add(start) };
+                let aligned_ptr = unsafe { align_allocation(full_alloc_ptr, align) };
+
+                assert_eq!(aligned_ptr.align_offset(align), 0);
+                let offset = unsafe { aligned_ptr.offset_from(full_alloc_ptr) };
+                assert!(offset >= 8);
+                assert!(offset as usize + SIZE <= full_size);
+                assert_eq!(unsafe { unalign_allocation(aligned_ptr) }, full_alloc_ptr);
+
+                // The aligned allocation must be usable for its full size.
+                unsafe { aligned_ptr.write_bytes(0xff, SIZE) };
+            }
+        }
     }
 }
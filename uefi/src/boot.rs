@@ -19,6 +19,9 @@
 //!
 //! [`proto`]: crate::proto
 
+#[cfg(feature = "alloc")]
+pub mod events;
+
 pub use uefi_raw::table::boot::{
     EventType, MemoryAttribute, MemoryDescriptor, MemoryType, Tpl, PAGE_SIZE,
 };
@@ -26,7 +29,7 @@ pub use uefi_raw::table::boot::{
 use crate::data_types::PhysicalAddress;
 use crate::mem::memory_map::{MemoryMapBackingMemory, MemoryMapKey, MemoryMapMeta, MemoryMapOwned};
 use crate::polyfill::maybe_uninit_slice_assume_init_ref;
-#[cfg(doc)]
+#[cfg(any(doc, feature = "alloc"))]
 use crate::proto::device_path::LoadedImageDevicePath;
 use crate::proto::device_path::{DevicePath, FfiDevicePath};
 use crate::proto::loaded_image::LoadedImage;
@@ -41,10 +44,18 @@ use core::mem::MaybeUninit;
 use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull};
 use core::sync::atomic::{AtomicPtr, Ordering};
+use core::time::Duration;
 use core::{mem, slice};
 use uefi_raw::table::boot::InterfaceType;
 #[cfg(feature = "alloc")]
-use {alloc::vec::Vec, uefi::ResultExt};
+use {
+    crate::proto::device_path::DevicePathNodeEnum,
+    crate::{CStr16, CString16},
+    alloc::boxed::Box,
+    alloc::vec::Vec,
+    core::fmt::{self, Display, Formatter},
+    uefi::ResultExt,
+};
 
 /// Global image handle. This is only set by [`set_image_handle`], and it is
 /// only read by [`image_handle`].
@@ -118,6 +129,21 @@ pub unsafe fn raise_tpl(tpl: Tpl) -> TplGuard {
     }
 }
 
+/// Gets the current task priority level.
+///
+/// The UEFI spec has no function for this, but raising to [`Tpl::HIGH_LEVEL`]
+/// is always allowed and returns the previous level, which is then restored
+/// immediately.
+#[cfg(feature = "alloc")]
+fn current_tpl() -> Tpl {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let tpl = unsafe { (bt.raise_tpl)(Tpl::HIGH_LEVEL) };
+    unsafe { (bt.restore_tpl)(tpl) };
+    tpl
+}
+
 /// Allocates memory pages from the system.
 ///
 /// UEFI OS loaders should allocate memory of the type `LoaderData`.
@@ -143,6 +169,33 @@ pub fn allocate_pages(ty: AllocateType, mem_ty: MemoryType, count: usize) -> Res
     Ok(NonNull::new(ptr).expect("allocate_pages must not return a null pointer if successful"))
 }
 
+/// Allocates memory pages from the system, returning a [`PageAllocation`]
+/// that frees the pages when dropped.
+///
+/// The pages are zeroed, so the allocation can be accessed as a byte slice
+/// right away. For [`AllocateType::MaxAddress`], the allocation records the
+/// address the firmware actually allocated.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: allocation failed.
+/// * [`Status::INVALID_PARAMETER`]: `mem_ty` is [`MemoryType::PERSISTENT_MEMORY`],
+///   [`MemoryType::UNACCEPTED`], or in the range [`MemoryType::MAX`]`..=0x6fff_ffff`,
+///   or the size of `count` pages overflows a `usize`.
+/// * [`Status::NOT_FOUND`]: the requested pages could not be found.
+pub fn allocate_pages_owned(
+    ty: AllocateType,
+    mem_ty: MemoryType,
+    count: usize,
+) -> Result<PageAllocation> {
+    let len = count
+        .checked_mul(PAGE_SIZE)
+        .ok_or(Status::INVALID_PARAMETER)?;
+    let ptr = allocate_pages(ty, mem_ty, count)?;
+    unsafe { ptr.as_ptr().write_bytes(0, len) };
+    Ok(PageAllocation { ptr, count })
+}
+
 /// Frees memory pages allocated by [`allocate_pages`].
 ///
 /// # Safety
@@ -245,6 +298,11 @@ pub(crate) fn memory_map_size() -> MemoryMapMeta {
 /// Stores the current UEFI memory map in an UEFI-heap allocated buffer
 /// and returns a [`MemoryMapOwned`].
 ///
+/// The buffer is sized with some slack beyond the current size of the map,
+/// as allocating the buffer itself can add entries to the map. If the map
+/// still outgrows the buffer before it is retrieved, the buffer is freed
+/// and the operation is retried with a freshly sized buffer.
+///
 /// # Parameters
 ///
 /// - `mt`: The memory type for the backing memory on the UEFI heap.
@@ -253,14 +311,27 @@ pub(crate) fn memory_map_size() -> MemoryMapMeta {
 ///
 /// # Errors
 ///
-/// * [`Status::BUFFER_TOO_SMALL`]
+/// * [`Status::BUFFER_TOO_SMALL`]: the map kept outgrowing the buffer
+///   after several attempts.
 /// * [`Status::INVALID_PARAMETER`]
+/// * [`Status::OUT_OF_RESOURCES`]: the buffer could not be allocated.
 pub fn memory_map(mt: MemoryType) -> Result<MemoryMapOwned> {
-    let mut buffer = MemoryMapBackingMemory::new(mt)?;
-
-    let meta = get_memory_map(buffer.as_mut_slice())?;
-
-    Ok(MemoryMapOwned::from_initialized_mem(buffer, meta))
+    // Each attempt sizes the buffer from the current map, so a retry only
+    // fails if the map grows by more than the slack again. Bound the number
+    // of attempts anyway, rather than looping forever on broken firmware.
+    const MAX_ATTEMPTS: usize = 4;
+
+    let mut attempt = 1;
+    loop {
+        let mut buffer = MemoryMapBackingMemory::new(mt)?;
+        match get_memory_map(buffer.as_mut_slice()) {
+            Ok(meta) => return Ok(MemoryMapOwned::from_initialized_mem(buffer, meta)),
+            Err(err) if err.status() == Status::BUFFER_TOO_SMALL && attempt < MAX_ATTEMPTS => {
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
 }
 
 /// Calls the underlying `GetMemoryMap` function of UEFI. On success,
@@ -394,7 +465,7 @@ pub unsafe fn create_event_ex(
     let bt = boot_services_raw_panicking();
     let bt = unsafe { bt.as_ref() };
 
-    if bt.header.revision < Revision::EFI_2_00 {
+    if !bt.header.revision.supports_at_least(Revision::EFI_2_00) {
         return Err(Status::UNSUPPORTED.into());
     }
 
@@ -443,6 +514,24 @@ pub fn check_event(event: Event) -> Result<bool> {
     }
 }
 
+/// Places `event` in the signaled state.
+///
+/// If `event` is already signaled, this has no effect. If `event` is of type
+/// [`NOTIFY_SIGNAL`], its notification function is queued. If `event` is part
+/// of an event group, all events in the group are signaled.
+///
+/// [`NOTIFY_SIGNAL`]: EventType::NOTIFY_SIGNAL
+///
+/// # Errors
+///
+/// This function always succeeds.
+pub fn signal_event(event: &Event) -> Result {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    unsafe { (bt.signal_event)(event.as_ptr()) }.to_result()
+}
+
 /// Removes `event` from any event group to which it belongs and closes it.
 ///
 /// If `event` was registered with [`register_protocol_notify`], then the
@@ -462,6 +551,9 @@ pub fn close_event(event: Event) -> Result {
 
 /// Sets the trigger for an event of type [`TIMER`].
 ///
+/// Use [`TimerTrigger::relative`] or [`TimerTrigger::periodic`] to specify
+/// the trigger time as a [`Duration`] rather than in 100ns units.
+///
 /// # Errors
 ///
 /// * [`Status::INVALID_PARAMETER`]: `event` is not valid.
@@ -479,6 +571,53 @@ pub fn set_timer(event: &Event, trigger_time: TimerTrigger) -> Result {
     unsafe { (bt.set_timer)(event.as_ptr(), ty, time) }.to_result()
 }
 
+/// Creates a timer event that is signaled once, after `delay` has elapsed.
+///
+/// The event has no notification function, so it can be used with
+/// [`wait_for_event`] and [`check_event`]. Close it with [`close_event`]
+/// when it is no longer needed.
+///
+/// The delay is rounded down to 100ns units, and saturates at the
+/// largest delay UEFI can represent.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: the event could not be allocated.
+pub fn create_oneshot_timer(delay: Duration) -> Result<Event> {
+    create_timer(TimerTrigger::Relative(duration_to_100ns_saturating(delay)))
+}
+
+/// Creates a timer event that is signaled every `period`.
+///
+/// The event has no notification function, so it can be used with
+/// [`wait_for_event`] and [`check_event`]. Close it with [`close_event`]
+/// when it is no longer needed.
+///
+/// The period is rounded down to 100ns units, and saturates at the
+/// largest period UEFI can represent. A period of zero signals the event on
+/// every timer tick.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: the event could not be allocated.
+pub fn create_periodic_timer(period: Duration) -> Result<Event> {
+    create_timer(TimerTrigger::Periodic(duration_to_100ns_saturating(period)))
+}
+
+/// Creates a timer event without a notification function and arms it.
+fn create_timer(trigger: TimerTrigger) -> Result<Event> {
+    // Safety: there is no notification function, so nothing can be called
+    // after boot services have exited.
+    let event = unsafe { create_event(EventType::TIMER, Tpl::APPLICATION, None, None) }?;
+
+    if let Err(err) = set_timer(&event, trigger) {
+        // The event is unusable, so closing it is best-effort.
+        let _ = close_event(event);
+        return Err(err);
+    }
+    Ok(event)
+}
+
 /// Stops execution until an event is signaled.
 ///
 /// This function must be called at priority level [`Tpl::APPLICATION`].
@@ -631,6 +770,113 @@ pub unsafe fn install_protocol_interface(
     .to_result_with_val(|| Handle::from_ptr(handle).unwrap())
 }
 
+/// Maximum number of protocol interfaces accepted by
+/// [`install_multiple_protocol_interfaces`] and
+/// [`uninstall_multiple_protocol_interfaces`].
+pub const MAX_MULTIPLE_PROTOCOL_INTERFACES: usize = 8;
+
+/// Calls one of the variadic `*_multiple_protocol_interfaces` functions with
+/// the GUID/interface pairs in `$interfaces`, followed by the terminating null
+/// pointer. The variadic argument list must be known at compile time, so
+/// there is one call per supported number of pairs.
+macro_rules! call_multiple_protocol_interfaces {
+    ($f:expr, $handle:expr, $interfaces:expr) => {
+        call_multiple_protocol_interfaces!(
+            $f, $handle, $interfaces;
+            1 => [0],
+            2 => [0 1],
+            3 => [0 1 2],
+            4 => [0 1 2 3],
+            5 => [0 1 2 3 4],
+            6 => [0 1 2 3 4 5],
+            7 => [0 1 2 3 4 5 6],
+            8 => [0 1 2 3 4 5 6 7],
+        )
+    };
+    ($f:expr, $handle:expr, $interfaces:expr; $($len:literal => [$($n:literal)*],)*) => {{
+        let f = $f;
+        let handle = $handle;
+        let interfaces = $interfaces;
+        match interfaces.len() {
+            $($len => f(
+                handle,
+                $(ptr::from_ref(&interfaces[$n].0), interfaces[$n].1,)*
+                ptr::null::<c_void>(),
+            ),)*
+            _ => Status::INVALID_PARAMETER,
+        }
+    }};
+}
+
+/// Installs several protocol interfaces on a device handle at once.
+///
+/// Unlike repeated calls to [`install_protocol_interface`], this is atomic:
+/// if any of the interfaces cannot be installed, the ones that were already
+/// installed are removed again and the handle is left unchanged. The firmware
+/// also refuses to install a second [`DevicePath`] that is identical to one
+/// already installed on another handle.
+///
+/// If `handle` is `None`, a new handle will be created and returned.
+///
+/// # Safety
+///
+/// The caller is responsible for ensuring that each interface pointer is
+/// valid for the protocol identified by the corresponding `Guid`.
+///
+/// # Errors
+///
+/// * [`Status::ALREADY_STARTED`]: a device path protocol instance with the
+///   same device path is already installed.
+/// * [`Status::OUT_OF_RESOURCES`]: failed to allocate a new handle.
+/// * [`Status::INVALID_PARAMETER`]: a protocol is already installed on the
+///   handle, `interfaces` is empty, or `interfaces` has more than
+///   [`MAX_MULTIPLE_PROTOCOL_INTERFACES`] elements.
+pub unsafe fn install_multiple_protocol_interfaces(
+    handle: Option<Handle>,
+    interfaces: &[(Guid, *const c_void)],
+) -> Result<Handle> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let mut handle = Handle::opt_to_ptr(handle);
+    call_multiple_protocol_interfaces!(
+        bt.install_multiple_protocol_interfaces,
+        &mut handle,
+        interfaces
+    )
+    .to_result_with_val(|| Handle::from_ptr(handle).unwrap())
+}
+
+/// Removes several protocol interfaces from a device handle at once.
+///
+/// This is atomic: if any of the interfaces cannot be removed, the ones that
+/// were already removed are reinstalled and the handle is left unchanged.
+///
+/// # Safety
+///
+/// The same requirements as for [`uninstall_protocol_interface`] apply to
+/// each interface.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: one of the interfaces could not be
+///   removed, `interfaces` is empty, or `interfaces` has more than
+///   [`MAX_MULTIPLE_PROTOCOL_INTERFACES`] elements.
+pub unsafe fn uninstall_multiple_protocol_interfaces(
+    handle: Handle,
+    interfaces: &[(Guid, *const c_void)],
+) -> Result {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    call_multiple_protocol_interfaces!(
+        bt.uninstall_multiple_protocol_interfaces,
+        handle.as_ptr(),
+        interfaces
+    )
+    .to_result()
+}
+
 /// Reinstalls a protocol interface on a device handle. `old_interface` is replaced with `new_interface`.
 /// These interfaces may be the same, in which case the registered protocol notifications occur for the handle
 /// without replacing the interface.
@@ -714,6 +960,112 @@ pub fn register_protocol_notify(
     )
 }
 
+/// Calls `callback` with each handle on which a `P` interface is installed or
+/// reinstalled from now on.
+///
+/// This combines [`create_event`], [`register_protocol_notify`], and
+/// [`locate_handle`] with [`SearchType::ByRegisterNotify`]: when the
+/// notification event is signaled, every newly-registered handle is passed to
+/// `callback`. Handles that already supported `P` before this call are not
+/// reported; use [`find_handles`] to get those.
+///
+/// The notification is removed when the returned [`ProtocolWatch`] is dropped.
+///
+/// # Reentrancy
+///
+/// `callback` runs inside an event notification function at [`Tpl::CALLBACK`],
+/// interrupting whatever code triggered the install. It must therefore:
+/// * not rely on state that the interrupted code may be in the middle of
+///   modifying (for example, a `RefCell` that is borrowed mutably around an
+///   install call);
+/// * only use boot services that are allowed at `Tpl::CALLBACK`, which
+///   excludes waiting on events;
+/// * not drop its own `ProtocolWatch`.
+///
+/// Installing another `P` interface from within `callback` is allowed; the
+/// new handle is reported after `callback` returns.
+///
+/// The `ProtocolWatch` must be dropped before exiting boot services.
+///
+/// # Task priority level
+///
+/// This must be called at a TPL of at most [`Tpl::NOTIFY`], as required by
+/// the boot services it uses. If called above [`Tpl::CALLBACK`], `callback`
+/// only runs once the TPL is lowered to `Tpl::CALLBACK` or below.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: the event could not be allocated.
+#[cfg(feature = "alloc")]
+pub fn watch_protocol_installs<P, F>(callback: F) -> Result<ProtocolWatch>
+where
+    P: ProtocolPointer + ?Sized,
+    F: FnMut(Handle) + 'static,
+{
+    let state = NonNull::from(Box::leak(Box::new(ProtocolWatchState {
+        key: None,
+        callback: Box::new(callback),
+    })));
+
+    // Raise the TPL so the notification can't run until the search key has
+    // been stored. Raising to a lower TPL than the current one is not
+    // allowed, and at a higher TPL the notification can't run anyway.
+    let tpl = (current_tpl() < Tpl::CALLBACK).then(|| unsafe { raise_tpl(Tpl::CALLBACK) });
+
+    // Safety: the context pointer stays valid until the event is closed in
+    // `ProtocolWatch::drop`.
+    let event = match unsafe {
+        create_event(
+            EventType::NOTIFY_SIGNAL,
+            Tpl::CALLBACK,
+            Some(protocol_watch_notify),
+            Some(state.cast()),
+        )
+    } {
+        Ok(event) => event,
+        Err(err) => {
+            drop(unsafe { Box::from_raw(state.as_ptr()) });
+            return Err(err);
+        }
+    };
+
+    // Construct the watch now so that the event and state are cleaned up if
+    // registration fails.
+    let watch = ProtocolWatch { event, state };
+    match register_protocol_notify(&P::GUID, &watch.event)? {
+        SearchType::ByRegisterNotify(key) => unsafe { (*state.as_ptr()).key = Some(key) },
+        _ => unreachable!(),
+    }
+    drop(tpl);
+
+    Ok(watch)
+}
+
+/// Notification function for [`watch_protocol_installs`].
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn protocol_watch_notify(_event: Event, context: Option<NonNull<c_void>>) {
+    let Some(context) = context else {
+        return;
+    };
+    // Safety: the context was set to a valid `ProtocolWatchState` by
+    // `watch_protocol_installs`, and notification functions for the same
+    // event do not run concurrently.
+    let state = unsafe { context.cast::<ProtocolWatchState>().as_mut() };
+    let Some(key) = state.key else {
+        return;
+    };
+
+    // Each call returns the next handle registered since the last call, until
+    // there are none left.
+    loop {
+        let mut buffer = [MaybeUninit::uninit()];
+        match locate_handle(SearchType::ByRegisterNotify(key), &mut buffer) {
+            Ok(&[handle]) => (state.callback)(handle),
+            _ => break,
+        }
+    }
+}
+
 /// Get the list of protocol interface [`Guids`][Guid] that are installed
 /// on a [`Handle`].
 ///
@@ -736,6 +1088,29 @@ pub fn protocols_per_handle(handle: Handle) -> Result<ProtocolsPerHandle> {
         })
 }
 
+/// Get a description of a [`Handle`] for diagnostic output.
+///
+/// The description contains an owned copy of the protocol interface
+/// [`Guids`][Guid] installed on the handle, as returned by
+/// [`protocols_per_handle`]. Its [`Display`] impl lists the protocols by name
+/// where they are defined in this crate (see [`protocol_name`]), and by GUID
+/// otherwise.
+///
+/// [`protocol_name`]: crate::proto::protocol_name
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `handle` is invalid.
+/// * [`Status::OUT_OF_RESOURCES`]: out of memory.
+#[cfg(feature = "alloc")]
+pub fn describe_handle(handle: Handle) -> Result<HandleDescription> {
+    let protocols = protocols_per_handle(handle)?;
+    Ok(HandleDescription {
+        handle,
+        protocols: protocols.iter().map(|guid| **guid).collect(),
+    })
+}
+
 /// Locates the handle of a device on the device path that supports the specified protocol.
 ///
 /// The `device_path` is updated to point at the remaining part of the [`DevicePath`] after
@@ -883,6 +1258,32 @@ pub fn find_handles<P: ProtocolPointer + ?Sized>() -> Result<Vec<Handle>> {
     Ok(handles)
 }
 
+/// Returns all the handles implementing a certain protocol, together with
+/// a copy of each handle's device path.
+///
+/// Handles that do not have a [`DevicePath`] are skipped. The device paths
+/// are opened non-exclusively, so drivers using them are not disconnected,
+/// and copied before the next one is opened.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: no matching handles.
+#[cfg(feature = "alloc")]
+pub fn find_handles_with_paths<P: ProtocolPointer + ?Sized>(
+) -> Result<Vec<(Handle, Box<DevicePath>)>> {
+    let handles = find_handles::<P>()?;
+
+    Ok(handles
+        .into_iter()
+        .filter_map(|handle| {
+            // Safety: the device path is copied before the protocol is
+            // closed, and device paths are not modified once installed.
+            let device_path = unsafe { open_protocol_get::<DevicePath>(handle) }.ok()?;
+            Some((handle, device_path.to_boxed()))
+        })
+        .collect())
+}
+
 /// Find an arbitrary handle that supports a particular [`Protocol`]. Returns
 /// [`NOT_FOUND`] if no handles support the protocol.
 ///
@@ -949,10 +1350,16 @@ pub fn get_handle_for_protocol<P: ProtocolPointer + ?Sized>() -> Result<Handle>
 /// # Errors
 ///
 /// * [`Status::INVALID_PARAMETER`]: an invalid combination of `params` and
-///   `attributes` was provided.
+///   `attributes` was provided. In particular, `params.controller` must be
+///   set if [`attributes.requires_controller()`] is true, and for
+///   [`OpenProtocolAttributes::ByChildController`] it must differ from
+///   `params.handle`. These two conditions are checked before calling the
+///   firmware.
 /// * [`Status::UNSUPPORTED`]: the handle does not support the protocol.
 /// * [`Status::ACCESS_DENIED`] or [`Status::ALREADY_STARTED`]: the protocol is
 ///   already open in a way that is incompatible with the new request.
+///
+/// [`attributes.requires_controller()`]: OpenProtocolAttributes::requires_controller
 pub unsafe fn open_protocol<P: ProtocolPointer + ?Sized>(
     params: OpenProtocolParams,
     attributes: OpenProtocolAttributes,
@@ -960,6 +1367,10 @@ pub unsafe fn open_protocol<P: ProtocolPointer + ?Sized>(
     let bt = boot_services_raw_panicking();
     let bt = unsafe { bt.as_ref() };
 
+    if !attributes.is_valid_for(&params) {
+        return Err(Status::INVALID_PARAMETER.into());
+    }
+
     let mut interface = ptr::null_mut();
     (bt.open_protocol)(
         params.handle.as_ptr(),
@@ -982,6 +1393,35 @@ pub unsafe fn open_protocol<P: ProtocolPointer + ?Sized>(
     })
 }
 
+/// Closes a protocol interface that was opened with [`open_protocol`] and
+/// then kept open with [`ScopedProtocol::leak`].
+///
+/// The `params` must match those passed to [`open_protocol`].
+///
+/// # Safety
+///
+/// The caller must ensure that the protocol is not closed while a
+/// [`ScopedProtocol`] with the same `params` still exists, and that the
+/// interface pointer is not used after the protocol is closed.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the handle does not support the protocol, or the
+///   protocol is not currently open with these `params`.
+/// * [`Status::INVALID_PARAMETER`]: `params.controller` is invalid.
+pub unsafe fn close_protocol<P: ProtocolPointer + ?Sized>(params: OpenProtocolParams) -> Result {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    (bt.close_protocol)(
+        params.handle.as_ptr(),
+        &P::GUID,
+        params.agent.as_ptr(),
+        Handle::opt_to_ptr(params.controller),
+    )
+    .to_result()
+}
+
 /// Opens a protocol interface for a handle in exclusive mode.
 ///
 /// If successful, a [`ScopedProtocol`] is returned that will automatically
@@ -1010,6 +1450,95 @@ pub fn open_protocol_exclusive<P: ProtocolPointer + ?Sized>(
     }
 }
 
+/// Opens the protocol identified by `guid` for a handle in exclusive mode.
+///
+/// This is for protocols whose GUID is only known at runtime, so they can't
+/// implement [`Protocol`]. Prefer [`open_protocol_exclusive`] for protocols
+/// known at compile time.
+///
+/// If successful, a [`ScopedRawProtocol`] is returned that will
+/// automatically close the protocol interface when dropped. It only gives
+/// access to an untyped pointer to the interface; see its documentation for
+/// the requirements on using that pointer.
+///
+/// # Errors
+///
+/// * [`Status::UNSUPPORTED`]: the handle does not support the protocol.
+/// * [`Status::ACCESS_DENIED`]: the protocol is already open in a way that is
+///   incompatible with the new request.
+pub fn open_protocol_dynamic(handle: Handle, guid: Guid) -> Result<ScopedRawProtocol> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let open_params = OpenProtocolParams {
+        handle,
+        agent: image_handle(),
+        controller: None,
+    };
+    let mut interface = ptr::null_mut();
+    // Safety: opening in exclusive mode with the correct agent handle set
+    // ensures that the protocol cannot be modified or removed while it is
+    // open. The interface is only exposed as an untyped pointer.
+    unsafe {
+        (bt.open_protocol)(
+            open_params.handle.as_ptr(),
+            &guid,
+            &mut interface,
+            open_params.agent.as_ptr(),
+            Handle::opt_to_ptr(open_params.controller),
+            OpenProtocolAttributes::Exclusive as u32,
+        )
+    }
+    .to_result_with_val(|| ScopedRawProtocol {
+        guid,
+        interface: NonNull::new(interface),
+        open_params,
+    })
+}
+
+/// Opens a protocol interface for a handle without exclusive access.
+///
+/// This opens the protocol with [`OpenProtocolAttributes::GetProtocol`],
+/// using the current image as the agent. Unlike [`open_protocol_exclusive`],
+/// this does not disconnect drivers that are using the protocol. For
+/// example, the [`GraphicsOutput`] protocol can be used this way without
+/// breaking the connection between stdout and the video console.
+///
+/// If successful, a [`SharedProtocol`] is returned that will automatically
+/// close the protocol interface when dropped. Other agents may use the
+/// protocol at the same time, so it only gives shared access to the
+/// interface. Use [`open_protocol`] with
+/// [`OpenProtocolAttributes::GetProtocol`] if mutable access is required.
+///
+/// # Safety
+///
+/// Opening a protocol this way does not prevent it from being uninstalled.
+/// The caller must ensure that the handle and protocol remain valid until
+/// the `SharedProtocol` is dropped, e.g. by only holding it for a short read
+/// that does not call into code that could uninstall the protocol. See
+/// [`open_protocol`] for more details.
+///
+/// # Errors
+///
+/// * [`Status::UNSUPPORTED`]: the handle does not support the protocol.
+///
+/// [`GraphicsOutput`]: crate::proto::console::gop::GraphicsOutput
+pub unsafe fn open_protocol_get<P: ProtocolPointer + ?Sized>(
+    handle: Handle,
+) -> Result<SharedProtocol<P>> {
+    unsafe {
+        open_protocol::<P>(
+            OpenProtocolParams {
+                handle,
+                agent: image_handle(),
+                controller: None,
+            },
+            OpenProtocolAttributes::GetProtocol,
+        )
+    }
+    .map(SharedProtocol)
+}
+
 /// Tests whether a handle supports a protocol.
 ///
 /// Returns `Ok(true)` if the handle supports the protocol, `Ok(false)` if not.
@@ -1091,6 +1620,68 @@ pub fn load_image(parent_image_handle: Handle, source: LoadImageSource) -> Resul
     }
 }
 
+/// Loads a UEFI image from a full device path, using
+/// [`BootPolicy::ExactMatch`].
+///
+/// This is a shorthand for calling [`load_image`] with
+/// [`LoadImageSource::FromDevicePath`]. The path must be a full device path,
+/// including the device the file is on; see
+/// [`LoadImageSource::FromDevicePath`] for details.
+///
+/// # Errors
+///
+/// See [`load_image`] for the possible errors.
+pub fn load_image_from_path(parent_image_handle: Handle, path: &DevicePath) -> Result<Handle> {
+    load_image(
+        parent_image_handle,
+        LoadImageSource::FromDevicePath {
+            device_path: path,
+            boot_policy: BootPolicy::ExactMatch,
+        },
+    )
+}
+
+/// Loads a UEFI image from a file on the device the parent image was loaded
+/// from.
+///
+/// The full device path of the file is built by appending a file path node
+/// for `path` (e.g. `\EFI\tool.efi`) to the device path of the parent
+/// image's device, followed by an end-entire node.
+///
+/// # Errors
+///
+/// * [`Status::UNSUPPORTED`]: the parent image was not loaded from a device.
+/// * [`Status::INVALID_PARAMETER`]: `path` is too long for a device path node.
+///
+/// This function can also return errors from [`open_protocol_exclusive`] and
+/// [`load_image`].
+#[cfg(feature = "alloc")]
+pub fn load_image_from_file(parent_image_handle: Handle, path: &CStr16) -> Result<Handle> {
+    use crate::proto::device_path::build::{self, DevicePathBuilder};
+
+    let loaded_image = open_protocol_exclusive::<LoadedImage>(parent_image_handle)?;
+    let device_handle = loaded_image
+        .device()
+        .ok_or(Error::new(Status::UNSUPPORTED, ()))?;
+    let device_path = open_protocol_exclusive::<DevicePath>(device_handle)?;
+
+    // `node_iter` stops before the device path's end node, and `finalize`
+    // adds an end-entire node after the file path node.
+    let mut buf = Vec::new();
+    let mut builder = DevicePathBuilder::with_vec(&mut buf);
+    for node in device_path.node_iter() {
+        builder = builder
+            .push(&node)
+            .map_err(|_| Error::from(Status::INVALID_PARAMETER))?;
+    }
+    let file_path = builder
+        .push(&build::media::FilePath { path_name: path })
+        .and_then(DevicePathBuilder::finalize)
+        .map_err(|_| Error::from(Status::INVALID_PARAMETER))?;
+
+    load_image_from_path(parent_image_handle, file_path)
+}
+
 /// Unloads a UEFI image.
 ///
 /// # Errors
@@ -1188,7 +1779,16 @@ unsafe fn get_memory_map_and_exit_boot_services(buf: &mut [u8]) -> Result<Memory
 /// abstractions provided by this crate (see the [`helpers`] module),
 /// invoking this function will automatically disable them. If the
 /// `global_allocator` feature is enabled, attempting to use the allocator
-/// after exiting boot services will panic.
+/// after exiting boot services will panic. No memory may be allocated after
+/// this function returns.
+///
+/// The memory map buffer is allocated once, up front, with some extra room
+/// for entries added by the allocation itself. Nothing is allocated between
+/// retrieving the memory map and calling the firmware's `ExitBootServices`,
+/// so the map key only goes stale if the firmware itself changes the memory
+/// map (for example, in an event handler). In that case the firmware returns
+/// [`Status::INVALID_PARAMETER`], and the memory map is retrieved again and
+/// exiting is retried once.
 ///
 /// # Safety
 ///
@@ -1199,7 +1799,8 @@ unsafe fn get_memory_map_and_exit_boot_services(buf: &mut [u8]) -> Result<Memory
 ///   includes the [`Output`] protocols attached to stdout/stderr. The
 ///   caller must ensure that no protocol references remain.
 /// * The pool allocator is not usable after exiting boot services. Types
-///   such as [`PoolString`] which call [`free_pool`] on drop
+///   such as [`PoolString`] and [`PoolDevicePath`] which call [`free_pool`]
+///   on drop
 ///   must be cleaned up before calling `exit_boot_services`, or leaked to
 ///   avoid drop ever being called.
 /// * All data in the memory map marked as
@@ -1210,7 +1811,8 @@ unsafe fn get_memory_map_and_exit_boot_services(buf: &mut [u8]) -> Result<Memory
 ///
 /// This function will fail if it is unable to allocate memory for
 /// the memory map, if it fails to retrieve the memory map, or if
-/// exiting boot services fails (with up to one retry).
+/// exiting boot services fails (with up to one retry if the memory map
+/// key is stale).
 ///
 /// All errors are treated as unrecoverable because the system is
 /// now in an undefined state. Rather than returning control to the
@@ -1219,6 +1821,7 @@ unsafe fn get_memory_map_and_exit_boot_services(buf: &mut [u8]) -> Result<Memory
 /// [`helpers`]: crate::helpers
 /// [`Output`]: crate::proto::console::text::Output
 /// [`PoolString`]: crate::proto::device_path::text::PoolString
+/// [`PoolDevicePath`]: crate::proto::device_path::text::PoolDevicePath
 #[must_use]
 pub unsafe fn exit_boot_services(memory_type: MemoryType) -> MemoryMapOwned {
     crate::helpers::exit();
@@ -1237,7 +1840,14 @@ pub unsafe fn exit_boot_services(memory_type: MemoryType) -> MemoryMapOwned {
             }
             Err(err) => {
                 log::error!("Error retrieving the memory map for exiting the boot services");
-                status = err.status()
+                status = err.status();
+                // Only a stale map key is fixed by retrying. Any other
+                // error (e.g. the map no longer fits in the buffer) is
+                // unrecoverable, since allocating a new buffer is not
+                // allowed after a failed call to `exit_boot_services`.
+                if status != Status::INVALID_PARAMETER {
+                    break;
+                }
             }
         }
     }
@@ -1321,6 +1931,8 @@ pub fn set_watchdog_timer(
 }
 
 /// Stalls execution for the given number of microseconds.
+///
+/// See also [`stall_for`], which takes a [`Duration`].
 pub fn stall(microseconds: usize) {
     let bt = boot_services_raw_panicking();
     let bt = unsafe { bt.as_ref() };
@@ -1332,6 +1944,54 @@ pub fn stall(microseconds: usize) {
     }
 }
 
+/// Stalls execution for the given duration.
+///
+/// The duration is rounded down to whole microseconds. Durations that do not
+/// fit in a `usize` worth of microseconds are saturated.
+pub fn stall_for(duration: Duration) {
+    stall(duration_to_micros(duration));
+}
+
+/// Convert a duration to the microseconds used by [`stall`], saturating on
+/// overflow.
+fn duration_to_micros(duration: Duration) -> usize {
+    usize::try_from(duration.as_micros()).unwrap_or(usize::MAX)
+}
+
+/// Get the next value of the platform's 64-bit monotonic counter.
+///
+/// The upper 32 bits are the high count, which is stored in nonvolatile
+/// storage and incremented on every reset. The lower 32 bits are incremented
+/// on each call and reset to zero on every reset. The combined value is
+/// therefore unique across resets, e.g. for generating unique IDs.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: the device is not functioning properly.
+pub fn get_next_monotonic_count() -> Result<u64> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let mut count = 0;
+    unsafe { (bt.get_next_monotonic_count)(&mut count) }.to_result_with_val(|| count)
+}
+
+/// Computes the 32-bit CRC of `data`, using the same algorithm as the
+/// checksums in UEFI table headers and GPT headers.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `data` is empty. Not all firmware
+///   rejects empty data.
+pub fn calculate_crc32(data: &[u8]) -> Result<u32> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let mut crc = 0;
+    unsafe { (bt.calculate_crc32)(data.as_ptr().cast(), data.len(), &mut crc) }
+        .to_result_with_val(|| crc)
+}
+
 /// Retrieves a [`SimpleFileSystem`] protocol associated with the device the given
 /// image was loaded from.
 ///
@@ -1358,6 +2018,127 @@ pub fn get_image_file_system(image_handle: Handle) -> Result<ScopedProtocol<Simp
     open_protocol_exclusive(device_handle)
 }
 
+/// Get a copy of the full device path the given image was loaded from,
+/// e.g. `PciRoot(0x0)/Pci(0x1f,0x2)/.../\EFI\BOOT\BOOTX64.EFI`.
+///
+/// This is read from the image's [`LoadedImageDevicePath`] protocol. Use
+/// [`get_image_file_name`] to get just the file path portion.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the image was not loaded from a device path, e.g.
+///   because it was loaded from a buffer.
+///
+/// This function can also return errors from [`open_protocol_exclusive`].
+#[cfg(feature = "alloc")]
+pub fn get_image_file_path(image_handle: Handle) -> Result<Box<DevicePath>> {
+    let device_path = open_protocol_exclusive::<LoadedImageDevicePath>(image_handle)?;
+    device_path
+        .get()
+        .map(|device_path| device_path.to_boxed())
+        .ok_or_else(|| Status::NOT_FOUND.into())
+}
+
+/// Get the path of the file the given image was loaded from, e.g.
+/// `\EFI\BOOT\BOOTX64.EFI`.
+///
+/// This is the path name of the last file path node in the image's
+/// [`get_image_file_path`]. It is relative to the root of the file system
+/// returned by [`get_image_file_system`], so it can be used to locate files
+/// next to the image.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the image was not loaded from a device path, or
+///   the device path doesn't contain a file path node.
+/// * [`Status::INVALID_PARAMETER`]: the file path is not valid UCS-2.
+///
+/// This function can also return errors from [`open_protocol_exclusive`].
+#[cfg(feature = "alloc")]
+pub fn get_image_file_name(image_handle: Handle) -> Result<CString16> {
+    last_file_path_name(&get_image_file_path(image_handle)?)
+}
+
+/// Get the path name of the last file path node in `device_path`.
+#[cfg(feature = "alloc")]
+fn last_file_path_name(device_path: &DevicePath) -> Result<CString16> {
+    let node = device_path
+        .node_iter()
+        .filter_map(|node| match node.as_enum() {
+            Ok(DevicePathNodeEnum::MediaFilePath(node)) => Some(node),
+            _ => None,
+        })
+        .last()
+        .ok_or(Status::NOT_FOUND)?;
+    node.path_name()
+        .to_cstring16()
+        .map_err(|_| Status::INVALID_PARAMETER.into())
+}
+
+/// Memory pages returned by [`allocate_pages_owned`]. The pages are freed
+/// when this is dropped.
+///
+/// The allocation dereferences to a byte slice covering all of the pages.
+#[derive(Debug)]
+pub struct PageAllocation {
+    ptr: NonNull<u8>,
+    count: usize,
+}
+
+impl PageAllocation {
+    /// Get a pointer to the start of the allocation. The pointer is page
+    /// aligned.
+    #[must_use]
+    pub const fn as_ptr(&self) -> NonNull<u8> {
+        self.ptr
+    }
+
+    /// Get the number of pages in the allocation.
+    #[must_use]
+    pub const fn page_count(&self) -> usize {
+        self.count
+    }
+
+    /// Consume the allocation without freeing the pages, and return a pointer
+    /// to the start of the allocation.
+    ///
+    /// The pages can be freed later with [`free_pages`], passing
+    /// [`page_count`] pages. Otherwise they stay allocated, e.g. for passing
+    /// data to an OS after exiting boot services.
+    ///
+    /// [`page_count`]: Self::page_count
+    #[must_use]
+    pub const fn leak(self) -> NonNull<u8> {
+        let ptr = self.ptr;
+        mem::forget(self);
+        ptr
+    }
+}
+
+impl Drop for PageAllocation {
+    fn drop(&mut self) {
+        let _ = unsafe { free_pages(self.ptr, self.count) };
+    }
+}
+
+impl Deref for PageAllocation {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the pages are allocated and were initialized when the
+        // allocation was created. The size can't overflow, which was checked
+        // when the allocation was created.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.count * PAGE_SIZE) }
+    }
+}
+
+impl DerefMut for PageAllocation {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: see `deref`.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.count * PAGE_SIZE) }
+    }
+}
+
 /// Protocol interface [`Guids`][Guid] that are installed on a [`Handle`] as
 /// returned by [`protocols_per_handle`].
 #[derive(Debug)]
@@ -1390,6 +2171,54 @@ impl Deref for ProtocolsPerHandle {
     }
 }
 
+/// Description of a [`Handle`] returned by [`describe_handle`].
+///
+/// The [`Display`] impl formats the installed protocols as a comma-separated
+/// list, e.g. `SimpleFileSystem, DevicePath, 12345678-9abc-def0-1234-56789abcdef0`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandleDescription {
+    handle: Handle,
+    protocols: Vec<Guid>,
+}
+
+#[cfg(feature = "alloc")]
+impl HandleDescription {
+    /// Get the described handle.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Get the [`Guids`][Guid] of the protocols installed on the handle.
+    #[must_use]
+    pub fn protocols(&self) -> &[Guid] {
+        &self.protocols
+    }
+
+    /// Get the names of the protocols installed on the handle, or `None`
+    /// for protocols not defined in this crate.
+    pub fn protocol_names(&self) -> impl Iterator<Item = Option<&'static str>> + '_ {
+        self.protocols.iter().map(crate::proto::protocol_name)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for HandleDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, guid) in self.protocols.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            match crate::proto::protocol_name(guid) {
+                Some(name) => f.write_str(name)?,
+                None => write!(f, "{guid}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A buffer returned by [`locate_handle_buffer`] that contains an array of
 /// [`Handle`]s that support the requested protocol.
 #[derive(Debug, Eq, PartialEq)]
@@ -1412,6 +2241,44 @@ impl Deref for HandleBuffer {
     }
 }
 
+/// A protocol install notification created by [`watch_protocol_installs`].
+///
+/// Dropping the watch closes the notification event, after which the callback
+/// is no longer called.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct ProtocolWatch {
+    event: Event,
+    state: NonNull<ProtocolWatchState>,
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for ProtocolWatch {
+    fn drop(&mut self) {
+        // Safety: the event is not used again after this point.
+        let _ = close_event(unsafe { self.event.unsafe_clone() });
+        // Safety: the event is closed, so the notification function can no
+        // longer access the state.
+        drop(unsafe { Box::from_raw(self.state.as_ptr()) });
+    }
+}
+
+/// State shared between a [`ProtocolWatch`] and its notification function.
+#[cfg(feature = "alloc")]
+struct ProtocolWatchState {
+    key: Option<ProtocolSearchKey>,
+    callback: Box<dyn FnMut(Handle)>,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for ProtocolWatchState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProtocolWatchState")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
 /// An open protocol interface. Automatically closes the protocol
 /// interface on drop.
 ///
@@ -1485,6 +2352,129 @@ impl<P: Protocol + ?Sized> ScopedProtocol<P> {
     pub fn get_mut(&mut self) -> Option<&mut P> {
         self.interface.map(|mut p| unsafe { p.as_mut() })
     }
+
+    /// Consume the `ScopedProtocol` without closing the protocol, and
+    /// return a pointer to the interface data.
+    ///
+    /// This is used by drivers that open a protocol with
+    /// [`OpenProtocolAttributes::ByDriver`] in their `Start` function and
+    /// keep it open until their `Stop` function calls [`close_protocol`].
+    #[must_use]
+    pub const fn leak(self) -> Option<NonNull<P>> {
+        let interface = self.interface;
+        mem::forget(self);
+        interface
+    }
+}
+
+/// A protocol interface opened without exclusive access. Automatically closes
+/// the protocol interface on drop.
+///
+/// This is returned by [`open_protocol_get`]. Unlike [`ScopedProtocol`], it
+/// only implements [`Deref`], since other agents may be using the protocol at
+/// the same time. [`Deref`] panics if the interface data is null; the [`get`]
+/// method may be used to access the optional interface data without
+/// panicking.
+///
+/// [`get`]: SharedProtocol::get
+#[derive(Debug)]
+pub struct SharedProtocol<P: Protocol + ?Sized>(ScopedProtocol<P>);
+
+impl<P: Protocol + ?Sized> Deref for SharedProtocol<P> {
+    type Target = P;
+
+    #[track_caller]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P: Protocol + ?Sized> SharedProtocol<P> {
+    /// Get the protocol interface data, or `None` if the open protocol's
+    /// interface is null.
+    #[must_use]
+    pub fn get(&self) -> Option<&P> {
+        self.0.get()
+    }
+}
+
+/// An open protocol interface whose GUID is only known at runtime.
+///
+/// This is returned by [`open_protocol_dynamic`]. Like [`ScopedProtocol`],
+/// the protocol is automatically closed when this is dropped.
+///
+/// The crate knows nothing about the layout of the interface, so only an
+/// untyped pointer is exposed. The interface is dereferenced as a [`c_void`],
+/// which panics if the interface is null; use [`interface`] to check first.
+///
+/// # Safety of the interface pointer
+///
+/// Using the interface requires casting the pointer to the protocol's
+/// structure, which is inherently unsafe. The caller must make sure that:
+///
+/// * the structure matches the layout the firmware uses for the protocol
+///   with this GUID, including the calling convention (`efiapi`) of its
+///   function pointers,
+/// * the pointer is not used after this `ScopedRawProtocol` is dropped, since
+///   the protocol may be uninstalled once it is closed, and
+/// * the rules of the specific protocol are followed, e.g. which TPL its
+///   functions may be called at.
+///
+/// [`interface`]: Self::interface
+#[derive(Debug)]
+pub struct ScopedRawProtocol {
+    guid: Guid,
+    interface: Option<NonNull<c_void>>,
+    open_params: OpenProtocolParams,
+}
+
+impl ScopedRawProtocol {
+    /// Get the GUID of the open protocol.
+    #[must_use]
+    pub const fn guid(&self) -> Guid {
+        self.guid
+    }
+
+    /// Get a pointer to the protocol interface, or `None` if the open
+    /// protocol's interface is null.
+    #[must_use]
+    pub const fn interface(&self) -> Option<NonNull<c_void>> {
+        self.interface
+    }
+}
+
+impl Drop for ScopedRawProtocol {
+    fn drop(&mut self) {
+        let bt = boot_services_raw_panicking();
+        let bt = unsafe { bt.as_ref() };
+
+        let status = unsafe {
+            (bt.close_protocol)(
+                self.open_params.handle.as_ptr(),
+                &self.guid,
+                self.open_params.agent.as_ptr(),
+                Handle::opt_to_ptr(self.open_params.controller),
+            )
+        };
+        // See `ScopedProtocol::drop`.
+        assert_eq!(status, Status::SUCCESS);
+    }
+}
+
+impl Deref for ScopedRawProtocol {
+    type Target = c_void;
+
+    #[track_caller]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.interface.unwrap().as_ref() }
+    }
+}
+
+impl DerefMut for ScopedRawProtocol {
+    #[track_caller]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.interface.unwrap().as_mut() }
+    }
 }
 
 /// RAII guard for task priority level changes.
@@ -1523,7 +2513,7 @@ impl Drop for TplGuard {
 
 /// Attributes for [`open_protocol`].
 #[repr(u32)]
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OpenProtocolAttributes {
     /// Used by drivers to get a protocol interface for a handle. The
     /// driver will not be informed if the interface is uninstalled or
@@ -1555,6 +2545,34 @@ pub enum OpenProtocolAttributes {
     Exclusive = 0x20,
 }
 
+impl OpenProtocolAttributes {
+    /// Returns true if the [`controller`] field of [`OpenProtocolParams`]
+    /// must be set when opening a protocol with these attributes. This is
+    /// the case for [`ByChildController`], [`ByDriver`], and
+    /// [`ByDriverExclusive`].
+    ///
+    /// [`controller`]: OpenProtocolParams::controller
+    /// [`ByChildController`]: Self::ByChildController
+    /// [`ByDriver`]: Self::ByDriver
+    /// [`ByDriverExclusive`]: Self::ByDriverExclusive
+    #[must_use]
+    pub const fn requires_controller(self) -> bool {
+        matches!(
+            self,
+            Self::ByChildController | Self::ByDriver | Self::ByDriverExclusive
+        )
+    }
+
+    /// Check the constraints on `params` that the spec places on these
+    /// attributes, see [`open_protocol`].
+    fn is_valid_for(self, params: &OpenProtocolParams) -> bool {
+        match params.controller {
+            None => !self.requires_controller(),
+            Some(controller) => self != Self::ByChildController || controller != params.handle,
+        }
+    }
+}
+
 /// Parameters passed to [`open_protocol`].
 #[derive(Debug)]
 pub struct OpenProtocolParams {
@@ -1689,7 +2707,7 @@ impl SearchType<'_> {
 pub type EventNotifyFn = unsafe extern "efiapi" fn(event: Event, context: Option<NonNull<c_void>>);
 
 /// Timer events manipulation.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TimerTrigger {
     /// Cancel event's timer
     Cancel,
@@ -1703,8 +2721,197 @@ pub enum TimerTrigger {
     Relative(u64),
 }
 
+impl TimerTrigger {
+    /// Creates a [`TimerTrigger::Periodic`] trigger with the given period.
+    ///
+    /// The period is rounded down to 100ns units.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the period in 100ns units does not
+    ///   fit in a `u64`.
+    pub fn periodic(period: Duration) -> Result<Self> {
+        duration_to_100ns(period).map(Self::Periodic)
+    }
+
+    /// Creates a [`TimerTrigger::Relative`] trigger with the given delay.
+    ///
+    /// The delay is rounded down to 100ns units.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the delay in 100ns units does not
+    ///   fit in a `u64`.
+    pub fn relative(delay: Duration) -> Result<Self> {
+        duration_to_100ns(delay).map(Self::Relative)
+    }
+}
+
+/// Convert a duration to the 100ns units used by [`set_timer`].
+fn duration_to_100ns(duration: Duration) -> Result<u64> {
+    u64::try_from(duration.as_nanos() / 100).map_err(|_| Status::INVALID_PARAMETER.into())
+}
+
+/// Convert a duration to the 100ns units used by [`set_timer`], saturating
+/// at `u64::MAX`.
+fn duration_to_100ns_saturating(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos() / 100).unwrap_or(u64::MAX)
+}
+
 /// Opaque pointer returned by [`register_protocol_notify`] to be used
 /// with [`locate_handle`] via [`SearchType::ByRegisterNotify`].
 #[derive(Debug, Clone, Copy)]
 #[repr(transparent)]
 pub struct ProtocolSearchKey(pub(crate) NonNull<c_void>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_to_100ns() {
+        assert_eq!(duration_to_100ns(Duration::ZERO), Ok(0));
+        assert_eq!(duration_to_100ns(Duration::from_nanos(99)), Ok(0));
+        assert_eq!(duration_to_100ns(Duration::from_micros(1)), Ok(10));
+        assert_eq!(duration_to_100ns(Duration::from_secs(5)), Ok(50_000_000));
+        assert_eq!(
+            duration_to_100ns(Duration::MAX),
+            Err(Status::INVALID_PARAMETER.into())
+        );
+    }
+
+    #[test]
+    fn test_duration_to_100ns_saturating() {
+        assert_eq!(duration_to_100ns_saturating(Duration::ZERO), 0);
+        assert_eq!(duration_to_100ns_saturating(Duration::from_nanos(199)), 1);
+        assert_eq!(
+            duration_to_100ns_saturating(Duration::from_secs(5)),
+            50_000_000
+        );
+        assert_eq!(duration_to_100ns_saturating(Duration::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_duration_to_micros() {
+        assert_eq!(duration_to_micros(Duration::ZERO), 0);
+        assert_eq!(duration_to_micros(Duration::from_nanos(999)), 0);
+        assert_eq!(duration_to_micros(Duration::from_millis(1)), 1000);
+        assert_eq!(duration_to_micros(Duration::MAX), usize::MAX);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_handle_description_display() {
+        use crate::proto::device_path::DevicePath;
+        use crate::proto::media::fs::SimpleFileSystem;
+        use crate::{guid, Identify};
+        use alloc::string::ToString;
+        use alloc::vec;
+
+        let mut description = HandleDescription {
+            handle: unsafe { Handle::from_ptr(NonNull::dangling().as_ptr()) }.unwrap(),
+            protocols: Vec::new(),
+        };
+        assert_eq!(description.to_string(), "");
+
+        description.protocols = vec![
+            SimpleFileSystem::GUID,
+            DevicePath::GUID,
+            guid!("12345678-9abc-def0-1234-56789abcdef0"),
+        ];
+        assert_eq!(
+            description.to_string(),
+            "SimpleFileSystem, DevicePath, 12345678-9abc-def0-1234-56789abcdef0"
+        );
+        assert_eq!(
+            description.protocol_names().collect::<Vec<_>>(),
+            [Some("SimpleFileSystem"), Some("DevicePath"), None]
+        );
+    }
+
+    #[test]
+    fn test_open_protocol_attributes() {
+        use OpenProtocolAttributes::*;
+
+        let handle_a = unsafe { Handle::from_ptr(NonNull::dangling().as_ptr()) }.unwrap();
+        let handle_b =
+            unsafe { Handle::from_ptr(NonNull::<u64>::dangling().as_ptr().cast()) }.unwrap();
+        assert_ne!(handle_a, handle_b);
+        let params = |controller| OpenProtocolParams {
+            handle: handle_a,
+            agent: handle_a,
+            controller,
+        };
+
+        for attributes in [GetProtocol, Exclusive] {
+            assert!(!attributes.requires_controller());
+            assert!(attributes.is_valid_for(&params(None)));
+            assert!(attributes.is_valid_for(&params(Some(handle_b))));
+        }
+        for attributes in [ByChildController, ByDriver, ByDriverExclusive] {
+            assert!(attributes.requires_controller());
+            assert!(!attributes.is_valid_for(&params(None)));
+            assert!(attributes.is_valid_for(&params(Some(handle_b))));
+        }
+        assert!(ByDriver.is_valid_for(&params(Some(handle_a))));
+        assert!(!ByChildController.is_valid_for(&params(Some(handle_a))));
+    }
+
+    #[test]
+    fn test_timer_trigger() {
+        assert_eq!(
+            TimerTrigger::relative(Duration::from_millis(5)),
+            Ok(TimerTrigger::Relative(50_000))
+        );
+        assert_eq!(
+            TimerTrigger::periodic(Duration::from_secs(1)),
+            Ok(TimerTrigger::Periodic(10_000_000))
+        );
+        assert_eq!(
+            TimerTrigger::relative(Duration::MAX),
+            Err(Status::INVALID_PARAMETER.into())
+        );
+    }
+
+    #[test]
+    fn test_last_file_path_name() {
+        use crate::cstr16;
+        use crate::proto::device_path::build::{self, DevicePathBuilder};
+        use alloc::vec::Vec;
+
+        // A path with multiple file path nodes uses the last one.
+        let mut v = Vec::new();
+        let path = DevicePathBuilder::with_vec(&mut v)
+            .push(&build::acpi::Acpi {
+                hid: 0x41d0_0a03,
+                uid: 0,
+            })
+            .unwrap()
+            .push(&build::media::FilePath {
+                path_name: cstr16!("\\EFI"),
+            })
+            .unwrap()
+            .push(&build::media::FilePath {
+                path_name: cstr16!("\\EFI\\BOOT\\BOOTX64.EFI"),
+            })
+            .unwrap()
+            .finalize()
+            .unwrap();
+        assert_eq!(
+            last_file_path_name(path).unwrap(),
+            cstr16!("\\EFI\\BOOT\\BOOTX64.EFI")
+        );
+
+        // A path without a file path node.
+        let mut v = Vec::new();
+        let path = DevicePathBuilder::with_vec(&mut v)
+            .push(&build::acpi::Acpi {
+                hid: 0x41d0_0a03,
+                uid: 0,
+            })
+            .unwrap()
+            .finalize()
+            .unwrap();
+        assert_eq!(last_file_path_name(path), Err(Status::NOT_FOUND.into()));
+    }
+}
@@ -0,0 +1,156 @@
+//! Waiting on several event sources at once.
+//!
+//! [`WaitSet`] wraps [`wait_for_event`] for the common case of waiting for
+//! one of a few events, optionally with a timeout.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use core::time::Duration;
+//! use uefi::boot::events::{WaitOutcome, WaitSet};
+//! use uefi::system;
+//!
+//! # fn example() -> uefi::Result {
+//! let key_event = system::with_stdin(|stdin| stdin.wait_for_key_event()).unwrap();
+//! let mut wait_set = WaitSet::new()
+//!     .add(&key_event)
+//!     .add_timer(Duration::from_secs(5))?;
+//! match wait_set.wait()? {
+//!     WaitOutcome::Event(_) => log::info!("key pressed"),
+//!     WaitOutcome::Timeout => log::info!("timed out"),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{close_event, create_event, set_timer, wait_for_event, EventType, TimerTrigger, Tpl};
+use crate::{Event, Result, ResultExt, Status};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::time::Duration;
+
+/// Which source caused [`WaitSet::wait`] to return.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WaitOutcome {
+    /// The event at this index was signaled. Indices are in the order the
+    /// events were added with [`WaitSet::add`].
+    Event(usize),
+
+    /// The timer added with [`WaitSet::add_timer`] expired.
+    Timeout,
+}
+
+/// A set of events to wait on, with an optional timeout.
+///
+/// Events added with [`add`] are borrowed, and are not closed when the set is
+/// dropped. The timer event created by [`add_timer`] is owned by the set and
+/// closed when it is dropped.
+///
+/// [`add`]: Self::add
+/// [`add_timer`]: Self::add_timer
+#[derive(Debug, Default)]
+pub struct WaitSet<'a> {
+    events: Vec<Event>,
+    timer: Option<(Event, TimerTrigger)>,
+    _lifetime: PhantomData<&'a Event>,
+}
+
+impl<'a> WaitSet<'a> {
+    /// Create an empty wait set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an event to wait on.
+    #[allow(clippy::should_implement_trait)]
+    #[must_use]
+    pub fn add(mut self, event: &'a Event) -> Self {
+        // SAFETY: the clone is only used for waiting, and the lifetime ensures
+        // it is not used after the original event can be closed.
+        self.events.push(unsafe { event.unsafe_clone() });
+        self
+    }
+
+    /// Add a timeout. The timer starts when [`wait`] is called, and is
+    /// restarted on each call. If a timeout was already added, it is
+    /// replaced.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the timeout in 100ns units does not
+    ///   fit in a `u64`.
+    /// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be created.
+    ///
+    /// [`wait`]: Self::wait
+    pub fn add_timer(mut self, timeout: Duration) -> Result<Self> {
+        let trigger = TimerTrigger::relative(timeout)?;
+        let event = unsafe { create_event(EventType::TIMER, Tpl::CALLBACK, None, None) }?;
+        if let Some((old, _)) = self.timer.replace((event, trigger)) {
+            close_event(old)?;
+        }
+        Ok(self)
+    }
+
+    /// Block until one of the events is signaled or the timeout expires.
+    ///
+    /// If several events are signaled at once, the first one in the order
+    /// they were added is reported.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the set is empty, or one of the
+    ///   events is of type [`EventType::NOTIFY_SIGNAL`].
+    /// * [`Status::UNSUPPORTED`]: the current TPL is not
+    ///   [`Tpl::APPLICATION`].
+    pub fn wait(&mut self) -> Result<WaitOutcome> {
+        let mut events: Vec<Event> = self
+            .events
+            .iter()
+            .map(|event| unsafe { event.unsafe_clone() })
+            .collect();
+        if events.is_empty() && self.timer.is_none() {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        // Cancel the timer when leaving this function, including on errors,
+        // so that it does not keep firing after the wait is over.
+        let _armed = match &self.timer {
+            Some((timer, trigger)) => {
+                set_timer(timer, *trigger)?;
+                events.push(unsafe { timer.unsafe_clone() });
+                Some(ArmedTimer(timer))
+            }
+            None => None,
+        };
+
+        let index = wait_for_event(&mut events).discard_errdata()?;
+
+        if index < self.events.len() {
+            Ok(WaitOutcome::Event(index))
+        } else {
+            Ok(WaitOutcome::Timeout)
+        }
+    }
+}
+
+/// Cancels an armed timer when dropped.
+struct ArmedTimer<'t>(&'t Event);
+
+impl Drop for ArmedTimer<'_> {
+    fn drop(&mut self) {
+        // Cancelling only fails if the event is invalid, which cannot happen
+        // for the set's own timer.
+        let _ = set_timer(self.0, TimerTrigger::Cancel);
+    }
+}
+
+impl Drop for WaitSet<'_> {
+    fn drop(&mut self) {
+        if let Some((timer, _)) = self.timer.take() {
+            // Closing an event only fails if it is invalid, which cannot
+            // happen for an event created by `add_timer`.
+            let _ = close_event(timer);
+        }
+    }
+}
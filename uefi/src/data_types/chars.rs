@@ -17,6 +17,115 @@ impl Display for CharConversionError {
 
 impl core::error::Error for CharConversionError {}
 
+/// Implement ASCII classification and case mapping methods for a character
+/// type. Like the methods of the same name on [`u8`] and [`char`], these only
+/// consider the ASCII range: all other characters are left unchanged by the
+/// mapping methods and are not matched by the classification methods.
+macro_rules! impl_ascii_methods {
+    ($char:ty) => {
+        impl $char {
+            /// Get the character as an ASCII byte, or a non-ASCII byte if the
+            /// character is outside the ASCII range.
+            const fn ascii_byte(&self) -> u8 {
+                if self.is_ascii() {
+                    self.0 as u8
+                } else {
+                    0x80
+                }
+            }
+
+            /// Checks if the value is within the ASCII range.
+            #[must_use]
+            pub const fn is_ascii(&self) -> bool {
+                self.0 <= 127
+            }
+
+            /// Checks if the value is an ASCII alphabetic character (`A-Z`
+            /// or `a-z`).
+            #[must_use]
+            pub const fn is_ascii_alphabetic(&self) -> bool {
+                self.ascii_byte().is_ascii_alphabetic()
+            }
+
+            /// Checks if the value is an ASCII uppercase character (`A-Z`).
+            #[must_use]
+            pub const fn is_ascii_uppercase(&self) -> bool {
+                self.ascii_byte().is_ascii_uppercase()
+            }
+
+            /// Checks if the value is an ASCII lowercase character (`a-z`).
+            #[must_use]
+            pub const fn is_ascii_lowercase(&self) -> bool {
+                self.ascii_byte().is_ascii_lowercase()
+            }
+
+            /// Checks if the value is an ASCII decimal digit (`0-9`).
+            #[must_use]
+            pub const fn is_ascii_digit(&self) -> bool {
+                self.ascii_byte().is_ascii_digit()
+            }
+
+            /// Checks if the value is an ASCII hexadecimal digit (`0-9`,
+            /// `A-F` or `a-f`).
+            #[must_use]
+            pub const fn is_ascii_hexdigit(&self) -> bool {
+                self.ascii_byte().is_ascii_hexdigit()
+            }
+
+            /// Checks if the value is an ASCII alphanumeric character (`A-Z`,
+            /// `a-z` or `0-9`).
+            #[must_use]
+            pub const fn is_ascii_alphanumeric(&self) -> bool {
+                self.ascii_byte().is_ascii_alphanumeric()
+            }
+
+            /// Checks if the value is ASCII whitespace: space, horizontal
+            /// tab, line feed, form feed or carriage return.
+            ///
+            /// Non-ASCII whitespace, such as U+00A0 NO-BREAK SPACE, is not
+            /// matched.
+            #[must_use]
+            pub const fn is_ascii_whitespace(&self) -> bool {
+                self.ascii_byte().is_ascii_whitespace()
+            }
+
+            /// Checks if the value is an ASCII control character.
+            #[must_use]
+            pub const fn is_ascii_control(&self) -> bool {
+                self.ascii_byte().is_ascii_control()
+            }
+
+            /// Returns the ASCII uppercase equivalent of the value. Characters
+            /// other than `a-z` are returned unchanged.
+            #[must_use]
+            pub const fn to_ascii_uppercase(&self) -> Self {
+                if self.is_ascii_lowercase() {
+                    Self(self.0 - 0x20)
+                } else {
+                    *self
+                }
+            }
+
+            /// Returns the ASCII lowercase equivalent of the value. Characters
+            /// other than `A-Z` are returned unchanged.
+            #[must_use]
+            pub const fn to_ascii_lowercase(&self) -> Self {
+                if self.is_ascii_uppercase() {
+                    Self(self.0 + 0x20)
+                } else {
+                    *self
+                }
+            }
+
+            /// Checks that two values are equal, ignoring ASCII case.
+            #[must_use]
+            pub const fn eq_ignore_ascii_case(&self, other: &Self) -> bool {
+                self.to_ascii_lowercase().0 == other.to_ascii_lowercase().0
+            }
+        }
+    };
+}
+
 /// A Latin-1 character
 #[derive(Clone, Copy, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -69,6 +178,8 @@ impl PartialEq<char> for Char8 {
     }
 }
 
+impl_ascii_methods!(Char8);
+
 /// Latin-1 version of the NUL character
 pub const NUL_8: Char8 = Char8(0);
 
@@ -86,14 +197,10 @@ impl Char16 {
     pub const unsafe fn from_u16_unchecked(val: u16) -> Self {
         Self(val)
     }
-
-    /// Checks if the value is within the ASCII range.
-    #[must_use]
-    pub const fn is_ascii(&self) -> bool {
-        self.0 <= 127
-    }
 }
 
+impl_ascii_methods!(Char16);
+
 impl TryFrom<char> for Char16 {
     type Error = CharConversionError;
 
@@ -175,6 +282,58 @@ mod tests {
         assert_eq!(Char16::try_from('ꋃ').unwrap(), Char16(0xa2c3));
     }
 
+    #[test]
+    fn test_char16_surrogates() {
+        // Lone surrogates are not valid UCS-2 characters.
+        assert!(Char16::try_from(0xd800).is_err());
+        assert!(Char16::try_from(0xdfff).is_err());
+        assert_eq!(Char16::try_from(0xe000).unwrap(), Char16(0xe000));
+
+        // Characters outside the BMP can't be represented in UCS-2.
+        assert!(Char16::try_from('😀').is_err());
+    }
+
+    #[test]
+    fn test_char16_ascii() {
+        let c = |c| Char16::try_from(c).unwrap();
+
+        assert!(c('a').is_ascii_alphabetic());
+        assert!(c('Z').is_ascii_uppercase());
+        assert!(!c('Z').is_ascii_lowercase());
+        assert!(c('7').is_ascii_digit());
+        assert!(c('f').is_ascii_hexdigit());
+        assert!(!c('g').is_ascii_hexdigit());
+        assert!(c('7').is_ascii_alphanumeric());
+        assert!(c('\t').is_ascii_whitespace());
+        assert!(c('\r').is_ascii_control());
+
+        // Non-ASCII characters are never matched.
+        assert!(!c('é').is_ascii_alphabetic());
+        assert!(!c('\u{a0}').is_ascii_whitespace());
+        assert!(!c('\u{130}').is_ascii_alphanumeric());
+        assert!(!c('٣').is_ascii_digit());
+
+        assert_eq!(c('a').to_ascii_uppercase(), c('A'));
+        assert_eq!(c('A').to_ascii_lowercase(), c('a'));
+        assert_eq!(c('1').to_ascii_uppercase(), c('1'));
+        assert_eq!(c('é').to_ascii_uppercase(), c('é'));
+        assert!(c('q').eq_ignore_ascii_case(&c('Q')));
+        assert!(!c('é').eq_ignore_ascii_case(&c('É')));
+    }
+
+    #[test]
+    fn test_char8_ascii() {
+        assert!(Char8(b'a').is_ascii_alphabetic());
+        assert!(Char8(b' ').is_ascii_whitespace());
+        assert!(Char8(b'9').is_ascii_digit());
+        // Latin-1 characters outside the ASCII range are never matched.
+        assert!(!Char8(0xe9).is_ascii());
+        assert!(!Char8(0xa0).is_ascii_whitespace());
+        assert_eq!(Char8(b'x').to_ascii_uppercase(), Char8(b'X'));
+        assert_eq!(Char8(0xe9).to_ascii_uppercase(), Char8(0xe9));
+        assert!(Char8(b'X').eq_ignore_ascii_case(&Char8(b'x')));
+    }
+
     /// Test that `Char8` and `Char16` can be directly compared with `char`.
     #[test]
     fn test_char_eq() {
@@ -1,4 +1,34 @@
-pub use uguid::Guid;
+pub use uguid::{Guid, GuidFromStrError};
+
+/// Parse a GUID from a string, optionally surrounded by braces.
+///
+/// This accepts the same format as [`Guid::try_parse`]
+/// (`xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`), and also the
+/// `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}` form commonly used on Windows.
+///
+/// # Errors
+///
+/// Returns the error of [`Guid::try_parse`] for the string without braces.
+/// A string with only an opening or only a closing brace is rejected with
+/// [`GuidFromStrError::Length`].
+///
+/// # Example
+///
+/// ```
+/// use uefi::data_types::parse_guid;
+/// use uefi::guid;
+///
+/// let expected = guid!("01234567-89ab-cdef-0123-456789abcdef");
+/// assert_eq!(parse_guid("{01234567-89ab-cdef-0123-456789abcdef}"), Ok(expected));
+/// assert_eq!(parse_guid("01234567-89ab-cdef-0123-456789abcdef"), Ok(expected));
+/// ```
+pub fn parse_guid(s: &str) -> Result<Guid, GuidFromStrError> {
+    let s = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(s);
+    Guid::try_parse(s)
+}
 
 /// Several entities in the UEFI specification can be referred to by their GUID,
 /// this trait is a building block to interface them in uefi-rs.
@@ -22,3 +52,72 @@ pub unsafe trait Identify {
     /// Unique protocol identifier.
     const GUID: Guid;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_guid_parse_round_trip() {
+        let s = "01234567-89ab-cdef-0123-456789abcdef";
+        let guid = Guid::try_parse(s).unwrap();
+        assert_eq!(guid.to_string(), s);
+        assert_eq!(guid, uguid::guid!("01234567-89ab-cdef-0123-456789abcdef"));
+
+        // Uppercase input is accepted, but displayed as lowercase.
+        let guid = Guid::try_parse("01234567-89AB-CDEF-0123-456789ABCDEF").unwrap();
+        assert_eq!(guid.to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_guid_braces() {
+        let expected = uguid::guid!("01234567-89ab-cdef-0123-456789abcdef");
+        assert_eq!(
+            parse_guid("01234567-89ab-cdef-0123-456789abcdef"),
+            Ok(expected)
+        );
+        assert_eq!(
+            parse_guid("{01234567-89AB-CDEF-0123-456789ABCDEF}"),
+            Ok(expected)
+        );
+
+        // Unbalanced braces.
+        assert_eq!(
+            parse_guid("{01234567-89ab-cdef-0123-456789abcdef"),
+            Err(GuidFromStrError::Length)
+        );
+        assert_eq!(
+            parse_guid("01234567-89ab-cdef-0123-456789abcdef}"),
+            Err(GuidFromStrError::Length)
+        );
+        assert_eq!(
+            parse_guid("{{01234567-89ab-cdef-0123-456789abcdef}}"),
+            Err(GuidFromStrError::Length)
+        );
+        // Errors are reported for the string without braces.
+        assert_eq!(
+            parse_guid("{01234567_89ab-cdef-0123-456789abcdef}"),
+            Err(GuidFromStrError::Separator(8))
+        );
+    }
+
+    #[test]
+    fn test_guid_parse_error() {
+        assert_eq!(Guid::try_parse(""), Err(GuidFromStrError::Length));
+        assert_eq!(
+            Guid::try_parse("{01234567-89ab-cdef-0123-456789abcdef}"),
+            Err(GuidFromStrError::Length)
+        );
+        assert_eq!(
+            Guid::try_parse("01234567_89ab-cdef-0123-456789abcdef"),
+            Err(GuidFromStrError::Separator(8))
+        );
+        // The index of the hex byte containing the invalid character is
+        // reported.
+        assert_eq!(
+            Guid::try_parse("01234567-89ab-cdef-0123-456789abcdeg"),
+            Err(GuidFromStrError::Hex(34))
+        );
+    }
+}
@@ -157,7 +157,7 @@ impl Align for [u8] {
 }
 
 mod guid;
-pub use guid::{Guid, Identify};
+pub use guid::{parse_guid, Guid, GuidFromStrError, Identify};
 
 pub mod chars;
 pub use chars::{Char16, Char8};
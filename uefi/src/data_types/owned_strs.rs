@@ -10,25 +10,34 @@ use alloc::vec::Vec;
 use core::fmt::{self, Display, Formatter};
 use core::{ops, ptr};
 
-/// Error returned by [`CString16::try_from::<&str>`].
+/// U+FFFD REPLACEMENT CHARACTER, used by [`CString16::from_str_lossy`].
+const REPLACEMENT_16: Char16 = unsafe { Char16::from_u16_unchecked(0xfffd) };
+
+/// Error returned by [`CString16::try_from::<&str>`] and
+/// [`CString16::from_str_lossy`].
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum FromStrError {
-    /// Character conversion error.
-    InvalidChar,
-    /// Nul character found in the input.
-    InteriorNul,
+    /// Character conversion error. The character is outside the Basic
+    /// Multilingual Plane, so it cannot be represented in UCS-2.
+    InvalidChar {
+        /// Byte index of the character in the input.
+        index: usize,
+        /// The character that could not be converted.
+        ch: char,
+    },
+    /// Nul character found in the input at this byte index.
+    InteriorNul(usize),
 }
 
 impl Display for FromStrError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "UCS-2 Conversion Error: {}",
-            match self {
-                Self::InvalidChar => "Invalid character",
-                Self::InteriorNul => "Interior null terminator",
+        write!(f, "UCS-2 Conversion Error: ")?;
+        match self {
+            Self::InvalidChar { index, ch } => {
+                write!(f, "Invalid character {ch:?} at index {index}")
             }
-        )
+            Self::InteriorNul(index) => write!(f, "Interior null terminator at index {index}"),
+        }
     }
 }
 
@@ -108,32 +117,56 @@ impl CString16 {
     pub fn is_empty(&self) -> bool {
         self.num_chars() == 0
     }
-}
 
-impl Default for CString16 {
-    fn default() -> Self {
-        Self::new()
+    /// Converts a `&str` to a `CString16`, replacing characters that cannot
+    /// be represented in UCS-2 (those outside the Basic Multilingual Plane)
+    /// with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// This is useful for displaying labels where a best-effort conversion is
+    /// acceptable. Use [`CString16::try_from::<&str>`] to detect invalid
+    /// characters instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FromStrError::InteriorNul`] if the input contains a nul
+    /// character, since there is no meaningful replacement for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uefi::CString16;
+    ///
+    /// let s = CString16::from_str_lossy("smile 😀").unwrap();
+    /// assert_eq!(s.to_string(), "smile \u{fffd}");
+    /// ```
+    pub fn from_str_lossy(input: &str) -> Result<Self, FromStrError> {
+        Self::from_str_with(input, |_, _| Ok(REPLACEMENT_16))
     }
-}
-
-impl TryFrom<&str> for CString16 {
-    type Error = FromStrError;
 
-    fn try_from(input: &str) -> Result<Self, Self::Error> {
+    /// Converts a `&str` to a `CString16`, calling `on_invalid` with the byte
+    /// index and value of each character that cannot be represented in
+    /// UCS-2.
+    fn from_str_with(
+        input: &str,
+        mut on_invalid: impl FnMut(usize, char) -> Result<Char16, FromStrError>,
+    ) -> Result<Self, FromStrError> {
         // Initially allocate one Char16 for each byte of the input, plus
         // one for the null character. This should be a good guess for ASCII-ish
         // input.
         let mut output = Vec::with_capacity(input.len() + 1);
 
-        // Convert to UTF-16, then convert to UCS-2.
-        for c in input.encode_utf16() {
-            let c = Char16::try_from(c).map_err(|_| FromStrError::InvalidChar)?;
-
+        for (index, ch) in input.char_indices() {
             // Check for interior nul chars.
-            if c == NUL_16 {
-                return Err(FromStrError::InteriorNul);
+            if ch == '\0' {
+                return Err(FromStrError::InteriorNul(index));
             }
 
+            // A `char` is never a surrogate, so any character in the Basic
+            // Multilingual Plane is valid UCS-2.
+            let c = match Char16::try_from(ch) {
+                Ok(c) => c,
+                Err(_) => on_invalid(index, ch)?,
+            };
             output.push(c);
         }
 
@@ -144,6 +177,22 @@ impl TryFrom<&str> for CString16 {
     }
 }
 
+impl Default for CString16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<&str> for CString16 {
+    type Error = FromStrError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::from_str_with(input, |index, ch| {
+            Err(FromStrError::InvalidChar { index, ch })
+        })
+    }
+}
+
 impl TryFrom<Vec<u16>> for CString16 {
     type Error = FromSliceWithNulError;
 
@@ -260,9 +309,30 @@ mod tests {
             CString16(vec![Char16::try_from('x').unwrap(), NUL_16])
         );
 
-        assert_eq!(CString16::try_from("😀"), Err(FromStrError::InvalidChar));
+        assert_eq!(
+            CString16::try_from("ab😀"),
+            Err(FromStrError::InvalidChar {
+                index: 2, ch: '😀'
+            })
+        );
+
+        assert_eq!(
+            CString16::try_from("x\0"),
+            Err(FromStrError::InteriorNul(1))
+        );
+    }
 
-        assert_eq!(CString16::try_from("x\0"), Err(FromStrError::InteriorNul));
+    #[test]
+    fn test_cstring16_from_str_lossy() {
+        assert_eq!(CString16::from_str_lossy("abc").unwrap(), cstr16!("abc"));
+        assert_eq!(
+            CString16::from_str_lossy("a😀b😀").unwrap(),
+            cstr16!("a\u{fffd}b\u{fffd}")
+        );
+        assert_eq!(
+            CString16::from_str_lossy("😀\0"),
+            Err(FromStrError::InteriorNul(4))
+        );
     }
 
     #[test]
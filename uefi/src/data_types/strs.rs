@@ -622,6 +622,68 @@ impl CStr16 {
     pub const fn as_bytes(&self) -> &[u8] {
         unsafe { slice::from_raw_parts(self.0.as_ptr().cast(), self.num_bytes()) }
     }
+
+    /// Returns an iterator over the pieces of this string separated by
+    /// `separator`, without allocating.
+    ///
+    /// Only the last piece of a `CStr16` is null-terminated, so the pieces
+    /// are returned as [`Char16`] slices borrowed from this string, rather
+    /// than as `&CStr16`. Like [`str::split`], an empty string yields a single
+    /// empty piece, and adjacent separators yield empty pieces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use uefi::{cstr16, Char16};
+    ///
+    /// let sep = Char16::try_from(';').unwrap();
+    /// let mut pieces = cstr16!("a;;bc").split(sep);
+    /// assert_eq!(pieces.next(), Some(cstr16!("a").as_slice()));
+    /// assert_eq!(pieces.next(), Some(&[][..]));
+    /// assert_eq!(pieces.next(), Some(cstr16!("bc").as_slice()));
+    /// assert_eq!(pieces.next(), None);
+    /// ```
+    pub fn split(&self, separator: Char16) -> impl Iterator<Item = &[Char16]> + '_ {
+        self.as_slice().split(move |c| *c == separator)
+    }
+
+    /// Returns this string with leading whitespace removed.
+    ///
+    /// The result is a suffix of this string, so it is still null-terminated.
+    #[must_use]
+    pub fn trim_start(&self) -> &Self {
+        let start = self
+            .as_slice()
+            .iter()
+            .position(|c| !char::from(*c).is_whitespace())
+            .unwrap_or(self.num_chars());
+        // Safety: the suffix still ends with the null character, and does not
+        // contain interior nulls.
+        unsafe { Self::from_char16_with_nul_unchecked(&self.0[start..]) }
+    }
+
+    /// Returns this string with trailing whitespace removed.
+    ///
+    /// The result is not null-terminated, so it is returned as a [`Char16`]
+    /// slice.
+    #[must_use]
+    pub fn trim_end(&self) -> &[Char16] {
+        let chars = self.as_slice();
+        let end = chars
+            .iter()
+            .rposition(|c| !char::from(*c).is_whitespace())
+            .map_or(0, |i| i + 1);
+        &chars[..end]
+    }
+
+    /// Returns this string with leading and trailing whitespace removed.
+    ///
+    /// The result is not null-terminated, so it is returned as a [`Char16`]
+    /// slice.
+    #[must_use]
+    pub fn trim(&self) -> &[Char16] {
+        self.trim_start().trim_end()
+    }
 }
 
 impl AsRef<[u8]> for CStr16 {
@@ -762,6 +824,7 @@ mod tests {
     use super::*;
     use crate::{cstr16, cstr8};
     use alloc::string::String;
+    use alloc::vec::Vec;
 
     // Tests if our CStr8 type can be constructed from a valid core::ffi::CStr
     #[test]
@@ -992,6 +1055,32 @@ mod tests {
         assert_eq!(<CStr16 as Borrow<[u8]>>::borrow(string), &[b'a', 0, 0, 0]);
     }
 
+    #[test]
+    fn test_cstr16_split() {
+        let sep = Char16::try_from(';').unwrap();
+        let pieces: Vec<_> = cstr16!("a;;bc;").split(sep).collect();
+        assert_eq!(
+            pieces,
+            [cstr16!("a").as_slice(), &[], cstr16!("bc").as_slice(), &[]]
+        );
+
+        let pieces: Vec<_> = cstr16!("").split(sep).collect();
+        assert_eq!(pieces, [&[] as &[Char16]]);
+    }
+
+    #[test]
+    fn test_cstr16_trim() {
+        let string = cstr16!(" \t a b \r\n");
+        assert_eq!(string.trim_start(), cstr16!("a b \r\n"));
+        assert_eq!(string.trim_end(), cstr16!(" \t a b").as_slice());
+        assert_eq!(string.trim(), cstr16!("a b").as_slice());
+
+        let string = cstr16!("  ");
+        assert_eq!(string.trim_start(), cstr16!(""));
+        assert!(string.trim_end().is_empty());
+        assert!(string.trim().is_empty());
+    }
+
     // Code generation helper for the compare tests of our CStrX types against "str" and "String"
     // from the standard library.
     #[allow(non_snake_case)]
@@ -51,6 +51,10 @@ pub enum IoErrorContext {
     CantDeleteFile,
     /// Error flushing file.
     FlushFailure,
+    /// Can't open the [`SimpleFileSystem`] protocol on the handle.
+    ///
+    /// [`SimpleFileSystem`]: crate::proto::media::fs::SimpleFileSystem
+    CantOpenProtocol,
     /// Can't open the root directory of the underlying volume.
     CantOpenVolume,
     /// Error while reading the metadata of the file.
@@ -68,6 +72,12 @@ pub enum IoErrorContext {
     /// The path exists but does not correspond to a file when a file was
     /// expected.
     NotAFile,
+    /// The directory can't be removed because it is not empty.
+    DirectoryNotEmpty,
+    /// The file contents were written, but its metadata could not be set.
+    SetMetadataFailure,
+    /// The file could not be renamed.
+    RenameFailure,
 }
 
 impl Display for IoErrorContext {
@@ -76,6 +86,7 @@ impl Display for IoErrorContext {
             Self::CantDeleteDirectory => "failed to delete directory",
             Self::CantDeleteFile => "failed to delete file",
             Self::FlushFailure => "failed to flush file",
+            Self::CantOpenProtocol => "failed to open file system protocol",
             Self::CantOpenVolume => "failed to open volume",
             Self::Metadata => "failed to read metadata",
             Self::OpenError => "failed to open file",
@@ -83,6 +94,9 @@ impl Display for IoErrorContext {
             Self::WriteFailure => "failed to write file",
             Self::NotADirectory => "expected a directory",
             Self::NotAFile => "expected a file",
+            Self::DirectoryNotEmpty => "directory is not empty",
+            Self::SetMetadataFailure => "failed to set metadata",
+            Self::RenameFailure => "failed to rename file",
         };
         write!(f, "{s}")
     }
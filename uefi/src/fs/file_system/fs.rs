@@ -1,7 +1,8 @@
 //! Module for [`FileSystem`].
 
+use crate::data_types::Align;
 use crate::fs::*;
-use crate::Status;
+use crate::{boot, cstr16, CString16, Handle, Status};
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec;
@@ -29,6 +30,33 @@ impl FileSystem {
         proto.into()
     }
 
+    /// Opens the [`SimpleFileSystemProtocol`] on `handle` in exclusive mode,
+    /// and checks that the volume can be opened.
+    ///
+    /// The protocol stays open until the `FileSystem` is dropped. Each
+    /// operation opens a fresh handle to the root directory of the volume,
+    /// so nothing else is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IoError`] with the context
+    /// [`IoErrorContext::CantOpenProtocol`] if the protocol can't be opened,
+    /// or [`IoErrorContext::CantOpenVolume`] if the volume can't be opened,
+    /// for example because there is no media.
+    pub fn from_handle(handle: Handle) -> FileSystemResult<Self> {
+        let proto =
+            boot::open_protocol_exclusive::<SimpleFileSystemProtocol>(handle).map_err(|err| {
+                Error::Io(IoError {
+                    path: root_path(),
+                    context: IoErrorContext::CantOpenProtocol,
+                    uefi_error: err,
+                })
+            })?;
+        let mut fs = Self(proto);
+        fs.open_root()?;
+        Ok(fs)
+    }
+
     /// Returns `Ok(true)` if the path points at an existing file.
     ///
     /// If the file does not exist, `Ok(false)` is returned. If it cannot be
@@ -54,9 +82,65 @@ impl FileSystem {
         src_path: impl AsRef<Path>,
         dest_path: impl AsRef<Path>,
     ) -> FileSystemResult<()> {
-        let src_path = src_path.as_ref();
+        self.copy_file(src_path.as_ref(), dest_path.as_ref())?;
+        Ok(())
+    }
+
+    /// Copies the contents of one file to another like [`Self::copy`], and
+    /// then sets the creation, last access, and modification times and the
+    /// attributes of the destination to those of the source.
+    ///
+    /// The source is only opened for reading, so read-only files can be
+    /// copied. The [`READ_ONLY`] attribute itself is not carried over to the
+    /// destination.
+    ///
+    /// If the contents were copied but the metadata could not be set, an
+    /// [`IoError`] with context [`IoErrorContext::SetMetadataFailure`] is
+    /// returned. The destination file then exists with the complete contents,
+    /// so the caller can decide whether to treat this as fatal.
+    ///
+    /// [`READ_ONLY`]: UefiFileAttribute::READ_ONLY
+    pub fn copy_preserving_metadata(
+        &mut self,
+        src_path: impl AsRef<Path>,
+        dest_path: impl AsRef<Path>,
+    ) -> FileSystemResult<()> {
         let dest_path = dest_path.as_ref();
+        let (mut dest, src_info) = self.copy_file(src_path.as_ref(), dest_path)?;
+
+        let mut set_metadata = || {
+            let dest_info = dest.get_boxed_info::<UefiFileInfo>()?;
+            let builder = dest_info
+                .to_builder()
+                .create_time(*src_info.create_time())
+                .last_access_time(*src_info.last_access_time())
+                .modification_time(*src_info.modification_time())
+                .attribute(src_info.attribute() - UefiFileAttribute::READ_ONLY);
+
+            // Add enough space to align the buffer.
+            let mut buf = vec![0; builder.required_size() + UefiFileInfo::alignment() - 1];
+            let info = builder
+                .build(&mut buf)
+                .expect("buffer is large enough for the new FileInfo");
+            dest.set_info(info)?;
+            dest.flush()
+        };
+        set_metadata().map_err(|err| {
+            Error::Io(IoError {
+                path: dest_path.to_path_buf(),
+                context: IoErrorContext::SetMetadataFailure,
+                uefi_error: err,
+            })
+        })
+    }
 
+    /// Copies the contents of `src_path` to `dest_path`. Returns the open
+    /// destination file and the metadata of the source file.
+    fn copy_file(
+        &mut self,
+        src_path: &Path,
+        dest_path: &Path,
+    ) -> FileSystemResult<(UefiRegularFileHandle, Box<UefiFileInfo>)> {
         // Open the source file for reading.
         let mut src = self
             .open(src_path, UefiFileMode::Read, false)?
@@ -67,17 +151,15 @@ impl FileSystem {
                 uefi_error: Status::INVALID_PARAMETER.into(),
             }))?;
 
-        // Get the source file's size in bytes.
-        let src_size = {
-            let src_info = src.get_boxed_info::<UefiFileInfo>().map_err(|err| {
-                Error::Io(IoError {
-                    path: src_path.to_path_buf(),
-                    context: IoErrorContext::Metadata,
-                    uefi_error: err,
-                })
-            })?;
-            src_info.file_size()
-        };
+        // Get the source file's metadata, including its size in bytes.
+        let src_info = src.get_boxed_info::<UefiFileInfo>().map_err(|err| {
+            Error::Io(IoError {
+                path: src_path.to_path_buf(),
+                context: IoErrorContext::Metadata,
+                uefi_error: err,
+            })
+        })?;
+        let src_size = src_info.file_size();
 
         // Try to delete the destination file in case it already exists. Allow
         // this to fail, since it might not exist. Or it might exist, but be a
@@ -140,6 +222,60 @@ impl FileSystem {
             })
         })?;
 
+        Ok((dest, src_info))
+    }
+
+    /// Recursively copies a directory and all of its contents. Creates the
+    /// destination directory and any missing parents. Files that already
+    /// exist in the destination are overwritten.
+    ///
+    /// File contents are copied in fixed-size chunks with [`Self::copy`], so
+    /// files are never read into memory as a whole. The directory tree is
+    /// traversed iteratively, so deeply nested trees can't overflow the stack.
+    ///
+    /// The destination must not be inside the source directory.
+    pub fn copy_dir_recursive(
+        &mut self,
+        src_path: impl AsRef<Path>,
+        dest_path: impl AsRef<Path>,
+    ) -> FileSystemResult<()> {
+        // Stack of (source, destination) directory pairs that remain to be
+        // copied.
+        let mut dirs_to_copy = vec![(
+            src_path.as_ref().to_path_buf(),
+            dest_path.as_ref().to_path_buf(),
+        )];
+
+        while let Some((src_dir, dest_dir)) = dirs_to_copy.pop() {
+            let entries = self.read_dir(&src_dir)?;
+            self.create_dir_all(&dest_dir)?;
+
+            for entry in entries {
+                let entry = entry.map_err(|err| {
+                    Error::Io(IoError {
+                        path: src_dir.clone(),
+                        context: IoErrorContext::ReadFailure,
+                        uefi_error: err,
+                    })
+                })?;
+
+                if COMMON_SKIP_DIRS.contains(&entry.file_name()) {
+                    continue;
+                }
+
+                let mut src_entry_path = src_dir.clone();
+                src_entry_path.push(entry.file_name());
+                let mut dest_entry_path = dest_dir.clone();
+                dest_entry_path.push(entry.file_name());
+
+                if entry.is_directory() {
+                    dirs_to_copy.push((src_entry_path, dest_entry_path));
+                } else {
+                    self.copy(&src_entry_path, &dest_entry_path)?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -190,7 +326,16 @@ impl FileSystem {
     }
 
     /// Read the entire contents of a file into a bytes vector.
+    ///
+    /// The file size is queried first so that the whole file can usually be
+    /// read with a single allocation and a single read. The reported size
+    /// is not trusted though: some firmware reports a size of zero for
+    /// special files, so reading continues until the end of the file.
     pub fn read(&mut self, path: impl AsRef<Path>) -> FileSystemResult<Vec<u8>> {
+        /// Number of bytes to read at a time after the reported file size has
+        /// been read.
+        const CHUNK_SIZE: usize = 4096;
+
         let path = path.as_ref();
 
         let mut file = self
@@ -212,20 +357,42 @@ impl FileSystem {
             })
         })?;
 
-        let mut vec = vec![0; info.file_size() as usize];
-        let read_bytes = file.read(vec.as_mut_slice()).map_err(|err| {
+        let mut read = |buf: &mut [u8]| {
+            file.read(buf).map_err(|err| {
+                Error::Io(IoError {
+                    path: path.to_path_buf(),
+                    context: IoErrorContext::ReadFailure,
+                    uefi_error: err.to_err_without_payload(),
+                })
+            })
+        };
+
+        // A file that doesn't fit in the address space can't be read into a
+        // vector.
+        let size = usize::try_from(info.file_size()).map_err(|_| {
             Error::Io(IoError {
                 path: path.to_path_buf(),
                 context: IoErrorContext::ReadFailure,
-                uefi_error: err.to_err_without_payload(),
+                uefi_error: Status::BAD_BUFFER_SIZE.into(),
             })
         })?;
-
-        // we read the whole file at once!
-        if read_bytes != info.file_size() as usize {
-            log::error!("Did only read {}/{} bytes", info.file_size(), read_bytes);
+        let mut vec = vec![0; size];
+        let mut len = read(&mut vec)?;
+
+        // If the whole buffer was filled, the reported size may have been
+        // stale, so keep reading until the end of the file. A read that
+        // doesn't fill the buffer stops at the end of the file.
+        while len == vec.len() {
+            vec.resize(len + CHUNK_SIZE, 0);
+            let read_bytes = read(&mut vec[len..])?;
+            if read_bytes == 0 {
+                break;
+            }
+            len += read_bytes;
         }
 
+        // The file may also have been shorter than reported.
+        vec.truncate(len);
         Ok(vec)
     }
 
@@ -251,6 +418,10 @@ impl FileSystem {
     }
 
     /// Removes an empty directory.
+    ///
+    /// Returns an error with [`IoErrorContext::DirectoryNotEmpty`] if the
+    /// directory is not empty. Use [`Self::remove_dir_all`] to remove it
+    /// together with its contents.
     pub fn remove_dir(&mut self, path: impl AsRef<Path>) -> FileSystemResult<()> {
         let path = path.as_ref();
 
@@ -260,13 +431,29 @@ impl FileSystem {
             .unwrap();
 
         match file {
-            UefiFileType::Dir(dir) => dir.delete().map_err(|err| {
-                Error::Io(IoError {
-                    path: path.to_path_buf(),
-                    context: IoErrorContext::CantDeleteDirectory,
-                    uefi_error: err,
+            UefiFileType::Dir(mut dir) => {
+                let is_empty = dir.is_empty().map_err(|err| {
+                    Error::Io(IoError {
+                        path: path.to_path_buf(),
+                        context: IoErrorContext::ReadFailure,
+                        uefi_error: err,
+                    })
+                })?;
+                if !is_empty {
+                    return Err(Error::Io(IoError {
+                        path: path.to_path_buf(),
+                        context: IoErrorContext::DirectoryNotEmpty,
+                        uefi_error: Status::ACCESS_DENIED.into(),
+                    }));
+                }
+                dir.delete().map_err(|err| {
+                    Error::Io(IoError {
+                        path: path.to_path_buf(),
+                        context: IoErrorContext::CantDeleteDirectory,
+                        uefi_error: err,
+                    })
                 })
-            }),
+            }
             UefiFileType::Regular(_) => {
                 Err(Error::Io(IoError {
                     path: path.to_path_buf(),
@@ -384,15 +571,71 @@ impl FileSystem {
         Ok(())
     }
 
+    /// Write a slice as the entire contents of a file, like [`write`], but
+    /// without leaving a partially written file at `path` if writing fails
+    /// or the system loses power.
+    ///
+    /// The contents are first written to a temporary file named
+    /// `<path>.tmp`, which is then renamed to `path`. If `path` already
+    /// exists and the file system refuses to rename over it, `path` is
+    /// deleted and the rename is retried.
+    ///
+    /// Whether the rename itself is atomic depends on the underlying file
+    /// system; on FAT it is not guaranteed, and there is a short window where
+    /// `path` doesn't exist after it was deleted. This still keeps the window
+    /// in which a failure corrupts the file much smaller than with [`write`].
+    ///
+    /// If the rename fails, an [`IoError`] with context
+    /// [`IoErrorContext::RenameFailure`] is returned and the temporary file
+    /// is kept with the new contents.
+    ///
+    /// [`write`]: Self::write
+    pub fn write_atomic(
+        &mut self,
+        path: impl AsRef<Path>,
+        content: impl AsRef<[u8]>,
+    ) -> FileSystemResult<()> {
+        let path = path.as_ref();
+        let Some(file_name) = path.file_name() else {
+            return Err(Error::Io(IoError {
+                path: path.to_path_buf(),
+                context: IoErrorContext::NotAFile,
+                uefi_error: Status::INVALID_PARAMETER.into(),
+            }));
+        };
+
+        let mut tmp_path = CString16::from(path.to_cstr16());
+        tmp_path.push_str(cstr16!(".tmp"));
+        let tmp_path = PathBuf::from(tmp_path);
+        self.write(&tmp_path, content)?;
+
+        let mut tmp = self
+            .open(&tmp_path, UefiFileMode::ReadWrite, false)?
+            .into_regular_file()
+            .unwrap();
+        let rename_error = |err| {
+            Error::Io(IoError {
+                path: path.to_path_buf(),
+                context: IoErrorContext::RenameFailure,
+                uefi_error: err,
+            })
+        };
+        let mut rename = || tmp.rename(&file_name).and_then(|()| tmp.flush());
+        match rename() {
+            Ok(()) => Ok(()),
+            Err(_) if self.try_exists(path)? => {
+                self.remove_file(path)?;
+                rename().map_err(rename_error)
+            }
+            Err(err) => Err(rename_error(err)),
+        }
+    }
+
     /// Opens a fresh handle to the root directory of the volume.
     fn open_root(&mut self) -> FileSystemResult<UefiDirectoryHandle> {
         self.0.open_volume().map_err(|err| {
             Error::Io(IoError {
-                path: {
-                    let mut path = PathBuf::new();
-                    path.push(SEPARATOR_STR);
-                    path
-                },
+                path: root_path(),
                 context: IoErrorContext::CantOpenVolume,
                 uefi_error: err,
             })
@@ -438,6 +681,14 @@ impl Debug for FileSystem {
     }
 }
 
+/// Path of the root directory, used as the path for errors that aren't
+/// specific to a file.
+fn root_path() -> PathBuf {
+    let mut path = PathBuf::new();
+    path.push(SEPARATOR_STR);
+    path
+}
+
 impl From<uefi::boot::ScopedProtocol<SimpleFileSystemProtocol>> for FileSystem {
     fn from(proto: uefi::boot::ScopedProtocol<SimpleFileSystemProtocol>) -> Self {
         Self(proto)
@@ -9,9 +9,13 @@
 //!
 //! # Path Structure
 //!
-//! Paths use the [`SEPARATOR`] character as separator. Paths are absolute and
-//! do not contain `.` or `..` components. However, this can be implemented in
-//! the future.
+//! Paths use the [`SEPARATOR`] character (`\\`) as separator. [`PathBuf`]
+//! translates `/` to [`SEPARATOR`] on construction and in
+//! [`PathBuf::push`]; a [`Path`] created from a [`CStr16`] is taken as is.
+//!
+//! The file system only accepts absolute paths without `.` or `..`
+//! components. Use [`Path::normalize`] to resolve such components without
+//! accessing the file system.
 
 mod path;
 mod pathbuf;
@@ -2,7 +2,8 @@
 #![allow(clippy::module_inception)]
 
 use crate::fs::path::{PathBuf, SEPARATOR};
-use crate::{CStr16, CString16};
+use crate::{cstr16, CStr16, CString16};
+use alloc::vec::Vec;
 use core::fmt::{Display, Formatter};
 use core::ptr;
 
@@ -78,6 +79,62 @@ impl Path {
         Some(path)
     }
 
+    /// Returns the final component of the path, if there is one.
+    ///
+    /// Returns `None` if the path is empty, is the root directory, or ends
+    /// in `..`.
+    #[must_use]
+    pub fn file_name(&self) -> Option<CString16> {
+        self.components()
+            .last()
+            .filter(|name| !name.is_empty() && &**name != cstr16!(".."))
+    }
+
+    /// Creates an owned [`PathBuf`] with `path` appended to `self`. See
+    /// [`PathBuf::push`].
+    #[must_use]
+    pub fn join<P: AsRef<Self>>(&self, path: P) -> PathBuf {
+        let mut buf = self.to_path_buf();
+        buf.push(path);
+        buf
+    }
+
+    /// Returns a normalized copy of the path, without accessing the file
+    /// system.
+    ///
+    /// `/` is translated to [`SEPARATOR`], `.` and empty components are
+    /// removed, and `..` removes the preceding component. A `..` that would
+    /// go above the first component is dropped, so e.g. `\a\..\..\b` is
+    /// normalized to `\b`. A leading separator is preserved.
+    #[must_use]
+    pub fn normalize(&self) -> PathBuf {
+        // Translate '/' to the separator first, so that it is treated as one.
+        let path = self.to_path_buf();
+
+        let mut components = Vec::new();
+        for component in path.components() {
+            if component.is_empty() || &*component == cstr16!(".") {
+                continue;
+            } else if &*component == cstr16!("..") {
+                components.pop();
+            } else {
+                components.push(component);
+            }
+        }
+
+        let mut normalized = CString16::new();
+        if path.to_cstr16().as_slice().first() == Some(&SEPARATOR) {
+            normalized.push(SEPARATOR);
+        }
+        for (i, component) in components.iter().enumerate() {
+            if i != 0 {
+                normalized.push(SEPARATOR);
+            }
+            normalized.push_str(component);
+        }
+        PathBuf::from(normalized)
+    }
+
     /// Returns of the path is empty.
     #[must_use]
     pub const fn is_empty(&self) -> bool {
@@ -274,6 +331,57 @@ mod tests {
         assert_eq!(Path::new(cstr16!("abc")).parent(), None,);
     }
 
+    #[test]
+    fn test_file_name() {
+        assert_eq!(Path::new(cstr16!("")).file_name(), None);
+        assert_eq!(Path::new(cstr16!("\\")).file_name(), None);
+        assert_eq!(Path::new(cstr16!("a\\..")).file_name(), None);
+        assert_eq!(
+            Path::new(cstr16!("abc")).file_name(),
+            Some(CString16::try_from("abc").unwrap())
+        );
+        assert_eq!(
+            Path::new(cstr16!("\\a\\b\\c.txt")).file_name(),
+            Some(CString16::try_from("c.txt").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            Path::new(cstr16!("\\a")).join(cstr16!("b\\c")),
+            PathBuf::from(cstr16!("\\a\\b\\c"))
+        );
+        assert_eq!(
+            Path::new(cstr16!("a\\")).join(cstr16!("b/c")).to_cstr16(),
+            cstr16!("a\\b\\c")
+        );
+        assert_eq!(
+            Path::new(cstr16!("a")).join(cstr16!("")).to_cstr16(),
+            cstr16!("a")
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let normalize = |s: &CStr16| Path::new(s).normalize();
+        let assert_normalized = |input: &CStr16, expected: &CStr16| {
+            assert_eq!(normalize(input).to_cstr16(), expected, "{input}");
+        };
+
+        assert_normalized(cstr16!(""), cstr16!(""));
+        assert_normalized(cstr16!("\\"), cstr16!("\\"));
+        assert_normalized(cstr16!("\\a\\b\\c"), cstr16!("\\a\\b\\c"));
+        assert_normalized(cstr16!("\\a\\.\\b\\.\\"), cstr16!("\\a\\b"));
+        assert_normalized(cstr16!("\\a\\\\b"), cstr16!("\\a\\b"));
+        assert_normalized(cstr16!("\\a\\b\\..\\c"), cstr16!("\\a\\c"));
+        assert_normalized(cstr16!("a/b/../c"), cstr16!("a\\c"));
+        // `..` above the root saturates at the root.
+        assert_normalized(cstr16!("\\a\\..\\..\\..\\b"), cstr16!("\\b"));
+        assert_normalized(cstr16!("\\.."), cstr16!("\\"));
+        assert_normalized(cstr16!("a\\..\\.."), cstr16!(""));
+    }
+
     #[test]
     fn partial_eq() {
         let path1 = Path::new(cstr16!(r"a\b"));
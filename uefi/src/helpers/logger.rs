@@ -6,36 +6,72 @@
 //!
 //! # Implementation details
 //!
-//! The implementation is not the most efficient, since there is no buffering done,
-//! and the messages have to be converted from UTF-8 to UEFI's UCS-2.
+//! Output to the UEFI console is line-buffered: each line of a log message,
+//! including its level prefix, is collected in a small stack buffer and written
+//! with a single call to the output protocol. The messages still have to be
+//! converted from UTF-8 to UEFI's UCS-2.
 //!
 //! The last part also means that some Unicode characters might not be
 //! supported by the UEFI console. Don't expect emoji output support.
 
+use crate::boot::{self, Tpl};
 use crate::proto::console::text::Output;
-use crate::system;
+use crate::{system, Result, Status};
+use core::cell::UnsafeCell;
 use core::fmt::{self, Write};
 use core::ptr;
 use core::sync::atomic::{AtomicPtr, Ordering};
+use log::LevelFilter;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "log-debugcon"
+))]
+use core::sync::atomic::AtomicBool;
 
 /// Global logger object
 static LOGGER: Logger = Logger::new();
 
-/// Set up logging
+/// Set up logging with the maximum level `level`. If `debugcon` is false,
+/// messages are not written to the debugcon device even if the
+/// `log-debugcon` feature is enabled.
 ///
 /// This is unsafe because you must arrange for the logger to be reset with
 /// disable() on exit from UEFI boot services.
-pub unsafe fn init() {
+pub unsafe fn init(level: LevelFilter, debugcon: bool) {
     // Connect the logger to stdout.
     system::with_stdout(|stdout| {
         LOGGER.set_output(stdout);
     });
 
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "log-debugcon"
+    ))]
+    LOGGER.debugcon.store(debugcon, Ordering::Release);
+    #[cfg(not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "log-debugcon"
+    )))]
+    let _ = debugcon;
+
     // Set the logger.
     log::set_logger(&LOGGER).unwrap(); // Can only fail if already initialized.
 
-    // Set logger max level to level specified by log features
-    log::set_max_level(log::STATIC_MAX_LEVEL);
+    LOGGER.with_filters(|filters| filters.default_level = level);
+    log::set_max_level(level);
+}
+
+/// Override the maximum level of messages from `target` and its submodules.
+pub fn set_target_level(target: &str, level: LevelFilter) -> Result {
+    LOGGER.with_filters(|filters| filters.set(target, level))?;
+
+    // The `log` macros drop messages above the global maximum level before
+    // the logger sees them, so it must allow the most verbose override.
+    if level > log::max_level() {
+        log::set_max_level(level);
+    }
+    Ok(())
 }
 
 pub fn disable() {
@@ -84,6 +120,16 @@ impl core::fmt::Write for DebugconWriter {
 #[derive(Debug)]
 pub struct Logger {
     writer: AtomicPtr<Output>,
+
+    /// Per-target level overrides.
+    filters: UnsafeCell<TargetFilters>,
+
+    /// Whether messages are also written to the debugcon device.
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "log-debugcon"
+    ))]
+    debugcon: AtomicBool,
 }
 
 impl Logger {
@@ -96,6 +142,12 @@ impl Logger {
     pub const fn new() -> Self {
         Self {
             writer: AtomicPtr::new(ptr::null_mut()),
+            filters: UnsafeCell::new(TargetFilters::new()),
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "log-debugcon"
+            ))]
+            debugcon: AtomicBool::new(true),
         }
     }
 
@@ -128,34 +180,52 @@ impl Logger {
     pub fn disable(&self) {
         unsafe { self.set_output(ptr::null_mut()) }
     }
+
+    /// Access the target filters. The TPL is raised so that no message can
+    /// be logged from an event notification function in the meantime.
+    fn with_filters<R>(&self, f: impl FnOnce(&mut TargetFilters) -> R) -> R {
+        let _tpl = unsafe { boot::raise_tpl(Tpl::HIGH_LEVEL) };
+        // Safety: the logger is only used on one processor, and nothing else
+        // can access the filters at this TPL.
+        f(unsafe { &mut *self.filters.get() })
+    }
 }
 
 impl log::Log for Logger {
-    fn enabled(&self, _metadata: &log::Metadata) -> bool {
-        // We decide in `log` already if something is printed. We do not
-        // need micro optimizations here.
-        true
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        // Safety: the filters are only modified at `Tpl::HIGH_LEVEL`, so
+        // they can't change while this runs.
+        let filters = unsafe { &*self.filters.get() };
+        metadata.level() <= filters.level(metadata.target())
     }
 
     fn log(&self, record: &log::Record) {
+        // The `log` macros only check the global maximum level.
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
         if let Some(writer) = unsafe { self.output().as_mut() } {
+            let mut writer = LineBuffer::new(writer);
+
             // Ignore all errors. Since we're in the logger implementation we
             // can't log the error. We also don't want to panic, since logging
             // is generally not critical functionality.
             let _ = DecoratedLog::write(
-                writer,
+                &mut writer,
                 record.level(),
                 record.args(),
                 record.file().unwrap_or("<unknown file>"),
                 record.line().unwrap_or(0),
             );
+            let _ = writer.flush();
         }
 
         #[cfg(all(
             any(target_arch = "x86", target_arch = "x86_64"),
             feature = "log-debugcon"
         ))]
-        {
+        if self.debugcon.load(Ordering::Acquire) {
             // Ignore all errors. Since we're in the logger implementation we
             // can't log the error. We also don't want to panic, since logging
             // is generally not critical functionality.
@@ -170,7 +240,8 @@ impl log::Log for Logger {
     }
 
     fn flush(&self) {
-        // This simple logger does not buffer output.
+        // Output is only buffered for the duration of a call to `log`, so
+        // there is nothing left to flush here.
     }
 }
 
@@ -178,6 +249,158 @@ impl log::Log for Logger {
 unsafe impl Sync for Logger {}
 unsafe impl Send for Logger {}
 
+/// Maximum number of targets with a level override.
+const MAX_TARGET_FILTERS: usize = 8;
+
+/// Maximum length in bytes of a target with a level override.
+const MAX_TARGET_LEN: usize = 64;
+
+/// Level override for a target.
+#[derive(Clone, Copy, Debug)]
+struct TargetFilter {
+    target: [u8; MAX_TARGET_LEN],
+    len: usize,
+    level: LevelFilter,
+}
+
+impl TargetFilter {
+    fn target(&self) -> &str {
+        // OK to unwrap: the bytes were copied from a `str`.
+        core::str::from_utf8(&self.target[..self.len]).unwrap()
+    }
+
+    /// Whether `target` is this filter's target or one of its submodules.
+    fn matches(&self, target: &str) -> bool {
+        target
+            .strip_prefix(self.target())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    }
+}
+
+/// Fixed-capacity table of per-target level overrides.
+#[derive(Debug)]
+struct TargetFilters {
+    /// Level of targets without an override.
+    default_level: LevelFilter,
+    filters: [Option<TargetFilter>; MAX_TARGET_FILTERS],
+}
+
+impl TargetFilters {
+    const fn new() -> Self {
+        Self {
+            default_level: log::STATIC_MAX_LEVEL,
+            filters: [None; MAX_TARGET_FILTERS],
+        }
+    }
+
+    /// Set the level of `target`, replacing any previous override.
+    fn set(&mut self, target: &str, level: LevelFilter) -> Result {
+        if target.len() > MAX_TARGET_LEN {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        let slot = match self
+            .filters
+            .iter()
+            .position(|f| f.is_some_and(|f| f.target() == target))
+        {
+            Some(index) => index,
+            None => self
+                .filters
+                .iter()
+                .position(Option::is_none)
+                .ok_or(Status::OUT_OF_RESOURCES)?,
+        };
+
+        let mut filter = TargetFilter {
+            target: [0; MAX_TARGET_LEN],
+            len: target.len(),
+            level,
+        };
+        filter.target[..target.len()].copy_from_slice(target.as_bytes());
+        self.filters[slot] = Some(filter);
+        Ok(())
+    }
+
+    /// Get the maximum level of `target`. The most specific matching override
+    /// is used.
+    fn level(&self, target: &str) -> LevelFilter {
+        self.filters
+            .iter()
+            .flatten()
+            .filter(|f| f.matches(target))
+            .max_by_key(|f| f.len)
+            .map_or(self.default_level, |f| f.level)
+    }
+}
+
+/// Writer wrapper which collects text in a fixed-size buffer and passes it on
+/// to the inner writer one line at a time.
+///
+/// The buffer is flushed after every newline and whenever it is full. Any
+/// remaining text must be written out by calling [`LineBuffer::flush`].
+struct LineBuffer<'writer, W: fmt::Write> {
+    writer: &'writer mut W,
+    buf: [u8; LINE_BUFFER_SIZE],
+    len: usize,
+}
+
+/// Size of the [`LineBuffer`] buffer in bytes.
+const LINE_BUFFER_SIZE: usize = 256;
+
+impl<'writer, W: fmt::Write> LineBuffer<'writer, W> {
+    const fn new(writer: &'writer mut W) -> Self {
+        Self {
+            writer,
+            buf: [0; LINE_BUFFER_SIZE],
+            len: 0,
+        }
+    }
+
+    /// Write any buffered text to the inner writer.
+    fn flush(&mut self) -> fmt::Result {
+        if self.len == 0 {
+            return Ok(());
+        }
+        // Only whole UTF-8 sequences are added to the buffer, so this cannot
+        // fail.
+        let s = core::str::from_utf8(&self.buf[..self.len]).map_err(|_| fmt::Error)?;
+        self.len = 0;
+        self.writer.write_str(s)
+    }
+
+    /// Add `s` to the buffer, flushing it as needed to make room.
+    fn push(&mut self, mut s: &str) -> fmt::Result {
+        while s.len() > LINE_BUFFER_SIZE - self.len {
+            // Fill the buffer as far as possible without splitting a
+            // character.
+            let mut split = LINE_BUFFER_SIZE - self.len;
+            while !s.is_char_boundary(split) {
+                split -= 1;
+            }
+            let (head, tail) = s.split_at(split);
+            self.buf[self.len..self.len + head.len()].copy_from_slice(head.as_bytes());
+            self.len += head.len();
+            self.flush()?;
+            s = tail;
+        }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for LineBuffer<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for line in s.split_inclusive('\n') {
+            self.push(line)?;
+            if line.ends_with('\n') {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Writer wrapper which prints a log level in front of every line of text
 ///
 /// This is less easy than it sounds because...
@@ -253,3 +476,106 @@ impl<W: fmt::Write> fmt::Write for DecoratedLog<'_, '_, W> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// Writer that records each call to `write_str`.
+    #[derive(Default)]
+    struct RecordingWriter(Vec<String>);
+
+    impl fmt::Write for RecordingWriter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.push(s.into());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_target_filters() {
+        let mut filters = TargetFilters::new();
+        filters.default_level = LevelFilter::Info;
+        assert_eq!(filters.level("uefi::proto"), LevelFilter::Info);
+
+        filters.set("uefi::proto", LevelFilter::Warn).unwrap();
+        filters
+            .set("uefi::proto::network", LevelFilter::Off)
+            .unwrap();
+        assert_eq!(filters.level("uefi::proto"), LevelFilter::Warn);
+        assert_eq!(filters.level("uefi::proto::media"), LevelFilter::Warn);
+        assert_eq!(filters.level("uefi::proto::network::tcp"), LevelFilter::Off);
+        // Only whole path segments match.
+        assert_eq!(filters.level("uefi::protocols"), LevelFilter::Info);
+        assert_eq!(filters.level("uefi"), LevelFilter::Info);
+
+        // Setting a target again replaces its level.
+        filters.set("uefi::proto", LevelFilter::Trace).unwrap();
+        assert_eq!(filters.level("uefi::proto::media"), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn test_target_filters_limits() {
+        let mut filters = TargetFilters::new();
+        assert_eq!(
+            filters.set(&"a".repeat(MAX_TARGET_LEN + 1), LevelFilter::Off),
+            Err(Status::INVALID_PARAMETER.into())
+        );
+        for i in 0..MAX_TARGET_FILTERS {
+            filters.set(&format!("t{i}"), LevelFilter::Off).unwrap();
+        }
+        assert_eq!(
+            filters.set("other", LevelFilter::Off),
+            Err(Status::OUT_OF_RESOURCES.into())
+        );
+        // Existing targets can still be updated.
+        filters.set("t0", LevelFilter::Error).unwrap();
+        assert_eq!(filters.level("t0"), LevelFilter::Error);
+    }
+
+    #[test]
+    fn test_line_buffer() {
+        let mut out = RecordingWriter::default();
+        let mut writer = LineBuffer::new(&mut out);
+        writer.write_str("a").unwrap();
+        writer.write_str("bc\nd").unwrap();
+        writer.write_str("e\n\nf").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(out.0, ["abc\n", "de\n", "\n", "f"]);
+    }
+
+    #[test]
+    fn test_line_buffer_full() {
+        let mut out = RecordingWriter::default();
+        let mut writer = LineBuffer::new(&mut out);
+        // Multi-byte characters must not be split when the buffer fills.
+        let line = "é".repeat(LINE_BUFFER_SIZE);
+        writer.write_str(&line).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(out.0.concat(), line);
+        assert!(out.0.iter().all(|s| s.len() <= LINE_BUFFER_SIZE));
+        assert_eq!(out.0.len(), 2);
+    }
+
+    #[test]
+    fn test_decorated_log_buffered() {
+        let mut out = RecordingWriter::default();
+        let mut writer = LineBuffer::new(&mut out);
+        DecoratedLog::write(
+            &mut writer,
+            log::Level::Info,
+            &format_args!("hello\nworld"),
+            "file.rs",
+            7,
+        )
+        .unwrap();
+        writer.flush().unwrap();
+        assert_eq!(
+            out.0,
+            ["[ INFO]:      file.rs@007: hello\n", "INFO: world\n"]
+        );
+    }
+}
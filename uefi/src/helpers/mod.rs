@@ -10,7 +10,8 @@
 //!   (only on x86)  (feature `log-debugcon`).
 //! - [`print!`][print_macro] and [`println!`][println_macro] macros defaulting
 //!   to the uefi boot service stdout stream
-//! - default panic handler (feature `panic_handler`)
+//! - default panic handler (feature `panic_handler`), optionally printing a
+//!   backtrace (feature `panic-backtrace`)
 //!
 //! **PLEASE NOTE** that these helpers are meant for the pre exit boot service
 //! epoch.
@@ -19,6 +20,7 @@
 //! [println_macro]: uefi::println!
 
 use crate::Result;
+use log::LevelFilter;
 #[doc(hidden)]
 pub use println::_print;
 
@@ -30,9 +32,41 @@ mod logger;
 mod panic_handler;
 mod println;
 
+/// Runtime configuration of the helpers, passed to [`init_with`].
+///
+/// Each option only has an effect if the Cargo feature of the corresponding
+/// helper is activated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HelpersConfig {
+    /// Install the logger (feature `logger`). Set this to `false` to route
+    /// log messages elsewhere.
+    pub logger: bool,
+
+    /// Maximum level of messages that are logged. Defaults to
+    /// [`log::STATIC_MAX_LEVEL`].
+    pub log_level: LevelFilter,
+
+    /// Also write log messages to the debugcon device (feature
+    /// `log-debugcon`, x86 only).
+    pub debugcon: bool,
+}
+
+impl Default for HelpersConfig {
+    fn default() -> Self {
+        Self {
+            logger: true,
+            log_level: log::STATIC_MAX_LEVEL,
+            debugcon: true,
+        }
+    }
+}
+
 /// Initialize all helpers defined in [`uefi::helpers`] whose Cargo features
 /// are activated.
 ///
+/// This is equivalent to calling [`init_with`] with the default
+/// [`HelpersConfig`].
+///
 /// This must be called as early as possible, before trying to use logging.
 ///
 /// **PLEASE NOTE** that these helpers are meant for the pre exit boot service
@@ -42,17 +76,62 @@ mod println;
 /// # Panics
 ///
 /// This function may panic if called more than once.
-#[allow(clippy::missing_const_for_fn)]
 pub fn init() -> Result<()> {
+    init_with(HelpersConfig::default())
+}
+
+/// Initialize the helpers defined in [`uefi::helpers`] whose Cargo features
+/// are activated and that are enabled in `config`.
+///
+/// This must be called as early as possible, before trying to use logging.
+///
+/// **PLEASE NOTE** that these helpers are meant for the pre exit boot service
+/// epoch. Limited functionality might work after exiting them, such as logging
+/// to the debugcon device.
+///
+/// # Panics
+///
+/// This function may panic if the logger is enabled and this function (or
+/// [`init`]) is called more than once.
+#[allow(clippy::missing_const_for_fn)]
+pub fn init_with(config: HelpersConfig) -> Result<()> {
     // Set up logging.
     #[cfg(feature = "logger")]
-    unsafe {
-        logger::init();
+    if config.logger {
+        unsafe {
+            logger::init(config.log_level, config.debugcon);
+        }
     }
+    #[cfg(not(feature = "logger"))]
+    let _ = config;
 
     Ok(())
 }
 
+/// Override the maximum log level for messages whose target is `target` or
+/// one of its submodules (feature `logger`).
+///
+/// By default the target of a message is the module path it was logged from,
+/// e.g. `uefi::proto::network::tcp`. If several overrides match a target, the
+/// most specific one is used. Setting the level of a target again replaces
+/// the previous override.
+///
+/// This can be used to silence a noisy module, or to enable verbose messages
+/// for just one module. It has no effect on messages that are compiled out by
+/// the `log` crate's `max_level_*` features.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `target` is longer than 64 bytes.
+/// * [`Status::OUT_OF_RESOURCES`]: 8 targets already have an override.
+///
+/// [`Status::INVALID_PARAMETER`]: crate::Status::INVALID_PARAMETER
+/// [`Status::OUT_OF_RESOURCES`]: crate::Status::OUT_OF_RESOURCES
+#[cfg(feature = "logger")]
+pub fn logger_set_target_level(target: &str, level: LevelFilter) -> Result {
+    logger::set_target_level(target, level)
+}
+
 #[allow(clippy::missing_const_for_fn)]
 pub(crate) fn exit() {
     #[cfg(feature = "logger")]
@@ -5,6 +5,9 @@ use cfg_if::cfg_if;
 fn panic_handler(info: &core::panic::PanicInfo) -> ! {
     println!("[PANIC]: {}", info);
 
+    #[cfg(all(target_arch = "x86_64", feature = "panic-backtrace"))]
+    backtrace::print();
+
     // Give the user some time to read the message
     if boot::are_boot_services_active() {
         boot::stall(10_000_000);
@@ -60,3 +63,107 @@ fn panic_handler(info: &core::panic::PanicInfo) -> ! {
         }
     }
 }
+
+/// Best-effort backtrace printing for the panic handler.
+///
+/// The backtrace is created by walking the chain of saved frame pointers,
+/// so the application must be built with `-C force-frame-pointers=yes`.
+/// Return addresses are printed relative to the base of the loaded image,
+/// so that they can be looked up in the symbol map of the `.efi` file.
+#[cfg(all(target_arch = "x86_64", feature = "panic-backtrace"))]
+mod backtrace {
+    use crate::proto::loaded_image::LoadedImage;
+    use crate::{boot, println};
+    use core::arch::asm;
+    use core::ops::Range;
+
+    /// Maximum number of frames to print.
+    const MAX_FRAMES: usize = 64;
+
+    /// Maximum distance between two consecutive frames. Larger distances
+    /// indicate that the frame pointer chain is broken.
+    const MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+    /// Maximum distance of a frame from the current stack pointer. Frames
+    /// further away are assumed not to be on the stack.
+    const MAX_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+    /// Get the address range of the running image, if boot services are
+    /// still active and the [`LoadedImage`] protocol can be opened.
+    fn image_range() -> Option<Range<usize>> {
+        if !boot::are_boot_services_active() {
+            return None;
+        }
+        let loaded_image =
+            unsafe { boot::open_protocol_get::<LoadedImage>(boot::image_handle()) }.ok()?;
+        let (base, size) = loaded_image.info();
+        let base = base as usize;
+        Some(base..base.checked_add(usize::try_from(size).ok()?)?)
+    }
+
+    /// Print the return addresses of the current call stack.
+    #[inline(never)]
+    pub fn print() {
+        let image = image_range();
+        match &image {
+            Some(image) => println!("Backtrace (image base {:#x}):", image.start),
+            None => println!("Backtrace (image base unknown):"),
+        }
+
+        let mut frame: usize;
+        let stack_pointer: usize;
+        unsafe {
+            asm!("mov {}, rbp", out(reg) frame, options(nomem, nostack));
+            asm!("mov {}, rsp", out(reg) stack_pointer, options(nomem, nostack));
+        }
+        // All frames of the callers are above the current stack pointer.
+        let stack = stack_pointer..stack_pointer.saturating_add(MAX_STACK_SIZE);
+
+        for index in 0..MAX_FRAMES {
+            // Stop at the end of the chain, or if the frame pointer is
+            // obviously not valid, e.g. because frame pointers are omitted.
+            if !is_plausible_frame(frame, &stack) {
+                if index == 0 {
+                    println!("  <unavailable, frame pointers omitted?>");
+                }
+                return;
+            }
+
+            // Safety: `frame` is aligned and the two words read are within
+            // the stack. It points to the saved frame pointer, followed by
+            // the return address, as long as all functions on the stack
+            // maintain frame pointers.
+            let (next, return_address) = unsafe {
+                let ptr = frame as *const usize;
+                (ptr.read(), ptr.add(1).read())
+            };
+            if return_address == 0 {
+                return;
+            }
+
+            match &image {
+                Some(image) if image.contains(&return_address) => println!(
+                    "  {index:2}: {return_address:#018x} (image + {:#x})",
+                    return_address - image.start
+                ),
+                _ => println!("  {index:2}: {return_address:#018x}"),
+            }
+
+            // The stack grows downwards, so the caller's frame must be at a
+            // higher address.
+            if next <= frame || next - frame > MAX_FRAME_SIZE {
+                return;
+            }
+            frame = next;
+        }
+    }
+
+    /// Check that `frame` is aligned and that the saved frame pointer and
+    /// return address it points to are within `stack`.
+    const fn is_plausible_frame(frame: usize, stack: &Range<usize>) -> bool {
+        let Some(end) = frame.checked_add(2 * size_of::<usize>()) else {
+            return false;
+        };
+        frame % align_of::<usize>() == 0 && stack.start <= frame && end <= stack.end
+    }
+}
@@ -130,9 +130,15 @@
 //!   using this feature, or no allocator at all if you don't need to
 //!   dynamically allocate any memory.
 //! - `logger`: Logging implementation for the standard [`log`] crate
-//!   that prints output to the UEFI console. No buffering is done; this
-//!   is not a high-performance logger.
+//!   that prints output to the UEFI console. Each record is buffered and
+//!   written out a line at a time, and the rest of the record is flushed
+//!   at its end, so output is never held back between records.
 //! - `panic_handler`: Add a default panic handler that logs to `stdout`.
+//! - `panic-backtrace`: Print a backtrace in the default panic handler.
+//!   The backtrace is created by walking frame pointers, so this only works
+//!   on x86_64 when building with `-C force-frame-pointers=yes`. Addresses
+//!   are printed relative to the image base, so that they can be looked up
+//!   in the symbol map of the `.efi` file. Implies `panic_handler`.
 //! - `unstable`: Enable functionality that depends on [unstable
 //!   features] in the nightly compiler.
 //!   As example, in conjunction with the `alloc`-feature, this gate allows
@@ -258,7 +264,7 @@ mod util;
 #[cfg(feature = "alloc")]
 pub use data_types::CString16;
 pub use data_types::{CStr16, CStr8, Char16, Char8, Event, Guid, Handle, Identify};
-pub use result::{Error, Result, ResultExt, Status, StatusExt};
+pub use result::{ContextError, Error, Result, ResultContextExt, ResultExt, Status, StatusExt};
 /// Re-export ucs2_cstr so that it can be used in the implementation of the
 /// cstr16 macro. It is hidden since it's not intended to be used directly.
 #[doc(hidden)]
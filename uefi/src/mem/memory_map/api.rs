@@ -87,6 +87,40 @@ pub trait MemoryMap: Debug + Index<usize, Output = MemoryDescriptor> {
         }
         true
     }
+
+    /// Returns an iterator over all entries of the given [`MemoryType`].
+    fn entries_of_type(&self, ty: MemoryType) -> impl Iterator<Item = &MemoryDescriptor>
+    where
+        Self: Sized,
+    {
+        self.entries().filter(move |desc| desc.ty == ty)
+    }
+
+    /// Returns the [`MemoryType::CONVENTIONAL`] entry with the largest number
+    /// of pages, if any.
+    ///
+    /// Adjacent free entries are not merged; use [`MemoryMap::coalesce`]
+    /// first to find the largest contiguous free region.
+    #[must_use]
+    fn largest_free_region(&self) -> Option<MemoryDescriptor>
+    where
+        Self: Sized,
+    {
+        self.entries_of_type(MemoryType::CONVENTIONAL)
+            .max_by_key(|desc| desc.page_count)
+            .copied()
+    }
+
+    /// Returns a copy of the memory map, sorted by physical address, where
+    /// adjacent entries are merged if they have the same [`MemoryType`] and
+    /// [`MemoryAttribute`] and their physical ranges are contiguous.
+    ///
+    /// The copy is allocated from the UEFI pool as
+    /// [`MemoryType::LOADER_DATA`], so boot services must still be active.
+    /// Note that this allocation may itself change the firmware's memory map.
+    fn coalesce(&self) -> crate::Result<MemoryMapOwned> {
+        MemoryMapOwned::coalesced_from(self.buffer(), self.meta())
+    }
 }
 
 /// Extension to [`MemoryMap`] that adds mutable operations. This also includes
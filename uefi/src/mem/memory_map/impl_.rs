@@ -11,17 +11,51 @@ use uefi_raw::PhysicalAddress;
 
 /// Errors that may happen when constructing a [`MemoryMapRef`] or
 /// [`MemoryMapRefMut`].
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum MemoryMapError {
     /// The buffer is not 8-byte aligned.
     Misaligned,
     /// The memory map size is invalid.
     InvalidSize,
+    /// The descriptor size is smaller than [`MemoryDescriptor`].
+    DescriptorTooSmall(usize),
+    /// The memory map size is not a multiple of the descriptor size, i.e.
+    /// the final descriptor is truncated. The value is the number of bytes
+    /// of the truncated descriptor.
+    TruncatedDescriptor(usize),
 }
 
 impl Display for MemoryMapError {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        Debug::fmt(self, f)
+        match self {
+            Self::DescriptorTooSmall(desc_size) => write!(
+                f,
+                "descriptor size {desc_size} is smaller than the minimum of {}",
+                size_of::<MemoryDescriptor>()
+            ),
+            Self::TruncatedDescriptor(trailing) => write!(
+                f,
+                "memory map ends with a truncated descriptor of {trailing} bytes"
+            ),
+            _ => Debug::fmt(self, f),
+        }
+    }
+}
+
+/// Checks shared by [`MemoryMapRef::new`] and [`MemoryMapRefMut::new`].
+fn check_buffer(buffer: &[u8], meta: &MemoryMapMeta) -> Result<(), MemoryMapError> {
+    if buffer.as_ptr().align_offset(8) != 0 {
+        return Err(MemoryMapError::Misaligned);
+    }
+    if buffer.len() < meta.map_size {
+        return Err(MemoryMapError::InvalidSize);
+    }
+    if meta.desc_size < size_of::<MemoryDescriptor>() {
+        return Err(MemoryMapError::DescriptorTooSmall(meta.desc_size));
+    }
+    match meta.map_size % meta.desc_size {
+        0 => Ok(()),
+        trailing => Err(MemoryMapError::TruncatedDescriptor(trailing)),
     }
 }
 
@@ -42,18 +76,44 @@ impl<'a> MemoryMapRef<'a> {
     /// which can't be checked during construction of this type. The entry
     /// iterator might yield unexpected results.
     pub fn new(buffer: &'a [u8], meta: MemoryMapMeta) -> Result<Self, MemoryMapError> {
-        if buffer.as_ptr().align_offset(8) != 0 {
-            return Err(MemoryMapError::Misaligned);
-        }
-        if buffer.len() < meta.map_size {
-            return Err(MemoryMapError::InvalidSize);
-        }
+        check_buffer(buffer, &meta)?;
         Ok(Self {
             buf: buffer,
             meta,
             len: meta.entry_count(),
         })
     }
+
+    /// Constructs a new [`MemoryMapRef`] from a raw memory map that is not
+    /// obtained from UEFI directly, e.g. one that a bootloader passed to a
+    /// kernel in its boot information.
+    ///
+    /// `bytes` must contain exactly the descriptors of the memory map,
+    /// `desc_size` is the size of each descriptor in bytes, which may be
+    /// larger than [`MemoryDescriptor`], and `desc_version` is the version
+    /// of the descriptors, usually [`MemoryDescriptor::VERSION`]. The
+    /// [`MemoryMapKey`] of the map is zero.
+    ///
+    /// # Errors
+    ///
+    /// * [`MemoryMapError::Misaligned`]: `bytes` is not 8-byte aligned.
+    /// * [`MemoryMapError::DescriptorTooSmall`]: `desc_size` is smaller than
+    ///   [`MemoryDescriptor`].
+    /// * [`MemoryMapError::TruncatedDescriptor`]: the length of `bytes` is
+    ///   not a multiple of `desc_size`.
+    pub fn from_raw(
+        bytes: &'a [u8],
+        desc_size: usize,
+        desc_version: u32,
+    ) -> Result<Self, MemoryMapError> {
+        let meta = MemoryMapMeta {
+            map_size: bytes.len(),
+            desc_size,
+            map_key: MemoryMapKey::default(),
+            desc_version,
+        };
+        Self::new(bytes, meta)
+    }
 }
 
 impl MemoryMap for MemoryMapRef<'_> {
@@ -104,12 +164,7 @@ impl<'a> MemoryMapRefMut<'a> {
     /// which can't be checked during construction of this type. The entry
     /// iterator might yield unexpected results.
     pub fn new(buffer: &'a mut [u8], meta: MemoryMapMeta) -> Result<Self, MemoryMapError> {
-        if buffer.as_ptr().align_offset(8) != 0 {
-            return Err(MemoryMapError::Misaligned);
-        }
-        if buffer.len() < meta.map_size {
-            return Err(MemoryMapError::InvalidSize);
-        }
+        check_buffer(buffer, &meta)?;
         Ok(Self {
             buf: buffer,
             meta,
@@ -279,15 +334,21 @@ impl MemoryMapBackingMemory {
     pub(crate) fn new(memory_type: MemoryType) -> crate::Result<Self> {
         let memory_map_meta = boot::memory_map_size();
         let len = Self::safe_allocation_size_hint(memory_map_meta);
+
+        // If this panics, the UEFI implementation is broken.
+        assert_eq!(memory_map_meta.map_size % memory_map_meta.desc_size, 0);
+
+        Self::with_len(memory_type, len)
+    }
+
+    /// Constructs a new [`MemoryMapBackingMemory`] of `len` bytes.
+    pub(crate) fn with_len(memory_type: MemoryType, len: usize) -> crate::Result<Self> {
         let ptr = boot::allocate_pool(memory_type, len)?.as_ptr();
 
         // Should be fine as UEFI always has  allocations with a guaranteed
         // alignment of 8 bytes.
         assert_eq!(ptr.align_offset(align_of::<MemoryDescriptor>()), 0);
 
-        // If this panics, the UEFI implementation is broken.
-        assert_eq!(memory_map_meta.map_size % memory_map_meta.desc_size, 0);
-
         unsafe { Ok(Self::from_raw(ptr, len)) }
     }
 
@@ -370,6 +431,51 @@ impl MemoryMapOwned {
         let len = meta.entry_count();
         Self { buf, meta, len }
     }
+
+    /// Creates a sorted and coalesced copy of the memory map in `buffer`.
+    /// See [`MemoryMap::coalesce`].
+    pub(crate) fn coalesced_from(buffer: &[u8], meta: MemoryMapMeta) -> crate::Result<Self> {
+        let mut buf = MemoryMapBackingMemory::with_len(MemoryType::LOADER_DATA, meta.map_size)?;
+        buf.as_mut_slice().copy_from_slice(&buffer[..meta.map_size]);
+
+        let mut mmap = Self::from_initialized_mem(buf, meta);
+        mmap.sort();
+        mmap.merge_adjacent();
+        Ok(mmap)
+    }
+
+    /// Merges adjacent entries with the same type and attributes whose
+    /// physical ranges are contiguous. The map must be sorted.
+    fn merge_adjacent(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+
+        let desc_size = self.meta.desc_size;
+        let mut last = 0;
+        for index in 1..self.len {
+            let next = self[index];
+            let prev = &mut self[last];
+
+            let prev_end = prev
+                .page_count
+                .checked_mul(boot::PAGE_SIZE as u64)
+                .and_then(|size| prev.phys_start.checked_add(size));
+            if prev.ty == next.ty && prev.att == next.att && prev_end == Some(next.phys_start) {
+                prev.page_count += next.page_count;
+            } else {
+                last += 1;
+                // Copy the whole descriptor, including any bytes beyond
+                // `size_of::<MemoryDescriptor>()`.
+                self.buf
+                    .as_mut_slice()
+                    .copy_within(index * desc_size..(index + 1) * desc_size, last * desc_size);
+            }
+        }
+
+        self.len = last + 1;
+        self.meta.map_size = self.len * desc_size;
+    }
 }
 
 impl MemoryMap for MemoryMapOwned {
@@ -490,6 +596,65 @@ mod tests {
         assert!(!mmap.is_sorted());
     }
 
+    /// Checks for [`MemoryMapRef::from_raw`].
+    #[test]
+    fn memory_map_ref_from_raw() {
+        let mut memory = new_mmap_memory();
+        let (mmap, _) = mmap_raw(&mut memory);
+        let desc_size = size_of::<MemoryDescriptor>();
+
+        let mmap_ref = MemoryMapRef::from_raw(mmap, desc_size, MemoryDescriptor::VERSION).unwrap();
+        assert_eq!(mmap_ref.len(), 3);
+        assert_eq!(mmap_ref.key(), MemoryMapKey::default());
+        assert_eq!(
+            mmap_ref.entries().copied().collect::<Vec<_>>().as_slice(),
+            &BASE_MMAP_UNSORTED
+        );
+
+        // Descriptor size too small.
+        assert_eq!(
+            MemoryMapRef::from_raw(mmap, desc_size - 8, MemoryDescriptor::VERSION).unwrap_err(),
+            MemoryMapError::DescriptorTooSmall(desc_size - 8)
+        );
+        assert_eq!(
+            MemoryMapRef::from_raw(mmap, 0, MemoryDescriptor::VERSION).unwrap_err(),
+            MemoryMapError::DescriptorTooSmall(0)
+        );
+
+        // Truncated final descriptor.
+        assert_eq!(
+            MemoryMapRef::from_raw(
+                &mmap[..mmap.len() - 8],
+                desc_size,
+                MemoryDescriptor::VERSION
+            )
+            .unwrap_err(),
+            MemoryMapError::TruncatedDescriptor(desc_size - 8)
+        );
+
+        // A larger descriptor size: the map now has one 80-byte descriptor
+        // with 40 bytes of padding, and 40 bytes left over.
+        assert_eq!(
+            MemoryMapRef::from_raw(mmap, 2 * desc_size, MemoryDescriptor::VERSION).unwrap_err(),
+            MemoryMapError::TruncatedDescriptor(desc_size)
+        );
+        let mmap_ref = MemoryMapRef::from_raw(
+            &mmap[..2 * desc_size],
+            2 * desc_size,
+            MemoryDescriptor::VERSION,
+        )
+        .unwrap();
+        assert_eq!(mmap_ref.len(), 1);
+        assert_eq!(mmap_ref[0], BASE_MMAP_UNSORTED[0]);
+
+        // Misaligned buffer.
+        assert_eq!(
+            MemoryMapRef::from_raw(&mmap[1..=desc_size], desc_size, MemoryDescriptor::VERSION)
+                .unwrap_err(),
+            MemoryMapError::Misaligned
+        );
+    }
+
     /// Basic sanity checks for the type [`MemoryMapRefMut`].
     #[test]
     fn memory_map_ref_mut() {
@@ -507,6 +672,83 @@ mod tests {
         assert!(mmap.is_sorted());
     }
 
+    /// Tests for [`MemoryMap::entries_of_type`] and
+    /// [`MemoryMap::largest_free_region`].
+    #[test]
+    fn memory_map_filter() {
+        let mut memory = new_mmap_memory();
+        memory[1].ty = MemoryType::LOADER_DATA;
+        memory[2].page_count = 2;
+        let (mmap, meta) = mmap_raw(&mut memory);
+        let mmap = MemoryMapRef::new(mmap, meta).unwrap();
+
+        assert_eq!(mmap.entries_of_type(MemoryType::CONVENTIONAL).count(), 2);
+        assert_eq!(
+            mmap.entries_of_type(MemoryType::LOADER_DATA)
+                .copied()
+                .collect::<Vec<_>>(),
+            [memory[1]]
+        );
+        assert_eq!(mmap.largest_free_region(), Some(memory[2]));
+    }
+
+    /// Test merging of adjacent entries in a [`MemoryMapOwned`].
+    #[test]
+    fn memory_map_merge_adjacent() {
+        let base = MemoryDescriptor {
+            ty: MemoryType::CONVENTIONAL,
+            phys_start: 0x1000,
+            virt_start: 0,
+            page_count: 1,
+            att: MemoryAttribute::WRITE_BACK,
+        };
+        let mut memory = [
+            base,
+            // Contiguous with the same type and attributes: merged.
+            MemoryDescriptor {
+                phys_start: 0x2000,
+                page_count: 2,
+                ..base
+            },
+            // Contiguous, but different attributes: not merged.
+            MemoryDescriptor {
+                phys_start: 0x4000,
+                att: MemoryAttribute::UNCACHEABLE,
+                ..base
+            },
+            // Not contiguous: not merged.
+            MemoryDescriptor {
+                phys_start: 0x8000,
+                ..base
+            },
+        ];
+        let (mmap, meta) = mmap_raw(&mut memory);
+        let mmap = MemoryMapBackingMemory::from_slice(mmap);
+        let mut mmap = MemoryMapOwned::from_initialized_mem(mmap, meta);
+        mmap.merge_adjacent();
+
+        assert_eq!(mmap.len(), 3);
+        assert_eq!(mmap.meta().map_size, 3 * size_of::<MemoryDescriptor>());
+        assert_eq!(
+            mmap.entries().copied().collect::<Vec<_>>(),
+            [
+                MemoryDescriptor {
+                    page_count: 3,
+                    ..base
+                },
+                MemoryDescriptor {
+                    phys_start: 0x4000,
+                    att: MemoryAttribute::UNCACHEABLE,
+                    ..base
+                },
+                MemoryDescriptor {
+                    phys_start: 0x8000,
+                    ..base
+                },
+            ]
+        );
+    }
+
     /// Basic sanity checks for the type [`MemoryMapOwned`].
     #[test]
     fn memory_map_owned() {
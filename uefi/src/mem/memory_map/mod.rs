@@ -17,6 +17,9 @@
 //! If you have a chunk of memory and want to parse it as UEFI memory map, which
 //! might be the case if a bootloader such as GRUB or Limine passes its boot
 //! information, you can use [`MemoryMapRef`] or [`MemoryMapRefMut`].
+//! [`MemoryMapRef::from_raw`] takes just the descriptor bytes, descriptor
+//! size, and descriptor version, which is what boot protocols such as
+//! Multiboot2 provide.
 //!
 //! # All relevant exports:
 //!
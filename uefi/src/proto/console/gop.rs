@@ -48,19 +48,25 @@
 //! the frame buffer gets sent to the screen, but it's safe to assume that
 //! the graphics card will re-draw the buffer at around the monitor's refresh rate.
 //! You will have to implement your own double buffering if you want to
-//! avoid tearing with animations.
+//! avoid tearing with animations. The [`BackBuffer`] type provides a simple
+//! off-screen buffer that is presented with a single blit.
 
 use crate::proto::unsafe_protocol;
 use crate::util::usize_from_u32;
-use crate::{boot, Result, StatusExt};
+use crate::{boot, Result, Status, StatusExt};
 use core::fmt::{Debug, Formatter};
 use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull};
+use core::slice;
 use uefi_raw::protocol::console::{
-    GraphicsOutputBltOperation, GraphicsOutputModeInformation, GraphicsOutputProtocol,
-    GraphicsOutputProtocolMode,
+    EdidActiveProtocol, EdidDiscoveredProtocol, GraphicsOutputBltOperation,
+    GraphicsOutputModeInformation, GraphicsOutputProtocol, GraphicsOutputProtocolMode,
 };
 
+#[cfg(feature = "alloc")]
+use {crate::Error, alloc::vec, alloc::vec::Vec};
+
 pub use uefi_raw::protocol::console::PixelBitmask;
 
 /// Provides access to the video hardware's frame buffer.
@@ -115,7 +121,40 @@ impl GraphicsOutput {
     ///
     /// This function will invalidate the current framebuffer.
     pub fn set_mode(&mut self, mode: &Mode) -> Result {
-        unsafe { (self.0.set_mode)(&mut self.0, mode.index) }.to_result()
+        self.set_mode_index(mode.index)
+    }
+
+    /// Temporarily switch to the mode with the given [`index`], e.g. to show
+    /// a splash screen.
+    ///
+    /// The returned guard gives access to the protocol in the new mode. When
+    /// it is dropped, the mode that was active before this call is set again.
+    /// Errors while restoring the mode are logged, since they can't be
+    /// returned from `drop`.
+    ///
+    /// Like [`set_mode`], this clears the display and invalidates the current
+    /// framebuffer, both when entering the mode and when restoring the
+    /// original one.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::DEVICE_ERROR`]: the device had an error and could not
+    ///   complete the request.
+    /// * [`Status::UNSUPPORTED`]: the mode is not supported by the device.
+    ///
+    /// [`index`]: Mode::index
+    /// [`set_mode`]: Self::set_mode
+    pub fn enter_mode(&mut self, index: u32) -> Result<ModeGuard<'_>> {
+        let original_mode = self.mode().mode;
+        self.set_mode_index(index)?;
+        Ok(ModeGuard {
+            gop: self,
+            original_mode,
+        })
+    }
+
+    fn set_mode_index(&mut self, index: u32) -> Result {
+        unsafe { (self.0.set_mode)(&mut self.0, index) }.to_result()
     }
 
     /// Performs a blt (block transfer) operation on the frame buffer.
@@ -250,6 +289,57 @@ impl GraphicsOutput {
         }
     }
 
+    /// Reads a rectangle of the screen into a new buffer. The pixels are
+    /// returned in row-major order, with `dims.0` pixels per row.
+    ///
+    /// This works in all modes, including [`PixelFormat::BltOnly`], since
+    /// the pixels are read with a [`BltOp::VideoToBltBuffer`] operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the rectangle does not fit within the
+    ///   resolution of the current mode.
+    /// * [`Status::DEVICE_ERROR`]: the device had an error and could not
+    ///   complete the request.
+    #[cfg(feature = "alloc")]
+    pub fn read_region(
+        &mut self,
+        coords: (usize, usize),
+        dims: (usize, usize),
+    ) -> Result<Vec<BltPixel>> {
+        let resolution = self.current_mode_info().resolution();
+        if !region_fits(resolution, coords, dims) {
+            return Err(Error::from(Status::INVALID_PARAMETER));
+        }
+
+        let mut pixels = vec![BltPixel::new(0, 0, 0); dims.0 * dims.1];
+        // Firmware rejects empty rectangles, but there is nothing to read.
+        if !pixels.is_empty() {
+            self.blt(BltOp::VideoToBltBuffer {
+                buffer: &mut pixels,
+                src: coords,
+                dest: BltRegion::Full,
+                dims,
+            })?;
+        }
+        Ok(pixels)
+    }
+
+    /// Reads the whole screen into a new buffer, e.g. to take a screenshot.
+    /// Returns the pixels in row-major order, and the (width, height) of the
+    /// screen.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::DEVICE_ERROR`]: the device had an error and could not
+    ///   complete the request.
+    #[cfg(feature = "alloc")]
+    pub fn capture_full(&mut self) -> Result<(Vec<BltPixel>, (usize, usize))> {
+        let resolution = self.current_mode_info().resolution();
+        let pixels = self.read_region((0, 0), resolution)?;
+        Ok((pixels, resolution))
+    }
+
     /// Memory-safety check for accessing a region of the framebuffer
     fn check_framebuffer_region(&self, coords: (usize, usize), dims: (usize, usize)) {
         let (width, height) = self.current_mode_info().resolution();
@@ -293,19 +383,39 @@ impl GraphicsOutput {
     }
 
     /// Access the frame buffer directly
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current mode uses [`PixelFormat::BltOnly`]. Use
+    /// [`try_frame_buffer`] to handle that case without panicking.
+    ///
+    /// [`try_frame_buffer`]: Self::try_frame_buffer
     pub fn frame_buffer(&mut self) -> FrameBuffer {
-        assert!(
-            self.current_mode_info().pixel_format() != PixelFormat::BltOnly,
-            "Cannot access the framebuffer in a Blt-only mode"
-        );
+        self.try_frame_buffer()
+            .expect("Cannot access the framebuffer in a Blt-only mode")
+    }
+
+    /// Access the frame buffer directly.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the current mode uses
+    ///   [`PixelFormat::BltOnly`], so there is no frame buffer that can be
+    ///   accessed directly.
+    pub fn try_frame_buffer(&mut self) -> Result<FrameBuffer<'_>> {
+        let info = self
+            .current_mode_info()
+            .frame_buffer_info()
+            .ok_or(Status::UNSUPPORTED)?;
         let base = self.mode().frame_buffer_base as *mut u8;
         let size = self.mode().frame_buffer_size;
 
-        FrameBuffer {
+        Ok(FrameBuffer {
             base,
             size,
+            info,
             _lifetime: PhantomData,
-        }
+        })
     }
 
     const fn mode(&self) -> &GraphicsOutputProtocolMode {
@@ -313,6 +423,53 @@ impl GraphicsOutput {
     }
 }
 
+/// Guard that restores the original graphics mode when dropped.
+///
+/// This is returned by [`GraphicsOutput::enter_mode`], and dereferences to
+/// the [`GraphicsOutput`] protocol.
+#[derive(Debug)]
+pub struct ModeGuard<'gop> {
+    gop: &'gop mut GraphicsOutput,
+    original_mode: u32,
+}
+
+impl ModeGuard<'_> {
+    /// Index of the mode that will be restored when the guard is dropped.
+    #[must_use]
+    pub const fn original_mode(&self) -> u32 {
+        self.original_mode
+    }
+}
+
+impl Deref for ModeGuard<'_> {
+    type Target = GraphicsOutput;
+
+    fn deref(&self) -> &GraphicsOutput {
+        self.gop
+    }
+}
+
+impl DerefMut for ModeGuard<'_> {
+    fn deref_mut(&mut self) -> &mut GraphicsOutput {
+        self.gop
+    }
+}
+
+impl Drop for ModeGuard<'_> {
+    fn drop(&mut self) {
+        if self.gop.mode().mode == self.original_mode {
+            return;
+        }
+        if let Err(err) = self.gop.set_mode_index(self.original_mode) {
+            log::error!(
+                "Failed to restore graphics mode {}: {:?}",
+                self.original_mode,
+                err.status()
+            );
+        }
+    }
+}
+
 /// Represents the format of the pixels in a frame buffer.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[repr(u32)]
@@ -343,6 +500,12 @@ pub struct Mode {
 }
 
 impl Mode {
+    /// The index of the mode, as used by [`GraphicsOutput::enter_mode`].
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
     /// The size of the info structure in bytes.
     ///
     /// Newer versions of the spec might add extra information, in a backwards compatible way.
@@ -404,6 +567,123 @@ impl ModeInfo {
     pub const fn stride(&self) -> usize {
         usize_from_u32(self.0.pixels_per_scan_line)
     }
+
+    /// Returns the layout of the frame buffer in this mode, or `None` if the
+    /// mode uses [`PixelFormat::BltOnly`].
+    #[must_use]
+    pub const fn frame_buffer_info(&self) -> Option<FrameBufferInfo> {
+        let bitmask = match self.pixel_format() {
+            PixelFormat::Rgb => PixelBitmask {
+                red: 0x0000_00ff,
+                green: 0x0000_ff00,
+                blue: 0x00ff_0000,
+                reserved: 0xff00_0000,
+            },
+            PixelFormat::Bgr => PixelBitmask {
+                red: 0x00ff_0000,
+                green: 0x0000_ff00,
+                blue: 0x0000_00ff,
+                reserved: 0xff00_0000,
+            },
+            PixelFormat::Bitmask => self.0.pixel_information,
+            PixelFormat::BltOnly => return None,
+        };
+        Some(FrameBufferInfo {
+            resolution: self.resolution(),
+            stride: self.stride(),
+            bitmask,
+        })
+    }
+}
+
+/// Layout of the pixels in a frame buffer.
+///
+/// Returned by [`ModeInfo::frame_buffer_info`] and [`FrameBuffer::info`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FrameBufferInfo {
+    resolution: (usize, usize),
+    stride: usize,
+    bitmask: PixelBitmask,
+}
+
+impl FrameBufferInfo {
+    /// Returns the (horizontal, vertical) resolution.
+    #[must_use]
+    pub const fn resolution(&self) -> (usize, usize) {
+        self.resolution
+    }
+
+    /// Returns the number of pixels per scanline.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the number of bytes per scanline.
+    #[must_use]
+    pub const fn stride_bytes(&self) -> usize {
+        self.stride * self.bytes_per_pixel()
+    }
+
+    /// Returns the bitmask describing which bits of a pixel hold each color.
+    ///
+    /// For [`PixelFormat::Rgb`] and [`PixelFormat::Bgr`] this is the
+    /// equivalent bitmask of the 32-bit little-endian pixel.
+    #[must_use]
+    pub const fn pixel_bitmask(&self) -> PixelBitmask {
+        self.bitmask
+    }
+
+    /// Returns the number of bytes used by each pixel.
+    ///
+    /// This is four for [`PixelFormat::Rgb`] and [`PixelFormat::Bgr`]. For
+    /// [`PixelFormat::Bitmask`], it is the number of bytes needed to hold the
+    /// highest bit set in any of the masks.
+    #[must_use]
+    pub const fn bytes_per_pixel(&self) -> usize {
+        let mask =
+            self.bitmask.red | self.bitmask.green | self.bitmask.blue | self.bitmask.reserved;
+        let bits = (u32::BITS - mask.leading_zeros()) as usize;
+        bits.div_ceil(8)
+    }
+
+    /// Returns the byte offset of the pixel at (`x`, `y`) in the frame
+    /// buffer, or `None` if the coordinates are outside the resolution.
+    #[must_use]
+    pub const fn pixel_offset(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.resolution.0 && y < self.resolution.1 {
+            Some((y * self.stride + x) * self.bytes_per_pixel())
+        } else {
+            None
+        }
+    }
+
+    /// Encodes `color` in the frame buffer's pixel format. The pixel is
+    /// stored in the lowest [`bytes_per_pixel`] bytes of the little-endian
+    /// value.
+    ///
+    /// For bitmask formats, each 8-bit channel is scaled to the width of its
+    /// mask.
+    ///
+    /// [`bytes_per_pixel`]: Self::bytes_per_pixel
+    #[must_use]
+    pub const fn encode_pixel(&self, color: BltPixel) -> u32 {
+        encode_channel(color.red, self.bitmask.red)
+            | encode_channel(color.green, self.bitmask.green)
+            | encode_channel(color.blue, self.bitmask.blue)
+    }
+}
+
+/// Scale an 8-bit color channel to the width of `mask` and shift it into
+/// position. The mask is expected to have contiguous bits.
+const fn encode_channel(value: u8, mask: u32) -> u32 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let max = (mask >> shift) as u64;
+    let scaled = (value as u64 * max + 127) / 255;
+    ((scaled as u32) << shift) & mask
 }
 
 /// Iterator for [`Mode`]s of the [`GraphicsOutput`] protocol.
@@ -482,6 +762,18 @@ impl From<u32> for BltPixel {
     }
 }
 
+/// Returns true if the rectangle at `coords` with size `dims` fits within a
+/// screen of size `resolution`.
+#[cfg(feature = "alloc")]
+const fn region_fits(
+    resolution: (usize, usize),
+    coords: (usize, usize),
+    dims: (usize, usize),
+) -> bool {
+    matches!(coords.0.checked_add(dims.0), Some(right) if right <= resolution.0)
+        && matches!(coords.1.checked_add(dims.1), Some(bottom) if bottom <= resolution.1)
+}
+
 /// Region of the `BltBuffer` which we are operating on
 ///
 /// Some `Blt` operations can operate on either the full `BltBuffer` or a
@@ -553,10 +845,42 @@ pub enum BltOp<'buf> {
 pub struct FrameBuffer<'gop> {
     base: *mut u8,
     size: usize,
+    info: FrameBufferInfo,
     _lifetime: PhantomData<&'gop mut u8>,
 }
 
 impl FrameBuffer<'_> {
+    /// Returns the layout of the pixels in the frame buffer.
+    #[must_use]
+    pub const fn info(&self) -> &FrameBufferInfo {
+        &self.info
+    }
+
+    /// Writes the pixel at (`x`, `y`), encoding `color` in the frame buffer's
+    /// pixel format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside the resolution, or if the pixel
+    /// does not fit within the frame buffer.
+    pub fn write_pixel(&mut self, x: usize, y: usize, color: BltPixel) {
+        let offset = self
+            .info
+            .pixel_offset(x, y)
+            .expect("Frame buffer pixel coordinates out of bounds");
+        let bytes_per_pixel = self.info.bytes_per_pixel();
+        assert!(
+            offset.saturating_add(bytes_per_pixel) <= self.size,
+            "Frame buffer accessed out of bounds"
+        );
+
+        let bytes = self.info.encode_pixel(color).to_le_bytes();
+        for (i, byte) in bytes.iter().take(bytes_per_pixel).enumerate() {
+            // Safety: the write is within the frame buffer, checked above.
+            unsafe { self.base.add(offset + i).write_volatile(*byte) };
+        }
+    }
+
     /// Access the raw framebuffer pointer
     ///
     /// To use this pointer safely and correctly, you must...
@@ -648,3 +972,371 @@ impl FrameBuffer<'_> {
         (self.base.add(index) as *const T).read_volatile()
     }
 }
+
+/// EDID (Extended Display Identification Data) of the display connected to a
+/// video output device, as read from the display.
+///
+/// This protocol is installed on the handle of a [`GraphicsOutput`] child
+/// device. See [`EdidActive`] for the EDID that is actually in use.
+///
+/// The corresponding C type is `EFI_EDID_DISCOVERED_PROTOCOL`.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(EdidDiscoveredProtocol::GUID)]
+pub struct EdidDiscovered(EdidDiscoveredProtocol);
+
+/// EDID (Extended Display Identification Data) of the display connected to a
+/// video output device, as used by the firmware.
+///
+/// This is the same as the [`EdidDiscovered`] EDID, unless the platform
+/// overrides it. This protocol is installed on the handle of a
+/// [`GraphicsOutput`] child device.
+///
+/// The corresponding C type is `EFI_EDID_ACTIVE_PROTOCOL`.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(EdidActiveProtocol::GUID)]
+pub struct EdidActive(EdidActiveProtocol);
+
+/// Implement the accessors shared by the EDID protocols, which all have the
+/// same layout.
+macro_rules! impl_edid_methods {
+    ($($edid:ty),*) => {
+        $(
+            impl $edid {
+                /// Returns the raw EDID bytes, or `None` if there is no EDID
+                /// (for example because no display is connected).
+                #[must_use]
+                pub fn raw_edid(&self) -> Option<&[u8]> {
+                    unsafe { edid_slice(self.0.edid, self.0.size_of_edid) }
+                }
+
+                /// Returns the (horizontal, vertical) active resolution of the
+                /// display's preferred timing, read from the first detailed
+                /// timing descriptor of the EDID base block.
+                ///
+                /// Returns `None` if there is no EDID, the base block is
+                /// invalid, or the first descriptor is not a timing
+                /// descriptor.
+                #[must_use]
+                pub fn parse_preferred_timing(&self) -> Option<(usize, usize)> {
+                    parse_preferred_timing(self.raw_edid()?)
+                }
+            }
+        )*
+    };
+}
+
+impl_edid_methods!(EdidDiscovered, EdidActive);
+
+/// Create a slice from the EDID pointer and size provided by firmware.
+unsafe fn edid_slice<'a>(edid: *const u8, size: u32) -> Option<&'a [u8]> {
+    if edid.is_null() || size == 0 {
+        None
+    } else {
+        Some(slice::from_raw_parts(edid, usize_from_u32(size)))
+    }
+}
+
+/// Get the active resolution from the first detailed timing descriptor of
+/// an EDID base block.
+fn parse_preferred_timing(edid: &[u8]) -> Option<(usize, usize)> {
+    const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+    const DESCRIPTOR_OFFSET: usize = 54;
+
+    let base = edid.get(..128)?;
+    if base[..8] != HEADER || base.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) != 0 {
+        return None;
+    }
+
+    let descriptor = &base[DESCRIPTOR_OFFSET..DESCRIPTOR_OFFSET + 18];
+    // A pixel clock of zero indicates a display descriptor rather than a
+    // timing descriptor.
+    if descriptor[0] == 0 && descriptor[1] == 0 {
+        return None;
+    }
+
+    let horizontal = usize::from(descriptor[2]) | (usize::from(descriptor[4] & 0xf0) << 4);
+    let vertical = usize::from(descriptor[5]) | (usize::from(descriptor[7] & 0xf0) << 4);
+    Some((horizontal, vertical))
+}
+
+/// Off-screen pixel buffer for double buffering with [`GraphicsOutput`].
+///
+/// The buffer is sized to the resolution of the graphics mode that is active
+/// when the `BackBuffer` is created. Drawing operations only modify the
+/// in-memory buffer; the screen is updated by calling [`present`], which
+/// copies the whole buffer to the frame buffer with a single
+/// [`BltOp::BufferToVideo`] operation.
+///
+/// Since all writes go through `blt`, this also works in
+/// [`PixelFormat::BltOnly`] modes where the frame buffer cannot be
+/// accessed directly.
+///
+/// [`present`]: Self::present
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct BackBuffer<'gop> {
+    gop: &'gop mut GraphicsOutput,
+    mode_info: ModeInfo,
+    width: usize,
+    height: usize,
+    pixels: Vec<BltPixel>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'gop> BackBuffer<'gop> {
+    /// Create a back buffer matching the current mode of `gop`. All pixels
+    /// are initially black.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the current mode uses
+    ///   [`PixelFormat::Bitmask`], for which the [`BltPixel`] layout does not
+    ///   describe the pixel format.
+    pub fn new(gop: &'gop mut GraphicsOutput) -> Result<Self> {
+        let mode_info = gop.current_mode_info();
+        if mode_info.pixel_format() == PixelFormat::Bitmask {
+            return Err(Error::from(Status::UNSUPPORTED));
+        }
+
+        let (width, height) = mode_info.resolution();
+        Ok(Self {
+            gop,
+            mode_info,
+            width,
+            height,
+            pixels: vec![BltPixel::new(0, 0, 0); width * height],
+        })
+    }
+
+    /// Returns the (width, height) of the buffer in pixels.
+    #[must_use]
+    pub const fn resolution(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the pixels of the buffer in row-major order.
+    #[must_use]
+    pub fn pixels(&self) -> &[BltPixel] {
+        &self.pixels
+    }
+
+    /// Returns the pixels of the buffer in row-major order.
+    #[must_use]
+    pub fn pixels_mut(&mut self) -> &mut [BltPixel] {
+        &mut self.pixels
+    }
+
+    /// Returns a mutable reference to the pixel at (`x`, `y`), or `None` if
+    /// the coordinates are out of bounds.
+    #[must_use]
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> Option<&mut BltPixel> {
+        if x < self.width && y < self.height {
+            self.pixels.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Fills a rectangle of the buffer with a single color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle does not fit within the buffer.
+    pub fn fill_rect(&mut self, dest: (usize, usize), dims: (usize, usize), color: BltPixel) {
+        let (dest_x, dest_y) = dest;
+        let (width, height) = dims;
+        assert!(
+            dest_x.saturating_add(width) <= self.width,
+            "Horizontal BackBuffer coordinate out of bounds"
+        );
+        assert!(
+            dest_y.saturating_add(height) <= self.height,
+            "Vertical BackBuffer coordinate out of bounds"
+        );
+
+        for row in self
+            .pixels
+            .chunks_exact_mut(self.width)
+            .skip(dest_y)
+            .take(height)
+        {
+            row[dest_x..dest_x + width].fill(color);
+        }
+    }
+
+    /// Fills the whole buffer with a single color.
+    pub fn clear(&mut self, color: BltPixel) {
+        self.pixels.fill(color);
+    }
+
+    /// Copies the whole buffer to the screen.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the graphics mode has changed since
+    ///   the buffer was created, so the buffer no longer matches the screen.
+    /// * [`Status::DEVICE_ERROR`]: the device had an error and could not
+    ///   complete the request.
+    pub fn present(&mut self) -> Result {
+        if self.gop.current_mode_info() != self.mode_info {
+            return Err(Error::from(Status::INVALID_PARAMETER));
+        }
+
+        self.gop.blt(BltOp::BufferToVideo {
+            buffer: &self.pixels,
+            src: BltRegion::Full,
+            dest: (0, 0),
+            dims: (self.width, self.height),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn fb_info(bitmask: PixelBitmask) -> FrameBufferInfo {
+        FrameBufferInfo {
+            resolution: (640, 480),
+            stride: 648,
+            bitmask,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_region_fits() {
+        let resolution = (640, 480);
+        assert!(region_fits(resolution, (0, 0), (640, 480)));
+        assert!(region_fits(resolution, (600, 400), (40, 80)));
+        assert!(region_fits(resolution, (640, 480), (0, 0)));
+        assert!(!region_fits(resolution, (600, 400), (41, 80)));
+        assert!(!region_fits(resolution, (600, 400), (40, 81)));
+        assert!(!region_fits(resolution, (1, 0), (usize::MAX, 1)));
+        assert!(!region_fits(resolution, (0, 1), (1, usize::MAX)));
+    }
+
+    #[test]
+    fn test_encode_pixel_rgb565() {
+        let info = fb_info(PixelBitmask {
+            red: 0xf800,
+            green: 0x07e0,
+            blue: 0x001f,
+            reserved: 0,
+        });
+        assert_eq!(info.bytes_per_pixel(), 2);
+        assert_eq!(info.stride_bytes(), 648 * 2);
+
+        assert_eq!(info.encode_pixel(BltPixel::new(0, 0, 0)), 0);
+        assert_eq!(info.encode_pixel(BltPixel::new(255, 255, 255)), 0xffff);
+        assert_eq!(info.encode_pixel(BltPixel::new(255, 0, 0)), 0xf800);
+        assert_eq!(info.encode_pixel(BltPixel::new(0, 255, 0)), 0x07e0);
+        assert_eq!(info.encode_pixel(BltPixel::new(0, 0, 255)), 0x001f);
+        // 128 / 255 * 31 rounds to 16, and 128 / 255 * 63 rounds to 32.
+        assert_eq!(
+            info.encode_pixel(BltPixel::new(128, 128, 128)),
+            (16 << 11) | (32 << 5) | 16
+        );
+    }
+
+    #[test]
+    fn test_encode_pixel_8bit_masks() {
+        // 24-bit BGR with the reserved bits in the high byte.
+        let info = fb_info(PixelBitmask {
+            red: 0x00ff_0000,
+            green: 0x0000_ff00,
+            blue: 0x0000_00ff,
+            reserved: 0xff00_0000,
+        });
+        assert_eq!(info.bytes_per_pixel(), 4);
+        assert_eq!(
+            info.encode_pixel(BltPixel::new(0x12, 0x34, 0x56)),
+            0x0012_3456
+        );
+
+        // 10-bit channels.
+        let info = fb_info(PixelBitmask {
+            red: 0x3ff0_0000,
+            green: 0x000f_fc00,
+            blue: 0x0000_03ff,
+            reserved: 0xc000_0000,
+        });
+        assert_eq!(info.bytes_per_pixel(), 4);
+        assert_eq!(
+            info.encode_pixel(BltPixel::new(255, 0, 255)),
+            0x3ff0_0000 | 0x0000_03ff
+        );
+
+        // A missing channel is left as zero.
+        let info = fb_info(PixelBitmask {
+            red: 0xff,
+            green: 0,
+            blue: 0,
+            reserved: 0,
+        });
+        assert_eq!(info.bytes_per_pixel(), 1);
+        assert_eq!(info.encode_pixel(BltPixel::new(0x80, 0xff, 0xff)), 0x80);
+    }
+
+    #[test]
+    fn test_parse_preferred_timing() {
+        // EDID base block with a 1280x800 preferred timing.
+        let mut edid = [0u8; 128];
+        edid[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+        edid[54..72].copy_from_slice(&[
+            0x2c, 0x22, 0x00, 0xa0, 0x50, 0x20, 0x14, 0x30, 0x28, 0x10, 0x36, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x1e,
+        ]);
+        let fix_checksum = |edid: &mut [u8; 128]| {
+            edid[127] = 0;
+            let sum = edid.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+            edid[127] = sum.wrapping_neg();
+        };
+        fix_checksum(&mut edid);
+        assert_eq!(parse_preferred_timing(&edid), Some((1280, 800)));
+
+        // Extension blocks are ignored.
+        let mut with_extension = [0u8; 256];
+        with_extension[..128].copy_from_slice(&edid);
+        assert_eq!(parse_preferred_timing(&with_extension), Some((1280, 800)));
+
+        // Too short.
+        assert_eq!(parse_preferred_timing(&edid[..127]), None);
+        assert_eq!(parse_preferred_timing(&[]), None);
+
+        // Bad checksum.
+        let mut bad = edid;
+        bad[127] = bad[127].wrapping_add(1);
+        assert_eq!(parse_preferred_timing(&bad), None);
+
+        // Bad header.
+        let mut bad = edid;
+        bad[0] = 0xff;
+        fix_checksum(&mut bad);
+        assert_eq!(parse_preferred_timing(&bad), None);
+
+        // First descriptor is a display descriptor.
+        let mut bad = edid;
+        bad[54] = 0;
+        bad[55] = 0;
+        fix_checksum(&mut bad);
+        assert_eq!(parse_preferred_timing(&bad), None);
+    }
+
+    #[test]
+    fn test_pixel_offset() {
+        let info = fb_info(PixelBitmask {
+            red: 0x0000_00ff,
+            green: 0x0000_ff00,
+            blue: 0x00ff_0000,
+            reserved: 0xff00_0000,
+        });
+        assert_eq!(info.pixel_offset(0, 0), Some(0));
+        assert_eq!(info.pixel_offset(3, 2), Some((2 * 648 + 3) * 4));
+        assert_eq!(info.pixel_offset(639, 479), Some((479 * 648 + 639) * 4));
+        assert_eq!(info.pixel_offset(640, 0), None);
+        assert_eq!(info.pixel_offset(0, 480), None);
+    }
+}
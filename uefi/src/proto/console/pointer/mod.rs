@@ -2,7 +2,7 @@
 
 use crate::proto::unsafe_protocol;
 use crate::{Event, Result, Status, StatusExt};
-use uefi_raw::protocol::console::SimplePointerProtocol;
+use uefi_raw::protocol::console::{SimplePointerProtocol, SimplePointerState};
 
 /// Provides information about a pointer device.
 #[derive(Debug)]
@@ -26,20 +26,22 @@ impl Pointer {
     /// Retrieves the pointer device's current state, if a state change occurred
     /// since the last time this function was called.
     ///
-    /// Use `wait_for_input_event()` with the [`boot::wait_for_event`]
+    /// Use [`wait_for_input_event`] with the [`boot::wait_for_event`]
     /// interface in order to wait for input from the pointer device.
     ///
     /// # Errors
     /// - `DeviceError` if there was an issue with the pointer device.
     ///
+    /// [`wait_for_input_event`]: Self::wait_for_input_event
     /// [`boot::wait_for_event`]: crate::boot::wait_for_event
     pub fn read_state(&mut self) -> Result<Option<PointerState>> {
-        let mut pointer_state = PointerState::default();
-        let pointer_state_ptr: *mut _ = &mut pointer_state;
+        // Read into the raw type, since the firmware is not guaranteed to
+        // only write 0 or 1 to the button fields.
+        let mut pointer_state = SimplePointerState::default();
 
-        match unsafe { (self.0.get_state)(&mut self.0, pointer_state_ptr.cast()) } {
+        match unsafe { (self.0.get_state)(&mut self.0, &mut pointer_state) } {
             Status::NOT_READY => Ok(None),
-            other => other.to_result_with_val(|| Some(pointer_state)),
+            other => other.to_result_with_val(|| Some(pointer_state.into())),
         }
     }
 
@@ -83,3 +85,39 @@ pub struct PointerState {
     /// If `PointerMode` indicates a button is not supported, it must be ignored.
     pub button: [bool; 2],
 }
+
+impl From<SimplePointerState> for PointerState {
+    fn from(state: SimplePointerState) -> Self {
+        Self {
+            relative_movement: [
+                state.relative_movement_x,
+                state.relative_movement_y,
+                state.relative_movement_z,
+            ],
+            button: [state.left_button != 0, state.right_button != 0],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer_state_from_raw() {
+        let raw = SimplePointerState {
+            relative_movement_x: 1,
+            relative_movement_y: -2,
+            relative_movement_z: 0,
+            left_button: 0xff,
+            right_button: 0,
+        };
+        assert_eq!(
+            PointerState::from(raw),
+            PointerState {
+                relative_movement: [1, -2, 0],
+                button: [true, false],
+            }
+        );
+    }
+}
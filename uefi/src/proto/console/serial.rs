@@ -1,8 +1,9 @@
 //! Abstraction over byte stream devices, also known as serial I/O devices.
 
 use crate::proto::unsafe_protocol;
-use crate::{Result, StatusExt};
+use crate::{Result, Status, StatusExt};
 use core::fmt::Write;
+use core::time::Duration;
 use uefi_raw::protocol::console::serial::SerialIoProtocol;
 
 pub use uefi_raw::protocol::console::serial::{
@@ -61,6 +62,43 @@ impl Serial {
         .to_result()
     }
 
+    /// Sets the timeout for read and write operations, keeping the other
+    /// attributes unchanged.
+    ///
+    /// The timeout has microsecond granularity. A timeout of zero resets the
+    /// timeout to the device's default.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the timeout does not fit in a `u32`
+    ///   number of microseconds, or is not supported by the device.
+    pub fn set_timeout(&mut self, timeout: Duration) -> Result {
+        let timeout = u32::try_from(timeout.as_micros()).map_err(|_| Status::INVALID_PARAMETER)?;
+        self.update_attributes(|mode| mode.timeout = timeout)
+    }
+
+    /// Sets the baud rate, keeping the other attributes unchanged.
+    ///
+    /// A baud rate of zero resets the baud rate to the device's default.
+    /// Otherwise, the baud rate is rounded down to the nearest value
+    /// supported by the device.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the baud rate is not supported by
+    ///   the device.
+    pub fn set_baud_rate(&mut self, baud_rate: u64) -> Result {
+        self.update_attributes(|mode| mode.baud_rate = baud_rate)
+    }
+
+    /// Copy the current I/O mode, apply `f` to it, and set it as the
+    /// device's new attributes.
+    fn update_attributes(&mut self, f: impl FnOnce(&mut IoMode)) -> Result {
+        let mut mode = *self.io_mode();
+        f(&mut mode);
+        self.set_attributes(&mode)
+    }
+
     /// Retrieve the device's current control bits.
     pub fn get_control_bits(&self) -> Result<ControlBits> {
         let mut bits = ControlBits::empty();
@@ -70,11 +108,30 @@ impl Serial {
     /// Sets the device's new control bits.
     ///
     /// Not all bits can be modified with this function. A mask of the allowed
-    /// bits is stored in the [`ControlBits::SETTABLE`] constant.
+    /// bits is stored in the [`ControlBits::SETTABLE`] constant. Other bits,
+    /// such as the read-only status bits returned by [`get_control_bits`],
+    /// are ignored.
+    ///
+    /// [`get_control_bits`]: Self::get_control_bits
     pub fn set_control_bits(&mut self, bits: ControlBits) -> Result {
+        let bits = bits & ControlBits::SETTABLE;
         unsafe { (self.0.set_control_bits)(&mut self.0, bits) }.to_result()
     }
 
+    /// Enables or disables hardware flow control (RTS/CTS), keeping the
+    /// other control bits unchanged.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the device does not support hardware flow
+    ///   control.
+    /// * [`Status::DEVICE_ERROR`]: the control bits could not be read or set.
+    pub fn set_hardware_flow_control(&mut self, enable: bool) -> Result {
+        let mut bits = self.get_control_bits()?;
+        bits.set(ControlBits::HARDWARE_FLOW_CONTROL_ENABLE, enable);
+        self.set_control_bits(bits)
+    }
+
     /// Reads data from this device.
     ///
     /// This operation will block until the buffer has been filled with data or
@@ -88,6 +145,33 @@ impl Serial {
         )
     }
 
+    /// Reads the data that is immediately available from this device,
+    /// without waiting for the buffer to be filled.
+    ///
+    /// The timeout is temporarily set to the minimum of one microsecond for
+    /// the read, since a timeout of zero means the device's default. Returns
+    /// the number of bytes read, which may be less than the size of the
+    /// buffer or zero.
+    ///
+    /// If the old timeout can't be restored afterwards, a warning is logged
+    /// and the result of the read is still returned, since the bytes have
+    /// already been consumed from the device.
+    pub fn try_read(&mut self, data: &mut [u8]) -> Result<usize> {
+        let old_timeout = self.io_mode().timeout;
+        self.update_attributes(|mode| mode.timeout = 1)?;
+
+        let result = match self.read(data) {
+            Ok(()) => Ok(data.len()),
+            Err(err) if err.status() == Status::TIMEOUT => Ok(*err.data()),
+            Err(err) => Err(err.to_err_without_payload()),
+        };
+
+        if let Err(err) = self.update_attributes(|mode| mode.timeout = old_timeout) {
+            log::warn!("failed to restore the serial timeout: {}", err.status());
+        }
+        result
+    }
+
     /// Writes data to this device.
     ///
     /// This operation will block until the data has been fully written or an
@@ -0,0 +1,262 @@
+use super::Key;
+use crate::proto::unsafe_protocol;
+use crate::{Event, Result, Status, StatusExt};
+use core::ffi::c_void;
+use core::mem::MaybeUninit;
+use core::ptr::{self, NonNull};
+use uefi_raw::protocol::console::{InputKey, SimpleTextInputExProtocol};
+
+pub use uefi_raw::protocol::console::{KeyNotifyFunction, KeyShiftState, KeyState, KeyToggleState};
+
+/// Extended interface for text-based input devices.
+///
+/// In addition to the functionality of [`Input`], this protocol reports the
+/// state of modifier keys like Shift and Ctrl, allows setting the state of
+/// toggle keys like Caps Lock, and supports registering callbacks for
+/// specific keystrokes.
+///
+/// [`Input`]: super::Input
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(SimpleTextInputExProtocol::GUID)]
+pub struct InputEx(SimpleTextInputExProtocol);
+
+impl InputEx {
+    /// Resets the input device hardware.
+    ///
+    /// The `extended_verification` parameter is used to request that UEFI
+    /// performs an extended check and reset of the input device.
+    ///
+    /// # Errors
+    ///
+    /// - [`Status::DEVICE_ERROR`] if the device is malfunctioning and cannot
+    ///   be reset.
+    pub fn reset(&mut self, extended_verification: bool) -> Result {
+        unsafe { (self.0.reset)(&mut self.0, extended_verification) }.to_result()
+    }
+
+    /// Reads the next keystroke from the input device, if any, together with
+    /// the state of the modifier and toggle keys.
+    ///
+    /// Use [`wait_for_key_event`] with the [`boot::wait_for_event`]
+    /// interface in order to wait for a key to be pressed.
+    ///
+    /// If [`KeyToggleState::KEY_STATE_EXPOSED`] has been set with
+    /// [`set_state`], partial keystrokes such as a lone Shift press are
+    /// reported too. The key of such a keystroke is
+    /// [`Key::Printable`] with a null character.
+    ///
+    /// [`boot::wait_for_event`]: crate::boot::wait_for_event
+    /// [`set_state`]: Self::set_state
+    /// [`wait_for_key_event`]: Self::wait_for_key_event
+    ///
+    /// # Errors
+    ///
+    /// - [`Status::DEVICE_ERROR`] if there was an issue with the input device
+    /// - [`Status::UNSUPPORTED`] if the keystroke is not supported by the
+    ///   current keyboard layout
+    pub fn read_key_stroke(&mut self) -> Result<Option<KeyData>> {
+        let mut key_data = MaybeUninit::<uefi_raw::protocol::console::KeyData>::uninit();
+
+        match unsafe { (self.0.read_key_stroke_ex)(&mut self.0, key_data.as_mut_ptr()) } {
+            Status::NOT_READY => Ok(None),
+            other => other.to_result_with_val(|| Some(unsafe { key_data.assume_init() }.into())),
+        }
+    }
+
+    /// Event to be used with [`boot::wait_for_event`] in order to wait
+    /// for a key to be available
+    ///
+    /// [`boot::wait_for_event`]: crate::boot::wait_for_event
+    #[must_use]
+    pub fn wait_for_key_event(&self) -> Option<Event> {
+        unsafe { Event::from_ptr(self.0.wait_for_key_ex) }
+    }
+
+    /// Sets the state of the toggle keys, e.g. to turn on Num Lock.
+    ///
+    /// [`KeyToggleState::TOGGLE_STATE_VALID`] must be set for the other
+    /// flags to take effect.
+    ///
+    /// # Errors
+    ///
+    /// - [`Status::DEVICE_ERROR`] if the device is not functioning correctly
+    /// - [`Status::UNSUPPORTED`] if the device does not support the
+    ///   requested state change
+    pub fn set_state(&mut self, state: KeyToggleState) -> Result {
+        unsafe { (self.0.set_state)(&mut self.0, &state) }.to_result()
+    }
+
+    /// Registers a function that is called when the given keystroke is
+    /// entered.
+    ///
+    /// The modifier and toggle state in `key_data` are only matched if
+    /// [`KeyShiftState::SHIFT_STATE_VALID`] or
+    /// [`KeyToggleState::TOGGLE_STATE_VALID`] respectively are set. The
+    /// returned handle must be passed to [`unregister_key_notify`] before the
+    /// function becomes invalid, e.g. before the image that contains it is
+    /// unloaded.
+    ///
+    /// [`unregister_key_notify`]: Self::unregister_key_notify
+    ///
+    /// # Errors
+    ///
+    /// - [`Status::OUT_OF_RESOURCES`] if the notification could not be
+    ///   registered
+    pub fn register_key_notify(
+        &mut self,
+        key_data: &KeyData,
+        notify_fn: KeyNotifyFunction,
+    ) -> Result<KeyNotifyHandle> {
+        let key_data = uefi_raw::protocol::console::KeyData::from(*key_data);
+        let mut handle = ptr::null_mut();
+        unsafe { (self.0.register_key_notify)(&mut self.0, &key_data, notify_fn, &mut handle) }
+            .to_result()?;
+        NonNull::new(handle)
+            .map(KeyNotifyHandle)
+            .ok_or_else(|| Status::OUT_OF_RESOURCES.into())
+    }
+
+    /// Removes a notification registered with [`register_key_notify`].
+    ///
+    /// [`register_key_notify`]: Self::register_key_notify
+    ///
+    /// # Errors
+    ///
+    /// - [`Status::INVALID_PARAMETER`] if the handle is not valid
+    pub fn unregister_key_notify(&mut self, handle: KeyNotifyHandle) -> Result {
+        unsafe { (self.0.unregister_key_notify)(&mut self.0, handle.0.as_ptr()) }.to_result()
+    }
+}
+
+/// A keystroke read from the console together with the state of the modifier
+/// and toggle keys.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct KeyData {
+    /// The key that was pressed.
+    pub key: Key,
+
+    /// The state of the modifier and toggle keys.
+    pub state: KeyState,
+}
+
+impl KeyData {
+    /// Returns the state of the modifier keys, or an empty set if the
+    /// device does not report it.
+    #[must_use]
+    pub const fn shift_state(&self) -> KeyShiftState {
+        let shift = self.state.key_shift_state;
+        if shift.contains(KeyShiftState::SHIFT_STATE_VALID) {
+            shift.difference(KeyShiftState::SHIFT_STATE_VALID)
+        } else {
+            KeyShiftState::empty()
+        }
+    }
+
+    /// Returns true if either Ctrl key is held.
+    #[must_use]
+    pub fn is_control_pressed(&self) -> bool {
+        self.shift_state()
+            .intersects(KeyShiftState::LEFT_CONTROL_PRESSED | KeyShiftState::RIGHT_CONTROL_PRESSED)
+    }
+
+    /// Returns true if either Alt key is held.
+    #[must_use]
+    pub fn is_alt_pressed(&self) -> bool {
+        self.shift_state()
+            .intersects(KeyShiftState::LEFT_ALT_PRESSED | KeyShiftState::RIGHT_ALT_PRESSED)
+    }
+
+    /// Returns true if either Shift key is held.
+    #[must_use]
+    pub fn is_shift_pressed(&self) -> bool {
+        self.shift_state()
+            .intersects(KeyShiftState::LEFT_SHIFT_PRESSED | KeyShiftState::RIGHT_SHIFT_PRESSED)
+    }
+}
+
+impl From<uefi_raw::protocol::console::KeyData> for KeyData {
+    fn from(data: uefi_raw::protocol::console::KeyData) -> Self {
+        Self {
+            key: data.key.into(),
+            state: data.key_state,
+        }
+    }
+}
+
+impl From<KeyData> for uefi_raw::protocol::console::KeyData {
+    fn from(data: KeyData) -> Self {
+        let key = match data.key {
+            Key::Printable(c) => InputKey {
+                scan_code: 0,
+                unicode_char: c.into(),
+            },
+            Key::Special(scan_code) => InputKey {
+                scan_code: scan_code.0,
+                unicode_char: 0,
+            },
+        };
+        Self {
+            key,
+            key_state: data.state,
+        }
+    }
+}
+
+/// Opaque handle returned by [`InputEx::register_key_notify`].
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct KeyNotifyHandle(NonNull<c_void>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::console::text::ScanCode;
+    use crate::Char16;
+    use uefi_raw::protocol::console::KeyData as RawKeyData;
+
+    #[test]
+    fn test_key_data_conversion() {
+        let data = KeyData {
+            key: Key::Printable(Char16::try_from('c').unwrap()),
+            state: KeyState {
+                key_shift_state: KeyShiftState::SHIFT_STATE_VALID
+                    | KeyShiftState::LEFT_CONTROL_PRESSED,
+                key_toggle_state: KeyToggleState::empty(),
+            },
+        };
+        let raw = RawKeyData::from(data);
+        assert_eq!(raw.key.scan_code, 0);
+        assert_eq!(raw.key.unicode_char, u16::from(b'c'));
+        assert_eq!(KeyData::from(raw), data);
+
+        let data = KeyData {
+            key: Key::Special(ScanCode::ESCAPE),
+            state: KeyState::default(),
+        };
+        let raw = RawKeyData::from(data);
+        assert_eq!(raw.key.scan_code, ScanCode::ESCAPE.0);
+        assert_eq!(raw.key.unicode_char, 0);
+        assert_eq!(KeyData::from(raw), data);
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let mut data = KeyData {
+            key: Key::Printable(Char16::try_from('c').unwrap()),
+            state: KeyState {
+                key_shift_state: KeyShiftState::LEFT_CONTROL_PRESSED
+                    | KeyShiftState::RIGHT_ALT_PRESSED,
+                key_toggle_state: KeyToggleState::empty(),
+            },
+        };
+        // Without SHIFT_STATE_VALID, the modifier bits are ignored.
+        assert_eq!(data.shift_state(), KeyShiftState::empty());
+        assert!(!data.is_control_pressed());
+
+        data.state.key_shift_state |= KeyShiftState::SHIFT_STATE_VALID;
+        assert!(data.is_control_pressed());
+        assert!(data.is_alt_pressed());
+        assert!(!data.is_shift_pressed());
+    }
+}
@@ -3,5 +3,10 @@
 mod input;
 pub use input::{Input, Key, ScanCode};
 
+mod input_ex;
+pub use input_ex::{
+    InputEx, KeyData, KeyNotifyFunction, KeyNotifyHandle, KeyShiftState, KeyState, KeyToggleState,
+};
+
 mod output;
 pub use output::{Color, Output, OutputMode};
@@ -57,6 +57,52 @@ impl Output {
         })
     }
 
+    /// Writes a Rust string to the output device, without requiring it to be
+    /// converted to a [`CStr16`] first.
+    ///
+    /// Characters that cannot be encoded in UCS-2 (characters outside the
+    /// Basic Multilingual Plane) and null characters are replaced with
+    /// `'?'`. Line feeds are converted to `"\r\n"`. Characters that the
+    /// device cannot render are skipped, as in [`output_string_lossy`].
+    ///
+    /// The string is written in chunks from a small stack buffer, so no heap
+    /// allocation is needed.
+    ///
+    /// [`output_string_lossy`]: Self::output_string_lossy
+    pub fn output_str_lossy(&mut self, string: &str) -> Result {
+        const BUF_SIZE: usize = 128;
+        // Add 1 extra character for the null terminator.
+        let mut buf = [0u16; BUF_SIZE + 1];
+        let mut i = 0;
+
+        let flush = |output: &mut Self, buf: &mut [u16], i: &mut usize| {
+            buf[*i] = 0;
+            let text = CStr16::from_u16_with_nul(&buf[..=*i]).unwrap();
+            *i = 0;
+            output.output_string_lossy(text)
+        };
+
+        for ch in string.chars() {
+            if ch == '\n' {
+                buf[i] = u16::from(b'\r');
+                i += 1;
+            }
+            buf[i] = match u16::try_from(u32::from(ch)) {
+                Ok(0) | Err(_) => u16::from(b'?'),
+                // Surrogate code points cannot occur in a `str`.
+                Ok(code) => code,
+            };
+            i += 1;
+
+            // Leave room for a "\r\n" pair.
+            if i >= BUF_SIZE - 1 {
+                flush(self, &mut buf, &mut i)?;
+            }
+        }
+
+        flush(self, &mut buf, &mut i)
+    }
+
     /// Checks if a string contains only supported characters.
     ///
     /// UEFI applications are encouraged to try to print a string even if it contains
@@ -79,6 +125,29 @@ impl Output {
         }
     }
 
+    /// Returns the supported text mode with the most character cells
+    /// (`columns * rows`), or `None` if no mode is supported.
+    ///
+    /// Modes are not guaranteed to be listed in order of size, so this checks
+    /// every mode returned by [`modes`]. If several modes have the same size,
+    /// the one with the lowest index is returned.
+    ///
+    /// [`modes`]: Self::modes
+    pub fn largest_mode(&mut self) -> Option<OutputMode> {
+        largest_mode_of(self.modes())
+    }
+
+    /// Switches to the [largest supported text mode].
+    ///
+    /// Returns [`Status::UNSUPPORTED`] if the device reports no supported
+    /// modes.
+    ///
+    /// [largest supported text mode]: Self::largest_mode
+    pub fn set_largest_mode(&mut self) -> Result {
+        let mode = self.largest_mode().ok_or(Status::UNSUPPORTED)?;
+        self.set_mode(mode)
+    }
+
     /// Returns the width (column count) and height (row count) of a text mode.
     ///
     /// Devices are required to support at least an 80x25 text mode and to
@@ -143,6 +212,74 @@ impl Output {
         unsafe { (self.0.set_cursor_position)(&mut self.0, column, row) }.to_result()
     }
 
+    /// Runs `f`, then moves the cursor back to where it was before `f` was
+    /// called. This is useful for updating text in place, such as a status
+    /// line.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::ABORTED`]: `f` changed the text mode, so the saved cursor
+    ///   position may no longer be meaningful. The cursor is left where `f`
+    ///   put it.
+    /// * Any error from [`current_mode`] or [`set_cursor_position`].
+    ///
+    /// [`current_mode`]: Self::current_mode
+    /// [`set_cursor_position`]: Self::set_cursor_position
+    pub fn with_saved_cursor<F, R>(&mut self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let mode = self.current_mode()?;
+        let (column, row) = self.cursor_position();
+
+        let ret = f(self);
+
+        if self.current_mode()?.map(|m| m.columns()) != mode.map(|m| m.columns()) {
+            return Err(Status::ABORTED.into());
+        }
+        self.set_cursor_position(column, row)?;
+        Ok(ret)
+    }
+
+    /// Overwrites `row` with spaces in the current background color, and moves
+    /// the cursor to the start of that row.
+    ///
+    /// The whole width of the row is cleared, except on the last row of the
+    /// screen: writing to its last column moves the cursor past the end of the
+    /// screen, which makes the console scroll up by one line. That column is
+    /// left untouched instead.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: there is no current text mode, or `row` is
+    ///   outside the screen's bounds.
+    pub fn clear_line(&mut self, row: usize) -> Result {
+        let mode = self.current_mode()?.ok_or(Status::UNSUPPORTED)?;
+        let columns = mode.columns();
+        let is_last_row = mode.rows().checked_sub(1) == Some(row);
+
+        self.set_cursor_position(0, row)?;
+
+        // Write the spaces in chunks from a small buffer on the stack.
+        const BUF_SIZE: usize = 64;
+        let mut buf = [u16::from(b' '); BUF_SIZE + 1];
+        let mut remaining = if is_last_row {
+            columns.saturating_sub(1)
+        } else {
+            columns
+        };
+        while remaining > 0 {
+            let len = remaining.min(BUF_SIZE);
+            buf[len] = 0;
+            let spaces = CStr16::from_u16_with_nul(&buf[..=len]).unwrap();
+            self.output_string(spaces)?;
+            buf[len] = u16::from(b' ');
+            remaining -= len;
+        }
+
+        self.set_cursor_position(0, row)
+    }
+
     /// Sets the text and background colors for the console.
     ///
     /// Note that for the foreground color you can choose any color.
@@ -274,6 +411,17 @@ impl Iterator for OutputModeIter<'_> {
     }
 }
 
+/// Returns the mode with the greatest area, preferring the earliest on ties.
+fn largest_mode_of(modes: impl Iterator<Item = OutputMode>) -> Option<OutputMode> {
+    modes.reduce(|best, mode| {
+        if mode.columns() * mode.rows() > best.columns() * best.rows() {
+            mode
+        } else {
+            best
+        }
+    })
+}
+
 /// Colors for the UEFI console.
 ///
 /// All colors can be used as foreground colors.
@@ -298,3 +446,28 @@ pub enum Color {
     Yellow,
     White,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn mode(index: usize, columns: usize, rows: usize) -> OutputMode {
+        OutputMode {
+            index,
+            dims: (columns, rows),
+        }
+    }
+
+    #[test]
+    fn test_largest_mode_of() {
+        assert_eq!(largest_mode_of([].into_iter()), None);
+
+        // The largest mode is not necessarily the last one.
+        let modes = [mode(0, 80, 25), mode(2, 100, 31), mode(3, 80, 30)];
+        assert_eq!(largest_mode_of(modes.into_iter()), Some(mode(2, 100, 31)));
+
+        // Ties go to the lowest index.
+        let modes = [mode(0, 80, 25), mode(1, 80, 50), mode(2, 160, 25)];
+        assert_eq!(largest_mode_of(modes.into_iter()), Some(mode(1, 80, 50)));
+    }
+}
@@ -17,6 +17,40 @@ pub union SystemContext {
     aarch64: *mut SystemContextAARCH64,
 }
 
+impl SystemContext {
+    /// Get the context of an x64 processor.
+    ///
+    /// The pointer is only valid for the duration of the callback, and only
+    /// if the [`DebugSupport`] protocol that invoked the callback has the
+    /// [`ProcessorArch::X86_64`] architecture. Changes made to the registers
+    /// through the pointer take effect when the callback returns.
+    ///
+    /// [`DebugSupport`]: super::DebugSupport
+    /// [`ProcessorArch::X86_64`]: super::ProcessorArch::X86_64
+    #[cfg(target_arch = "x86_64")]
+    #[must_use]
+    pub const fn x64(&self) -> *mut SystemContextX64 {
+        // Safety: all fields of the union are pointers.
+        unsafe { self.x64 }
+    }
+
+    /// Get the context of an AArch64 processor.
+    ///
+    /// The pointer is only valid for the duration of the callback, and only
+    /// if the [`DebugSupport`] protocol that invoked the callback has the
+    /// [`ProcessorArch::AARCH_64`] architecture. Changes made to the registers
+    /// through the pointer take effect when the callback returns.
+    ///
+    /// [`DebugSupport`]: super::DebugSupport
+    /// [`ProcessorArch::AARCH_64`]: super::ProcessorArch::AARCH_64
+    #[cfg(target_arch = "aarch64")]
+    #[must_use]
+    pub const fn aarch64(&self) -> *mut SystemContextAARCH64 {
+        // Safety: all fields of the union are pointers.
+        unsafe { self.aarch64 }
+    }
+}
+
 /// System context for virtual EBC processors
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
@@ -328,51 +362,52 @@ pub struct FxSaveStateIA32 {
 }
 
 /// System context for x64 processors
+#[allow(missing_docs)]
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct SystemContextX64 {
-    exception_data: u64, // additional data pushed on the stack by some types of exceptions
-    fx_save_state: FxSaveStateX64,
-    dr0: u64,
-    dr1: u64,
-    dr2: u64,
-    dr3: u64,
-    dr6: u64,
-    dr7: u64,
-    cr0: u64,
-    cr1: u64, // Noted as "Reserved" in the UEFI Specification
-    cr2: u64,
-    cr3: u64,
-    cr4: u64,
-    cr8: u64,
-    rflags: u64,
-    ldtr: u64,
-    tr: u64,
-    gdtr: [u64; 2],
-    idtr: [u64; 2],
-    rip: u64,
-    gs: u64,
-    fs: u64,
-    es: u64,
-    ds: u64,
-    cs: u64,
-    ss: u64,
-    rdi: u64,
-    rsi: u64,
-    rbp: u64,
-    rsp: u64,
-    rbx: u64,
-    rdx: u64,
-    rcx: u64,
-    rax: u64,
-    r8: u64,
-    r9: u64,
-    r10: u64,
-    r11: u64,
-    r12: u64,
-    r13: u64,
-    r14: u64,
-    r15: u64,
+    pub exception_data: u64, // additional data pushed on the stack by some types of exceptions
+    pub fx_save_state: FxSaveStateX64,
+    pub dr0: u64,
+    pub dr1: u64,
+    pub dr2: u64,
+    pub dr3: u64,
+    pub dr6: u64,
+    pub dr7: u64,
+    pub cr0: u64,
+    pub cr1: u64, // Noted as "Reserved" in the UEFI Specification
+    pub cr2: u64,
+    pub cr3: u64,
+    pub cr4: u64,
+    pub cr8: u64,
+    pub rflags: u64,
+    pub ldtr: u64,
+    pub tr: u64,
+    pub gdtr: [u64; 2],
+    pub idtr: [u64; 2],
+    pub rip: u64,
+    pub gs: u64,
+    pub fs: u64,
+    pub es: u64,
+    pub ds: u64,
+    pub cs: u64,
+    pub ss: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rbp: u64,
+    pub rsp: u64,
+    pub rbx: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rax: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
 }
 
 /// FP / MMX / XMM registers for X64
@@ -568,78 +603,79 @@ pub struct SystemContextARM {
 }
 
 /// System context for AARCH64 processors
+#[allow(missing_docs)]
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct SystemContextAARCH64 {
     // General Purpose Registers
-    x0: u64,
-    x1: u64,
-    x2: u64,
-    x3: u64,
-    x4: u64,
-    x5: u64,
-    x6: u64,
-    x7: u64,
-    x8: u64,
-    x9: u64,
-    x10: u64,
-    x11: u64,
-    x12: u64,
-    x13: u64,
-    x14: u64,
-    x15: u64,
-    x16: u64,
-    x17: u64,
-    x18: u64,
-    x19: u64,
-    x20: u64,
-    x21: u64,
-    x22: u64,
-    x23: u64,
-    x24: u64,
-    x25: u64,
-    x26: u64,
-    x27: u64,
-    x28: u64,
-    fp: u64, // x29 - Frame Pointer
-    lr: u64, // x30 - Link Register
-    sp: u64, // x31 - Stack Pointer
+    pub x0: u64,
+    pub x1: u64,
+    pub x2: u64,
+    pub x3: u64,
+    pub x4: u64,
+    pub x5: u64,
+    pub x6: u64,
+    pub x7: u64,
+    pub x8: u64,
+    pub x9: u64,
+    pub x10: u64,
+    pub x11: u64,
+    pub x12: u64,
+    pub x13: u64,
+    pub x14: u64,
+    pub x15: u64,
+    pub x16: u64,
+    pub x17: u64,
+    pub x18: u64,
+    pub x19: u64,
+    pub x20: u64,
+    pub x21: u64,
+    pub x22: u64,
+    pub x23: u64,
+    pub x24: u64,
+    pub x25: u64,
+    pub x26: u64,
+    pub x27: u64,
+    pub x28: u64,
+    pub fp: u64, // x29 - Frame Pointer
+    pub lr: u64, // x30 - Link Register
+    pub sp: u64, // x31 - Stack Pointer
     // FP/SIMD Registers
-    v0: [u64; 2],
-    v1: [u64; 2],
-    v2: [u64; 2],
-    v3: [u64; 2],
-    v4: [u64; 2],
-    v5: [u64; 2],
-    v6: [u64; 2],
-    v7: [u64; 2],
-    v8: [u64; 2],
-    v9: [u64; 2],
-    v10: [u64; 2],
-    v11: [u64; 2],
-    v12: [u64; 2],
-    v13: [u64; 2],
-    v14: [u64; 2],
-    v15: [u64; 2],
-    v16: [u64; 2],
-    v17: [u64; 2],
-    v18: [u64; 2],
-    v19: [u64; 2],
-    v20: [u64; 2],
-    v21: [u64; 2],
-    v22: [u64; 2],
-    v23: [u64; 2],
-    v24: [u64; 2],
-    v25: [u64; 2],
-    v26: [u64; 2],
-    v27: [u64; 2],
-    v28: [u64; 2],
-    v29: [u64; 2],
-    v30: [u64; 2],
-    v31: [u64; 2],
-    elr: u64,  // Exception Link Register
-    spsr: u64, // Saved Processor Status Register
-    fpsr: u64, // Floating Point Status Register
-    esr: u64,  // Exception Syndrome Register
-    far: u64,  // Fault Address Register
+    pub v0: [u64; 2],
+    pub v1: [u64; 2],
+    pub v2: [u64; 2],
+    pub v3: [u64; 2],
+    pub v4: [u64; 2],
+    pub v5: [u64; 2],
+    pub v6: [u64; 2],
+    pub v7: [u64; 2],
+    pub v8: [u64; 2],
+    pub v9: [u64; 2],
+    pub v10: [u64; 2],
+    pub v11: [u64; 2],
+    pub v12: [u64; 2],
+    pub v13: [u64; 2],
+    pub v14: [u64; 2],
+    pub v15: [u64; 2],
+    pub v16: [u64; 2],
+    pub v17: [u64; 2],
+    pub v18: [u64; 2],
+    pub v19: [u64; 2],
+    pub v20: [u64; 2],
+    pub v21: [u64; 2],
+    pub v22: [u64; 2],
+    pub v23: [u64; 2],
+    pub v24: [u64; 2],
+    pub v25: [u64; 2],
+    pub v26: [u64; 2],
+    pub v27: [u64; 2],
+    pub v28: [u64; 2],
+    pub v29: [u64; 2],
+    pub v30: [u64; 2],
+    pub v31: [u64; 2],
+    pub elr: u64,  // Exception Link Register
+    pub spsr: u64, // Saved Processor Status Register
+    pub fpsr: u64, // Floating Point Status Register
+    pub esr: u64,  // Exception Syndrome Register
+    pub far: u64,  // Fault Address Register
 }
@@ -15,7 +15,7 @@ use crate::proto::unsafe_protocol;
 use crate::{Result, Status, StatusExt};
 
 // re-export for ease of use
-pub use context::SystemContext;
+pub use context::{FxSaveStateX64, SystemContext, SystemContextAARCH64, SystemContextX64};
 pub use exception::ExceptionType;
 
 mod context;
@@ -242,8 +242,10 @@ mod tests {
     use crate::guid;
     use crate::proto::device_path::media::{PartitionFormat, PartitionSignature};
     use crate::proto::device_path::messaging::{
-        Ipv4AddressOrigin, IscsiLoginOptions, IscsiProtocol, RestServiceAccessMode, RestServiceType,
+        Ipv4AddressOrigin, Ipv6AddressOrigin, IscsiLoginOptions, IscsiProtocol,
+        RestServiceAccessMode, RestServiceType,
     };
+    use crate::proto::device_path::DevicePathNodeEnum;
     use core::slice;
 
     fn path_to_bytes(path: &DevicePath) -> &[u8] {
@@ -480,6 +482,98 @@ mod tests {
         Ok(())
     }
 
+    /// Test that the network boot nodes (MAC, IPv4, IPv6, and URI) can be
+    /// built and parsed back.
+    #[test]
+    fn test_network_nodes_round_trip() -> Result<(), BuildError> {
+        let mut mac_address = [0; 32];
+        mac_address[..6].copy_from_slice(&[0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        let ipv6_local = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+        let ipv6_gateway = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xfe];
+        // Use an odd length to check that the node length is not rounded.
+        let uri = b"http://192.168.0.1/boot.efi";
+
+        let mut v = Vec::new();
+        let path = DevicePathBuilder::with_vec(&mut v)
+            .push(&messaging::MacAddress {
+                mac_address,
+                interface_type: 0x01,
+            })?
+            .push(&messaging::Ipv4 {
+                local_ip_address: [192, 168, 0, 2],
+                remote_ip_address: [192, 168, 0, 1],
+                local_port: 68,
+                remote_port: 67,
+                protocol: 17,
+                ip_address_origin: Ipv4AddressOrigin::DHCP,
+                gateway_ip_address: [192, 168, 0, 254],
+                subnet_mask: [255, 255, 255, 0],
+            })?
+            .push(&messaging::Ipv6 {
+                local_ip_address: ipv6_local,
+                remote_ip_address: [0; 16],
+                local_port: 546,
+                remote_port: 547,
+                protocol: 17,
+                ip_address_origin: Ipv6AddressOrigin::STATEFUL_CONFIGURATION,
+                prefix_length: 64,
+                gateway_ip_address: ipv6_gateway,
+            })?
+            .push(&messaging::Uri { value: uri })?
+            .finalize()?;
+
+        let mut nodes = path.node_iter();
+
+        let node = nodes.next().unwrap();
+        assert_eq!(node.length(), 37);
+        let DevicePathNodeEnum::MessagingMacAddress(node) = node.as_enum().unwrap() else {
+            panic!("expected MAC address node");
+        };
+        assert_eq!(node.mac_address(), mac_address);
+        assert_eq!(node.interface_type(), 0x01);
+
+        let node = nodes.next().unwrap();
+        assert_eq!(node.length(), 27);
+        let DevicePathNodeEnum::MessagingIpv4(node) = node.as_enum().unwrap() else {
+            panic!("expected IPv4 node");
+        };
+        assert_eq!(node.local_ip_address(), [192, 168, 0, 2]);
+        assert_eq!(node.remote_ip_address(), [192, 168, 0, 1]);
+        assert_eq!(node.local_port(), 68);
+        assert_eq!(node.remote_port(), 67);
+        assert_eq!(node.protocol(), 17);
+        assert_eq!(node.ip_address_origin(), Ipv4AddressOrigin::DHCP);
+        assert_eq!(node.gateway_ip_address(), [192, 168, 0, 254]);
+        assert_eq!(node.subnet_mask(), [255, 255, 255, 0]);
+
+        let node = nodes.next().unwrap();
+        assert_eq!(node.length(), 60);
+        let DevicePathNodeEnum::MessagingIpv6(node) = node.as_enum().unwrap() else {
+            panic!("expected IPv6 node");
+        };
+        assert_eq!(node.local_ip_address(), ipv6_local);
+        assert_eq!(node.remote_ip_address(), [0; 16]);
+        assert_eq!(node.local_port(), 546);
+        assert_eq!(node.remote_port(), 547);
+        assert_eq!(node.protocol(), 17);
+        assert_eq!(
+            node.ip_address_origin(),
+            Ipv6AddressOrigin::STATEFUL_CONFIGURATION
+        );
+        assert_eq!(node.prefix_length(), 64);
+        assert_eq!(node.gateway_ip_address(), ipv6_gateway);
+
+        let node = nodes.next().unwrap();
+        assert_eq!(usize::from(node.length()), 4 + uri.len());
+        let DevicePathNodeEnum::MessagingUri(node) = node.as_enum().unwrap() else {
+            panic!("expected URI node");
+        };
+        assert_eq!(node.value(), uri);
+
+        assert!(nodes.next().is_none());
+        Ok(())
+    }
+
     /// This test is based on the "IPv4 configuration" example from the
     /// UEFI Specification.
     #[test]
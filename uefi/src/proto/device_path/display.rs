@@ -0,0 +1,298 @@
+//! Built-in text formatting of device paths.
+//!
+//! This is a best-effort alternative to the [`DevicePathToText`] protocol
+//! that works without any protocol or allocation, e.g. for logging in very
+//! early boot. The common node types are formatted the same way as by the
+//! EDK2 implementation of `DevicePathToText`, with hexadecimal numbers in
+//! uppercase after a lowercase `0x` (and with `DisplayOnly` and
+//! `AllowShortcuts` disabled); other nodes fall back to the generic
+//! `Path(type,subtype,data)` form, which EDK2 only uses for unknown nodes.
+//!
+//! Note that the inherent `to_string` methods of [`DevicePath`] and
+//! [`DevicePathNode`] still use the protocol. Use `format!` or `{}` to get
+//! the built-in formatting.
+//!
+//! [`DevicePathToText`]: super::text::DevicePathToText
+
+use super::messaging::{Ipv4AddressOrigin, Ipv6AddressOrigin};
+use super::{DevicePath, DevicePathNode, DevicePathNodeEnum};
+use crate::proto::device_path::media::PartitionSignature;
+use core::fmt::{self, Display, Formatter};
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+/// Formats the node like EDK2 if it is a common node type, or as
+/// `Path(type,subtype,data)` otherwise.
+impl Display for DevicePathNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.as_enum() {
+            Ok(DevicePathNodeEnum::EndInstance(_)) => f.write_str(","),
+            Ok(DevicePathNodeEnum::HardwarePci(pci)) => {
+                write!(f, "Pci(0x{:X},0x{:X})", pci.device(), pci.function())
+            }
+            Ok(DevicePathNodeEnum::AcpiAcpi(acpi)) => fmt_acpi(f, acpi.hid(), acpi.uid()),
+            Ok(DevicePathNodeEnum::MessagingMacAddress(mac)) => {
+                // Only Ethernet (and unspecified) interfaces have a known
+                // address size, otherwise the whole padded field is shown.
+                let interface_type = mac.interface_type();
+                let len = if interface_type <= 1 { 6 } else { 32 };
+                f.write_str("MAC(")?;
+                for byte in &mac.mac_address()[..len] {
+                    write!(f, "{byte:02X}")?;
+                }
+                write!(f, ",0x{interface_type:X})")
+            }
+            Ok(DevicePathNodeEnum::MessagingIpv4(ip)) => {
+                let origin = if ip.ip_address_origin() == Ipv4AddressOrigin::DHCP {
+                    "DHCP"
+                } else {
+                    "Static"
+                };
+                write!(f, "IPv4({},", Ipv4Addr::from(ip.remote_ip_address()))?;
+                fmt_ip_protocol(f, ip.protocol())?;
+                write!(
+                    f,
+                    ",{origin},{},{},{})",
+                    Ipv4Addr::from(ip.local_ip_address()),
+                    Ipv4Addr::from(ip.gateway_ip_address()),
+                    Ipv4Addr::from(ip.subnet_mask())
+                )
+            }
+            Ok(DevicePathNodeEnum::MessagingIpv6(ip)) => {
+                let origin = match ip.ip_address_origin() {
+                    Ipv6AddressOrigin::MANUAL => "Static",
+                    Ipv6AddressOrigin::STATELESS_AUTO_CONFIGURATION => "StatelessAutoConfigure",
+                    _ => "StatefulAutoConfigure",
+                };
+                write!(f, "IPv6({},", Ipv6Addr::from(ip.remote_ip_address()))?;
+                fmt_ip_protocol(f, ip.protocol())?;
+                write!(
+                    f,
+                    ",{origin},{},0x{:X},{})",
+                    Ipv6Addr::from(ip.local_ip_address()),
+                    ip.prefix_length(),
+                    Ipv6Addr::from(ip.gateway_ip_address())
+                )
+            }
+            Ok(DevicePathNodeEnum::MediaHardDrive(hd)) => {
+                write!(f, "HD({},", hd.partition_number())?;
+                match hd.partition_signature() {
+                    PartitionSignature::Mbr(sig) => {
+                        write!(f, "MBR,0x{:08X}", u32::from_le_bytes(sig))?;
+                    }
+                    PartitionSignature::Guid(guid) => {
+                        let mut hex = guid.to_ascii_hex_lower();
+                        hex.make_ascii_uppercase();
+                        write!(f, "GPT,{}", core::str::from_utf8(&hex).unwrap())?;
+                    }
+                    PartitionSignature::None => f.write_str("0,0")?,
+                    PartitionSignature::Unknown { signature_type, .. } => {
+                        write!(f, "{signature_type},0")?;
+                    }
+                }
+                write!(
+                    f,
+                    ",0x{:X},0x{:X})",
+                    hd.partition_start(),
+                    hd.partition_size()
+                )
+            }
+            Ok(DevicePathNodeEnum::MediaFilePath(file_path)) => {
+                let chars = file_path.path_name().into_iter().take_while(|c| *c != 0);
+                for c in char::decode_utf16(chars) {
+                    write!(f, "{}", c.unwrap_or(char::REPLACEMENT_CHARACTER))?;
+                }
+                Ok(())
+            }
+            _ => {
+                write!(f, "Path({},{}", self.device_type().0, self.sub_type().0)?;
+                if !self.data().is_empty() {
+                    f.write_str(",")?;
+                    for byte in self.data() {
+                        write!(f, "{byte:02X}")?;
+                    }
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// Formats the path's nodes as described for [`DevicePathNode`], separated
+/// by `/`. Instances are separated by `,`.
+impl Display for DevicePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, instance) in self.instance_iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            for (j, node) in instance.node_iter().enumerate() {
+                if j != 0 {
+                    f.write_str("/")?;
+                }
+                Display::fmt(node, f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats an ACPI node. Well-known devices get their dedicated names, and
+/// other compressed EISA IDs are expanded, e.g. `PNP0c0a`.
+fn fmt_acpi(f: &mut Formatter<'_>, hid: u32, uid: u32) -> fmt::Result {
+    // Compressed EISA ID of "PNP", stored in the low 16 bits.
+    const EISA_PNP_ID: u32 = 0x41d0;
+
+    if hid & 0xffff != EISA_PNP_ID {
+        return write!(f, "Acpi(0x{hid:08X},0x{uid:X})");
+    }
+    match hid >> 16 {
+        0x0a03 => write!(f, "PciRoot(0x{uid:X})"),
+        0x0a08 => write!(f, "PcieRoot(0x{uid:X})"),
+        0x0604 => write!(f, "Floppy(0x{uid:X})"),
+        0x0301 => write!(f, "Keyboard(0x{uid:X})"),
+        0x0501 => write!(f, "Serial(0x{uid:X})"),
+        0x0401 => write!(f, "ParallelPort(0x{uid:X})"),
+        product => write!(f, "Acpi(PNP{product:04x},0x{uid:X})"),
+    }
+}
+
+/// Formats an IP protocol number, using the names of TCP and UDP.
+fn fmt_ip_protocol(f: &mut Formatter<'_>, protocol: u16) -> fmt::Result {
+    match protocol {
+        6 => f.write_str("TCP"),
+        17 => f.write_str("UDP"),
+        _ => write!(f, "0x{protocol:X}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::device_path::build::{self, DevicePathBuilder};
+    use crate::proto::device_path::media::PartitionFormat;
+    use crate::{cstr16, guid};
+    use alloc::format;
+    use alloc::vec::Vec;
+    use uefi_raw::table::boot::MemoryType;
+
+    #[test]
+    fn test_display_disk_path() {
+        let mut v = Vec::new();
+        let path = DevicePathBuilder::with_vec(&mut v)
+            .push(&build::acpi::Acpi {
+                hid: 0x0a03_41d0,
+                uid: 0,
+            })
+            .unwrap()
+            .push(&build::hardware::Pci {
+                function: 2,
+                device: 0x1f,
+            })
+            .unwrap()
+            .push(&build::media::HardDrive {
+                partition_number: 1,
+                partition_start: 0x800,
+                partition_size: 0x10_0000,
+                partition_signature: PartitionSignature::Guid(guid!(
+                    "01234567-89ab-cdef-0123-456789abcdef"
+                )),
+                partition_format: PartitionFormat::GPT,
+            })
+            .unwrap()
+            .push(&build::media::FilePath {
+                path_name: cstr16!("\\EFI\\BOOT\\BOOTX64.EFI"),
+            })
+            .unwrap()
+            .finalize()
+            .unwrap();
+
+        assert_eq!(
+            format!("{path}"),
+            "PciRoot(0x0)/Pci(0x1F,0x2)/\
+             HD(1,GPT,01234567-89AB-CDEF-0123-456789ABCDEF,0x800,0x100000)/\
+             \\EFI\\BOOT\\BOOTX64.EFI"
+        );
+    }
+
+    #[test]
+    fn test_display_network_path() {
+        let mut mac_address = [0; 32];
+        mac_address[..6].copy_from_slice(&[0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+
+        let mut v = Vec::new();
+        let path = DevicePathBuilder::with_vec(&mut v)
+            .push(&build::messaging::MacAddress {
+                mac_address,
+                interface_type: 1,
+            })
+            .unwrap()
+            .push(&build::messaging::Ipv4 {
+                local_ip_address: [192, 168, 0, 2],
+                remote_ip_address: [192, 168, 0, 1],
+                local_port: 0,
+                remote_port: 0,
+                protocol: 6,
+                ip_address_origin: Ipv4AddressOrigin::STATIC,
+                gateway_ip_address: [0; 4],
+                subnet_mask: [0; 4],
+            })
+            .unwrap()
+            .push(&build::end::Instance)
+            .unwrap()
+            .push(&build::messaging::Ipv6 {
+                local_ip_address: Ipv6Addr::LOCALHOST.octets(),
+                remote_ip_address: Ipv6Addr::UNSPECIFIED.octets(),
+                local_port: 0,
+                remote_port: 0,
+                protocol: 17,
+                ip_address_origin: Ipv6AddressOrigin::STATELESS_AUTO_CONFIGURATION,
+                prefix_length: 64,
+                gateway_ip_address: [0; 16],
+            })
+            .unwrap()
+            .finalize()
+            .unwrap();
+
+        assert_eq!(
+            format!("{path}"),
+            "MAC(525400123456,0x1)/\
+             IPv4(192.168.0.1,TCP,Static,192.168.0.2,0.0.0.0,0.0.0.0),\
+             IPv6(::,UDP,StatelessAutoConfigure,::1,0x40,::)"
+        );
+    }
+
+    #[test]
+    fn test_display_fallback() {
+        let mut v = Vec::new();
+        let path = DevicePathBuilder::with_vec(&mut v)
+            .push(&build::acpi::Acpi {
+                hid: 0x0501_41d0,
+                uid: 1,
+            })
+            .unwrap()
+            .push(&build::acpi::Acpi {
+                hid: 0x0c0a_41d0,
+                uid: 0,
+            })
+            .unwrap()
+            .push(&build::acpi::Acpi {
+                hid: 0x1234,
+                uid: 0,
+            })
+            .unwrap()
+            .push(&build::hardware::MemoryMapped {
+                memory_type: MemoryType::LOADER_DATA,
+                start_address: 0x1000,
+                end_address: 0x1fff,
+            })
+            .unwrap()
+            .finalize()
+            .unwrap();
+
+        assert_eq!(
+            format!("{path}"),
+            "Serial(0x1)/Acpi(PNP0c0a,0x0)/Acpi(0x00001234,0x0)/\
+             Path(1,3,020000000010000000000000FF1F000000000000)"
+        );
+    }
+}
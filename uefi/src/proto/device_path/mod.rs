@@ -77,6 +77,7 @@ pub mod build;
 pub mod text;
 
 mod device_path_gen;
+mod display;
 pub use device_path_gen::{
     acpi, bios_boot_spec, end, hardware, media, messaging, DevicePathNodeEnum,
 };
@@ -468,6 +469,32 @@ impl DevicePath {
         &self.data
     }
 
+    /// Returns true if the nodes of `prefix` are the first nodes of this
+    /// path. The end-entire node of `prefix` is ignored, so this can be used
+    /// to check whether a path is under a controller's device path.
+    ///
+    /// Nodes are equal if their type, subtype, and data are equal.
+    #[must_use]
+    pub fn starts_with(&self, prefix: &Self) -> bool {
+        let mut nodes = self.node_iter();
+        prefix.node_iter().all(|node| nodes.next() == Some(node))
+    }
+
+    /// Returns true if the nodes of `suffix` are the last nodes of this
+    /// path, ignoring the end-entire nodes of both paths. For example, this
+    /// can be used to check whether a path ends with a particular file path
+    /// node.
+    ///
+    /// Nodes are equal if their type, subtype, and data are equal.
+    #[must_use]
+    pub fn ends_with(&self, suffix: &Self) -> bool {
+        let num_nodes = self.node_iter().count();
+        let num_suffix_nodes = suffix.node_iter().count();
+        num_nodes
+            .checked_sub(num_suffix_nodes)
+            .is_some_and(|skip| self.node_iter().skip(skip).eq(suffix.node_iter()))
+    }
+
     /// Returns a boxed copy of that value.
     #[cfg(feature = "alloc")]
     #[must_use]
@@ -477,6 +504,30 @@ impl DevicePath {
         unsafe { mem::transmute(data) }
     }
 
+    /// Copies the device path to the start of `buffer`, and returns a
+    /// reference to the copy.
+    ///
+    /// This is an allocation-free alternative to [`to_boxed`], e.g. for
+    /// keeping a device path around after the protocol it was read from has
+    /// been closed.
+    ///
+    /// # Errors
+    ///
+    /// The [`ByteConversionError::InvalidLength`] error will be returned
+    /// when `buffer` is smaller than the device path.
+    ///
+    /// [`to_boxed`]: Self::to_boxed
+    pub fn copy_into<'buf>(
+        &self,
+        buffer: &'buf mut [u8],
+    ) -> Result<&'buf Self, ByteConversionError> {
+        let dst = buffer
+            .get_mut(..self.data.len())
+            .ok_or(ByteConversionError::InvalidLength)?;
+        dst.copy_from_slice(&self.data);
+        <&Self>::try_from(&*dst)
+    }
+
     /// Transforms the device path to its string representation using the
     /// [`DevicePathToText`] protocol.
     #[cfg(feature = "alloc")]
@@ -739,6 +790,7 @@ fn open_text_protocol() -> Result<ScopedProtocol<DevicePathToText>, DevicePathTo
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
     use alloc::vec::Vec;
     use core::mem::{size_of, size_of_val};
 
@@ -846,6 +898,61 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    /// Create a device path from `nodes`, with an end-entire node added.
+    fn create_path(nodes: &[(u8, u8, &[u8])]) -> Vec<u8> {
+        let mut raw_data = Vec::new();
+        for (device_type, sub_type, data) in nodes {
+            add_node(&mut raw_data, *device_type, *sub_type, data);
+        }
+        add_node(
+            &mut raw_data,
+            DeviceType::END.0,
+            DeviceSubType::END_ENTIRE.0,
+            &[],
+        );
+        raw_data
+    }
+
+    #[test]
+    fn test_device_path_starts_ends_with() {
+        let full = create_path(&[(0xa0, 0xb0, &[10, 11]), (0xa1, 0xb1, &[20, 21, 22])]);
+        let full = <&DevicePath>::try_from(full.as_slice()).unwrap();
+
+        let first = create_path(&[(0xa0, 0xb0, &[10, 11])]);
+        let first = <&DevicePath>::try_from(first.as_slice()).unwrap();
+        let last = create_path(&[(0xa1, 0xb1, &[20, 21, 22])]);
+        let last = <&DevicePath>::try_from(last.as_slice()).unwrap();
+        let empty = create_path(&[]);
+        let empty = <&DevicePath>::try_from(empty.as_slice()).unwrap();
+        // Same type as `first`, but different data.
+        let other_data = create_path(&[(0xa0, 0xb0, &[10, 12])]);
+        let other_data = <&DevicePath>::try_from(other_data.as_slice()).unwrap();
+        // Same data as `first`, but different subtype.
+        let other_type = create_path(&[(0xa0, 0xb1, &[10, 11])]);
+        let other_type = <&DevicePath>::try_from(other_type.as_slice()).unwrap();
+
+        assert!(full.starts_with(full));
+        assert!(full.starts_with(first));
+        assert!(full.starts_with(empty));
+        assert!(!full.starts_with(last));
+        assert!(!full.starts_with(other_data));
+        assert!(!full.starts_with(other_type));
+        assert!(!first.starts_with(full));
+
+        assert!(full.ends_with(full));
+        assert!(full.ends_with(last));
+        assert!(full.ends_with(empty));
+        assert!(!full.ends_with(first));
+        assert!(!last.ends_with(full));
+
+        // Nodes are compared correctly when the path is not aligned.
+        let mut unaligned = vec![0];
+        unaligned.extend(full.as_bytes());
+        let unaligned = <&DevicePath>::try_from(&unaligned[1..]).unwrap();
+        assert!(unaligned.starts_with(first));
+        assert!(unaligned.ends_with(last));
+    }
+
     #[test]
     fn test_to_owned() {
         // Relevant assertion to verify the transmute is fine.
@@ -862,6 +969,31 @@ mod tests {
         assert_eq!(owned_dp_ref, dp)
     }
 
+    #[test]
+    fn test_copy_into() {
+        let raw_data = create_raw_device_path();
+        let dp = <&DevicePath>::try_from(raw_data.as_slice()).unwrap();
+
+        // Buffer of the exact size.
+        let mut buffer = [0; 36];
+        assert_eq!(dp.copy_into(&mut buffer), Ok(dp));
+        assert_eq!(buffer.as_slice(), raw_data.as_slice());
+
+        // Larger buffer, only the start is used.
+        let mut buffer = [0xff; 40];
+        let copy = dp.copy_into(&mut buffer).unwrap();
+        assert_eq!(copy, dp);
+        assert_eq!(copy.as_bytes().len(), 36);
+        assert_eq!(buffer[36..], [0xff; 4]);
+
+        // Buffer too small.
+        let mut buffer = [0; 35];
+        assert_eq!(
+            dp.copy_into(&mut buffer),
+            Err(ByteConversionError::InvalidLength)
+        );
+    }
+
     #[test]
     fn test_device_path_node_from_bytes() {
         let mut raw_data = Vec::new();
@@ -5,10 +5,15 @@
 // `EFI_STATUS`. A NULL pointer is used to indicate an error, and the
 // spec says that will only happen if the input pointer is null (which
 // can't happen here since we use references as input, not pointers), or
-// if there is insufficient memory. So we treat any NULL output as an
-// `OUT_OF_RESOURCES` error.
+// if there is insufficient memory. So we treat any NULL output from the
+// to-text functions as an `OUT_OF_RESOURCES` error.
+//
+// In practice, firmware also returns NULL from the from-text functions
+// when the text cannot be parsed as a device path. Since that is far more
+// likely than running out of memory, NULL output from those functions is
+// treated as a `NOT_FOUND` error.
 
-use crate::proto::device_path::{DevicePath, DevicePathNode};
+use crate::proto::device_path::{DevicePath, DevicePathNode, FfiDevicePath};
 use crate::proto::unsafe_protocol;
 use crate::{boot, CStr16, Char16, Result, Status};
 use core::ops::Deref;
@@ -66,6 +71,60 @@ impl Drop for PoolString {
     }
 }
 
+/// Wrapper for a [`DevicePath`] internally allocated from
+/// UEFI boot services memory.
+#[derive(Debug)]
+pub struct PoolDevicePath(NonNull<FfiDevicePath>);
+
+impl PoolDevicePath {
+    fn new(path: *const FfiDevicePath) -> Result<Self> {
+        NonNull::new(path.cast_mut())
+            .map(Self)
+            .ok_or(Status::NOT_FOUND.into())
+    }
+}
+
+impl Deref for PoolDevicePath {
+    type Target = DevicePath;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { DevicePath::from_ffi_ptr(self.0.as_ptr()) }
+    }
+}
+
+impl Drop for PoolDevicePath {
+    fn drop(&mut self) {
+        unsafe { boot::free_pool(self.0.cast()) }.expect("Failed to free pool [{addr:#?}]");
+    }
+}
+
+/// Wrapper for a [`DevicePathNode`] internally allocated from
+/// UEFI boot services memory.
+#[derive(Debug)]
+pub struct PoolDevicePathNode(NonNull<FfiDevicePath>);
+
+impl PoolDevicePathNode {
+    fn new(node: *const FfiDevicePath) -> Result<Self> {
+        NonNull::new(node.cast_mut())
+            .map(Self)
+            .ok_or(Status::NOT_FOUND.into())
+    }
+}
+
+impl Deref for PoolDevicePathNode {
+    type Target = DevicePathNode;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { DevicePathNode::from_ffi_ptr(self.0.as_ptr()) }
+    }
+}
+
+impl Drop for PoolDevicePathNode {
+    fn drop(&mut self) {
+        unsafe { boot::free_pool(self.0.cast()) }.expect("Failed to free pool [{addr:#?}]");
+    }
+}
+
 /// Device Path to Text protocol.
 ///
 /// This protocol provides common utility functions for converting device
@@ -127,7 +186,7 @@ impl DevicePathToText {
 /// device paths and device nodes.
 #[derive(Debug)]
 #[repr(transparent)]
-#[unsafe_protocol("05c99a21-c70f-4ad2-8a5f-35df3343f51e")]
+#[unsafe_protocol(DevicePathFromTextProtocol::GUID)]
 pub struct DevicePathFromText(DevicePathFromTextProtocol);
 
 impl DevicePathFromText {
@@ -137,22 +196,18 @@ impl DevicePathFromText {
     /// Conversion starts with the first character and continues until
     /// the first non-device node character.
     ///
-    /// Returns an [`OUT_OF_RESOURCES`] error if there is insufficient
-    /// memory for the conversion.
+    /// Returns a [`NOT_FOUND`] error if the text could not be converted,
+    /// either because it is not a valid device node or because there is
+    /// insufficient memory for the conversion.
     ///
-    /// [`OUT_OF_RESOURCES`]: Status::OUT_OF_RESOURCES
+    /// [`NOT_FOUND`]: Status::NOT_FOUND
     pub fn convert_text_to_device_node(
         &self,
         text_device_node: &CStr16,
-    ) -> Result<&DevicePathNode> {
-        unsafe {
-            let ptr = (self.0.convert_text_to_device_node)(text_device_node.as_ptr().cast());
-            if ptr.is_null() {
-                Err(Status::OUT_OF_RESOURCES.into())
-            } else {
-                Ok(DevicePathNode::from_ffi_ptr(ptr.cast()))
-            }
-        }
+    ) -> Result<PoolDevicePathNode> {
+        let device_node =
+            unsafe { (self.0.convert_text_to_device_node)(text_device_node.as_ptr().cast()) };
+        PoolDevicePathNode::new(device_node.cast())
     }
 
     /// Convert a text to its binary device path representation.
@@ -161,18 +216,14 @@ impl DevicePathFromText {
     /// Conversion starts with the first character and continues until
     /// the first non-device path character.
     ///
-    /// Returns an [`OUT_OF_RESOURCES`] error if there is insufficient
-    /// memory for the conversion.
+    /// Returns a [`NOT_FOUND`] error if the text could not be converted,
+    /// either because it is not a valid device path or because there is
+    /// insufficient memory for the conversion.
     ///
-    /// [`OUT_OF_RESOURCES`]: Status::OUT_OF_RESOURCES
-    pub fn convert_text_to_device_path(&self, text_device_path: &CStr16) -> Result<&DevicePath> {
-        unsafe {
-            let ptr = (self.0.convert_text_to_device_path)(text_device_path.as_ptr().cast());
-            if ptr.is_null() {
-                Err(Status::OUT_OF_RESOURCES.into())
-            } else {
-                Ok(DevicePath::from_ffi_ptr(ptr.cast()))
-            }
-        }
+    /// [`NOT_FOUND`]: Status::NOT_FOUND
+    pub fn convert_text_to_device_path(&self, text_device_path: &CStr16) -> Result<PoolDevicePath> {
+        let device_path =
+            unsafe { (self.0.convert_text_to_device_path)(text_device_path.as_ptr().cast()) };
+        PoolDevicePath::new(device_path.cast())
     }
 }
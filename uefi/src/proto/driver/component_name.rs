@@ -49,8 +49,15 @@ impl ComponentName1 {
     ///
     /// `language` must be one of the languages returned by [`supported_languages`].
     ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_LANGUAGE`]: `language` is not one of the
+    ///   supported languages.
+    /// * [`Status::UNSUPPORTED`]: the driver does not provide a name.
+    ///
     /// [`supported_languages`]: Self::supported_languages
     pub fn driver_name(&self, language: &str) -> Result<&CStr16> {
+        check_language(self.supported_languages(), language)?;
         let language = language_to_cstr(language)?;
         let mut driver_name = ptr::null();
         unsafe { (self.0.get_driver_name)(&self.0, language.as_ptr(), &mut driver_name) }
@@ -61,6 +68,13 @@ impl ComponentName1 {
     ///
     /// `language` must be one of the languages returned by [`supported_languages`].
     ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_LANGUAGE`]: `language` is not one of the
+    ///   supported languages.
+    /// * [`Status::UNSUPPORTED`]: the driver is not managing the controller,
+    ///   or does not provide a name for it.
+    ///
     /// [`supported_languages`]: Self::supported_languages
     pub fn controller_name(
         &self,
@@ -68,6 +82,7 @@ impl ComponentName1 {
         child_handle: Option<Handle>,
         language: &str,
     ) -> Result<&CStr16> {
+        check_language(self.supported_languages(), language)?;
         let language = language_to_cstr(language)?;
         let mut driver_name = ptr::null();
         unsafe {
@@ -116,8 +131,15 @@ impl ComponentName2 {
     ///
     /// `language` must be one of the languages returned by [`supported_languages`].
     ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_LANGUAGE`]: `language` is not one of the
+    ///   supported languages.
+    /// * [`Status::UNSUPPORTED`]: the driver does not provide a name.
+    ///
     /// [`supported_languages`]: Self::supported_languages
     pub fn driver_name(&self, language: &str) -> Result<&CStr16> {
+        check_language(self.supported_languages(), language)?;
         let language = language_to_cstr(language)?;
         let mut driver_name = ptr::null();
         unsafe { (self.0.get_driver_name)(&self.0, language.as_ptr(), &mut driver_name) }
@@ -128,6 +150,13 @@ impl ComponentName2 {
     ///
     /// `language` must be one of the languages returned by [`supported_languages`].
     ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_LANGUAGE`]: `language` is not one of the
+    ///   supported languages.
+    /// * [`Status::UNSUPPORTED`]: the driver is not managing the controller,
+    ///   or does not provide a name for it.
+    ///
     /// [`supported_languages`]: Self::supported_languages
     pub fn controller_name(
         &self,
@@ -135,6 +164,7 @@ impl ComponentName2 {
         child_handle: Option<Handle>,
         language: &str,
     ) -> Result<&CStr16> {
+        check_language(self.supported_languages(), language)?;
         let language = language_to_cstr(language)?;
         let mut driver_name = ptr::null();
         unsafe {
@@ -193,6 +223,12 @@ impl ComponentName {
     ///
     /// `language` must be one of the languages returned by [`supported_languages`].
     ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_LANGUAGE`]: `language` is not one of the
+    ///   supported languages.
+    /// * [`Status::UNSUPPORTED`]: the driver does not provide a name.
+    ///
     /// [`supported_languages`]: Self::supported_languages
     pub fn driver_name(&self, language: &str) -> Result<&CStr16> {
         match self {
@@ -205,6 +241,13 @@ impl ComponentName {
     ///
     /// `language` must be one of the languages returned by [`supported_languages`].
     ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_LANGUAGE`]: `language` is not one of the
+    ///   supported languages.
+    /// * [`Status::UNSUPPORTED`]: the driver is not managing the controller,
+    ///   or does not provide a name for it.
+    ///
     /// [`supported_languages`]: Self::supported_languages
     pub fn controller_name(
         &self,
@@ -329,6 +372,26 @@ impl<'a> Iterator for LanguageIter<'a> {
 /// null terminator. Round up to 64 bytes just for aesthetics.
 type LanguageCStr = [u8; 64];
 
+/// Check that `language` is one of the `supported` languages.
+///
+/// Firmware returns [`Status::UNSUPPORTED`] both for an unsupported language
+/// and for a missing name, so check the language first and report it with
+/// [`Status::INVALID_LANGUAGE`]. Language codes are compared
+/// case-insensitively, as both ISO 639-2 and RFC 4646 codes are. If the
+/// supported languages list is invalid the check is skipped and left to the
+/// firmware.
+fn check_language(
+    supported: core::result::Result<LanguageIter, LanguageError>,
+    language: &str,
+) -> Result {
+    if let Ok(mut supported) = supported {
+        if !supported.any(|lang| lang.eq_ignore_ascii_case(language)) {
+            return Err(Status::INVALID_LANGUAGE.into());
+        }
+    }
+    Ok(())
+}
+
 fn language_to_cstr(language: &str) -> Result<LanguageCStr> {
     let mut lang_cstr: LanguageCStr = [0; 64];
     // Ensure there's room for a null-terminator.
@@ -408,6 +471,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_check_language() {
+        let data = "en;fr\0";
+        assert_eq!(
+            check_language(LanguageIter::new(data.as_ptr(), V2), "fr"),
+            Ok(())
+        );
+        assert_eq!(
+            check_language(LanguageIter::new(data.as_ptr(), V2), "FR"),
+            Ok(())
+        );
+        assert_eq!(
+            check_language(LanguageIter::new(data.as_ptr(), V2), "de")
+                .unwrap_err()
+                .status(),
+            Status::INVALID_LANGUAGE
+        );
+
+        // An invalid list skips the check.
+        let data = "engæ\0";
+        assert_eq!(
+            check_language(LanguageIter::new(data.as_ptr(), V2), "de"),
+            Ok(())
+        );
+    }
+
     #[test]
     fn test_language_to_cstr() {
         let mut expected = [0; 64];
@@ -0,0 +1,223 @@
+use crate::proto::unsafe_protocol;
+use crate::Handle;
+use uefi_raw::protocol::driver::DriverBindingProtocol;
+
+#[cfg(feature = "alloc")]
+use {
+    crate::{boot, Identify, Result},
+    alloc::boxed::Box,
+    core::ffi::c_void,
+    core::ptr::NonNull,
+};
+
+#[cfg(doc)]
+use crate::Status;
+
+/// Callback that checks whether a driver supports a controller.
+///
+/// See [`DriverBindingBuilder::new`] for the safety contract of the
+/// callbacks.
+pub type SupportedFn = unsafe extern "efiapi" fn(
+    this: *const DriverBindingProtocol,
+    controller_handle: uefi_raw::Handle,
+    remaining_device_path: *const uefi_raw::protocol::device_path::DevicePathProtocol,
+) -> uefi_raw::Status;
+
+/// Callback that starts a driver on a controller.
+///
+/// See [`DriverBindingBuilder::new`] for the safety contract of the
+/// callbacks.
+pub type StartFn = SupportedFn;
+
+/// Callback that stops a driver on a controller.
+///
+/// See [`DriverBindingBuilder::new`] for the safety contract of the
+/// callbacks.
+pub type StopFn = unsafe extern "efiapi" fn(
+    this: *const DriverBindingProtocol,
+    controller_handle: uefi_raw::Handle,
+    number_of_children: usize,
+    child_handle_buffer: *const uefi_raw::Handle,
+) -> uefi_raw::Status;
+
+/// Protocol that a UEFI driver installs to be managed by the UEFI driver
+/// model.
+///
+/// Firmware uses this protocol when connecting and disconnecting
+/// controllers: it calls `supported` to find drivers for a controller, and
+/// then `start` and `stop` to bind the driver to it. Drivers register the
+/// protocol with [`DriverBindingBuilder`].
+///
+/// The corresponding C type is `EFI_DRIVER_BINDING_PROTOCOL`.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(DriverBindingProtocol::GUID)]
+pub struct DriverBinding(DriverBindingProtocol);
+
+impl DriverBinding {
+    /// Version of the driver. When several drivers support a controller,
+    /// the one with the highest version is started first.
+    #[must_use]
+    pub const fn version(&self) -> u32 {
+        self.0.version
+    }
+
+    /// Handle of the image that produced this protocol.
+    #[must_use]
+    pub fn image_handle(&self) -> Option<Handle> {
+        unsafe { Handle::from_ptr(self.0.image_handle) }
+    }
+
+    /// Handle on which this protocol is installed.
+    #[must_use]
+    pub fn driver_binding_handle(&self) -> Option<Handle> {
+        unsafe { Handle::from_ptr(self.0.driver_binding_handle) }
+    }
+
+    /// Uninstall a protocol installed with [`DriverBindingBuilder::install`]
+    /// and free its memory. This is typically called from the driver's
+    /// unload function.
+    ///
+    /// # Safety
+    ///
+    /// `binding` must have been returned by [`DriverBindingBuilder::install`]
+    /// and not uninstalled already. The driver must have been stopped on all
+    /// controllers, and there must be no remaining references to the
+    /// protocol.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the protocol is not installed on its
+    ///   driver binding handle.
+    /// * [`Status::ACCESS_DENIED`]: the protocol is still in use. The
+    ///   protocol remains installed and its memory is not freed.
+    #[cfg(feature = "alloc")]
+    pub unsafe fn uninstall(binding: NonNull<Self>) -> Result {
+        let handle = unsafe { binding.as_ref() }
+            .driver_binding_handle()
+            .expect("driver binding handle is null");
+        unsafe {
+            boot::uninstall_protocol_interface(
+                handle,
+                &Self::GUID,
+                binding.as_ptr().cast::<c_void>(),
+            )?;
+            drop(Box::from_raw(binding.as_ptr()));
+        }
+        Ok(())
+    }
+}
+
+/// Builder for installing a [`DriverBinding`] protocol.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct DriverBindingBuilder {
+    supported: SupportedFn,
+    start: StartFn,
+    stop: StopFn,
+    version: u32,
+    image_handle: Handle,
+    driver_binding_handle: Handle,
+}
+
+#[cfg(feature = "alloc")]
+impl DriverBindingBuilder {
+    /// Create a builder from the driver's callbacks.
+    ///
+    /// The version defaults to `0x10`, and both the image handle and the
+    /// driver binding handle default to [`boot::image_handle`].
+    ///
+    /// # Callback safety contract
+    ///
+    /// The callbacks are called by firmware with raw pointers, so they must
+    /// uphold the following:
+    /// * `this` points to the installed protocol. It can be converted to a
+    ///   `&DriverBinding` for as long as the protocol stays installed, but it
+    ///   must not be used to modify the protocol.
+    /// * `controller_handle` and the entries of `child_handle_buffer` are
+    ///   non-null handles; they can be converted with [`Handle::from_ptr`].
+    /// * `remaining_device_path` may be null. If non-null, it points to a
+    ///   valid device path that is only guaranteed to live for the duration
+    ///   of the call.
+    /// * `child_handle_buffer` points to `number_of_children` handles, and
+    ///   may be null if `number_of_children` is zero.
+    /// * The callbacks must not panic. Unwinding across the `efiapi` boundary
+    ///   is not possible, so a panic aborts the whole application.
+    /// * `supported` must not modify the state of the controller, and should
+    ///   return quickly since it may be called for every controller in the
+    ///   system.
+    #[must_use]
+    pub fn new(supported: SupportedFn, start: StartFn, stop: StopFn) -> Self {
+        let image_handle = boot::image_handle();
+        Self {
+            supported,
+            start,
+            stop,
+            version: 0x10,
+            image_handle,
+            driver_binding_handle: image_handle,
+        }
+    }
+
+    /// Set the version of the driver. Versions `0x0..=0xf` and
+    /// `0xffff_fff0..=0xffff_ffff` are reserved for platform and OEM drivers.
+    #[must_use]
+    pub const fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set the handle of the image that produced the protocol.
+    #[must_use]
+    pub const fn image_handle(mut self, image_handle: Handle) -> Self {
+        self.image_handle = image_handle;
+        self
+    }
+
+    /// Set the handle on which the protocol is installed. Drivers that
+    /// install several driver binding protocols need a separate handle
+    /// for each one.
+    #[must_use]
+    pub const fn driver_binding_handle(mut self, driver_binding_handle: Handle) -> Self {
+        self.driver_binding_handle = driver_binding_handle;
+        self
+    }
+
+    /// Allocate the protocol and install it on the driver binding handle.
+    ///
+    /// The protocol stays allocated until it is removed with
+    /// [`DriverBinding::uninstall`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: a driver binding protocol is already
+    ///   installed on the handle.
+    pub fn install(self) -> Result<NonNull<DriverBinding>> {
+        let binding = Box::new(DriverBinding(DriverBindingProtocol {
+            supported: self.supported,
+            start: self.start,
+            stop: self.stop,
+            version: self.version,
+            image_handle: self.image_handle.as_ptr(),
+            driver_binding_handle: self.driver_binding_handle.as_ptr(),
+        }));
+        let binding = NonNull::from(Box::leak(binding));
+
+        let result = unsafe {
+            boot::install_protocol_interface(
+                Some(self.driver_binding_handle),
+                &DriverBinding::GUID,
+                binding.as_ptr().cast::<c_void>(),
+            )
+        };
+        match result {
+            Ok(_) => Ok(binding),
+            Err(err) => {
+                // Safety: the protocol was not installed, so there are no
+                // other references to it.
+                drop(unsafe { Box::from_raw(binding.as_ptr()) });
+                Err(err)
+            }
+        }
+    }
+}
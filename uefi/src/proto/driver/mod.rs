@@ -1,5 +1,7 @@
 //! UEFI driver model protocols.
 
 mod component_name;
+mod driver_binding;
 
 pub use component_name::*;
+pub use driver_binding::*;
@@ -0,0 +1,155 @@
+//! `Hash2` protocol.
+//!
+//! The [`Hash2`] protocol computes hashes using the firmware's
+//! implementation of the hash algorithms, which may be hardware
+//! accelerated. An instance is created with the service binding protocol:
+//! open [`Hash2ServiceBinding`] and call [`Hash2ServiceBinding::create_child`]
+//! to get a new handle with the [`Hash2`] protocol installed on it.
+
+use crate::proto::unsafe_protocol;
+use crate::{Handle, Result, Status, StatusExt};
+use core::ptr;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::hash2::{Hash2Output, Hash2Protocol};
+
+pub use uefi_raw::protocol::hash2::HashAlgorithm;
+
+/// Service binding protocol for [`Hash2`].
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Hash2Protocol::SERVICE_BINDING_GUID)]
+pub struct Hash2ServiceBinding(ServiceBindingProtocol);
+
+impl Hash2ServiceBinding {
+    /// Create a new hash instance, returning the handle on which [`Hash2`]
+    /// is installed.
+    pub fn create_child(&mut self) -> Result<Handle> {
+        let mut handle = ptr::null_mut();
+        unsafe { (self.0.create_child)(&mut self.0, &mut handle) }
+            .to_result_with_val(|| unsafe { Handle::from_ptr(handle) }.unwrap())
+    }
+
+    /// Destroy a hash instance created with [`create_child`].
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, handle: Handle) -> Result {
+        unsafe { (self.0.destroy_child)(&mut self.0, handle.as_ptr()) }.to_result()
+    }
+}
+
+/// Hash2 protocol.
+///
+/// Use [`Hash2ServiceBinding`] to create an instance of this protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Hash2Protocol::GUID)]
+pub struct Hash2(Hash2Protocol);
+
+impl Hash2 {
+    /// Get the size in bytes of the hash produced by `algorithm`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the algorithm is not supported.
+    pub fn get_hash_size(&self, algorithm: HashAlgorithm) -> Result<usize> {
+        let mut hash_size = 0;
+        unsafe { (self.0.get_hash_size)(&self.0, &algorithm, &mut hash_size) }
+            .to_result_with_val(|| hash_size)
+    }
+
+    /// Hash `data` in one step, writing the hash to the start of `out`.
+    ///
+    /// Returns the size of the hash in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BUFFER_TOO_SMALL`]: `out` is smaller than the hash size of
+    ///   `algorithm`.
+    /// * [`Status::UNSUPPORTED`]: the algorithm is not supported.
+    /// * [`Status::ALREADY_STARTED`]: an incremental hash started with
+    ///   [`hash_init`] has not been finished.
+    ///
+    /// [`hash_init`]: Self::hash_init
+    pub fn hash(&mut self, algorithm: HashAlgorithm, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let hash_size = self.check_output_size(algorithm, out)?;
+
+        let mut output = Hash2Output::default();
+        unsafe {
+            (self.0.hash)(
+                &mut self.0,
+                &algorithm,
+                data.as_ptr(),
+                data.len(),
+                &mut output,
+            )
+        }
+        .to_result_with_val(|| {
+            out[..hash_size].copy_from_slice(&output.0[..hash_size]);
+            hash_size
+        })
+    }
+
+    /// Start an incremental hash with `algorithm`.
+    ///
+    /// Feed data into the hash with [`hash_update`] and get the result with
+    /// [`hash_final`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the algorithm is not supported.
+    /// * [`Status::ALREADY_STARTED`]: a previous incremental hash has not been
+    ///   finished.
+    ///
+    /// [`hash_final`]: Self::hash_final
+    /// [`hash_update`]: Self::hash_update
+    pub fn hash_init(&mut self, algorithm: HashAlgorithm) -> Result {
+        unsafe { (self.0.hash_init)(&mut self.0, &algorithm) }.to_result()
+    }
+
+    /// Add `data` to an incremental hash started with [`hash_init`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_READY`]: [`hash_init`] has not been called.
+    ///
+    /// [`hash_init`]: Self::hash_init
+    pub fn hash_update(&mut self, data: &[u8]) -> Result {
+        unsafe { (self.0.hash_update)(&mut self.0, data.as_ptr(), data.len()) }.to_result()
+    }
+
+    /// Finish an incremental hash started with [`hash_init`], writing the
+    /// hash to the start of `out`.
+    ///
+    /// `algorithm` must be the algorithm passed to [`hash_init`]; it is used
+    /// to check the size of `out`. If `out` is too small the hash is left
+    /// unfinished, so this function can be called again with a larger
+    /// buffer.
+    ///
+    /// Returns the size of the hash in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BUFFER_TOO_SMALL`]: `out` is smaller than the hash size of
+    ///   `algorithm`.
+    /// * [`Status::NOT_READY`]: [`hash_init`] has not been called.
+    ///
+    /// [`hash_init`]: Self::hash_init
+    pub fn hash_final(&mut self, algorithm: HashAlgorithm, out: &mut [u8]) -> Result<usize> {
+        let hash_size = self.check_output_size(algorithm, out)?;
+
+        let mut output = Hash2Output::default();
+        unsafe { (self.0.hash_final)(&mut self.0, &mut output) }.to_result_with_val(|| {
+            out[..hash_size].copy_from_slice(&output.0[..hash_size]);
+            hash_size
+        })
+    }
+
+    /// Get the hash size of `algorithm` and check that `out` can hold it.
+    fn check_output_size(&self, algorithm: HashAlgorithm, out: &[u8]) -> Result<usize> {
+        let hash_size = self.get_hash_size(algorithm)?;
+        if out.len() < hash_size || hash_size > size_of::<Hash2Output>() {
+            return Err(Status::BUFFER_TOO_SMALL.into());
+        }
+        Ok(hash_size)
+    }
+}
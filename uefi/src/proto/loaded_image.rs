@@ -7,6 +7,7 @@ use crate::proto::unsafe_protocol;
 use crate::util::usize_from_u32;
 use crate::{CStr16, Handle, Status};
 use core::ffi::c_void;
+use core::fmt::{self, Display, Formatter};
 use core::{mem, slice};
 use uefi_raw::protocol::loaded_image::LoadedImageProtocol;
 
@@ -17,7 +18,7 @@ use uefi_raw::protocol::loaded_image::LoadedImageProtocol;
 pub struct LoadedImage(LoadedImageProtocol);
 
 /// Errors that can be raised during parsing of the load options.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum LoadOptionsError {
     /// Load options are not set.
     NotSet,
@@ -29,6 +30,25 @@ pub enum LoadOptionsError {
     InvalidString(FromSliceWithNulError),
 }
 
+impl Display for LoadOptionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSet => write!(f, "load options are not set"),
+            Self::NotAligned => write!(f, "load options are not u16-aligned"),
+            Self::InvalidString(err) => write!(f, "load options are not a valid string: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for LoadOptionsError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidString(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 impl LoadedImage {
     /// Returns a handle to the storage device on which the image is located.
     #[must_use]
@@ -174,6 +194,19 @@ impl LoadedImage {
         self.0.load_options_size = size;
     }
 
+    /// Set the load options for the image to a null-terminated UCS-2
+    /// string, which is the format typically used for command lines. See
+    /// [`set_load_options`] for more details.
+    ///
+    /// This is a safe wrapper around [`set_load_options`]: requiring a
+    /// `'static` string ensures that the load options outlive the image.
+    ///
+    /// [`set_load_options`]: Self::set_load_options
+    pub fn set_load_options_cstr16(&mut self, options: &'static CStr16) {
+        let size = u32::try_from(options.num_bytes()).expect("load options are too large");
+        unsafe { self.set_load_options(options.as_ptr().cast(), size) }
+    }
+
     /// Returns the base address and the size in bytes of the loaded image.
     #[must_use]
     pub const fn info(&self) -> (*const c_void, u64) {
@@ -1,7 +1,8 @@
 //! Block I/O protocols.
 
 use crate::proto::unsafe_protocol;
-use crate::{Result, StatusExt};
+use crate::util::usize_from_u32;
+use crate::{Result, Status, StatusExt};
 
 pub use uefi_raw::protocol::block::{BlockIoProtocol, Lba};
 
@@ -41,13 +42,16 @@ impl BlockIO {
     /// * `uefi::Status::DEVICE_ERROR`       The device reported an error while attempting to perform the read
     ///     operation.
     /// * `uefi::Status::NO_MEDIA`           There is no media in the device.
-    /// * `uefi::Status::MEDIA_CHANGED`      The `media_id` is not for the current media.
-    /// * `uefi::Status::BAD_BUFFER_SIZE`    The buffer size parameter is not a multiple of the intrinsic block size of
-    ///     the device.
-    /// * `uefi::Status::INVALID_PARAMETER`  The read request contains LBAs that are not valid, or the buffer is not on
-    ///     proper alignment.
+    /// * `uefi::Status::MEDIA_CHANGED`      The `media_id` is not for the current media. Call [`media`] to get
+    ///     the new media ID before retrying.
+    /// * `uefi::Status::INVALID_PARAMETER`  The read request contains LBAs that are not valid, the buffer is not on
+    ///     proper alignment, or the buffer size is not a multiple of the intrinsic block size of the device. The
+    ///     buffer size is checked before calling the firmware.
+    ///
+    /// [`media`]: Self::media
     pub fn read_blocks(&self, media_id: u32, lba: Lba, buffer: &mut [u8]) -> Result {
         let buffer_size = buffer.len();
+        self.check_buffer_size(buffer_size)?;
         unsafe {
             (self.0.read_blocks)(
                 &self.0,
@@ -70,15 +74,18 @@ impl BlockIO {
     /// # Errors
     /// * `uefi::Status::WRITE_PROTECTED`       The device cannot be written to.
     /// * `uefi::Status::NO_MEDIA`              There is no media in the device.
-    /// * `uefi::Status::MEDIA_CHANGED`         The `media_id` is not for the current media.
+    /// * `uefi::Status::MEDIA_CHANGED`         The `media_id` is not for the current media. Call [`media`] to get
+    ///     the new media ID before retrying.
     /// * `uefi::Status::DEVICE_ERROR`          The device reported an error while attempting to perform the write
     ///     operation.
-    /// * `uefi::Status::BAD_BUFFER_SIZE`       The buffer size parameter is not a multiple of the intrinsic block size
-    ///     of the device.
-    /// * `uefi::Status::INVALID_PARAMETER`     The write request contains LBAs that are not valid, or the buffer is not
-    ///     on proper alignment.
+    /// * `uefi::Status::INVALID_PARAMETER`     The write request contains LBAs that are not valid, the buffer is not
+    ///     on proper alignment, or the buffer size is not a multiple of the intrinsic block size of the device. The
+    ///     buffer size is checked before calling the firmware.
+    ///
+    /// [`media`]: Self::media
     pub fn write_blocks(&mut self, media_id: u32, lba: Lba, buffer: &[u8]) -> Result {
         let buffer_size = buffer.len();
+        self.check_buffer_size(buffer_size)?;
         unsafe {
             (self.0.write_blocks)(
                 &mut self.0,
@@ -99,6 +106,18 @@ impl BlockIO {
     pub fn flush_blocks(&mut self) -> Result {
         unsafe { (self.0.flush_blocks)(&mut self.0) }.to_result()
     }
+
+    /// Check that `buffer_size` is a multiple of the media's block size.
+    fn check_buffer_size(&self, buffer_size: usize) -> Result {
+        // A block size of zero means there is no media; let the firmware
+        // report that.
+        let block_size = usize_from_u32(self.media().block_size());
+        if block_size != 0 && buffer_size % block_size != 0 {
+            Err(Status::INVALID_PARAMETER.into())
+        } else {
+            Ok(())
+        }
+    }
 }
 
 /// Media information structure
@@ -6,6 +6,16 @@ use crate::{Event, Result, Status, StatusExt};
 use core::ptr::NonNull;
 use uefi_raw::protocol::disk::{DiskIo2Protocol, DiskIoProtocol};
 
+/// Check that the byte range starting at `offset` with length `len` does not
+/// overflow a `u64`.
+fn check_range(offset: u64, len: usize) -> Result {
+    u64::try_from(len)
+        .ok()
+        .and_then(|len| offset.checked_add(len))
+        .map(|_| ())
+        .ok_or_else(|| Status::INVALID_PARAMETER.into())
+}
+
 /// The disk I/O protocol.
 ///
 /// This protocol is used to abstract the block accesses of the block I/O
@@ -27,12 +37,14 @@ impl DiskIo {
     ///
     /// # Errors:
     /// * `uefi::status::INVALID_PARAMETER` The read request contains device addresses that
-    ///                                     are not valid for the device.
+    ///                                     are not valid for the device, or `offset` plus
+    ///                                     the buffer length overflows a `u64`.
     /// * `uefi::status::DEVICE_ERROR`      The device reported an error while performing
     ///                                     the read operation.
     /// * `uefi::status::NO_MEDIA`          There is no medium in the device.
     /// * `uefi::status::MEDIA_CHANGED`     `media_id` is not for the current medium.
     pub fn read_disk(&self, media_id: u32, offset: u64, buffer: &mut [u8]) -> Result {
+        check_range(offset, buffer.len())?;
         unsafe {
             (self.0.read_disk)(
                 &self.0,
@@ -54,13 +66,15 @@ impl DiskIo {
     ///
     /// # Errors:
     /// * `uefi::status::INVALID_PARAMETER` The write request contains device addresses that
-    ///                                     are not valid for the device.
+    ///                                     are not valid for the device, or `offset` plus
+    ///                                     the buffer length overflows a `u64`.
     /// * `uefi::status::DEVICE_ERROR`      The device reported an error while performing
     ///                                     the write operation.
     /// * `uefi::status::NO_MEDIA`          There is no medium in the device.
     /// * `uefi::status::MEDIA_CHANGED`     `media_id` is not for the current medium.
     /// * `uefi::status::WRITE_PROTECTED`   The device cannot be written to.
     pub fn write_disk(&mut self, media_id: u32, offset: u64, buffer: &[u8]) -> Result {
+        check_range(offset, buffer.len())?;
         unsafe {
             (self.0.write_disk)(
                 &mut self.0,
@@ -119,7 +133,8 @@ impl DiskIo2 {
     ///
     /// # Errors:
     /// * `uefi::status::INVALID_PARAMETER` The read request contains device addresses
-    ///                                     that are not valid for the device.
+    ///                                     that are not valid for the device, or `offset`
+    ///                                     plus `len` overflows a `u64`.
     /// * `uefi::status::OUT_OF_RESOURCES`  The request could not be completed due to
     ///                                     a lack of resources.
     /// * `uefi::status::MEDIA_CHANGED`     `media_id` is not for the current medium.
@@ -134,6 +149,7 @@ impl DiskIo2 {
         len: usize,
         buffer: *mut u8,
     ) -> Result {
+        check_range(offset, len)?;
         let token = opt_nonnull_to_ptr(token);
         (self.0.read_disk_ex)(&self.0, media_id, offset, token.cast(), len, buffer.cast())
             .to_result()
@@ -155,7 +171,8 @@ impl DiskIo2 {
     ///
     /// # Errors:
     /// * `uefi::status::INVALID_PARAMETER` The write request contains device addresses
-    ///                                     that are not valid for the device.
+    ///                                     that are not valid for the device, or `offset`
+    ///                                     plus `len` overflows a `u64`.
     /// * `uefi::status::OUT_OF_RESOURCES`  The request could not be completed due to
     ///                                     a lack of resources.
     /// * `uefi::status::MEDIA_CHANGED`     `media_id` is not for the current medium.
@@ -171,6 +188,7 @@ impl DiskIo2 {
         len: usize,
         buffer: *const u8,
     ) -> Result {
+        check_range(offset, len)?;
         let token = opt_nonnull_to_ptr(token);
         (self.0.write_disk_ex)(
             &mut self.0,
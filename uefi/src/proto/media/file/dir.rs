@@ -1,7 +1,8 @@
-use super::{File, FileHandle, FileInfo, FromUefi, RegularFile};
+use super::{File, FileAttribute, FileHandle, FileInfo, FileMode, FileType, FromUefi, RegularFile};
 use crate::data_types::Align;
-use crate::Result;
+use crate::{cstr16, CStr16, Error, Result, Status};
 use core::ffi::c_void;
+use core::fmt::{self, Display, Formatter};
 #[cfg(feature = "alloc")]
 use {crate::mem::make_boxed, alloc::boxed::Box};
 #[cfg(all(feature = "unstable", feature = "alloc"))]
@@ -117,6 +118,20 @@ impl Directory {
         Ok(Some(file_info))
     }
 
+    /// Returns an iterator over the remaining directory entries, yielding an
+    /// owned copy of each entry. See [`Self::read_entry_boxed`].
+    ///
+    /// The iterator ends after the last entry has been read, or after the
+    /// first error. Use [`Self::reset_entry_readout`] to iterate over the
+    /// entries again.
+    #[cfg(feature = "alloc")]
+    pub fn entries(&mut self) -> DirectoryEntries<'_> {
+        DirectoryEntries {
+            dir: self,
+            done: false,
+        }
+    }
+
     /// Start over the process of enumerating directory entries
     ///
     /// # Errors
@@ -125,8 +140,236 @@ impl Directory {
     pub fn reset_entry_readout(&mut self) -> Result {
         self.0.set_position(0)
     }
+
+    /// Returns true if the directory has no entries other than the `.` and
+    /// `..` pseudo-entries.
+    ///
+    /// This resets the entry readout, both before and after checking.
+    ///
+    /// # Errors
+    ///
+    /// All errors come from calls to [`Self::read_entry`] and
+    /// [`Self::reset_entry_readout`].
+    pub fn is_empty(&mut self) -> Result<bool> {
+        // Large enough for the `.` and `..` entries. An entry that doesn't
+        // fit has a longer name, so it's a real entry.
+        let mut buffer = [0; 128 + 8];
+        let buffer = FileInfo::align_buf(&mut buffer).unwrap();
+
+        self.reset_entry_readout()?;
+        let is_empty = loop {
+            match self.read_entry(buffer) {
+                Ok(None) => break true,
+                Ok(Some(info)) => {
+                    let name = info.file_name();
+                    if name != cstr16!(".") && name != cstr16!("..") {
+                        break false;
+                    }
+                }
+                Err(err) if err.status() == Status::BUFFER_TOO_SMALL => break false,
+                Err(err) => return Err(err.to_err_without_payload()),
+            }
+        };
+        self.reset_entry_readout()?;
+        Ok(is_empty)
+    }
+
+    /// Opens the file `name` like [`File::open`], but first checks on a
+    /// best-effort basis that the file may be written, so that the caller
+    /// doesn't clobber a file that is in use elsewhere.
+    ///
+    /// The UEFI file protocol has no file locking or sharing modes, so this
+    /// can't reliably detect that a file is open elsewhere. Firmware file
+    /// systems such as EDK2's FAT driver allow any number of handles to the
+    /// same file, including handles that the firmware itself holds, e.g. for
+    /// the loaded image. The check only detects files that the file system
+    /// refuses to open for writing even though neither the file nor the
+    /// volume is read-only, which is the case for file systems that enforce
+    /// exclusive access. It is also racy, since the file can be opened
+    /// elsewhere right after the check.
+    ///
+    /// The check is done by opening the file in [`FileMode::ReadWrite`],
+    /// even if `mode` is [`FileMode::Read`]. The file is not modified.
+    ///
+    /// # Errors
+    ///
+    /// * [`OpenExclusiveError::WouldBlock`]: the file system refused write
+    ///   access to the file, and the file is not read-only.
+    /// * [`OpenExclusiveError::Uefi`] with [`Status::ACCESS_DENIED`]: the file
+    ///   is read-only, or its attributes could not be read to tell.
+    /// * [`OpenExclusiveError::Uefi`] with [`Status::WRITE_PROTECTED`]: the
+    ///   volume is write protected.
+    ///
+    /// Other [`OpenExclusiveError::Uefi`] errors come from [`File::open`].
+    pub fn open_exclusive(
+        &mut self,
+        name: &CStr16,
+        mode: FileMode,
+        attributes: FileAttribute,
+    ) -> core::result::Result<FileHandle, OpenExclusiveError> {
+        let handle = self
+            .open(name, mode, attributes)
+            .map_err(|err| self.sharing_error(name, err))?;
+        if mode == FileMode::Read {
+            // Closed right away, only its success matters.
+            self.open(name, FileMode::ReadWrite, FileAttribute::empty())
+                .map_err(|err| self.sharing_error(name, err))?;
+        }
+        Ok(handle)
+    }
+
+    /// Converts an error from opening `name` for writing into
+    /// [`OpenExclusiveError::WouldBlock`] if the file system denied access
+    /// although the file is not read-only.
+    fn sharing_error(&mut self, name: &CStr16, err: Error) -> OpenExclusiveError {
+        if err.status() != Status::ACCESS_DENIED {
+            return err.into();
+        }
+
+        // Large enough for the info of a file with a name of up to 255
+        // characters, the longest name FAT supports.
+        let mut buffer = [0; 80 + 2 * 256 + 8];
+        let buffer = FileInfo::align_buf(&mut buffer).unwrap();
+        let is_read_only = self
+            .open(name, FileMode::Read, FileAttribute::empty())
+            .ok()
+            .and_then(|mut file| {
+                let info = file.get_info::<FileInfo>(buffer).ok()?;
+                Some(info.attribute().contains(FileAttribute::READ_ONLY))
+            });
+        match is_read_only {
+            Some(false) => OpenExclusiveError::WouldBlock,
+            _ => err.into(),
+        }
+    }
+
+    /// Removes the empty directory `name` from this directory.
+    ///
+    /// # Errors
+    ///
+    /// * [`RemoveDirError::NotEmpty`]: the directory is not empty.
+    /// * [`RemoveDirError::NotADirectory`]: `name` is not a directory.
+    /// * [`RemoveDirError::Uefi`] with [`Status::WARN_DELETE_FAILURE`]: the
+    ///   directory could not be deleted.
+    ///
+    /// Other [`RemoveDirError::Uefi`] errors come from opening the directory
+    /// with [`File::open`] and reading its entries.
+    pub fn remove_dir(&mut self, name: &CStr16) -> core::result::Result<(), RemoveDirError> {
+        let handle = self.open(name, FileMode::ReadWrite, FileAttribute::empty())?;
+        match handle.into_type()? {
+            FileType::Dir(mut dir) => {
+                if dir.is_empty()? {
+                    Ok(dir.delete()?)
+                } else {
+                    Err(RemoveDirError::NotEmpty)
+                }
+            }
+            FileType::Regular(_) => Err(RemoveDirError::NotADirectory),
+        }
+    }
+}
+
+/// Error returned by [`Directory::open_exclusive`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum OpenExclusiveError {
+    /// The file system refused write access to a file that is not
+    /// read-only, which indicates that the file is in use elsewhere.
+    WouldBlock,
+
+    /// Opening the file failed for another reason.
+    Uefi(Error),
 }
 
+impl Display for OpenExclusiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WouldBlock => f.write_str("file is in use"),
+            Self::Uefi(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl core::error::Error for OpenExclusiveError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Uefi(err) => Some(err),
+            Self::WouldBlock => None,
+        }
+    }
+}
+
+impl From<Error> for OpenExclusiveError {
+    fn from(err: Error) -> Self {
+        Self::Uefi(err)
+    }
+}
+
+/// Error returned by [`Directory::remove_dir`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum RemoveDirError {
+    /// The directory contains entries other than `.` and `..`.
+    NotEmpty,
+
+    /// The file to remove is not a directory.
+    NotADirectory,
+
+    /// Opening, reading, or deleting the directory failed.
+    Uefi(Error),
+}
+
+impl Display for RemoveDirError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEmpty => f.write_str("directory is not empty"),
+            Self::NotADirectory => f.write_str("not a directory"),
+            Self::Uefi(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl core::error::Error for RemoveDirError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Uefi(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for RemoveDirError {
+    fn from(err: Error) -> Self {
+        Self::Uefi(err)
+    }
+}
+
+/// Iterator over the entries of a [`Directory`], created with
+/// [`Directory::entries`].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct DirectoryEntries<'a> {
+    dir: &'a mut Directory,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for DirectoryEntries<'_> {
+    type Item = Result<Box<FileInfo>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let entry = self.dir.read_entry_boxed().transpose();
+        if !matches!(entry, Some(Ok(_))) {
+            self.done = true;
+        }
+        entry
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::iter::FusedIterator for DirectoryEntries<'_> {}
+
 impl File for Directory {
     #[inline]
     fn handle(&mut self) -> &mut FileHandle {
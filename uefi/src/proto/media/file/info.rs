@@ -1,7 +1,7 @@
 use super::FileAttribute;
 use crate::data_types::Align;
 use crate::runtime::Time;
-use crate::{CStr16, Char16, Guid, Identify};
+use crate::{cstr16, CStr16, Char16, Guid, Identify};
 use core::ffi::c_void;
 use core::fmt::{self, Display, Formatter};
 use core::ptr;
@@ -39,6 +39,13 @@ trait InfoInternal: Align + ptr_meta::Pointee<Metadata = usize> {
     /// the struct.
     fn name_offset() -> usize;
 
+    /// Size in bytes of the struct with the given name, including the
+    /// trailing null character and padding up to the struct's alignment.
+    fn info_size(name: &CStr16) -> usize {
+        let name_size = size_of_val(name.as_slice_with_nul());
+        Self::round_up_to_alignment(Self::name_offset() + name_size)
+    }
+
     /// Get a mutable pointer to the name slice at the end of the
     /// struct.
     unsafe fn name_ptr(ptr: *mut u8) -> *mut Char16 {
@@ -69,9 +76,7 @@ trait InfoInternal: Align + ptr_meta::Pointee<Metadata = usize> {
     {
         // Calculate the final size of the struct.
         let name_length_ucs2 = name.as_slice_with_nul().len();
-        let name_size = size_of_val(name.as_slice_with_nul());
-        let info_size = Self::name_offset() + name_size;
-        let info_size = Self::round_up_to_alignment(info_size);
+        let info_size = Self::info_size(name);
 
         // Make sure that the storage is properly aligned
         let storage = Self::align_buf(storage)
@@ -203,6 +208,40 @@ impl FileInfo {
         }
     }
 
+    /// Create a [`FileInfoBuilder`] with all fields zeroed and an empty
+    /// file name.
+    #[must_use]
+    pub fn builder() -> FileInfoBuilder<'static> {
+        FileInfoBuilder::default()
+    }
+
+    /// Create a [`FileInfoBuilder`] initialized with the fields of `self`.
+    ///
+    /// This is useful to change a single field with `set_info()`, e.g. the
+    /// file name.
+    #[must_use]
+    pub fn to_builder(&self) -> FileInfoBuilder<'_> {
+        FileInfoBuilder {
+            file_size: self.file_size,
+            physical_size: self.physical_size,
+            create_time: self.create_time,
+            last_access_time: self.last_access_time,
+            modification_time: self.modification_time,
+            attribute: self.attribute,
+            file_name: self.file_name(),
+        }
+    }
+
+    /// Size in bytes of a `FileInfo` with the given file name, including the
+    /// trailing null character and padding.
+    ///
+    /// The storage passed to [`FileInfo::new`] must be at least this large
+    /// after aligning it.
+    #[must_use]
+    pub fn required_size(file_name: &CStr16) -> usize {
+        Self::info_size(file_name)
+    }
+
     /// File size (number of bytes stored in the file)
     #[must_use]
     pub const fn file_size(&self) -> u64 {
@@ -258,6 +297,123 @@ impl FileInfo {
     }
 }
 
+/// Builder for [`FileInfo`], created with [`FileInfo::builder`] or
+/// [`FileInfo::to_builder`].
+///
+/// Timestamps default to all zeros, which causes `set_info()` to leave them
+/// unchanged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileInfoBuilder<'a> {
+    file_size: u64,
+    physical_size: u64,
+    create_time: Time,
+    last_access_time: Time,
+    modification_time: Time,
+    attribute: FileAttribute,
+    file_name: &'a CStr16,
+}
+
+impl Default for FileInfoBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            file_size: 0,
+            physical_size: 0,
+            create_time: Time::invalid(),
+            last_access_time: Time::invalid(),
+            modification_time: Time::invalid(),
+            attribute: FileAttribute::empty(),
+            file_name: cstr16!(""),
+        }
+    }
+}
+
+impl<'a> FileInfoBuilder<'a> {
+    /// Set the file size.
+    #[must_use]
+    pub const fn file_size(mut self, file_size: u64) -> Self {
+        self.file_size = file_size;
+        self
+    }
+
+    /// Set the physical size.
+    #[must_use]
+    pub const fn physical_size(mut self, physical_size: u64) -> Self {
+        self.physical_size = physical_size;
+        self
+    }
+
+    /// Set the creation time.
+    #[must_use]
+    pub const fn create_time(mut self, time: Time) -> Self {
+        self.create_time = time;
+        self
+    }
+
+    /// Set the last access time.
+    #[must_use]
+    pub const fn last_access_time(mut self, time: Time) -> Self {
+        self.last_access_time = time;
+        self
+    }
+
+    /// Set the modification time.
+    #[must_use]
+    pub const fn modification_time(mut self, time: Time) -> Self {
+        self.modification_time = time;
+        self
+    }
+
+    /// Set the attribute bits.
+    #[must_use]
+    pub const fn attribute(mut self, attribute: FileAttribute) -> Self {
+        self.attribute = attribute;
+        self
+    }
+
+    /// Set the file name.
+    #[must_use]
+    pub const fn file_name<'b>(self, file_name: &'b CStr16) -> FileInfoBuilder<'b> {
+        FileInfoBuilder {
+            file_size: self.file_size,
+            physical_size: self.physical_size,
+            create_time: self.create_time,
+            last_access_time: self.last_access_time,
+            modification_time: self.modification_time,
+            attribute: self.attribute,
+            file_name,
+        }
+    }
+
+    /// Size in bytes that the [`FileInfo`] will occupy, see
+    /// [`FileInfo::required_size`].
+    #[must_use]
+    pub fn required_size(&self) -> usize {
+        FileInfo::required_size(self.file_name)
+    }
+
+    /// Create the [`FileInfo`] in `storage`, see [`FileInfo::new`].
+    ///
+    /// # Errors
+    ///
+    /// [`FileInfoCreationError::InsufficientStorage`] with the required size
+    /// in bytes if `storage` is too small after aligning it.
+    pub fn build<'buf>(
+        &self,
+        storage: &'buf mut [u8],
+    ) -> core::result::Result<&'buf mut FileInfo, FileInfoCreationError> {
+        FileInfo::new(
+            storage,
+            self.file_size,
+            self.physical_size,
+            self.create_time,
+            self.last_access_time,
+            self.modification_time,
+            self.attribute,
+            self.file_name,
+        )
+    }
+}
+
 impl Align for FileInfo {
     fn alignment() -> usize {
         8
@@ -491,6 +647,48 @@ mod tests {
         assert_eq!(info.file_name(), name);
     }
 
+    #[test]
+    fn test_file_info_builder() {
+        let name = CString16::try_from("test_name").unwrap();
+        let builder = FileInfo::builder()
+            .file_size(123)
+            .attribute(FileAttribute::READ_ONLY)
+            .file_name(&name);
+
+        // Header size of 80 bytes plus 20 bytes for the name, rounded up to
+        // the alignment of 8.
+        assert_eq!(builder.required_size(), 104);
+        assert_eq!(FileInfo::required_size(cstr16!("")), 88);
+
+        // Too small, even when correctly aligned.
+        let mut storage = vec![0u64; 12];
+        let storage_bytes =
+            unsafe { core::slice::from_raw_parts_mut(storage.as_mut_ptr().cast::<u8>(), 96) };
+        assert_eq!(
+            builder.build(storage_bytes),
+            Err(FileInfoCreationError::InsufficientStorage(104))
+        );
+
+        let mut storage = vec![0; 128];
+        let info = builder.build(&mut storage).unwrap();
+        assert_eq!(info.size, 104);
+        assert_eq!(info.file_size(), 123);
+        assert_eq!(info.physical_size(), 0);
+        assert_eq!(info.create_time(), &Time::invalid());
+        assert_eq!(info.attribute(), FileAttribute::READ_ONLY);
+        assert_eq!(info.file_name(), &*name);
+
+        // Round trip through `to_builder`, replacing only the name.
+        let new_name = CString16::try_from("a_longer_new_name").unwrap();
+        let builder = info.to_builder().file_name(&new_name);
+        let mut storage = vec![0; 128];
+        let renamed = builder.build(&mut storage).unwrap();
+        assert_eq!(renamed.size, 120);
+        assert_eq!(renamed.file_size(), 123);
+        assert_eq!(renamed.attribute(), FileAttribute::READ_ONLY);
+        assert_eq!(renamed.file_name(), &*new_name);
+    }
+
     #[test]
     fn test_file_system_info() {
         let mut storage = vec![0; 128];
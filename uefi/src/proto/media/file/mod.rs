@@ -20,14 +20,16 @@ use uefi_raw::protocol::file_system::FileProtocolV1;
 use {alloc::alloc::Global, core::alloc::Allocator};
 
 #[cfg(feature = "alloc")]
-use {crate::mem::make_boxed, alloc::boxed::Box};
+use {crate::data_types::Align, crate::mem::make_boxed, alloc::boxed::Box, alloc::vec};
 
-pub use dir::Directory;
+#[cfg(feature = "alloc")]
+pub use dir::DirectoryEntries;
+pub use dir::{Directory, OpenExclusiveError, RemoveDirError};
 pub use info::{
-    FileInfo, FileInfoCreationError, FileProtocolInfo, FileSystemInfo, FileSystemVolumeLabel,
-    FromUefi,
+    FileInfo, FileInfoBuilder, FileInfoCreationError, FileProtocolInfo, FileSystemInfo,
+    FileSystemVolumeLabel, FromUefi,
 };
-pub use regular::RegularFile;
+pub use regular::{RegularFile, SeekFrom};
 pub use uefi_raw::protocol::file_system::FileAttribute;
 
 /// Common interface to `FileHandle`, `RegularFile`, and `Directory`.
@@ -190,6 +192,36 @@ pub trait File: Sized {
         unsafe { (self.imp().flush)(self.imp()) }.to_result()
     }
 
+    /// Changes the name of the file.
+    ///
+    /// This reads the current [`FileInfo`], replaces the file name, and
+    /// writes it back with [`set_info`]. How `new_name` is interpreted is up
+    /// to the file system driver. The FAT driver, for example, treats a name
+    /// without a leading `\` as relative to the directory containing the
+    /// file, and a name with a leading `\` as relative to the root.
+    ///
+    /// [`set_info`]: Self::set_info
+    ///
+    /// # Errors
+    ///
+    /// See [`set_info`]. In particular:
+    ///
+    /// * [`uefi::Status::ACCESS_DENIED`]: a file named `new_name` already
+    ///   exists, or the file is read-only.
+    /// * [`uefi::Status::WRITE_PROTECTED`]: the media is read-only.
+    #[cfg(feature = "alloc")]
+    fn rename(&mut self, new_name: &CStr16) -> Result {
+        let info = self.get_boxed_info::<FileInfo>()?;
+        let builder = info.to_builder().file_name(new_name);
+
+        // Add enough space to align the buffer.
+        let mut buf = vec![0; builder.required_size() + FileInfo::alignment() - 1];
+        let new_info = builder
+            .build(&mut buf)
+            .expect("buffer is large enough for the new FileInfo");
+        self.set_info(new_info)
+    }
+
     /// Read the dynamically allocated info for a file.
     #[cfg(feature = "alloc")]
     fn get_boxed_info<Info: FileProtocolInfo + ?Sized + Debug>(&mut self) -> Result<Box<Info>> {
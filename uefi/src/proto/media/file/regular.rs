@@ -1,5 +1,7 @@
 use super::{File, FileHandle, FileInternal};
 use crate::{Error, Result, Status, StatusExt};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// A `FileHandle` that is also a regular (data) file.
 ///
@@ -101,6 +103,41 @@ impl RegularFile {
             .to_result_with_err(|_| buffer_size)
     }
 
+    /// Read all data from the current position to the end of the file,
+    /// appending it to `buffer`. The buffer is grown as needed.
+    ///
+    /// Returns the number of bytes that were read. On error, `buffer` keeps
+    /// the data that was read before the error occurred.
+    ///
+    /// # Errors
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: Self::read
+    #[cfg(feature = "alloc")]
+    pub fn read_to_end(&mut self, buffer: &mut Vec<u8>) -> Result<usize> {
+        read_to_end_with(buffer, |buf| self.read(buf))
+    }
+
+    /// Write all of `buffer` to the file, increment the file pointer.
+    ///
+    /// Unlike [`write`], this keeps writing if the firmware writes only part
+    /// of the buffer without reporting an error. If an error occurs, returns
+    /// the total number of bytes that were written.
+    ///
+    /// # Errors
+    ///
+    /// See [`write`] for the possible errors. Additionally,
+    /// [`Status::DEVICE_ERROR`] is returned if the firmware reports success
+    /// without writing anything.
+    ///
+    /// [`write`]: Self::write
+    pub fn write_all(&mut self, buffer: &[u8]) -> Result<(), usize> {
+        write_all_with(buffer, |buf, buf_size| unsafe {
+            (self.imp().write)(self.imp(), buf_size, buf.cast())
+        })
+    }
+
     /// Get the file's current position
     ///
     /// # Errors
@@ -131,6 +168,68 @@ impl RegularFile {
     pub fn set_position(&mut self, position: u64) -> Result {
         unsafe { (self.imp().set_position)(self.imp(), position) }.to_result()
     }
+
+    /// Seek to an offset relative to the start, end, or current position of
+    /// the file, like [`std::io::Seek::seek`]. Returns the new position,
+    /// relative to the start of the file.
+    ///
+    /// Seeking past the end of the file is allowed, and the next write grows
+    /// the file. Reads at the end of the file return zero bytes, but per the
+    /// specification reads beyond it fail with
+    /// [`uefi::Status::DEVICE_ERROR`].
+    ///
+    /// # Errors
+    ///
+    /// * [`uefi::Status::INVALID_PARAMETER`]: the new position would be
+    ///   before the start of the file, or is not representable. The position
+    ///   is left unchanged.
+    /// * [`uefi::Status::DEVICE_ERROR`]: the position could not be read or
+    ///   set.
+    ///
+    /// [`std::io::Seek::seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html#tymethod.seek
+    pub fn seek(&mut self, from: SeekFrom) -> Result<u64> {
+        let position = match from {
+            SeekFrom::Start(position) => position,
+            SeekFrom::Current(offset) => offset_position(self.get_position()?, offset)?,
+            SeekFrom::End(offset) => {
+                // Seeking to the special end-of-file position gives the file
+                // size without having to read the file info.
+                let current = self.get_position()?;
+                self.set_position(Self::END_OF_FILE)?;
+                let size = self.get_position()?;
+                match offset_position(size, offset) {
+                    Ok(position) => position,
+                    Err(err) => {
+                        self.set_position(current)?;
+                        return Err(err);
+                    }
+                }
+            }
+        };
+        if position == Self::END_OF_FILE {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        self.set_position(position)?;
+        Ok(position)
+    }
+}
+
+/// Position to seek to with [`RegularFile::seek`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SeekFrom {
+    /// Absolute position, in bytes from the start of the file.
+    Start(u64),
+    /// Offset in bytes from the end of the file.
+    End(i64),
+    /// Offset in bytes from the current position.
+    Current(i64),
+}
+
+/// Apply `offset` to `base`, returning [`Status::INVALID_PARAMETER`] if the
+/// result is negative or overflows.
+fn offset_position(base: u64, offset: i64) -> Result<u64> {
+    base.checked_add_signed(offset)
+        .ok_or_else(|| Status::INVALID_PARAMETER.into())
 }
 
 impl File for RegularFile {
@@ -186,6 +285,68 @@ where
     Ok(total_read_size)
 }
 
+/// Append data to `buffer` by calling `read` until it returns zero bytes.
+///
+/// This separate function exists for easier unit testing.
+#[cfg(feature = "alloc")]
+fn read_to_end_with<F>(buffer: &mut Vec<u8>, mut read: F) -> Result<usize>
+where
+    F: FnMut(&mut [u8]) -> Result<usize>,
+{
+    // Size of the first read if `buffer` is empty. Later reads double the
+    // size of the buffer.
+    const MIN_READ_SIZE: usize = 4096;
+
+    let start_len = buffer.len();
+    loop {
+        let len = buffer.len();
+        buffer.resize(len + len.max(MIN_READ_SIZE), 0);
+        match read(&mut buffer[len..]) {
+            Ok(0) => {
+                buffer.truncate(len);
+                return Ok(len - start_len);
+            }
+            Ok(read_size) => buffer.truncate(len + read_size),
+            Err(err) => {
+                buffer.truncate(len);
+                return Err(err);
+            }
+        }
+    }
+}
+
+/// Write all of `buffer` by calling `write` until every byte has been
+/// written. Writing is done by calling `write`, which takes a pointer to a
+/// byte buffer and the buffer's size, and updates the size to the number
+/// of bytes written.
+///
+/// This separate function exists for easier unit testing.
+fn write_all_with<F>(buffer: &[u8], mut write: F) -> Result<(), usize>
+where
+    F: FnMut(*const u8, &mut usize) -> Status,
+{
+    let mut total_written_size = 0;
+
+    while total_written_size < buffer.len() {
+        let remaining = &buffer[total_written_size..];
+        let mut written_size = remaining.len();
+        let status = write(remaining.as_ptr(), &mut written_size);
+
+        // Guard against firmware reporting more than was requested.
+        total_written_size += written_size.min(remaining.len());
+
+        if status.is_error() || status.is_warning() {
+            return Err(Error::new(status, total_written_size));
+        }
+        if written_size == 0 {
+            // No progress, stop rather than looping forever.
+            return Err(Error::new(Status::DEVICE_ERROR, total_written_size));
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,6 +376,21 @@ mod tests {
             Status::SUCCESS
         }
 
+        /// Write at most `max_write_size` bytes at a time, to emulate
+        /// firmware that performs short writes.
+        fn write(
+            &self,
+            buffer: *const u8,
+            buffer_size: &mut usize,
+            max_write_size: usize,
+        ) -> Status {
+            let size_to_write = (*buffer_size).min(max_write_size);
+            let data = unsafe { core::slice::from_raw_parts(buffer, size_to_write) };
+            self.data.borrow_mut().extend_from_slice(data);
+            *buffer_size = size_to_write;
+            Status::SUCCESS
+        }
+
         fn reset(&self) {
             *self.data.borrow_mut() = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
             *self.offset.borrow_mut() = 0;
@@ -273,4 +449,110 @@ mod tests {
         assert_eq!(read_chunked(&mut buffer, 10, read), Ok(0));
         assert_eq!(buffer, [0; 10]);
     }
+
+    /// Test reading a whole file into a growing buffer.
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_file_read_to_end() {
+        let file = TestFile::default();
+        let read = |buf: &mut [u8]| read_chunked(buf, 3, |p, s| file.read(p, s));
+
+        // Data is appended to the existing contents of the buffer.
+        file.reset();
+        let mut buffer = vec![0xff];
+        assert_eq!(read_to_end_with(&mut buffer, read), Ok(10));
+        assert_eq!(buffer, [0xff, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        // Empty file.
+        file.reset();
+        file.data.borrow_mut().clear();
+        let mut buffer = Vec::new();
+        assert_eq!(read_to_end_with(&mut buffer, read), Ok(0));
+        assert!(buffer.is_empty());
+
+        // File bigger than the initial read size.
+        file.reset();
+        *file.data.borrow_mut() = (0..10000).map(|i| i as u8).collect();
+        let mut buffer = Vec::new();
+        assert_eq!(read_to_end_with(&mut buffer, read), Ok(10000));
+        assert_eq!(buffer, *file.data.borrow());
+
+        // Errors keep the data read so far.
+        let mut calls = 0;
+        let mut buffer = Vec::new();
+        let result = read_to_end_with(&mut buffer, |buf| {
+            calls += 1;
+            if calls == 1 {
+                buf[..2].copy_from_slice(&[7, 8]);
+                Ok(2)
+            } else {
+                Err(Status::DEVICE_ERROR.into())
+            }
+        });
+        assert_eq!(result, Err(Status::DEVICE_ERROR.into()));
+        assert_eq!(buffer, [7, 8]);
+    }
+
+    #[test]
+    fn test_offset_position() {
+        assert_eq!(offset_position(10, 0), Ok(10));
+        assert_eq!(offset_position(10, 5), Ok(15));
+        assert_eq!(offset_position(10, -10), Ok(0));
+        assert_eq!(
+            offset_position(10, -11),
+            Err(Status::INVALID_PARAMETER.into())
+        );
+        assert_eq!(
+            offset_position(u64::MAX, 1),
+            Err(Status::INVALID_PARAMETER.into())
+        );
+    }
+
+    /// Test writing a whole buffer with short writes.
+    #[test]
+    fn test_file_write_all() {
+        let file = TestFile::default();
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+        // Writes that complete in one call.
+        assert_eq!(
+            write_all_with(&data, |buf, buf_size| file.write(buf, buf_size, 100)),
+            Ok(())
+        );
+        assert_eq!(*file.data.borrow(), data);
+
+        // Short writes.
+        file.data.borrow_mut().clear();
+        assert_eq!(
+            write_all_with(&data, |buf, buf_size| file.write(buf, buf_size, 3)),
+            Ok(())
+        );
+        assert_eq!(*file.data.borrow(), data);
+
+        // Empty buffer.
+        assert_eq!(write_all_with(&[], |_, _| unreachable!()), Ok(()));
+
+        // An error reports the number of bytes written so far.
+        file.data.borrow_mut().clear();
+        let mut calls = 0;
+        let result = write_all_with(&data, |buf, buf_size| {
+            calls += 1;
+            if calls == 1 {
+                file.write(buf, buf_size, 4)
+            } else {
+                *buf_size = 1;
+                Status::VOLUME_FULL
+            }
+        });
+        assert_eq!(result, Err(Error::new(Status::VOLUME_FULL, 5)));
+
+        // Success without progress is an error.
+        assert_eq!(
+            write_all_with(&data, |_, buf_size| {
+                *buf_size = 0;
+                Status::SUCCESS
+            }),
+            Err(Error::new(Status::DEVICE_ERROR, 0))
+        );
+    }
 }
@@ -28,6 +28,12 @@ pub struct SimpleFileSystem(SimpleFileSystemProtocol);
 impl SimpleFileSystem {
     /// Open the root directory on a volume.
     ///
+    /// Each call returns a new handle to the root directory, which is
+    /// closed when the [`Directory`] is dropped. A single handle can be used
+    /// to open any number of files, so there is no need to call this
+    /// function again unless the media changes. The [`uefi::fs::FileSystem`]
+    /// API opens a fresh root directory for each operation.
+    ///
     /// # Errors
     ///
     /// See section `EFI_SIMPLE_FILE_SYSTEM_PROTOCOL.OpenVolume()` in the UEFI Specification
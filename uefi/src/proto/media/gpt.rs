@@ -0,0 +1,425 @@
+//! GUID Partition Table (GPT) parsing.
+//!
+//! This module reads the primary GPT header and partition entry array directly
+//! through the [`DiskIo`] protocol, validating the signature and CRC32
+//! checksums. This allows partitions to be enumerated before any filesystem or
+//! partition driver has bound to the disk.
+//!
+//! Only the primary header is read; the backup header at the end of the disk
+//! is not checked.
+
+use super::disk::DiskIo;
+use crate::{boot, Guid, Result, Status};
+use core::mem;
+
+#[cfg(feature = "alloc")]
+use {
+    super::partition::{GptPartitionEntry, GptPartitionType},
+    alloc::vec,
+    alloc::vec::Vec,
+};
+
+/// The size in bytes of the header fields defined by the UEFI specification.
+const HEADER_SIZE: usize = 92;
+
+/// Offset of [`GptHeader::header_crc32`] within the header.
+const HEADER_CRC32_OFFSET: usize = 16;
+
+/// The size of the buffer used to read the header. The header must fit in the
+/// first 512 bytes of its block.
+const HEADER_BUF_SIZE: usize = 512;
+
+/// The minimum size of a partition entry.
+const MIN_ENTRY_SIZE: u32 = 128;
+
+/// The maximum size of a partition entry array that is read. The
+/// specification requires space for at least 128 entries of 128 bytes; this
+/// allows 256 times that, far more than any real disk uses.
+#[cfg(feature = "alloc")]
+const MAX_ENTRY_ARRAY_SIZE: u64 = 4 * 1024 * 1024;
+
+/// GPT header, stored at LBA 1 of the disk.
+#[repr(C)]
+#[repr(packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GptHeader {
+    /// Must be [`GptHeader::SIGNATURE`].
+    pub signature: [u8; 8],
+
+    /// Revision of the GPT header format.
+    pub revision: u32,
+
+    /// Size in bytes of the header. The CRC32 covers this many bytes.
+    pub header_size: u32,
+
+    /// CRC32 of the header, computed with this field set to zero.
+    pub header_crc32: u32,
+
+    /// Reserved, must be zero.
+    pub reserved: u32,
+
+    /// LBA that contains this header.
+    pub my_lba: u64,
+
+    /// LBA of the alternate (backup) header.
+    pub alternate_lba: u64,
+
+    /// First LBA that may be used by a partition.
+    pub first_usable_lba: u64,
+
+    /// Last LBA that may be used by a partition.
+    pub last_usable_lba: u64,
+
+    /// GUID that uniquely identifies the disk.
+    pub disk_guid: Guid,
+
+    /// Starting LBA of the partition entry array.
+    pub partition_entry_lba: u64,
+
+    /// Number of entries in the partition entry array.
+    pub number_of_partition_entries: u32,
+
+    /// Size in bytes of each partition entry.
+    pub size_of_partition_entry: u32,
+
+    /// CRC32 of the partition entry array.
+    pub partition_entry_array_crc32: u32,
+}
+
+impl GptHeader {
+    /// Signature of a GPT header.
+    pub const SIGNATURE: [u8; 8] = *b"EFI PART";
+
+    /// LBA of the primary GPT header.
+    pub const PRIMARY_LBA: u64 = 1;
+
+    /// Reads and validates the primary GPT header.
+    ///
+    /// `block_size` is the size of a logical block on the disk, as reported by
+    /// [`BlockIOMedia::block_size`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the header signature is missing, so the disk
+    ///   is not GPT formatted.
+    /// * [`Status::VOLUME_CORRUPTED`]: a header field is invalid, or the header
+    ///   is not at [`PRIMARY_LBA`].
+    /// * [`Status::CRC_ERROR`]: the header checksum is incorrect.
+    /// * [`Status::UNSUPPORTED`]: the header is larger than 512 bytes.
+    /// * Any error returned by [`DiskIo::read_disk`].
+    ///
+    /// [`BlockIOMedia::block_size`]: super::block::BlockIOMedia::block_size
+    /// [`PRIMARY_LBA`]: Self::PRIMARY_LBA
+    pub fn read(disk: &DiskIo, media_id: u32, block_size: u32) -> Result<Self> {
+        let mut block = [0; HEADER_BUF_SIZE];
+        disk.read_disk(
+            media_id,
+            Self::PRIMARY_LBA * u64::from(block_size),
+            &mut block,
+        )?;
+        parse_header(&mut block, Self::PRIMARY_LBA, boot::calculate_crc32)
+    }
+
+    /// Size in bytes of the partition entry array, or `None` on overflow.
+    #[must_use]
+    pub fn partition_entry_array_size(&self) -> Option<usize> {
+        let size = u64::from(self.number_of_partition_entries)
+            .checked_mul(u64::from(self.size_of_partition_entry))?;
+        usize::try_from(size).ok()
+    }
+}
+
+/// Reads the GPT partition entries of a disk.
+///
+/// The primary header is read and validated with [`GptHeader::read`], then the
+/// partition entry array is read and its CRC32 checked. Unused entries (those
+/// with a zero partition type GUID) are skipped.
+///
+/// `block_size` is the size of a logical block on the disk, as reported by
+/// [`BlockIOMedia::block_size`].
+///
+/// # Errors
+///
+/// * [`Status::CRC_ERROR`]: the partition entry array checksum is incorrect.
+/// * [`Status::VOLUME_CORRUPTED`]: the array does not end before the first
+///   usable LBA.
+/// * [`Status::UNSUPPORTED`]: the array is larger than 4 MiB.
+/// * Any error returned by [`GptHeader::read`] or [`DiskIo::read_disk`].
+///
+/// [`BlockIOMedia::block_size`]: super::block::BlockIOMedia::block_size
+#[cfg(feature = "alloc")]
+pub fn partition_entries(
+    disk: &DiskIo,
+    media_id: u32,
+    block_size: u32,
+) -> Result<Vec<GptPartitionEntry>> {
+    let header = GptHeader::read(disk, media_id, block_size)?;
+    let (offset, size) = entry_array_location(&header, block_size)?;
+
+    let mut array = vec![0; size];
+    disk.read_disk(media_id, offset, &mut array)?;
+    parse_entries(&header, &array, boot::calculate_crc32)
+}
+
+/// Parses and validates a GPT header from the start of `block`, which was
+/// read from `lba`. The CRC32 field in `block` is temporarily zeroed to
+/// compute the checksum.
+fn parse_header(
+    block: &mut [u8],
+    lba: u64,
+    crc32: impl Fn(&[u8]) -> Result<u32>,
+) -> Result<GptHeader> {
+    if block.len() < HEADER_SIZE {
+        return Err(Status::VOLUME_CORRUPTED.into());
+    }
+    // Safety: `block` is large enough, and `GptHeader` is packed and valid
+    // for any bit pattern.
+    let header = unsafe { block.as_ptr().cast::<GptHeader>().read_unaligned() };
+
+    if header.signature != GptHeader::SIGNATURE {
+        return Err(Status::NOT_FOUND.into());
+    }
+
+    let header_size = header.header_size as usize;
+    if header_size < HEADER_SIZE {
+        return Err(Status::VOLUME_CORRUPTED.into());
+    }
+    if header_size > block.len() {
+        return Err(Status::UNSUPPORTED.into());
+    }
+
+    let crc_field = HEADER_CRC32_OFFSET..HEADER_CRC32_OFFSET + mem::size_of::<u32>();
+    block[crc_field.clone()].fill(0);
+    let actual_crc = crc32(&block[..header_size]);
+    block[crc_field].copy_from_slice(&{ header.header_crc32 }.to_le_bytes());
+    if actual_crc? != header.header_crc32 {
+        return Err(Status::CRC_ERROR.into());
+    }
+
+    let entry_size = header.size_of_partition_entry;
+    if header.my_lba != lba
+        || entry_size % MIN_ENTRY_SIZE != 0
+        || !(entry_size / MIN_ENTRY_SIZE).is_power_of_two()
+    {
+        return Err(Status::VOLUME_CORRUPTED.into());
+    }
+
+    Ok(header)
+}
+
+/// Gets the byte offset and size of the partition entry array described by
+/// `header`. The array must end before the first usable LBA, and its size is
+/// limited so that a corrupted header can't cause a huge allocation.
+#[cfg(feature = "alloc")]
+fn entry_array_location(header: &GptHeader, block_size: u32) -> Result<(u64, usize)> {
+    // Can't overflow, both factors are `u32`.
+    let size =
+        u64::from(header.number_of_partition_entries) * u64::from(header.size_of_partition_entry);
+    let block_size = u64::from(block_size);
+    let offset = header.partition_entry_lba.checked_mul(block_size);
+    let end = offset.and_then(|offset| offset.checked_add(size));
+    let first_usable = header.first_usable_lba.checked_mul(block_size);
+    match (offset, end, first_usable) {
+        (Some(offset), Some(end), Some(first_usable)) if end <= first_usable => {
+            if size > MAX_ENTRY_ARRAY_SIZE {
+                return Err(Status::UNSUPPORTED.into());
+            }
+            let size = usize::try_from(size).map_err(|_| Status::UNSUPPORTED)?;
+            Ok((offset, size))
+        }
+        _ => Err(Status::VOLUME_CORRUPTED.into()),
+    }
+}
+
+/// Validates the CRC32 of a partition entry `array` and returns its used
+/// entries.
+#[cfg(feature = "alloc")]
+fn parse_entries(
+    header: &GptHeader,
+    array: &[u8],
+    crc32: impl Fn(&[u8]) -> Result<u32>,
+) -> Result<Vec<GptPartitionEntry>> {
+    if crc32(array)? != header.partition_entry_array_crc32 {
+        return Err(Status::CRC_ERROR.into());
+    }
+
+    let entries = array
+        .chunks_exact(header.size_of_partition_entry as usize)
+        .map(|chunk| {
+            // Safety: each chunk is at least `MIN_ENTRY_SIZE` bytes, which is
+            // the size of `GptPartitionEntry`, and the entry is packed and
+            // valid for any bit pattern.
+            unsafe { chunk.as_ptr().cast::<GptPartitionEntry>().read_unaligned() }
+        })
+        .filter(|entry| { entry.partition_type_guid } != GptPartitionType::UNUSED_ENTRY)
+        .collect();
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use crate::proto::media::partition::{GptPartitionEntry, GptPartitionType};
+
+    /// Bitwise CRC32, matching `boot::calculate_crc32`.
+    fn crc32(data: &[u8]) -> Result<u32> {
+        let mut crc = !0u32;
+        for byte in data {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        Ok(!crc)
+    }
+
+    /// Builds a two-entry partition array with one used entry.
+    fn make_array() -> [u8; 2 * MIN_ENTRY_SIZE as usize] {
+        let mut array = [0; 2 * MIN_ENTRY_SIZE as usize];
+        array[..16].copy_from_slice(&GptPartitionType::EFI_SYSTEM_PARTITION.0.to_bytes());
+        array[32..40].copy_from_slice(&34u64.to_le_bytes());
+        array[40..48].copy_from_slice(&2047u64.to_le_bytes());
+        array
+    }
+
+    /// Builds a valid header block describing `array`.
+    fn make_header_block(array: &[u8]) -> [u8; HEADER_BUF_SIZE] {
+        let mut block = [0; HEADER_BUF_SIZE];
+        block[..8].copy_from_slice(&GptHeader::SIGNATURE);
+        block[8..12].copy_from_slice(&0x0001_0000u32.to_le_bytes());
+        block[12..16].copy_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+        block[24..32].copy_from_slice(&1u64.to_le_bytes());
+        block[40..48].copy_from_slice(&34u64.to_le_bytes());
+        block[56..72].copy_from_slice(&guid!("01234567-89ab-cdef-0123-456789abcdef").to_bytes());
+        block[72..80].copy_from_slice(&2u64.to_le_bytes());
+        block[80..84].copy_from_slice(&2u32.to_le_bytes());
+        block[84..88].copy_from_slice(&MIN_ENTRY_SIZE.to_le_bytes());
+        block[88..92].copy_from_slice(&crc32(array).unwrap().to_le_bytes());
+        let crc = crc32(&block[..HEADER_SIZE]).unwrap();
+        block[16..20].copy_from_slice(&crc.to_le_bytes());
+        block
+    }
+
+    #[test]
+    fn test_header_layout() {
+        assert_eq!(mem::size_of::<GptHeader>(), HEADER_SIZE);
+        assert_eq!(mem::size_of::<GptPartitionEntry>(), MIN_ENTRY_SIZE as usize);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_parse_valid() {
+        let array = make_array();
+        let mut block = make_header_block(&array);
+        let original = block;
+
+        let header = parse_header(&mut block, 1, crc32).unwrap();
+        // The block is restored after the CRC check.
+        assert_eq!(block, original);
+        assert_eq!({ header.my_lba }, 1);
+        assert_eq!({ header.partition_entry_lba }, 2);
+        assert_eq!(header.partition_entry_array_size(), Some(256));
+
+        let entries = parse_entries(&header, &array, crc32).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            { entries[0].partition_type_guid },
+            GptPartitionType::EFI_SYSTEM_PARTITION
+        );
+        assert_eq!(entries[0].num_blocks(), Some(2014));
+    }
+
+    #[test]
+    fn test_parse_invalid_header() {
+        let array = make_array();
+
+        // Missing signature.
+        let mut block = make_header_block(&array);
+        block[0] = b'X';
+        assert_eq!(
+            parse_header(&mut block, 1, crc32).unwrap_err().status(),
+            Status::NOT_FOUND
+        );
+
+        // Corrupted field.
+        let mut block = make_header_block(&array);
+        block[60] ^= 1;
+        assert_eq!(
+            parse_header(&mut block, 1, crc32).unwrap_err().status(),
+            Status::CRC_ERROR
+        );
+
+        // Wrong LBA.
+        let mut block = make_header_block(&array);
+        assert_eq!(
+            parse_header(&mut block, 2, crc32).unwrap_err().status(),
+            Status::VOLUME_CORRUPTED
+        );
+
+        // Truncated block.
+        let mut block = make_header_block(&array);
+        assert_eq!(
+            parse_header(&mut block[..64], 1, crc32)
+                .unwrap_err()
+                .status(),
+            Status::VOLUME_CORRUPTED
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_parse_invalid_entries() {
+        let mut array = make_array();
+        let mut block = make_header_block(&array);
+        let header = parse_header(&mut block, 1, crc32).unwrap();
+
+        array[200] = 1;
+        assert_eq!(
+            parse_entries(&header, &array, crc32).unwrap_err().status(),
+            Status::CRC_ERROR
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_entry_array_location() {
+        let array = make_array();
+        let mut block = make_header_block(&array);
+        let header = parse_header(&mut block, 1, crc32).unwrap();
+        assert_eq!(entry_array_location(&header, 512), Ok((1024, 256)));
+
+        // The array overlaps the usable blocks.
+        let mut overlapping = header;
+        overlapping.first_usable_lba = 2;
+        assert_eq!(
+            entry_array_location(&overlapping, 512)
+                .unwrap_err()
+                .status(),
+            Status::VOLUME_CORRUPTED
+        );
+
+        // The offset overflows.
+        let mut overflowing = header;
+        overflowing.partition_entry_lba = u64::MAX;
+        assert_eq!(
+            entry_array_location(&overflowing, 512)
+                .unwrap_err()
+                .status(),
+            Status::VOLUME_CORRUPTED
+        );
+
+        // A huge array, even if it fits before the first usable LBA.
+        let mut huge = header;
+        huge.number_of_partition_entries = u32::MAX;
+        huge.first_usable_lba = u64::MAX / 512;
+        assert_eq!(
+            entry_array_location(&huge, 512).unwrap_err().status(),
+            Status::UNSUPPORTED
+        );
+    }
+}
@@ -1,15 +1,14 @@
 //! LoadFile and LoadFile2 protocols.
 
-use crate::proto::unsafe_protocol;
+use crate::proto::device_path::DevicePath;
+use crate::proto::{unsafe_protocol, BootPolicy};
+use crate::{Result, Status, StatusExt};
 #[cfg(all(feature = "alloc", feature = "unstable"))]
 use alloc::alloc::Global;
+use core::ptr;
 use uefi_raw::protocol::media::{LoadFile2Protocol, LoadFileProtocol};
 #[cfg(feature = "alloc")]
-use {
-    crate::{mem::make_boxed, proto::device_path::DevicePath, Result, StatusExt},
-    alloc::boxed::Box,
-    uefi::proto::BootPolicy,
-};
+use {crate::mem::make_boxed, alloc::boxed::Box};
 
 /// Load File Protocol.
 ///
@@ -91,6 +90,39 @@ impl LoadFile {
 
         Ok(file)
     }
+
+    /// Load a file into `buf` without allocating, returning the size of the
+    /// file.
+    ///
+    /// Pass `None` for `buf` to get the size of the file, then call this
+    /// again with a buffer of that size. See [`load_file`] for the possible
+    /// errors.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is `None` or too small, [`Status::BUFFER_TOO_SMALL`] is
+    /// returned with the required size in the error data.
+    ///
+    /// [`load_file`]: Self::load_file
+    pub fn load_file_into(
+        &mut self,
+        file_path: &DevicePath,
+        boot_policy: BootPolicy,
+        buf: Option<&mut [u8]>,
+    ) -> Result<usize, Option<usize>> {
+        let (mut size, buf_ptr) =
+            buf.map_or((0, ptr::null_mut()), |buf| (buf.len(), buf.as_mut_ptr()));
+        let status = unsafe {
+            (self.0.load_file)(
+                &mut self.0,
+                file_path.as_ffi_ptr().cast(),
+                boot_policy.into(),
+                &mut size,
+                buf_ptr.cast(),
+            )
+        };
+        to_load_result(status, size)
+    }
 }
 
 /// Load File2 Protocol.
@@ -157,4 +189,47 @@ impl LoadFile2 {
 
         Ok(file)
     }
+
+    /// Load a file into `buf` without allocating, returning the size of the
+    /// file.
+    ///
+    /// Pass `None` for `buf` to get the size of the file, then call this
+    /// again with a buffer of that size. This is the usual way to load an
+    /// initrd provided through the Linux initrd media device path. See
+    /// [`load_file`] for the possible errors.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is `None` or too small, [`Status::BUFFER_TOO_SMALL`] is
+    /// returned with the required size in the error data.
+    ///
+    /// [`load_file`]: Self::load_file
+    pub fn load_file_into(
+        &mut self,
+        file_path: &DevicePath,
+        buf: Option<&mut [u8]>,
+    ) -> Result<usize, Option<usize>> {
+        let (mut size, buf_ptr) =
+            buf.map_or((0, ptr::null_mut()), |buf| (buf.len(), buf.as_mut_ptr()));
+        let status = unsafe {
+            (self.0.load_file)(
+                &mut self.0,
+                file_path.as_ffi_ptr().cast(),
+                false, /* always false - see spec */
+                &mut size,
+                buf_ptr.cast(),
+            )
+        };
+        to_load_result(status, size)
+    }
+}
+
+/// Convert the status and buffer size returned by `LoadFile` or `LoadFile2`
+/// into a result, with the required size as error data if the buffer was too
+/// small.
+fn to_load_result(status: Status, size: usize) -> Result<usize, Option<usize>> {
+    status.to_result_with(
+        || size,
+        |status| (status == Status::BUFFER_TOO_SMALL).then_some(size),
+    )
 }
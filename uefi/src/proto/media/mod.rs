@@ -9,5 +9,6 @@ pub mod file;
 pub mod block;
 pub mod disk;
 pub mod fs;
+pub mod gpt;
 pub mod load_file;
 pub mod partition;
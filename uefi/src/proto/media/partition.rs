@@ -3,6 +3,9 @@
 use crate::proto::unsafe_protocol;
 use crate::{guid, Char16, Guid};
 
+#[cfg(feature = "alloc")]
+use crate::{CStr16, CString16};
+
 newtype_enum! {
     /// MBR OS type.
     ///
@@ -190,6 +193,23 @@ impl GptPartitionEntry {
             .checked_sub(self.starting_lba)?
             .checked_add(1)
     }
+
+    /// Get the partition name as a [`CString16`]. Returns `None` if the name
+    /// contains invalid characters.
+    ///
+    /// The name is null-terminated unless it uses all 36 characters of the
+    /// field. It is stored inline in this packed struct, so it cannot be
+    /// borrowed as a [`CStr16`] directly.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn name(&self) -> Option<CString16> {
+        // Copy the name into a buffer with room for a terminating null.
+        let mut name = [0; 37];
+        for (dst, src) in name.iter_mut().zip(self.partition_name) {
+            *dst = u16::from(src);
+        }
+        CStr16::from_u16_until_nul(&name).ok().map(CString16::from)
+    }
 }
 
 newtype_enum! {
@@ -277,6 +297,36 @@ impl PartitionInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cstr16;
+
+    #[test]
+    fn test_gpt_partition_name() {
+        let mut partition_name = [Char16::try_from('\0').unwrap(); 36];
+        for (dst, src) in partition_name.iter_mut().zip("EFI system".chars()) {
+            *dst = Char16::try_from(src).unwrap();
+        }
+        let mut entry = GptPartitionEntry {
+            partition_type_guid: GptPartitionType::EFI_SYSTEM_PARTITION,
+            unique_partition_guid: Guid::ZERO,
+            starting_lba: 34,
+            ending_lba: 2081,
+            attributes: GptPartitionAttributes::empty(),
+            partition_name,
+        };
+        assert_eq!(entry.name().unwrap(), cstr16!("EFI system"));
+        assert_eq!(entry.num_blocks(), Some(2048));
+
+        // A name using the whole field has no null terminator.
+        entry.partition_name = [Char16::try_from('a').unwrap(); 36];
+        assert_eq!(
+            entry.name().unwrap(),
+            cstr16!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+        );
+
+        // Unpaired surrogates are rejected.
+        entry.partition_name[3] = unsafe { Char16::from_u16_unchecked(0xd800) };
+        assert_eq!(entry.name(), None);
+    }
 
     #[test]
     fn test_partition_attributes() {
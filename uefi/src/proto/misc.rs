@@ -1,11 +1,16 @@
 //! Miscellaneous protocols.
 
 use uefi_raw::protocol::misc::{
-    ResetNotificationProtocol, ResetSystemFn, TimestampProperties, TimestampProtocol,
+    BootManagerPolicyProtocol, ResetNotificationProtocol, ResetSystemFn, TimestampProperties,
+    TimestampProtocol,
 };
 
+use crate::proto::device_path::DevicePath;
 use crate::proto::unsafe_protocol;
-use crate::{Result, StatusExt};
+use crate::{Guid, Result, StatusExt};
+
+#[cfg(doc)]
+use crate::Status;
 
 /// Protocol for retrieving a high-resolution timestamp counter.
 /// **Note:**
@@ -39,16 +44,42 @@ pub struct ResetNotification(ResetNotificationProtocol);
 impl ResetNotification {
     /// Register a notification function to be called when ResetSystem() is called.
     ///
+    /// # Callback constraints
+    ///
+    /// The notification function is called by the firmware from within
+    /// `ResetSystem`, which may happen after boot services have been exited
+    /// and at an elevated task priority level. The function must therefore:
+    ///
+    /// * not allocate or free memory,
+    /// * not call any boot services, which includes logging or printing to
+    ///   the console through this crate's helpers,
+    /// * not block or wait for events, and
+    /// * return promptly, since the reset is delayed until all notification
+    ///   functions have returned.
+    ///
+    /// The function must also remain valid until it is unregistered with
+    /// [`unregister_reset_notify`]. For a function in a driver, that means
+    /// unregistering it before the driver is unloaded.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::ALREADY_STARTED`]: `reset_function` is already registered.
+    /// * [`Status::OUT_OF_RESOURCES`]: the firmware has no room left for more
+    ///   notification functions. Unregistering another function may free up
+    ///   space.
+    /// * [`Status::INVALID_PARAMETER`]: the firmware rejected the function
+    ///   pointer.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use log::info;
+    /// use core::sync::atomic::{AtomicBool, Ordering};
     /// use uefi::{boot, Handle};
     /// use uefi::proto::misc::{ResetNotification};
     /// use uefi_raw::Status;
     /// use uefi_raw::table::runtime;
     ///
+    /// static RESET_STARTED: AtomicBool = AtomicBool::new(false);
     ///
     /// // value efi_reset_fn is the type of ResetSystemFn, a function pointer
     /// unsafe extern "efiapi" fn efi_reset_fn(
@@ -57,8 +88,8 @@ impl ResetNotification {
     ///         data_size: usize,
     ///         data: *const u8,
     /// ){
-    ///     info!("Inside the event callback");
-    ///     info!("do what you want");
+    ///     // Only do minimal work here; no allocation or boot services.
+    ///     RESET_STARTED.store(true, Ordering::Relaxed);
     /// }
     ///
     /// pub fn test(image: Handle) {
@@ -70,12 +101,87 @@ impl ResetNotification {
     ///         .expect("Failed to register a reset notification function!");
     /// }
     /// ```
+    ///
+    /// [`unregister_reset_notify`]: Self::unregister_reset_notify
     pub fn register_reset_notify(&mut self, reset_function: ResetSystemFn) -> Result {
         unsafe { (self.0.register_reset_notify)(&mut self.0, reset_function) }.to_result()
     }
 
     /// Remove a reset notification function that was previously registered with [`ResetNotification::register_reset_notify`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `reset_function` is not currently
+    ///   registered.
     pub fn unregister_reset_notify(&mut self, reset_function: ResetSystemFn) -> Result {
         unsafe { (self.0.unregister_reset_notify)(&mut self.0, reset_function) }.to_result()
     }
 }
+
+/// Protocol for connecting devices according to the platform's boot manager
+/// policy.
+///
+/// This is the preferred way for applications to connect devices that the
+/// platform has not connected yet, e.g. because it only binds drivers on
+/// demand to speed up boot. It avoids walking all handles and calling
+/// [`boot::connect_controller`] manually.
+///
+/// [`boot::connect_controller`]: crate::boot::connect_controller
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(BootManagerPolicyProtocol::GUID)]
+pub struct BootManagerPolicy(BootManagerPolicyProtocol);
+
+impl BootManagerPolicy {
+    /// Device class for all console devices.
+    pub const CONSOLE: Guid = BootManagerPolicyProtocol::CONSOLE_GUID;
+
+    /// Device class for all network devices.
+    pub const NETWORK: Guid = BootManagerPolicyProtocol::NETWORK_GUID;
+
+    /// Device class for all devices.
+    pub const CONNECT_ALL: Guid = BootManagerPolicyProtocol::CONNECT_ALL_GUID;
+
+    /// Connect the device at `device_path`, using the platform's policy for
+    /// selecting drivers. If `recursive` is true, all child devices are
+    /// connected as well.
+    ///
+    /// This must be called at [`Tpl::APPLICATION`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the device path was not found, or no drivers
+    ///   could be connected to it.
+    /// * [`Status::SECURITY_VIOLATION`]: the user has no permission to start
+    ///   UEFI device drivers on the device path.
+    /// * [`Status::UNSUPPORTED`]: the current TPL is not
+    ///   [`Tpl::APPLICATION`].
+    ///
+    /// [`Tpl::APPLICATION`]: crate::boot::Tpl::APPLICATION
+    pub fn connect_device_path(&self, device_path: &DevicePath, recursive: bool) -> Result {
+        unsafe { (self.0.connect_device_path)(&self.0, device_path.as_ffi_ptr().cast(), recursive) }
+            .to_result()
+    }
+
+    /// Connect all devices of the device class `class`, which is usually one
+    /// of [`CONSOLE`], [`NETWORK`] or [`CONNECT_ALL`]. Platforms may support
+    /// additional classes.
+    ///
+    /// This must be called at [`Tpl::APPLICATION`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the device class is not recognized by the
+    ///   platform.
+    /// * [`Status::DEVICE_ERROR`]: the platform failed to connect the devices.
+    /// * [`Status::UNSUPPORTED`]: the current TPL is not
+    ///   [`Tpl::APPLICATION`].
+    ///
+    /// [`CONSOLE`]: Self::CONSOLE
+    /// [`NETWORK`]: Self::NETWORK
+    /// [`CONNECT_ALL`]: Self::CONNECT_ALL
+    /// [`Tpl::APPLICATION`]: crate::boot::Tpl::APPLICATION
+    pub fn connect_device_class(&self, class: &Guid) -> Result {
+        unsafe { (self.0.connect_device_class)(&self.0, class) }.to_result()
+    }
+}
@@ -12,6 +12,7 @@ pub mod console;
 pub mod debug;
 pub mod device_path;
 pub mod driver;
+pub mod hash;
 pub mod loaded_image;
 pub mod media;
 pub mod misc;
@@ -23,10 +24,13 @@ pub mod shell_params;
 pub mod shim;
 pub mod string;
 pub mod tcg;
+pub mod usb;
 
 mod boot_policy;
+mod names;
 
 pub use boot_policy::{BootPolicy, BootPolicyError};
+pub use names::protocol_name;
 pub use uefi_macros::unsafe_protocol;
 
 use crate::Identify;
@@ -0,0 +1,146 @@
+// ComponentName1 is deprecated, but still has a well-known name.
+#![allow(deprecated)]
+
+use crate::proto::console::gop::{EdidActive, EdidDiscovered, GraphicsOutput};
+use crate::proto::console::pointer::Pointer;
+use crate::proto::console::serial::Serial;
+use crate::proto::console::text::{Input, InputEx, Output};
+use crate::proto::debug::{DebugPort, DebugSupport};
+use crate::proto::device_path::text::{DevicePathFromText, DevicePathToText};
+use crate::proto::device_path::{DevicePath, LoadedImageDevicePath};
+use crate::proto::driver::{ComponentName1, ComponentName2, DriverBinding};
+use crate::proto::hash::{Hash2, Hash2ServiceBinding};
+use crate::proto::loaded_image::LoadedImage;
+use crate::proto::media::block::BlockIO;
+use crate::proto::media::disk::{DiskIo, DiskIo2};
+use crate::proto::media::fs::SimpleFileSystem;
+use crate::proto::media::load_file::{LoadFile, LoadFile2};
+use crate::proto::media::partition::PartitionInfo;
+use crate::proto::misc::{BootManagerPolicy, ResetNotification, Timestamp};
+use crate::proto::network::dhcp4::{Dhcp4, Dhcp4ServiceBinding};
+use crate::proto::network::http::{Http, HttpServiceBinding};
+use crate::proto::network::ip4config2::Ip4Config2;
+use crate::proto::network::mnp::{ManagedNetwork, ManagedNetworkServiceBinding};
+use crate::proto::network::pxe::BaseCode;
+use crate::proto::network::snp::SimpleNetwork;
+use crate::proto::network::tcp::{Tcp4, Tcp4ServiceBinding};
+use crate::proto::network::udp::{Udp4, Udp4ServiceBinding};
+use crate::proto::pi::mp::MpServices;
+use crate::proto::rng::Rng;
+use crate::proto::security::{MemoryProtection, Security2};
+use crate::proto::shell_params::ShellParameters;
+use crate::proto::shim::ShimLock;
+use crate::proto::string::unicode_collation::UnicodeCollation;
+use crate::proto::tcg::{v1, v2};
+use crate::proto::usb::UsbIo;
+use crate::{Guid, Identify};
+
+macro_rules! known_protocols {
+    ($($ty:ty => $name:literal,)*) => {
+        /// GUIDs and names of the protocols defined in this crate.
+        const KNOWN_PROTOCOLS: &[(Guid, &str)] = &[$((<$ty as Identify>::GUID, $name),)*];
+    };
+}
+
+known_protocols! {
+    BaseCode => "BaseCode",
+    BlockIO => "BlockIO",
+    BootManagerPolicy => "BootManagerPolicy",
+    ComponentName1 => "ComponentName1",
+    ComponentName2 => "ComponentName2",
+    DebugPort => "DebugPort",
+    DebugSupport => "DebugSupport",
+    DevicePath => "DevicePath",
+    DevicePathFromText => "DevicePathFromText",
+    DevicePathToText => "DevicePathToText",
+    Dhcp4 => "Dhcp4",
+    Dhcp4ServiceBinding => "Dhcp4ServiceBinding",
+    DiskIo => "DiskIo",
+    DiskIo2 => "DiskIo2",
+    DriverBinding => "DriverBinding",
+    EdidActive => "EdidActive",
+    EdidDiscovered => "EdidDiscovered",
+    GraphicsOutput => "GraphicsOutput",
+    Hash2 => "Hash2",
+    Hash2ServiceBinding => "Hash2ServiceBinding",
+    Http => "Http",
+    HttpServiceBinding => "HttpServiceBinding",
+    Input => "Input",
+    InputEx => "InputEx",
+    Ip4Config2 => "Ip4Config2",
+    LoadFile => "LoadFile",
+    LoadFile2 => "LoadFile2",
+    LoadedImage => "LoadedImage",
+    LoadedImageDevicePath => "LoadedImageDevicePath",
+    ManagedNetwork => "ManagedNetwork",
+    ManagedNetworkServiceBinding => "ManagedNetworkServiceBinding",
+    MemoryProtection => "MemoryProtection",
+    MpServices => "MpServices",
+    Output => "Output",
+    PartitionInfo => "PartitionInfo",
+    Pointer => "Pointer",
+    ResetNotification => "ResetNotification",
+    Rng => "Rng",
+    Security2 => "Security2",
+    Serial => "Serial",
+    ShellParameters => "ShellParameters",
+    ShimLock => "ShimLock",
+    SimpleFileSystem => "SimpleFileSystem",
+    SimpleNetwork => "SimpleNetwork",
+    Tcp4 => "Tcp4",
+    Tcp4ServiceBinding => "Tcp4ServiceBinding",
+    Timestamp => "Timestamp",
+    Udp4 => "Udp4",
+    Udp4ServiceBinding => "Udp4ServiceBinding",
+    UnicodeCollation => "UnicodeCollation",
+    UsbIo => "UsbIo",
+    v1::Tcg => "Tcg (v1)",
+    v2::Tcg => "Tcg (v2)",
+}
+
+/// Get the name of a protocol defined in this crate from its GUID.
+///
+/// The name is the name of the protocol's type, e.g. `"SimpleFileSystem"`
+/// for [`SimpleFileSystem::GUID`]. Returns `None` if the GUID does not belong
+/// to a protocol defined in this crate.
+///
+/// [`SimpleFileSystem::GUID`]: crate::proto::media::fs::SimpleFileSystem
+#[must_use]
+pub fn protocol_name(guid: &Guid) -> Option<&'static str> {
+    KNOWN_PROTOCOLS
+        .iter()
+        .find(|(known, _)| known == guid)
+        .map(|(_, name)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+
+    #[test]
+    fn test_protocol_name() {
+        assert_eq!(
+            protocol_name(&SimpleFileSystem::GUID),
+            Some("SimpleFileSystem")
+        );
+        assert_eq!(protocol_name(&DevicePath::GUID), Some("DevicePath"));
+        assert_eq!(protocol_name(&v2::Tcg::GUID), Some("Tcg (v2)"));
+        assert_eq!(
+            protocol_name(&guid!("12345678-9abc-def0-1234-56789abcdef0")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_known_protocols_unique() {
+        for (i, (guid, name)) in KNOWN_PROTOCOLS.iter().enumerate() {
+            assert!(
+                KNOWN_PROTOCOLS[i + 1..]
+                    .iter()
+                    .all(|(other, _)| other != guid),
+                "duplicate GUID for {name}"
+            );
+        }
+    }
+}
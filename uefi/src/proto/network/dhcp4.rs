@@ -0,0 +1,151 @@
+//! DHCPv4 protocol.
+//!
+//! A DHCP instance is created with the service binding protocol installed on
+//! a network device: open [`Dhcp4ServiceBinding`] on the device's handle and
+//! call [`Dhcp4ServiceBinding::create_child`] to get a new handle with the
+//! [`Dhcp4`] protocol installed on it.
+
+use super::{Ipv4Address, MacAddress};
+use crate::proto::unsafe_protocol;
+use crate::{Handle, Result, StatusExt};
+use core::mem::MaybeUninit;
+use core::ptr;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::network::dhcp4::{Dhcp4ConfigData, Dhcp4ModeData, Dhcp4Protocol};
+
+#[cfg(doc)]
+use crate::Status;
+
+pub use uefi_raw::protocol::network::dhcp4::Dhcp4State;
+
+/// Service binding protocol for [`Dhcp4`].
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Dhcp4Protocol::SERVICE_BINDING_GUID)]
+pub struct Dhcp4ServiceBinding(ServiceBindingProtocol);
+
+impl Dhcp4ServiceBinding {
+    /// Create a new DHCPv4 instance, returning the handle on which [`Dhcp4`]
+    /// is installed.
+    pub fn create_child(&mut self) -> Result<Handle> {
+        super::create_child(&mut self.0)
+    }
+
+    /// Destroy a DHCPv4 instance created with [`create_child`].
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, handle: Handle) -> Result {
+        super::destroy_child(&mut self.0, handle)
+    }
+}
+
+/// DHCPv4 protocol.
+///
+/// Use [`Dhcp4ServiceBinding`] to create an instance of this protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Dhcp4Protocol::GUID)]
+pub struct Dhcp4(Dhcp4Protocol);
+
+impl Dhcp4 {
+    /// Get the current state and lease of the DHCP client.
+    pub fn mode_data(&self) -> Result<Dhcp4Lease> {
+        let mut mode_data = MaybeUninit::<Dhcp4ModeData>::uninit();
+        unsafe { (self.0.get_mode_data)(&self.0, mode_data.as_mut_ptr()) }.to_result_with_val(
+            || {
+                let mode_data = unsafe { mode_data.assume_init() };
+                Dhcp4Lease {
+                    state: mode_data.state,
+                    client_address: mode_data.client_address,
+                    client_mac_address: MacAddress(mode_data.client_mac_address.0),
+                    server_address: mode_data.server_address,
+                    router_address: mode_data.router_address,
+                    subnet_mask: mode_data.subnet_mask,
+                    lease_time: mode_data.lease_time,
+                }
+            },
+        )
+    }
+
+    /// Perform the DHCP handshake (discover, offer, request, acknowledge) to
+    /// acquire an address lease, blocking until it completes. On success, the
+    /// lease can be read with [`mode_data`].
+    ///
+    /// If the instance has not been configured yet, it is configured with the
+    /// firmware's default retry counts and timeouts.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: a server responded, but the handshake did not
+    ///   complete within the retry limit. Retrying may succeed.
+    /// * [`Status::NO_RESPONSE`]: no server responded to the discover
+    ///   messages.
+    /// * [`Status::ALREADY_STARTED`]: the handshake has already been started.
+    /// * [`Status::ABORTED`]: the handshake was aborted by [`stop`].
+    /// * [`Status::NO_MEDIA`]: the network cable is not connected.
+    ///
+    /// [`mode_data`]: Self::mode_data
+    /// [`stop`]: Self::stop
+    pub fn start(&mut self) -> Result {
+        if self.mode_data()?.state == Dhcp4State::STOPPED {
+            // Zero retry counts select the default retry counts and timeouts.
+            let config = Dhcp4ConfigData {
+                discover_try_count: 0,
+                discover_timeout: ptr::null_mut(),
+                request_try_count: 0,
+                request_timeout: ptr::null_mut(),
+                client_address: Ipv4Address::default(),
+                callback: None,
+                callback_context: ptr::null_mut(),
+                option_count: 0,
+                option_list: ptr::null_mut(),
+            };
+            unsafe { (self.0.configure)(&mut self.0, &config) }.to_result()?;
+        }
+
+        // Without a completion event, the firmware runs the handshake to
+        // completion before returning.
+        unsafe { (self.0.start)(&mut self.0, ptr::null_mut()) }.to_result()
+    }
+
+    /// Release the current lease and return to the initial state.
+    pub fn release(&mut self) -> Result {
+        unsafe { (self.0.release)(&mut self.0) }.to_result()
+    }
+
+    /// Stop the DHCP client, aborting any handshake in progress. The lease is
+    /// not released.
+    pub fn stop(&mut self) -> Result {
+        unsafe { (self.0.stop)(&mut self.0) }.to_result()
+    }
+}
+
+/// State and address lease of a [`Dhcp4`] client.
+///
+/// The address fields are only valid if [`state`] is [`Dhcp4State::BOUND`].
+///
+/// [`state`]: Self::state
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Dhcp4Lease {
+    /// State of the DHCP client.
+    pub state: Dhcp4State,
+
+    /// Leased IP address.
+    pub client_address: Ipv4Address,
+
+    /// MAC address of the network device.
+    pub client_mac_address: MacAddress,
+
+    /// Address of the DHCP server that granted the lease.
+    pub server_address: Ipv4Address,
+
+    /// Default router address.
+    pub router_address: Ipv4Address,
+
+    /// Subnet mask of the leased address.
+    pub subnet_mask: Ipv4Address,
+
+    /// Duration of the lease in seconds. `u32::MAX` means the lease never
+    /// expires.
+    pub lease_time: u32,
+}
@@ -0,0 +1,388 @@
+//! HTTP protocol.
+//!
+//! An HTTP instance is created with the service binding protocol installed on
+//! a network device: open [`HttpServiceBinding`] on the device's handle and
+//! call [`HttpServiceBinding::create_child`] to get a new handle with the
+//! [`Http`] protocol installed on it.
+//!
+//! The operations of [`Http`] are asynchronous in the UEFI API. The wrappers
+//! here block until the operation completes or the given timeout expires.
+//! After a timeout, the operation is cancelled and the wrapper keeps
+//! blocking until the firmware has released the buffers of the operation.
+
+use crate::proto::unsafe_protocol;
+use crate::{Handle, Result, StatusExt};
+use core::ptr;
+use core::time::Duration;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::network::http::HttpProtocol;
+
+#[cfg(feature = "alloc")]
+use {
+    super::{wait_or_cancel, TokenEvent},
+    crate::{boot, CStr16, Status},
+    alloc::string::String,
+    alloc::vec,
+    alloc::vec::Vec,
+    core::ffi::CStr,
+    uefi_raw::protocol::network::http::{
+        HttpHeader, HttpMessage, HttpMethod, HttpRequestData, HttpRequestOrResponse,
+        HttpResponseData, HttpToken,
+    },
+};
+
+#[cfg(all(doc, not(feature = "alloc")))]
+use crate::Status;
+
+pub use uefi_raw::protocol::network::http::{
+    HttpStatusCode, HttpV4AccessPoint, HttpV6AccessPoint, HttpVersion,
+};
+
+/// Service binding protocol for [`Http`].
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(HttpProtocol::SERVICE_BINDING_GUID)]
+pub struct HttpServiceBinding(ServiceBindingProtocol);
+
+impl HttpServiceBinding {
+    /// Create a new HTTP instance, returning the handle on which [`Http`] is
+    /// installed.
+    pub fn create_child(&mut self) -> Result<Handle> {
+        super::create_child(&mut self.0)
+    }
+
+    /// Destroy an HTTP instance created with [`create_child`].
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, handle: Handle) -> Result {
+        super::destroy_child(&mut self.0, handle)
+    }
+}
+
+/// Local address of an [`Http`] instance.
+#[derive(Debug)]
+pub enum HttpAccessPoint {
+    /// Use IPv4.
+    Ipv4(HttpV4AccessPoint),
+    /// Use IPv6.
+    Ipv6(HttpV6AccessPoint),
+}
+
+/// Configuration of an [`Http`] instance.
+#[derive(Debug)]
+pub struct HttpConfigData {
+    /// HTTP version to use for requests.
+    pub http_version: HttpVersion,
+
+    /// Timeout used by the firmware for each operation. Saturates at
+    /// `u32::MAX` milliseconds.
+    pub timeout: Duration,
+
+    /// Local address and port.
+    pub access_point: HttpAccessPoint,
+}
+
+/// HTTP protocol.
+///
+/// Use [`HttpServiceBinding`] to create an instance of this protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(HttpProtocol::GUID)]
+pub struct Http(HttpProtocol);
+
+impl Http {
+    /// Configure the instance, or reset it to the unconfigured state if
+    /// `config` is `None`. Resetting an instance aborts any pending request.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::ALREADY_STARTED`]: the instance is already configured.
+    ///   Reset it before configuring it again.
+    /// * [`Status::NO_MAPPING`]: the default address is used but has not been
+    ///   acquired yet (e.g. DHCP is still in progress). Try again later.
+    /// * [`Status::INVALID_PARAMETER`]: the configuration is invalid.
+    pub fn configure(&mut self, config: Option<&HttpConfigData>) -> Result {
+        let Some(config) = config else {
+            return unsafe { (self.0.configure)(&mut self.0, ptr::null()) }.to_result();
+        };
+
+        let (local_addr_is_ipv6, access_point) = match &config.access_point {
+            HttpAccessPoint::Ipv4(node) => (
+                false,
+                uefi_raw::protocol::network::http::HttpAccessPoint { ipv4_node: node },
+            ),
+            HttpAccessPoint::Ipv6(node) => (
+                true,
+                uefi_raw::protocol::network::http::HttpAccessPoint { ipv6_node: node },
+            ),
+        };
+        let raw_config = uefi_raw::protocol::network::http::HttpConfigData {
+            http_version: config.http_version,
+            time_out_millisec: u32::try_from(config.timeout.as_millis()).unwrap_or(u32::MAX),
+            local_addr_is_ipv6,
+            access_point,
+        };
+        unsafe { (self.0.configure)(&mut self.0, &raw_config) }.to_result()
+    }
+
+    /// Send a `GET` request for `url` and receive the whole response.
+    ///
+    /// `url` must be an absolute `http://` or `https://` URL; the `Host`
+    /// header is derived from it. `timeout` applies to sending the request
+    /// and to each part of the response separately.
+    ///
+    /// Redirects are not followed; the redirect response is returned as is.
+    /// Responses with chunked transfer encoding are not supported.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `url` is not an absolute URL.
+    /// * [`Status::UNSUPPORTED`]: the response uses chunked transfer
+    ///   encoding.
+    /// * [`Status::TIMEOUT`]: an operation did not complete within `timeout`.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    /// * [`Status::ACCESS_DENIED`]: the URL's host could not be resolved or
+    ///   connected to.
+    #[cfg(feature = "alloc")]
+    pub fn request_get(&mut self, url: &CStr16, timeout: Duration) -> Result<HttpResponse> {
+        let host = url_host(url).ok_or(Status::INVALID_PARAMETER)?;
+
+        let request_data = HttpRequestData {
+            method: HttpMethod::GET,
+            url: url.as_ptr().cast(),
+        };
+        let mut request_headers = [HttpHeader {
+            field_name: c"Host".as_ptr().cast(),
+            field_value: host.as_ptr().cast(),
+        }];
+        let mut message = HttpMessage {
+            data: HttpRequestOrResponse {
+                request: &request_data,
+            },
+            header_count: request_headers.len(),
+            header: request_headers.as_mut_ptr(),
+            body_length: 0,
+            body: ptr::null_mut(),
+        };
+        self.run(self.0.request, &mut message, timeout)?;
+
+        // Receive the headers, and possibly the start of the body.
+        let mut buffer = vec![0; BODY_CHUNK_SIZE];
+        let mut response_data = HttpResponseData {
+            status_code: HttpStatusCode::STATUS_UNSUPPORTED,
+        };
+        let response_data: *mut HttpResponseData = &mut response_data;
+        let mut message = HttpMessage {
+            data: HttpRequestOrResponse {
+                response: response_data,
+            },
+            header_count: 0,
+            header: ptr::null_mut(),
+            body_length: buffer.len(),
+            body: buffer.as_mut_ptr().cast(),
+        };
+        self.run(self.0.response, &mut message, timeout)?;
+
+        let mut response = HttpResponse {
+            status_code: unsafe { (*response_data).status_code },
+            headers: unsafe { take_headers(&message) },
+            body: Vec::new(),
+        };
+        response
+            .body
+            .extend_from_slice(&buffer[..message.body_length]);
+
+        if response
+            .header("Transfer-Encoding")
+            .is_some_and(|value| value.eq_ignore_ascii_case("chunked"))
+        {
+            return Err(Status::UNSUPPORTED.into());
+        }
+
+        // Receive the rest of the body. Without a content length, the body
+        // ends when the server closes the connection.
+        let content_length = response.content_length();
+        if let Some(len) = content_length {
+            response
+                .body
+                .reserve(len.saturating_sub(response.body.len()));
+        }
+        while content_length.map_or(true, |len| response.body.len() < len) {
+            let remaining = content_length.map_or(BODY_CHUNK_SIZE, |len| len - response.body.len());
+            let mut message = HttpMessage {
+                data: HttpRequestOrResponse {
+                    response: ptr::null(),
+                },
+                header_count: 0,
+                header: ptr::null_mut(),
+                body_length: remaining.min(buffer.len()),
+                body: buffer.as_mut_ptr().cast(),
+            };
+            match self.run(self.0.response, &mut message, timeout) {
+                Ok(()) if message.body_length == 0 => break,
+                Ok(()) => response
+                    .body
+                    .extend_from_slice(&buffer[..message.body_length]),
+                Err(err) if content_length.is_none() && err.status() == Status::CONNECTION_FIN => {
+                    break
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Start an operation with `function` and wait for it to complete, and
+    /// return the status it completed with. If `timeout` expires first, the
+    /// operation is stopped and [`Status::TIMEOUT`] is returned.
+    #[cfg(feature = "alloc")]
+    fn run(
+        &mut self,
+        function: unsafe extern "efiapi" fn(*mut HttpProtocol, *mut HttpToken) -> Status,
+        message: &mut HttpMessage,
+        timeout: Duration,
+    ) -> Result {
+        let event = TokenEvent::new()?;
+        let mut token = HttpToken {
+            event: event.0.as_ptr(),
+            status: Status::NOT_READY,
+            message,
+        };
+        unsafe { function(&mut self.0, &mut token) }.to_result()?;
+
+        let this: *mut HttpProtocol = &mut self.0;
+        let token: *mut HttpToken = &mut token;
+        let poll = || {
+            // Polling just speeds up processing, so errors can be ignored.
+            let _ = unsafe { ((*this).poll)(this) };
+        };
+        let cancel = || unsafe { ((*this).cancel)(this, token) };
+        // Resetting the instance aborts all pending operations.
+        let abort = || unsafe { ((*this).configure)(this, ptr::null()) };
+
+        if wait_or_cancel(&event, timeout, poll, cancel, abort) {
+            unsafe { (*token).status }.to_result()
+        } else {
+            Err(Status::TIMEOUT.into())
+        }
+    }
+}
+
+/// Maximum number of body bytes to receive at a time.
+#[cfg(feature = "alloc")]
+const BODY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Response to an HTTP request.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// Status code of the response.
+    pub status_code: HttpStatusCode,
+
+    /// Header fields of the response, as `(name, value)` pairs.
+    pub headers: Vec<(String, String)>,
+
+    /// Body of the response.
+    pub body: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl HttpResponse {
+    /// Get the value of the first header field named `name`, ignoring case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(field_name, _)| field_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the value of the `Content-Length` header field, if present and
+    /// valid.
+    #[must_use]
+    pub fn content_length(&self) -> Option<usize> {
+        self.header("Content-Length")?.trim().parse().ok()
+    }
+}
+
+/// Get the null-terminated value of the `Host` header for an absolute URL,
+/// i.e. the part between `://` and the next `/`.
+#[cfg(feature = "alloc")]
+fn url_host(url: &CStr16) -> Option<Vec<u8>> {
+    let url = String::from(url);
+    let (_, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next()?;
+    if host.is_empty() || !host.is_ascii() {
+        return None;
+    }
+    let mut host = Vec::from(host.as_bytes());
+    host.push(0);
+    Some(host)
+}
+
+/// Copy the header fields of a response message, and free them.
+///
+/// # Safety
+///
+/// The message must have been filled in by the firmware, which allocates the
+/// header array and each name and value separately.
+#[cfg(feature = "alloc")]
+unsafe fn take_headers(message: &HttpMessage) -> Vec<(String, String)> {
+    let Some(array) = ptr::NonNull::new(message.header) else {
+        return Vec::new();
+    };
+
+    let mut headers = Vec::with_capacity(message.header_count);
+    for i in 0..message.header_count {
+        let field = unsafe { array.as_ptr().add(i).read() };
+        let take = |s: *const u8| {
+            let Some(s) = ptr::NonNull::new(s.cast_mut()) else {
+                return String::new();
+            };
+            let string = unsafe { CStr::from_ptr(s.as_ptr().cast()) }
+                .to_string_lossy()
+                .into_owned();
+            let _ = unsafe { boot::free_pool(s) };
+            string
+        };
+        headers.push((take(field.field_name), take(field.field_value)));
+    }
+    let _ = unsafe { boot::free_pool(array.cast()) };
+    headers
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::cstr16;
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(
+            url_host(cstr16!("http://example.com/index.html")).unwrap(),
+            b"example.com\0"
+        );
+        assert_eq!(
+            url_host(cstr16!("https://10.0.2.2:8080?x")).unwrap(),
+            b"10.0.2.2:8080\0"
+        );
+        assert_eq!(url_host(cstr16!("example.com/index.html")), None);
+        assert_eq!(url_host(cstr16!("http:///index.html")), None);
+    }
+
+    #[test]
+    fn test_response_headers() {
+        let response = HttpResponse {
+            status_code: HttpStatusCode::STATUS_200_OK,
+            headers: vec![
+                ("content-length".into(), " 42 ".into()),
+                ("Server".into(), "test".into()),
+            ],
+            body: Vec::new(),
+        };
+        assert_eq!(response.header("SERVER"), Some("test"));
+        assert_eq!(response.header("Date"), None);
+        assert_eq!(response.content_length(), Some(42));
+    }
+}
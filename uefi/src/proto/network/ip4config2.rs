@@ -0,0 +1,238 @@
+//! IPv4 configuration protocol.
+//!
+//! [`Ip4Config2`] is installed on the handle of a network device, and sets
+//! the policy and addresses used by the IPv4 stack on that device.
+
+use super::Ipv4Address;
+use crate::proto::unsafe_protocol;
+#[cfg(feature = "alloc")]
+use crate::util::usize_from_u32;
+use crate::{boot, Result, ResultExt, Status, StatusExt};
+use core::{mem, ptr};
+use uefi_raw::protocol::network::ip4_config2::{
+    Ip4Config2DataType, Ip4Config2ManualAddress, Ip4Config2Protocol,
+};
+
+#[cfg(feature = "alloc")]
+use {
+    super::MacAddress,
+    crate::{CStr16, CString16},
+    alloc::vec,
+    alloc::vec::Vec,
+    core::ffi::c_void,
+    uefi_raw::protocol::network::ip4_config2::Ip4Config2InterfaceInfo,
+};
+
+pub use uefi_raw::protocol::network::ip4::Ip4RouteTable;
+pub use uefi_raw::protocol::network::ip4_config2::Ip4Config2Policy;
+
+/// IPv4 configuration protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Ip4Config2Protocol::GUID)]
+pub struct Ip4Config2(Ip4Config2Protocol);
+
+impl Ip4Config2 {
+    /// Get the current address configuration policy.
+    pub fn policy(&mut self) -> Result<Ip4Config2Policy> {
+        let mut policy = Ip4Config2Policy::STATIC;
+        let mut size = mem::size_of_val(&policy);
+        unsafe {
+            (self.0.get_data)(
+                &mut self.0,
+                Ip4Config2DataType::POLICY,
+                &mut size,
+                ptr::from_mut(&mut policy).cast(),
+            )
+        }
+        .to_result_with_val(|| policy)
+    }
+
+    /// Set the address configuration policy.
+    ///
+    /// Changing the policy clears the current addresses, gateways, and DNS
+    /// servers of the device.
+    pub fn set_policy(&mut self, policy: Ip4Config2Policy) -> Result {
+        unsafe {
+            (self.0.set_data)(
+                &mut self.0,
+                Ip4Config2DataType::POLICY,
+                mem::size_of_val(&policy),
+                ptr::from_ref(&policy).cast(),
+            )
+        }
+        .to_result()
+    }
+
+    /// Set a static address for the device, blocking until the address has
+    /// been applied.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::ACCESS_DENIED`]: the policy is [`Ip4Config2Policy::DHCP`].
+    ///   Use [`set_policy`] to switch to [`Ip4Config2Policy::STATIC`] first.
+    /// * [`Status::IP_ADDRESS_CONFLICT`]: the address is already in use on the
+    ///   network.
+    /// * [`Status::INVALID_PARAMETER`]: the address or subnet mask is invalid.
+    ///
+    /// [`set_policy`]: Self::set_policy
+    pub fn set_manual_address(&mut self, address: Ipv4Address, subnet_mask: Ipv4Address) -> Result {
+        if self.policy()? == Ip4Config2Policy::DHCP {
+            return Err(Status::ACCESS_DENIED.into());
+        }
+
+        let data = Ip4Config2ManualAddress {
+            address,
+            subnet_mask,
+        };
+
+        // The address may be applied asynchronously (e.g. after duplicate
+        // address detection), in which case the firmware returns `NOT_READY`
+        // and signals the data notify event once it is done.
+        let event = unsafe {
+            boot::create_event(boot::EventType::empty(), boot::Tpl::CALLBACK, None, None)
+        }?;
+        let result = unsafe {
+            (self.0.register_data_notify)(
+                &mut self.0,
+                Ip4Config2DataType::MANUAL_ADDRESS,
+                event.as_ptr(),
+            )
+        }
+        .to_result()
+        .and_then(|()| {
+            let status = unsafe {
+                (self.0.set_data)(
+                    &mut self.0,
+                    Ip4Config2DataType::MANUAL_ADDRESS,
+                    mem::size_of_val(&data),
+                    ptr::from_ref(&data).cast(),
+                )
+            };
+            let result = if status == Status::NOT_READY {
+                boot::wait_for_event(&mut [unsafe { event.unsafe_clone() }])
+                    .discard_errdata()
+                    .map(|_| ())
+            } else {
+                status.to_result()
+            };
+
+            let unregister_result = unsafe {
+                (self.0.unregister_data_notify)(
+                    &mut self.0,
+                    Ip4Config2DataType::MANUAL_ADDRESS,
+                    event.as_ptr(),
+                )
+            }
+            .to_result();
+            result.and(unregister_result)
+        });
+        boot::close_event(event)?;
+        result?;
+
+        // If the address could not be applied, the manual address is
+        // cleared.
+        let mut applied = Ip4Config2ManualAddress {
+            address: Ipv4Address::default(),
+            subnet_mask: Ipv4Address::default(),
+        };
+        let mut size = mem::size_of_val(&applied);
+        unsafe {
+            (self.0.get_data)(
+                &mut self.0,
+                Ip4Config2DataType::MANUAL_ADDRESS,
+                &mut size,
+                ptr::from_mut(&mut applied).cast(),
+            )
+        }
+        .to_result()?;
+        if applied.address != address {
+            return Err(Status::IP_ADDRESS_CONFLICT.into());
+        }
+        Ok(())
+    }
+
+    /// Get the current configuration of the device.
+    #[cfg(feature = "alloc")]
+    pub fn get_interface_info(&mut self) -> Result<InterfaceInfo> {
+        let mut size = 0;
+        let status = unsafe {
+            (self.0.get_data)(
+                &mut self.0,
+                Ip4Config2DataType::INTERFACE_INFO,
+                &mut size,
+                ptr::null_mut(),
+            )
+        };
+        if status != Status::BUFFER_TOO_SMALL {
+            status.to_result()?;
+        }
+
+        // The route table is stored in the same buffer, after the interface
+        // info. Use `u64` elements so the buffer is suitably aligned.
+        let mut buf = vec![0u64; size.div_ceil(mem::size_of::<u64>())];
+        let data: *mut c_void = buf.as_mut_ptr().cast();
+        unsafe {
+            (self.0.get_data)(
+                &mut self.0,
+                Ip4Config2DataType::INTERFACE_INFO,
+                &mut size,
+                data,
+            )
+        }
+        .to_result()?;
+
+        let info = unsafe { &*data.cast::<Ip4Config2InterfaceInfo>() };
+        let route_table = if info.route_table.is_null() {
+            Vec::new()
+        } else {
+            unsafe {
+                core::slice::from_raw_parts(info.route_table, usize_from_u32(info.route_table_size))
+            }
+            .to_vec()
+        };
+        let name = CStr16::from_u16_until_nul(&info.name)
+            .map(CString16::from)
+            .unwrap_or_default();
+
+        Ok(InterfaceInfo {
+            name,
+            if_type: info.if_type,
+            hw_address: MacAddress(info.hw_addr.0),
+            hw_address_size: usize_from_u32(info.hw_addr_size),
+            station_address: info.station_addr,
+            subnet_mask: info.subnet_mask,
+            route_table,
+        })
+    }
+}
+
+/// Configuration of a network device, returned by
+/// [`Ip4Config2::get_interface_info`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterfaceInfo {
+    /// Name of the device.
+    pub name: CString16,
+
+    /// Interface type, as defined in RFC 3232.
+    pub if_type: u8,
+
+    /// Hardware address of the device. Only the first
+    /// [`hw_address_size`] bytes are valid.
+    ///
+    /// [`hw_address_size`]: Self::hw_address_size
+    pub hw_address: MacAddress,
+
+    /// Size of the hardware address in bytes.
+    pub hw_address_size: usize,
+
+    /// Current IPv4 address of the device.
+    pub station_address: Ipv4Address,
+
+    /// Subnet mask of the current address.
+    pub subnet_mask: Ipv4Address,
+
+    /// Routes of the device.
+    pub route_table: Vec<Ip4RouteTable>,
+}
@@ -0,0 +1,314 @@
+//! Managed Network Protocol.
+//!
+//! Unlike [`SimpleNetwork`], which gives exclusive access to a network
+//! device, the Managed Network Protocol allows multiple drivers and
+//! applications to send and receive raw frames on the same device at the
+//! same time. This is what the firmware's own network stack is built on.
+//!
+//! An instance is created with the service binding protocol installed on
+//! a network device: open [`ManagedNetworkServiceBinding`] on the device's
+//! handle and call [`ManagedNetworkServiceBinding::create_child`] to get a
+//! new handle with the [`ManagedNetwork`] protocol installed on it.
+//!
+//! The operations of [`ManagedNetwork`] are asynchronous in the UEFI API.
+//! The wrappers here block until the operation completes or the given
+//! timeout expires.
+//! After a timeout, the operation is cancelled and the wrapper keeps
+//! blocking until the firmware has released the buffers of the operation.
+//!
+//! [`SimpleNetwork`]: super::snp::SimpleNetwork
+
+use super::snp::NetworkMode;
+use super::{wait_or_cancel, MacAddress, TokenEvent};
+use crate::proto::unsafe_protocol;
+use crate::util::usize_from_u32;
+use crate::{boot, Event, Handle, Result, Status, StatusExt};
+use core::mem::MaybeUninit;
+use core::time::Duration;
+use core::{ptr, slice};
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::network::mnp::{
+    ManagedNetworkCompletionToken, ManagedNetworkFragmentData, ManagedNetworkPacket,
+    ManagedNetworkProtocol, ManagedNetworkReceiveData, ManagedNetworkTransmitData,
+};
+
+pub use uefi_raw::protocol::network::mnp::ManagedNetworkConfigData as MnpConfigData;
+
+/// Service binding protocol for [`ManagedNetwork`].
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(ManagedNetworkProtocol::SERVICE_BINDING_GUID)]
+pub struct ManagedNetworkServiceBinding(ServiceBindingProtocol);
+
+impl ManagedNetworkServiceBinding {
+    /// Create a new managed network instance, returning the handle on which
+    /// [`ManagedNetwork`] is installed.
+    pub fn create_child(&mut self) -> Result<Handle> {
+        super::create_child(&mut self.0)
+    }
+
+    /// Destroy a managed network instance created with [`create_child`].
+    /// Any pending operations are aborted.
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, handle: Handle) -> Result {
+        super::destroy_child(&mut self.0, handle)
+    }
+}
+
+/// Information about a frame received with [`ManagedNetwork::receive`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceivedFrame {
+    /// Number of bytes of frame data written to the buffer.
+    pub len: usize,
+    /// Protocol type of the frame, e.g. `0x0800` for IPv4 on Ethernet.
+    pub protocol_type: u16,
+    /// Hardware address of the sender.
+    pub source: MacAddress,
+    /// Hardware address the frame was sent to.
+    pub destination: MacAddress,
+}
+
+/// Managed Network Protocol.
+///
+/// Use [`ManagedNetworkServiceBinding`] to create an instance of this
+/// protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(ManagedNetworkProtocol::GUID)]
+pub struct ManagedNetwork(ManagedNetworkProtocol);
+
+impl ManagedNetwork {
+    /// Get the instance's configuration and the mode of the underlying
+    /// network device. The device's hardware address is
+    /// [`NetworkMode::current_address`].
+    ///
+    /// If the instance has not been configured yet, the default
+    /// configuration is returned.
+    pub fn get_mode_data(&self) -> Result<(MnpConfigData, NetworkMode)> {
+        let mut config = MnpConfigData::default();
+        let mut mode = MaybeUninit::<NetworkMode>::uninit();
+        let status =
+            unsafe { (self.0.get_mode_data)(&self.0, &mut config, mode.as_mut_ptr().cast()) };
+        match status {
+            // The default configuration and the device mode are still
+            // returned if the instance is not configured.
+            Status::SUCCESS | Status::NOT_STARTED => Ok((config, unsafe { mode.assume_init() })),
+            _ => Err(status.into()),
+        }
+    }
+
+    /// Configure the instance, or reset it to the unconfigured state if
+    /// `config` is `None`. Resetting an instance aborts any pending
+    /// operations.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the configuration is invalid.
+    /// * [`Status::OUT_OF_RESOURCES`]: the firmware could not allocate the
+    ///   resources needed for the configuration.
+    /// * [`Status::UNSUPPORTED`]: the configuration is not supported by the
+    ///   network device.
+    /// * [`Status::DEVICE_ERROR`]: the network device reported an error.
+    pub fn configure(&mut self, config: Option<&MnpConfigData>) -> Result {
+        let config = config.map_or(ptr::null(), ptr::from_ref);
+        unsafe { (self.0.configure)(&mut self.0, config) }.to_result()
+    }
+
+    /// Send `data` as a single frame of type `protocol_type` to
+    /// `destination`.
+    ///
+    /// The media header is built by the firmware, so `data` is only the
+    /// payload of the frame. The source address is the hardware address of
+    /// the network device.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: the frame was not sent within `timeout`.
+    /// * [`Status::BAD_BUFFER_SIZE`]: `data` is too large for a frame.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    /// * [`Status::DEVICE_ERROR`]: the network device reported an error.
+    pub fn transmit(
+        &mut self,
+        data: &[u8],
+        destination: &MacAddress,
+        protocol_type: u16,
+        timeout: Duration,
+    ) -> Result {
+        let len = u32::try_from(data.len()).map_err(|_| Status::BAD_BUFFER_SIZE)?;
+
+        let event = TokenEvent::new()?;
+        let mut tx_data = ManagedNetworkTransmitData {
+            // The firmware only reads from the address.
+            destination_address: ptr::from_ref(destination).cast_mut().cast(),
+            source_address: ptr::null_mut(),
+            protocol_type,
+            data_length: len,
+            header_length: 0,
+            fragment_count: 1,
+            fragment_table: [ManagedNetworkFragmentData {
+                fragment_length: len,
+                // The firmware only reads from the buffer.
+                fragment_buffer: data.as_ptr().cast_mut().cast(),
+            }],
+        };
+        let mut token = completion_token(
+            &event,
+            ManagedNetworkPacket {
+                tx_data: &mut tx_data,
+            },
+        );
+        unsafe { (self.0.transmit)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token, timeout)
+    }
+
+    /// Receive a frame into `buffer`.
+    ///
+    /// Only the payload of the frame is written to `buffer`, not the media
+    /// header. If the payload is larger than `buffer`, the excess data is
+    /// discarded.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: no frame was received within `timeout`.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    /// * [`Status::DEVICE_ERROR`]: the network device reported an error.
+    pub fn receive(&mut self, buffer: &mut [u8], timeout: Duration) -> Result<ReceivedFrame> {
+        let event = TokenEvent::new()?;
+        let mut token = completion_token(
+            &event,
+            ManagedNetworkPacket {
+                rx_data: ptr::null_mut(),
+            },
+        );
+        unsafe { (self.0.receive)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token, timeout)?;
+
+        // Safety: on success the firmware has set `rx_data` to the received
+        // data, which stays valid until the recycle event is signaled.
+        let rx_data = unsafe { &*token.packet.rx_data };
+        let frame = unsafe { read_frame(rx_data, buffer) };
+
+        // Let the firmware release the receive buffers.
+        if let Some(recycle) = unsafe { Event::from_ptr(rx_data.recycle_event) } {
+            boot::signal_event(&recycle)?;
+        }
+
+        Ok(frame)
+    }
+
+    /// Wait for the operation using `token` to complete, and return the
+    /// status it completed with. If `timeout` expires first, the operation is
+    /// stopped and [`Status::TIMEOUT`] is returned.
+    fn wait(
+        &mut self,
+        event: &TokenEvent,
+        token: &mut ManagedNetworkCompletionToken,
+        timeout: Duration,
+    ) -> Result {
+        let this: *mut ManagedNetworkProtocol = &mut self.0;
+        let token: *mut ManagedNetworkCompletionToken = token;
+        let poll = || {
+            // Polling just speeds up processing, so errors can be ignored.
+            let _ = unsafe { ((*this).poll)(this) };
+        };
+        let cancel = || unsafe { ((*this).cancel)(this, token) };
+        // Resetting the instance aborts all pending operations.
+        let abort = || unsafe { ((*this).configure)(this, ptr::null()) };
+
+        // If the operation completed, a received packet must still be
+        // processed, so report its result.
+        if wait_or_cancel(event, timeout, poll, cancel, abort) {
+            unsafe { (*token).status }.to_result()
+        } else {
+            Err(Status::TIMEOUT.into())
+        }
+    }
+}
+
+/// Get a completion token that signals `event`.
+const fn completion_token(
+    event: &TokenEvent,
+    packet: ManagedNetworkPacket,
+) -> ManagedNetworkCompletionToken {
+    ManagedNetworkCompletionToken {
+        event: event.0.as_ptr(),
+        status: Status::NOT_READY,
+        packet,
+    }
+}
+
+/// Copy the payload of `rx_data` into `buffer`, discarding data that doesn't
+/// fit, and get the frame's addresses and type.
+///
+/// # Safety
+///
+/// The data and address pointers of `rx_data` must be valid for
+/// `data_length` and `address_length` bytes respectively.
+unsafe fn read_frame(rx_data: &ManagedNetworkReceiveData, buffer: &mut [u8]) -> ReceivedFrame {
+    let len = usize_from_u32(rx_data.data_length).min(buffer.len());
+    if len > 0 {
+        let data = unsafe { slice::from_raw_parts(rx_data.packet_data.cast::<u8>(), len) };
+        buffer[..len].copy_from_slice(data);
+    }
+
+    let address_length = usize_from_u32(rx_data.address_length);
+    let read_address = |address: *const u8| {
+        let mut mac = MacAddress([0; 32]);
+        let len = address_length.min(mac.0.len());
+        if len > 0 && !address.is_null() {
+            mac.0[..len].copy_from_slice(unsafe { slice::from_raw_parts(address, len) });
+        }
+        mac
+    };
+
+    ReceivedFrame {
+        len,
+        protocol_type: rx_data.protocol_type,
+        source: read_address(rx_data.source_address.cast()),
+        destination: read_address(rx_data.destination_address.cast()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem;
+
+    #[test]
+    fn test_read_frame() {
+        let mut data = *b"payload";
+        let mut src = [0x52, 0x54, 0x00, 0x12, 0x34, 0x56];
+        let mut dst = [0xff; 6];
+        let rx_data = ManagedNetworkReceiveData {
+            timestamp: unsafe { mem::zeroed() },
+            recycle_event: ptr::null_mut(),
+            packet_length: 21,
+            header_length: 14,
+            address_length: 6,
+            data_length: 7,
+            broadcast_flag: true,
+            multicast_flag: false,
+            promiscuous_flag: false,
+            protocol_type: 0x88b5,
+            destination_address: dst.as_mut_ptr().cast(),
+            source_address: src.as_mut_ptr().cast(),
+            media_header: ptr::null_mut(),
+            packet_data: data.as_mut_ptr().cast(),
+        };
+
+        let mut buffer = [0; 16];
+        let frame = unsafe { read_frame(&rx_data, &mut buffer) };
+        assert_eq!(frame.len, 7);
+        assert_eq!(&buffer[..7], b"payload");
+        assert_eq!(frame.protocol_type, 0x88b5);
+        assert_eq!(&frame.source.0[..6], &src);
+        assert_eq!(frame.source.0[6..], [0; 26]);
+        assert_eq!(&frame.destination.0[..6], &dst);
+
+        // Data that doesn't fit is discarded.
+        let mut buffer = [0; 3];
+        assert_eq!(unsafe { read_frame(&rx_data, &mut buffer) }.len, 3);
+        assert_eq!(&buffer, b"pay");
+    }
+}
@@ -2,8 +2,21 @@
 //!
 //! These protocols can be used to interact with network resources.
 
+pub mod dhcp4;
+pub mod http;
+pub mod ip4config2;
+pub mod mnp;
 pub mod pxe;
 pub mod snp;
+pub mod tcp;
+pub mod udp;
+
+use crate::{boot, Event, Handle, Result, Status, StatusExt};
+use core::ptr;
+use core::time::Duration;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+
+pub use uefi_raw::Ipv4Address;
 
 /// Represents an IPv4/v6 address.
 ///
@@ -31,9 +44,192 @@ impl IpAddress {
     }
 }
 
+impl From<core::net::Ipv4Addr> for IpAddress {
+    fn from(addr: core::net::Ipv4Addr) -> Self {
+        Self::new_v4(addr.octets())
+    }
+}
+
+impl From<core::net::Ipv6Addr> for IpAddress {
+    fn from(addr: core::net::Ipv6Addr) -> Self {
+        Self::new_v6(addr.octets())
+    }
+}
+
+impl From<core::net::IpAddr> for IpAddress {
+    fn from(addr: core::net::IpAddr) -> Self {
+        match addr {
+            core::net::IpAddr::V4(addr) => addr.into(),
+            core::net::IpAddr::V6(addr) => addr.into(),
+        }
+    }
+}
+
 /// Represents a MAC (media access control) address.
 ///
 /// Corresponds to the `EFI_MAC_ADDRESS` type in the C API.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct MacAddress(pub [u8; 32]);
+
+impl From<[u8; 6]> for MacAddress {
+    fn from(addr: [u8; 6]) -> Self {
+        uefi_raw::MacAddress::from(addr).into()
+    }
+}
+
+impl From<uefi_raw::MacAddress> for MacAddress {
+    fn from(addr: uefi_raw::MacAddress) -> Self {
+        Self(addr.0)
+    }
+}
+
+impl From<MacAddress> for uefi_raw::MacAddress {
+    fn from(addr: MacAddress) -> Self {
+        Self(addr.0)
+    }
+}
+
+/// Create a child handle with a network service binding protocol.
+fn create_child(service_binding: &mut ServiceBindingProtocol) -> Result<Handle> {
+    let mut handle = ptr::null_mut();
+    unsafe { (service_binding.create_child)(service_binding, &mut handle) }
+        .to_result_with_val(|| unsafe { Handle::from_ptr(handle) }.unwrap())
+}
+
+/// Destroy a child handle created with [`create_child`].
+fn destroy_child(service_binding: &mut ServiceBindingProtocol, handle: Handle) -> Result {
+    unsafe { (service_binding.destroy_child)(service_binding, handle.as_ptr()) }.to_result()
+}
+
+/// Interval between checks for completion of an asynchronous operation, in
+/// microseconds.
+const POLL_INTERVAL_US: usize = 1000;
+
+/// Time to wait for a stopped operation to complete before logging a
+/// warning.
+const STOP_WARN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Completion event of an asynchronous operation. The event is closed on
+/// drop.
+#[derive(Debug)]
+struct TokenEvent(Event);
+
+impl TokenEvent {
+    fn new() -> Result<Self> {
+        let event = unsafe {
+            boot::create_event(boot::EventType::empty(), boot::Tpl::CALLBACK, None, None)
+        }?;
+        Ok(Self(event))
+    }
+}
+
+impl Drop for TokenEvent {
+    fn drop(&mut self) {
+        let _ = boot::close_event(unsafe { self.0.unsafe_clone() });
+    }
+}
+
+/// Wait until `event` is signaled, calling `poll` between checks. Returns
+/// `false` if `timeout` expires first.
+///
+/// Checking fails only for events that have a notification function, which
+/// token events don't have, so a failure is treated as not signaled.
+fn wait_for_event(event: &Event, timeout: Duration, mut poll: impl FnMut()) -> bool {
+    let timeout = timeout.as_micros();
+    let mut elapsed = 0;
+    loop {
+        poll();
+        if boot::check_event(unsafe { event.unsafe_clone() }).unwrap_or(false) {
+            return true;
+        }
+        if elapsed >= timeout {
+            return false;
+        }
+        boot::stall(POLL_INTERVAL_US);
+        elapsed += POLL_INTERVAL_US as u128;
+    }
+}
+
+/// Wait for the asynchronous operation signaling `event` to complete,
+/// stopping it if `timeout` expires first. `poll` is called between checks.
+///
+/// Returns `true` if the operation completed on its own, in which case the
+/// status of its token holds the result, or `false` if it was stopped.
+///
+/// After a timeout, `cancel` is called to cancel the operation. If that
+/// fails, `abort` is called to abort all pending operations of the
+/// instance. The firmware references the token and the buffers of the
+/// operation until the event is signaled, so this never returns before
+/// then, even if both fail. See [`wait_until_stopped`].
+fn wait_or_cancel(
+    event: &TokenEvent,
+    timeout: Duration,
+    poll: impl FnMut() + Copy,
+    cancel: impl FnOnce() -> Status,
+    abort: impl FnOnce() -> Status,
+) -> bool {
+    if wait_for_event(&event.0, timeout, poll) {
+        return true;
+    }
+
+    let completed = match cancel() {
+        Status::SUCCESS => false,
+        // The operation completed in the meantime.
+        Status::NOT_FOUND => true,
+        cancel_status => {
+            let abort_status = abort();
+            if abort_status.is_error() {
+                log::warn!(
+                    "failed to stop network operation: cancel: {cancel_status}, abort: {abort_status}"
+                );
+            }
+            false
+        }
+    };
+    wait_until_stopped(event, poll);
+    completed
+}
+
+/// Wait until the stopped operation signaling `event` has completed,
+/// calling `poll` between checks.
+///
+/// This blocks indefinitely if the firmware never signals the event, since
+/// returning would let the firmware write to memory that is no longer
+/// valid. A warning is logged if it takes longer than
+/// [`STOP_WARN_TIMEOUT`].
+fn wait_until_stopped(event: &TokenEvent, poll: impl FnMut() + Copy) {
+    if wait_for_event(&event.0, STOP_WARN_TIMEOUT, poll) {
+        return;
+    }
+    log::warn!("still waiting for a stopped network operation to complete");
+    while !wait_for_event(&event.0, Duration::MAX, poll) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_ip_address_from_core_net() {
+        let v4 = Ipv4Addr::new(10, 0, 2, 15);
+        assert_eq!(IpAddress::from(v4), IpAddress::new_v4([10, 0, 2, 15]));
+        assert_eq!(IpAddress::from(IpAddr::V4(v4)), IpAddress::from(v4));
+
+        let v6 = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+        assert_eq!(IpAddress::from(v6), IpAddress::new_v6(v6.octets()));
+        assert_eq!(IpAddress::from(IpAddr::V6(v6)), IpAddress::from(v6));
+    }
+
+    #[test]
+    fn test_mac_address_conversions() {
+        let mac = MacAddress::from([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        assert_eq!(mac.0[..6], [0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        assert!(mac.0[6..].iter().all(|b| *b == 0));
+
+        let raw = uefi_raw::MacAddress::from(mac);
+        assert_eq!(raw.0, mac.0);
+        assert_eq!(MacAddress::from(raw), mac);
+    }
+}
@@ -164,13 +164,23 @@ impl BaseCode {
         status.to_result_with_val(|| buffer_size)
     }
 
-    /// Reads a file located on a TFTP server.
+    /// Reads a file located on a TFTP server, returning the size of the file.
+    ///
+    /// If `buffer` is `None`, the file is downloaded but not stored.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BUFFER_TOO_SMALL`]: `buffer` is too small to hold the
+    ///   file. The required size is returned in the error data.
+    /// * [`Status::TFTP_ERROR`]: the server returned an error; see
+    ///   [`Mode::tftp_error`].
+    /// * [`Status::TIMEOUT`]: the server did not respond.
     pub fn tftp_read_file(
         &mut self,
         server_ip: &IpAddress,
         filename: &CStr8,
         buffer: Option<&mut [u8]>,
-    ) -> Result<u64> {
+    ) -> Result<u64, Option<u64>> {
         let (buffer_ptr, mut buffer_size, dont_use_buffer) = if let Some(buffer) = buffer {
             let buffer_size = u64::try_from(buffer.len()).unwrap();
             (buffer.as_mut_ptr().cast(), buffer_size, false)
@@ -192,7 +202,11 @@ impl BaseCode {
                 dont_use_buffer,
             )
         };
-        status.to_result_with_val(|| buffer_size)
+        status.to_result_with(
+            || buffer_size,
+            // The required size is returned if the buffer is too small.
+            |status| (status == Status::BUFFER_TOO_SMALL).then_some(buffer_size),
+        )
     }
 
     /// Writes to a file located on a TFTP server.
@@ -324,14 +338,25 @@ impl BaseCode {
         status.to_result_with_val(|| buffer_size)
     }
 
-    /// Reads a file located on a MTFTP server.
+    /// Reads a file located on a MTFTP server, returning the size of the
+    /// file.
+    ///
+    /// If `buffer` is `None`, the file is downloaded but not stored.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BUFFER_TOO_SMALL`]: `buffer` is too small to hold the
+    ///   file. The required size is returned in the error data.
+    /// * [`Status::TFTP_ERROR`]: the server returned an error; see
+    ///   [`Mode::tftp_error`].
+    /// * [`Status::TIMEOUT`]: the server did not respond.
     pub fn mtftp_read_file(
         &mut self,
         server_ip: &IpAddress,
         filename: &CStr8,
         buffer: Option<&mut [u8]>,
         info: &MtftpInfo,
-    ) -> Result<u64> {
+    ) -> Result<u64, Option<u64>> {
         let (buffer_ptr, mut buffer_size, dont_use_buffer) = if let Some(buffer) = buffer {
             let buffer_size = u64::try_from(buffer.len()).unwrap();
             (buffer.as_mut_ptr().cast(), buffer_size, false)
@@ -353,7 +378,11 @@ impl BaseCode {
                 dont_use_buffer,
             )
         };
-        status.to_result_with_val(|| buffer_size)
+        status.to_result_with(
+            || buffer_size,
+            // The required size is returned if the buffer is too small.
+            |status| (status == Status::BUFFER_TOO_SMALL).then_some(buffer_size),
+        )
     }
 
     /// Reads a directory listing of a directory on a MTFTP server.
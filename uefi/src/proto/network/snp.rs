@@ -256,6 +256,46 @@ impl SimpleNetwork {
         status.to_result_with_val(|| buffer_size)
     }
 
+    /// Receive a packet from a network interface, if one is available.
+    ///
+    /// Unlike [`receive`], this returns `Ok(None)` instead of an error if
+    /// no packet has been received, and returns the header size and
+    /// addresses recovered from the media header along with the packet
+    /// size.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_STARTED`]: the network interface has not been started.
+    /// * [`Status::BUFFER_TOO_SMALL`]: `buffer` is too small for the
+    ///   received packet.
+    /// * [`Status::DEVICE_ERROR`]: the command could not be sent to the
+    ///   network interface.
+    ///
+    /// [`receive`]: Self::receive
+    pub fn try_receive(&self, buffer: &mut [u8]) -> Result<Option<ReceiveInfo>> {
+        let mut header_size = 0;
+        let mut src_addr = MacAddress([0; 32]);
+        let mut dest_addr = MacAddress([0; 32]);
+        let mut protocol = 0;
+        match self.receive(
+            buffer,
+            Some(&mut header_size),
+            Some(&mut src_addr),
+            Some(&mut dest_addr),
+            Some(&mut protocol),
+        ) {
+            Ok(len) => Ok(Some(ReceiveInfo {
+                len,
+                header_size,
+                src_addr,
+                dest_addr,
+                protocol,
+            })),
+            Err(err) if err.status() == Status::NOT_READY => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Event that fires once a packet is available to be received.
     ///
     /// On QEMU, this event seems to never fire; it is suggested to verify that your implementation
@@ -272,6 +312,22 @@ impl SimpleNetwork {
     }
 }
 
+/// Information about a packet received with [`SimpleNetwork::try_receive`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceiveInfo {
+    /// Size of the packet in bytes, including the media header.
+    pub len: usize,
+    /// Size of the media header in bytes.
+    pub header_size: usize,
+    /// Source hardware address of the packet.
+    pub src_addr: MacAddress,
+    /// Destination hardware address of the packet.
+    pub dest_addr: MacAddress,
+    /// Protocol type from the media header, such as `0x0800` for IPv4 on
+    /// Ethernet.
+    pub protocol: u16,
+}
+
 bitflags! {
     /// Flags to pass to receive_filters to enable/disable reception of some kinds of packets.
     #[repr(transparent)]
@@ -0,0 +1,289 @@
+//! TCP protocols.
+//!
+//! A TCP instance is created with the service binding protocol installed on
+//! a network device: open [`Tcp4ServiceBinding`] on the device's handle and
+//! call [`Tcp4ServiceBinding::create_child`] to get a new handle with the
+//! [`Tcp4`] protocol installed on it.
+//!
+//! The operations of [`Tcp4`] are asynchronous in the UEFI API. The wrappers
+//! here block until the operation completes or the given timeout expires.
+//! After a timeout, the operation is cancelled and the wrapper keeps
+//! blocking until the firmware has released the buffers of the operation.
+
+use super::{wait_or_cancel, wait_until_stopped, TokenEvent};
+use crate::proto::unsafe_protocol;
+use crate::util::usize_from_u32;
+use crate::{Handle, Result, Status, StatusExt};
+use core::ptr;
+use core::time::Duration;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::network::tcp4::{
+    Tcp4CloseToken, Tcp4CompletionToken, Tcp4ConnectionToken, Tcp4FragmentData, Tcp4IoToken,
+    Tcp4Packet, Tcp4Protocol, Tcp4ReceiveData, Tcp4TransmitData,
+};
+
+pub use uefi_raw::protocol::network::tcp4::{Tcp4AccessPoint, Tcp4ConnectionState, Tcp4Option};
+
+/// Service binding protocol for [`Tcp4`].
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Tcp4Protocol::SERVICE_BINDING_GUID)]
+pub struct Tcp4ServiceBinding(ServiceBindingProtocol);
+
+impl Tcp4ServiceBinding {
+    /// Create a new TCPv4 instance, returning the handle on which [`Tcp4`] is
+    /// installed.
+    pub fn create_child(&mut self) -> Result<Handle> {
+        super::create_child(&mut self.0)
+    }
+
+    /// Destroy a TCPv4 instance created with [`create_child`]. Any open
+    /// connection is aborted.
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, handle: Handle) -> Result {
+        super::destroy_child(&mut self.0, handle)
+    }
+}
+
+/// Configuration of a [`Tcp4`] instance.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Tcp4ConfigData {
+    /// Type of service field in transmitted IPv4 packets.
+    pub type_of_service: u8,
+
+    /// Time to live field in transmitted IPv4 packets.
+    pub time_to_live: u8,
+
+    /// Local and remote addresses and ports of the connection.
+    pub access_point: Tcp4AccessPoint,
+
+    /// Advanced TCP options. If `None`, the firmware's defaults are used.
+    pub control_option: Option<Tcp4Option>,
+}
+
+/// TCPv4 protocol.
+///
+/// Use [`Tcp4ServiceBinding`] to create an instance of this protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Tcp4Protocol::GUID)]
+pub struct Tcp4(Tcp4Protocol);
+
+impl Tcp4 {
+    /// Get the current state of the connection.
+    pub fn state(&self) -> Result<Tcp4ConnectionState> {
+        let mut state = Tcp4ConnectionState::CLOSED;
+        unsafe {
+            (self.0.get_mode_data)(
+                &self.0,
+                &mut state,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        }
+        .to_result_with_val(|| state)
+    }
+
+    /// Configure the instance, or reset it to the unconfigured state if
+    /// `config` is `None`. Resetting an instance aborts any open connection.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NO_MAPPING`]: the default address is used but has not been
+    ///   acquired yet (e.g. DHCP is still in progress). Try again later.
+    /// * [`Status::ACCESS_DENIED`]: the instance is already configured.
+    ///   Reset it before configuring it again.
+    /// * [`Status::INVALID_PARAMETER`]: the configuration is invalid.
+    pub fn configure(&mut self, config: Option<&Tcp4ConfigData>) -> Result {
+        let Some(config) = config else {
+            return unsafe { (self.0.configure)(&mut self.0, ptr::null()) }.to_result();
+        };
+
+        let mut control_option = config.control_option;
+        let raw_config = uefi_raw::protocol::network::tcp4::Tcp4ConfigData {
+            type_of_service: config.type_of_service,
+            time_to_live: config.time_to_live,
+            access_point: config.access_point,
+            control_option: control_option
+                .as_mut()
+                .map_or(ptr::null_mut(), ptr::from_mut),
+        };
+        unsafe { (self.0.configure)(&mut self.0, &raw_config) }.to_result()
+    }
+
+    /// Open an active connection to the remote address configured with
+    /// [`configure`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: the connection was not established within
+    ///   `timeout`.
+    /// * [`Status::CONNECTION_REFUSED`]: the remote refused the connection.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    /// * [`Status::ACCESS_DENIED`]: the instance is not configured as an active
+    ///   connection, or is not in the closed state.
+    ///
+    /// [`configure`]: Self::configure
+    pub fn connect(&mut self, timeout: Duration) -> Result {
+        let event = TokenEvent::new()?;
+        let mut token = Tcp4ConnectionToken {
+            completion_token: completion_token(&event),
+        };
+        unsafe { (self.0.connect)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token.completion_token, timeout)
+    }
+
+    /// Send all of `data` to the remote.
+    ///
+    /// The data is pushed to the remote immediately rather than buffered.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: the data was not sent within `timeout`.
+    /// * [`Status::BAD_BUFFER_SIZE`]: `data` is larger than `u32::MAX` bytes.
+    /// * [`Status::CONNECTION_FIN`]: the connection is closing.
+    /// * [`Status::CONNECTION_RESET`]: the connection was reset by the
+    ///   remote.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    pub fn transmit(&mut self, data: &[u8], timeout: Duration) -> Result {
+        let len = u32::try_from(data.len()).map_err(|_| Status::BAD_BUFFER_SIZE)?;
+
+        let event = TokenEvent::new()?;
+        let mut tx_data = Tcp4TransmitData {
+            push: true,
+            urgent: false,
+            data_length: len,
+            fragment_count: 1,
+            fragment_table: [Tcp4FragmentData {
+                fragment_length: len,
+                // The firmware only reads from the buffer.
+                fragment_buffer: data.as_ptr().cast_mut().cast(),
+            }],
+        };
+        let mut token = Tcp4IoToken {
+            completion_token: completion_token(&event),
+            packet: Tcp4Packet {
+                tx_data: &mut tx_data,
+            },
+        };
+        unsafe { (self.0.transmit)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token.completion_token, timeout)
+    }
+
+    /// Receive data from the remote into `buffer`, returning the number of
+    /// bytes received. This may be less than the size of `buffer`.
+    ///
+    /// If the remote has closed the connection and no more data is
+    /// available, `Ok(0)` is returned.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: no data was received within `timeout`.
+    /// * [`Status::CONNECTION_RESET`]: the connection was reset by the
+    ///   remote.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    pub fn receive(&mut self, buffer: &mut [u8], timeout: Duration) -> Result<usize> {
+        // Larger buffers can't be passed to the firmware, but a partial read
+        // is allowed, so just don't use the rest of the buffer.
+        let len = u32::try_from(buffer.len()).unwrap_or(u32::MAX);
+
+        let event = TokenEvent::new()?;
+        let mut rx_data = Tcp4ReceiveData {
+            urgent_flag: false,
+            data_length: len,
+            fragment_count: 1,
+            fragment_table: [Tcp4FragmentData {
+                fragment_length: len,
+                fragment_buffer: buffer.as_mut_ptr().cast(),
+            }],
+        };
+        let rx_data_ptr: *mut _ = &mut rx_data;
+        let mut token = Tcp4IoToken {
+            completion_token: completion_token(&event),
+            packet: Tcp4Packet {
+                rx_data: rx_data_ptr,
+            },
+        };
+
+        let result = unsafe { (self.0.receive)(&mut self.0, &mut token) }
+            .to_result()
+            .and_then(|()| self.wait(&event, &mut token.completion_token, timeout));
+        match result {
+            Ok(()) => Ok(usize_from_u32(unsafe { (*rx_data_ptr).data_length })),
+            Err(err) if err.status() == Status::CONNECTION_FIN => Ok(0),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Close the connection.
+    ///
+    /// If `abort` is true, the connection is reset immediately and any
+    /// pending operations are aborted. Otherwise the connection is closed
+    /// gracefully.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: the connection was not closed within `timeout`.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    pub fn close(&mut self, abort: bool, timeout: Duration) -> Result {
+        let event = TokenEvent::new()?;
+        let mut token = Tcp4CloseToken {
+            completion_token: completion_token(&event),
+            abort_on_close: abort,
+        };
+        unsafe { (self.0.close)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token.completion_token, timeout)
+    }
+
+    /// Wait for the operation using `token` to complete, and return the
+    /// status it completed with. If `timeout` expires first, the operation is
+    /// stopped and [`Status::TIMEOUT`] is returned.
+    fn wait(
+        &mut self,
+        event: &TokenEvent,
+        token: &mut Tcp4CompletionToken,
+        timeout: Duration,
+    ) -> Result {
+        let this: *mut Tcp4Protocol = &mut self.0;
+        let token: *mut Tcp4CompletionToken = token;
+        let poll = || {
+            // Polling just speeds up processing, so errors can be ignored.
+            let _ = unsafe { ((*this).poll)(this) };
+        };
+        let cancel = || unsafe { ((*this).cancel)(this, token) };
+        let abort = || {
+            // Cancellation is not supported by all implementations, but an
+            // abortive close flushes all pending operations.
+            let close_event = match TokenEvent::new() {
+                Ok(event) => event,
+                Err(err) => return err.status(),
+            };
+            let mut close_token = Tcp4CloseToken {
+                completion_token: completion_token(&close_event),
+                abort_on_close: true,
+            };
+            let status = unsafe { ((*this).close)(this, &mut close_token) };
+            if status.is_success() {
+                wait_until_stopped(&close_event, poll);
+            }
+            status
+        };
+
+        if wait_or_cancel(event, timeout, poll, cancel, abort) {
+            unsafe { (*token).status }.to_result()
+        } else {
+            Err(Status::TIMEOUT.into())
+        }
+    }
+}
+
+/// Get a completion token that signals `event`.
+const fn completion_token(event: &TokenEvent) -> Tcp4CompletionToken {
+    Tcp4CompletionToken {
+        event: event.0.as_ptr(),
+        status: Status::NOT_READY,
+    }
+}
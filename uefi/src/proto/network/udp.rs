@@ -0,0 +1,273 @@
+//! UDP protocols.
+//!
+//! A UDP instance is created with the service binding protocol installed on
+//! a network device: open [`Udp4ServiceBinding`] on the device's handle and
+//! call [`Udp4ServiceBinding::create_child`] to get a new handle with the
+//! [`Udp4`] protocol installed on it.
+//!
+//! The operations of [`Udp4`] are asynchronous in the UEFI API. The wrappers
+//! here block until the operation completes or the given timeout expires.
+//! After a timeout, the operation is cancelled and the wrapper keeps
+//! blocking until the firmware has released the buffers of the operation.
+
+use super::{wait_or_cancel, Ipv4Address, TokenEvent};
+use crate::proto::unsafe_protocol;
+use crate::util::usize_from_u32;
+use crate::{boot, Event, Handle, Result, Status, StatusExt};
+use core::time::Duration;
+use core::{ptr, slice};
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+use uefi_raw::protocol::network::udp4::{
+    Udp4CompletionToken, Udp4FragmentData, Udp4Packet, Udp4Protocol, Udp4ReceiveData,
+    Udp4SessionData, Udp4TransmitData,
+};
+
+pub use uefi_raw::protocol::network::udp4::Udp4ConfigData;
+
+/// Service binding protocol for [`Udp4`].
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Udp4Protocol::SERVICE_BINDING_GUID)]
+pub struct Udp4ServiceBinding(ServiceBindingProtocol);
+
+impl Udp4ServiceBinding {
+    /// Create a new UDPv4 instance, returning the handle on which [`Udp4`] is
+    /// installed.
+    pub fn create_child(&mut self) -> Result<Handle> {
+        super::create_child(&mut self.0)
+    }
+
+    /// Destroy a UDPv4 instance created with [`create_child`]. Any pending
+    /// operations are aborted.
+    ///
+    /// [`create_child`]: Self::create_child
+    pub fn destroy_child(&mut self, handle: Handle) -> Result {
+        super::destroy_child(&mut self.0, handle)
+    }
+}
+
+/// UDPv4 protocol.
+///
+/// Use [`Udp4ServiceBinding`] to create an instance of this protocol.
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Udp4Protocol::GUID)]
+pub struct Udp4(Udp4Protocol);
+
+impl Udp4 {
+    /// Configure the instance, or reset it to the unconfigured state if
+    /// `config` is `None`. Resetting an instance aborts any pending
+    /// operations.
+    ///
+    /// If `config.station_address` is `0.0.0.0`, the default address of the
+    /// network device (e.g. the one acquired by DHCP) is used, as if
+    /// `use_default_address` were set.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NO_MAPPING`]: the default address is used but has not been
+    ///   acquired yet (e.g. DHCP is still in progress). Try again later.
+    /// * [`Status::ALREADY_STARTED`]: the instance is already configured.
+    ///   Reset it before configuring it again.
+    /// * [`Status::ACCESS_DENIED`]: the station port is already in use and
+    ///   `allow_duplicate_port` is not set.
+    /// * [`Status::INVALID_PARAMETER`]: the configuration is invalid.
+    pub fn configure(&mut self, config: Option<&Udp4ConfigData>) -> Result {
+        let Some(config) = config else {
+            return unsafe { (self.0.configure)(&mut self.0, ptr::null()) }.to_result();
+        };
+
+        let mut config = *config;
+        if config.station_address == Ipv4Address::default() {
+            config.use_default_address = true;
+        }
+        unsafe { (self.0.configure)(&mut self.0, &config) }.to_result()
+    }
+
+    /// Send `data` as a single datagram to port `port` at `destination`.
+    ///
+    /// The source address and port are those of the instance's
+    /// configuration.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: the datagram was not sent within `timeout`.
+    /// * [`Status::BAD_BUFFER_SIZE`]: `data` is too large for a datagram.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    /// * [`Status::NO_MAPPING`]: the default address has not been acquired
+    ///   yet.
+    /// * [`Status::ICMP_ERROR`]: an ICMP error was received for the datagram.
+    pub fn transmit(
+        &mut self,
+        data: &[u8],
+        destination: Ipv4Address,
+        port: u16,
+        timeout: Duration,
+    ) -> Result {
+        let len = u32::try_from(data.len()).map_err(|_| Status::BAD_BUFFER_SIZE)?;
+
+        let event = TokenEvent::new()?;
+        let mut session = Udp4SessionData {
+            destination_address: destination,
+            destination_port: port,
+            ..Default::default()
+        };
+        let mut tx_data = Udp4TransmitData {
+            udp_session_data: &mut session,
+            gateway_address: ptr::null_mut(),
+            data_length: len,
+            fragment_count: 1,
+            fragment_table: [Udp4FragmentData {
+                fragment_length: len,
+                // The firmware only reads from the buffer.
+                fragment_buffer: data.as_ptr().cast_mut().cast(),
+            }],
+        };
+        let mut token = completion_token(
+            &event,
+            Udp4Packet {
+                tx_data: &mut tx_data,
+            },
+        );
+        unsafe { (self.0.transmit)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token, timeout)
+    }
+
+    /// Receive a datagram into `buffer`.
+    ///
+    /// Returns the number of bytes written to `buffer`, and the address and
+    /// port the datagram was sent from. If the datagram is larger than
+    /// `buffer`, the excess data is discarded.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::TIMEOUT`]: no datagram was received within `timeout`.
+    /// * [`Status::NOT_STARTED`]: the instance has not been configured.
+    /// * [`Status::NO_MAPPING`]: the default address has not been acquired
+    ///   yet.
+    /// * [`Status::ICMP_ERROR`]: an ICMP error was received.
+    pub fn receive(
+        &mut self,
+        buffer: &mut [u8],
+        timeout: Duration,
+    ) -> Result<(usize, Ipv4Address, u16)> {
+        let event = TokenEvent::new()?;
+        let mut token = completion_token(
+            &event,
+            Udp4Packet {
+                rx_data: ptr::null_mut(),
+            },
+        );
+        unsafe { (self.0.receive)(&mut self.0, &mut token) }.to_result()?;
+        self.wait(&event, &mut token, timeout)?;
+
+        // Safety: on success the firmware has set `rx_data` to the received
+        // data, which stays valid until the recycle event is signaled.
+        let rx_data = unsafe { token.packet.rx_data };
+        let len = unsafe { copy_fragments(rx_data, buffer) };
+        let session = unsafe { (*rx_data).udp_session };
+
+        // Let the firmware release the receive buffers.
+        if let Some(recycle) = unsafe { Event::from_ptr((*rx_data).recycle_signal) } {
+            boot::signal_event(&recycle)?;
+        }
+
+        Ok((len, session.source_address, session.source_port))
+    }
+
+    /// Wait for the operation using `token` to complete, and return the
+    /// status it completed with. If `timeout` expires first, the operation is
+    /// stopped and [`Status::TIMEOUT`] is returned.
+    fn wait(
+        &mut self,
+        event: &TokenEvent,
+        token: &mut Udp4CompletionToken,
+        timeout: Duration,
+    ) -> Result {
+        let this: *mut Udp4Protocol = &mut self.0;
+        let token: *mut Udp4CompletionToken = token;
+        let poll = || {
+            // Polling just speeds up processing, so errors can be ignored.
+            let _ = unsafe { ((*this).poll)(this) };
+        };
+        let cancel = || unsafe { ((*this).cancel)(this, token) };
+        // Resetting the instance aborts all pending operations.
+        let abort = || unsafe { ((*this).configure)(this, ptr::null()) };
+
+        // If the operation completed, a received packet must still be
+        // processed, so report its result.
+        if wait_or_cancel(event, timeout, poll, cancel, abort) {
+            unsafe { (*token).status }.to_result()
+        } else {
+            Err(Status::TIMEOUT.into())
+        }
+    }
+}
+
+/// Get a completion token that signals `event`.
+const fn completion_token(event: &TokenEvent, packet: Udp4Packet) -> Udp4CompletionToken {
+    Udp4CompletionToken {
+        event: event.0.as_ptr(),
+        status: Status::NOT_READY,
+        packet,
+    }
+}
+
+/// Copy the fragments of `rx_data` into `buffer`, returning the number of
+/// bytes copied. Data that doesn't fit in `buffer` is discarded.
+///
+/// # Safety
+///
+/// `rx_data` must point to a valid [`Udp4ReceiveData`] whose fragment table
+/// has `fragment_count` valid entries.
+unsafe fn copy_fragments(rx_data: *const Udp4ReceiveData, buffer: &mut [u8]) -> usize {
+    let fragments = unsafe {
+        slice::from_raw_parts(
+            ptr::addr_of!((*rx_data).fragment_table).cast::<Udp4FragmentData>(),
+            usize_from_u32((*rx_data).fragment_count),
+        )
+    };
+
+    let mut copied = 0;
+    for fragment in fragments {
+        let len = usize_from_u32(fragment.fragment_length).min(buffer.len() - copied);
+        if len == 0 {
+            continue;
+        }
+        let data = unsafe { slice::from_raw_parts(fragment.fragment_buffer.cast::<u8>(), len) };
+        buffer[copied..copied + len].copy_from_slice(data);
+        copied += len;
+    }
+    copied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem;
+
+    #[test]
+    fn test_copy_fragments() {
+        let mut data = *b"hello";
+        let rx_data = Udp4ReceiveData {
+            time_stamp: unsafe { mem::zeroed() },
+            recycle_signal: ptr::null_mut(),
+            udp_session: Udp4SessionData::default(),
+            data_length: 5,
+            fragment_count: 1,
+            fragment_table: [Udp4FragmentData {
+                fragment_length: 5,
+                fragment_buffer: data.as_mut_ptr().cast(),
+            }],
+        };
+
+        let mut buffer = [0; 8];
+        assert_eq!(unsafe { copy_fragments(&rx_data, &mut buffer) }, 5);
+        assert_eq!(&buffer[..5], b"hello");
+
+        // Data that doesn't fit is discarded.
+        let mut buffer = [0; 3];
+        assert_eq!(unsafe { copy_fragments(&rx_data, &mut buffer) }, 3);
+        assert_eq!(&buffer, b"hel");
+    }
+}
@@ -186,6 +186,63 @@ impl MpServices {
         .to_result()
     }
 
+    /// Runs the closure `f` on all enabled APs, blocking until they have all
+    /// finished or until the `timeout` expires.
+    ///
+    /// The closure is called with the processor number of the AP it is
+    /// running on. If `single_thread` is true, the APs run the closure one
+    /// after another in ascending order of processor number; otherwise they
+    /// run it simultaneously.
+    ///
+    /// This is a safe wrapper around [`startup_all_aps`] that passes the
+    /// closure through the procedure argument pointer.
+    ///
+    /// # Restrictions on the closure
+    ///
+    /// The closure runs on APs, where UEFI services are generally not
+    /// available: boot services and most protocols are not safe to call
+    /// from an AP. In particular, the closure must not allocate with the
+    /// global allocator (which uses [`boot::allocate_pool`]) or print to the
+    /// console. Shared state should be updated with atomics or other
+    /// lock-free primitives.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_STARTED`]: there are no enabled APs.
+    /// * [`Status::TIMEOUT`]: the `timeout` expired before all APs finished.
+    ///   The APs that had not finished have been terminated.
+    /// * [`Status::UNSUPPORTED`]: this function was called from an AP.
+    ///
+    /// [`boot::allocate_pool`]: crate::boot::allocate_pool
+    /// [`startup_all_aps`]: Self::startup_all_aps
+    pub fn run_on_all_aps<F>(&self, f: F, single_thread: bool, timeout: Option<Duration>) -> Result
+    where
+        F: Fn(usize) + Sync,
+    {
+        struct Context<'a, F> {
+            mps: &'a MpServices,
+            f: &'a F,
+        }
+
+        extern "efiapi" fn trampoline<F: Fn(usize) + Sync>(arg: *mut c_void) {
+            // Safety: `arg` points to the `Context` on the stack of
+            // `run_on_all_aps`, which is blocked until all APs finish.
+            let context = unsafe { &*arg.cast::<Context<F>>() };
+            // `who_am_i` can only fail if called with a null pointer.
+            let processor_number = context.mps.who_am_i().unwrap();
+            (context.f)(processor_number);
+        }
+
+        let context = Context { mps: self, f: &f };
+        self.startup_all_aps(
+            single_thread,
+            trampoline::<F>,
+            ptr::from_ref(&context).cast_mut().cast(),
+            None,
+            timeout,
+        )
+    }
+
     /// Executes provided function on a specific AP in blocking mode.
     pub fn startup_this_ap(
         &self,
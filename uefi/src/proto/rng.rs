@@ -1,8 +1,10 @@
 //! `Rng` protocol.
 
 use crate::proto::unsafe_protocol;
-use crate::{Result, Status, StatusExt};
+use crate::{Guid, Result, Status, StatusExt};
 use core::ptr;
+#[cfg(feature = "alloc")]
+use {alloc::boxed::Box, alloc::vec};
 
 pub use uefi_raw::protocol::rng::RngAlgorithmType;
 
@@ -14,6 +16,9 @@ pub struct Rng(uefi_raw::protocol::rng::RngProtocol);
 
 impl Rng {
     /// Returns information about the random number generation implementation.
+    ///
+    /// If `algorithm_list` is too small, a [`Status::BUFFER_TOO_SMALL`] error
+    /// is returned with the required buffer size in bytes.
     pub fn get_info<'buf>(
         &mut self,
         algorithm_list: &'buf mut [RngAlgorithmType],
@@ -42,6 +47,29 @@ impl Rng {
         }
     }
 
+    /// Returns the list of supported algorithms, allocating a buffer of
+    /// the required size.
+    #[cfg(feature = "alloc")]
+    pub fn get_info_boxed(&mut self) -> Result<Box<[RngAlgorithmType]>> {
+        // Query the required buffer size.
+        let size_in_bytes = match self.get_info(&mut []) {
+            Ok(_) => return Ok(Box::new([])),
+            Err(err) => match err.data() {
+                Some(size_in_bytes) => *size_in_bytes,
+                None => return Err(err.to_err_without_payload()),
+            },
+        };
+
+        let mut list =
+            vec![RngAlgorithmType::EMPTY_ALGORITHM; size_in_bytes / size_of::<RngAlgorithmType>()];
+        let len = self
+            .get_info(&mut list)
+            .map_err(|err| err.to_err_without_payload())?
+            .len();
+        list.truncate(len);
+        Ok(list.into_boxed_slice())
+    }
+
     /// Returns the next set of random numbers
     pub fn get_rng(&mut self, algorithm: Option<RngAlgorithmType>, buffer: &mut [u8]) -> Result {
         let buffer_length = buffer.len();
@@ -55,4 +83,64 @@ impl Rng {
             (self.0.get_rng)(&mut self.0, algo, buffer_length, buffer.as_mut_ptr()).to_result()
         }
     }
+
+    /// Returns a random (version 4) [`Guid`], e.g. for a vendor GUID that is
+    /// created at runtime.
+    ///
+    /// The version and variant bits are set as specified in RFC 9562, so the
+    /// remaining 122 bits are random.
+    pub fn get_guid(&mut self) -> Result<Guid> {
+        let mut bytes = [0; 16];
+        self.get_rng(None, &mut bytes)?;
+        Ok(guid_from_random_bytes(bytes))
+    }
+}
+
+/// Create a version 4 GUID from random bytes by setting the version and
+/// variant bits.
+const fn guid_from_random_bytes(mut bytes: [u8; 16]) -> Guid {
+    // The version is the high nibble of `time_high`, which is stored in
+    // little-endian order in bytes 6 and 7.
+    bytes[7] = (bytes[7] & 0x0f) | 0x40;
+    // The variant is the two high bits of byte 8: 0b10.
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Guid::from_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use uguid::Variant;
+
+    #[test]
+    fn test_guid_from_random_bytes() {
+        for bytes in [
+            [0x00; 16],
+            [0xff; 16],
+            [0x5a; 16],
+            core::array::from_fn(|i| i as u8),
+        ] {
+            let guid = guid_from_random_bytes(bytes);
+            assert_eq!(guid.version(), 4);
+            assert_eq!(guid.variant(), Variant::Rfc4122);
+
+            // All other bits are unchanged.
+            let out = guid.to_bytes();
+            for i in (0..16).filter(|i| *i != 7 && *i != 8) {
+                assert_eq!(out[i], bytes[i]);
+            }
+            assert_eq!(out[7] & 0x0f, bytes[7] & 0x0f);
+            assert_eq!(out[8] & 0x3f, bytes[8] & 0x3f);
+        }
+
+        assert_eq!(
+            guid_from_random_bytes([0; 16]),
+            guid!("00000000-0000-4000-8000-000000000000")
+        );
+        assert_eq!(
+            guid_from_random_bytes([0xff; 16]),
+            guid!("ffffffff-ffff-4fff-bfff-ffffffffffff")
+        );
+    }
 }
@@ -1,7 +1,8 @@
+use crate::boot::PAGE_SIZE;
 use crate::data_types::PhysicalAddress;
 use crate::mem::memory_map::MemoryAttribute;
 use crate::proto::unsafe_protocol;
-use crate::{Result, StatusExt};
+use crate::{Result, Status, StatusExt};
 use core::ops::Range;
 use uefi_raw::protocol::memory_protection::MemoryAttributeProtocol;
 
@@ -22,20 +23,20 @@ impl MemoryProtection {
     /// If the attributes are not consistent within the region,
     /// [`Status::NO_MAPPING`] is returned.
     ///
-    /// Implementations typically require that the start and end of the memory
-    /// region are aligned to the [UEFI page size].
+    /// The start and end of the memory region must be aligned to the [UEFI
+    /// page size], and the region must not be empty. Otherwise
+    /// [`Status::INVALID_PARAMETER`] is returned without calling the firmware.
     ///
     /// [`READ_PROTECT`]: MemoryAttribute::READ_PROTECT
     /// [`EXECUTE_PROTECT`]: MemoryAttribute::EXECUTE_PROTECT
     /// [`READ_ONLY`]: MemoryAttribute::READ_ONLY
-    /// [`Status::NO_MAPPING`]: crate::Status::NO_MAPPING
     /// [UEFI page size]: uefi::boot::PAGE_SIZE
     pub fn get_memory_attributes(
         &self,
         byte_region: Range<PhysicalAddress>,
     ) -> Result<MemoryAttribute> {
         let mut attributes = MemoryAttribute::empty();
-        let (base_address, length) = range_to_base_and_len(byte_region);
+        let (base_address, length) = range_to_base_and_len(byte_region)?;
         unsafe {
             (self.0.get_memory_attributes)(&self.0, base_address, length, &mut attributes)
                 .to_result_with_val(|| attributes)
@@ -45,10 +46,12 @@ impl MemoryProtection {
     /// Set the attributes of a memory region.
     ///
     /// The valid attributes to set are [`READ_PROTECT`],
-    /// [`EXECUTE_PROTECT`], and [`READ_ONLY`].
+    /// [`EXECUTE_PROTECT`], and [`READ_ONLY`]. Passing any other attribute
+    /// returns [`Status::INVALID_PARAMETER`].
     ///
-    /// Implementations typically require that the start and end of the memory
-    /// region are aligned to the [UEFI page size].
+    /// The start and end of the memory region must be aligned to the [UEFI
+    /// page size], and the region must not be empty. Otherwise
+    /// [`Status::INVALID_PARAMETER`] is returned without calling the firmware.
     ///
     /// [`READ_PROTECT`]: MemoryAttribute::READ_PROTECT
     /// [`EXECUTE_PROTECT`]: MemoryAttribute::EXECUTE_PROTECT
@@ -59,7 +62,8 @@ impl MemoryProtection {
         byte_region: Range<PhysicalAddress>,
         attributes: MemoryAttribute,
     ) -> Result {
-        let (base_address, length) = range_to_base_and_len(byte_region);
+        let (base_address, length) = range_to_base_and_len(byte_region)?;
+        check_attributes(attributes)?;
         unsafe {
             (self.0.set_memory_attributes)(&self.0, base_address, length, attributes).to_result()
         }
@@ -68,10 +72,12 @@ impl MemoryProtection {
     /// Clear the attributes of a memory region.
     ///
     /// The valid attributes to clear are [`READ_PROTECT`],
-    /// [`EXECUTE_PROTECT`], and [`READ_ONLY`].
+    /// [`EXECUTE_PROTECT`], and [`READ_ONLY`]. Passing any other attribute
+    /// returns [`Status::INVALID_PARAMETER`].
     ///
-    /// Implementations typically require that the start and end of the memory
-    /// region are aligned to the [UEFI page size].
+    /// The start and end of the memory region must be aligned to the [UEFI
+    /// page size], and the region must not be empty. Otherwise
+    /// [`Status::INVALID_PARAMETER`] is returned without calling the firmware.
     ///
     /// [`READ_PROTECT`]: MemoryAttribute::READ_PROTECT
     /// [`EXECUTE_PROTECT`]: MemoryAttribute::EXECUTE_PROTECT
@@ -82,16 +88,38 @@ impl MemoryProtection {
         byte_region: Range<PhysicalAddress>,
         attributes: MemoryAttribute,
     ) -> Result {
-        let (base_address, length) = range_to_base_and_len(byte_region);
+        let (base_address, length) = range_to_base_and_len(byte_region)?;
+        check_attributes(attributes)?;
         unsafe {
             (self.0.clear_memory_attributes)(&self.0, base_address, length, attributes).to_result()
         }
     }
 }
 
-/// Convert a byte `Range` to `(base_address, length)`.
-fn range_to_base_and_len(r: Range<PhysicalAddress>) -> (PhysicalAddress, PhysicalAddress) {
-    (r.start, r.end.checked_sub(r.start).unwrap())
+/// The attributes that can be passed to the protocol.
+const SUPPORTED_ATTRIBUTES: MemoryAttribute = MemoryAttribute::READ_PROTECT
+    .union(MemoryAttribute::EXECUTE_PROTECT)
+    .union(MemoryAttribute::READ_ONLY);
+
+/// Convert a byte `Range` to `(base_address, length)`, checking that it is
+/// non-empty and page aligned.
+fn range_to_base_and_len(r: Range<PhysicalAddress>) -> Result<(PhysicalAddress, PhysicalAddress)> {
+    let page_size = PAGE_SIZE as u64;
+    match r.end.checked_sub(r.start) {
+        Some(length) if length != 0 && r.start % page_size == 0 && length % page_size == 0 => {
+            Ok((r.start, length))
+        }
+        _ => Err(Status::INVALID_PARAMETER.into()),
+    }
+}
+
+/// Check that `attributes` only contains bits the protocol supports.
+fn check_attributes(attributes: MemoryAttribute) -> Result {
+    if SUPPORTED_ATTRIBUTES.contains(attributes) {
+        Ok(())
+    } else {
+        Err(Status::INVALID_PARAMETER.into())
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +128,30 @@ mod tests {
 
     #[test]
     fn test_range_conversion() {
-        assert_eq!(range_to_base_and_len(2..5), (2, 3));
+        assert_eq!(range_to_base_and_len(0x1000..0x3000), Ok((0x1000, 0x2000)));
+
+        let invalid = Err(Status::INVALID_PARAMETER.into());
+        // Unaligned start or end.
+        assert_eq!(range_to_base_and_len(0x1001..0x3000), invalid);
+        assert_eq!(range_to_base_and_len(0x1000..0x2fff), invalid);
+        // Empty or reversed.
+        assert_eq!(range_to_base_and_len(0x1000..0x1000), invalid);
+        assert_eq!(
+            range_to_base_and_len(Range {
+                start: 0x3000,
+                end: 0x1000
+            }),
+            invalid
+        );
+    }
+
+    #[test]
+    fn test_check_attributes() {
+        assert_eq!(check_attributes(MemoryAttribute::empty()), Ok(()));
+        assert_eq!(check_attributes(SUPPORTED_ATTRIBUTES), Ok(()));
+        assert_eq!(
+            check_attributes(MemoryAttribute::READ_ONLY | MemoryAttribute::WRITE_BACK),
+            Err(Status::INVALID_PARAMETER.into())
+        );
     }
 }
@@ -1,4 +1,7 @@
 //! Protocols related to secure technologies.
 
 mod memory_protection;
+mod security2;
+
 pub use memory_protection::MemoryProtection;
+pub use security2::Security2;
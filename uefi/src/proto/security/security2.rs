@@ -0,0 +1,70 @@
+use crate::proto::device_path::DevicePath;
+use crate::proto::unsafe_protocol;
+use crate::{Result, StatusExt};
+use core::ptr;
+use uefi_raw::protocol::security::Security2ArchProtocol;
+
+/// Security2 architectural protocol, for checking files against the
+/// platform's security policy (e.g. Secure Boot).
+///
+/// This is the protocol the firmware itself uses to authenticate images
+/// in `LoadImage`. It allows a custom loader to apply the same policy to a
+/// file before passing it to [`boot::start_image`] by other means.
+///
+/// This is an architectural protocol from the Platform Initialization
+/// specification. It is installed by the DXE core for its own use, so it
+/// is typically only available during early boot, and may be missing
+/// entirely on some platforms.
+///
+/// Corresponds to the C type `EFI_SECURITY2_ARCH_PROTOCOL`.
+///
+/// [`boot::start_image`]: crate::boot::start_image
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(Security2ArchProtocol::GUID)]
+pub struct Security2(Security2ArchProtocol);
+
+impl Security2 {
+    /// Checks whether the file at `device_path` is allowed by the
+    /// platform's security policy.
+    ///
+    /// `file_buffer` is the contents of the file, if it has already been
+    /// read into memory. `boot_policy` is true if the file is being loaded
+    /// as a boot option, as with the `BootPolicy` parameter of
+    /// `LoadImage`.
+    ///
+    /// Returns `Ok(())` if the file passes the policy.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::SECURITY_VIOLATION`]: the file could not be
+    ///   authenticated, but the platform policy may allow it to be loaded
+    ///   later (e.g. after the user confirms it). Images loaded with
+    ///   `LoadImage` in this state cannot be started.
+    /// * [`Status::ACCESS_DENIED`]: the file must not be used at all.
+    ///
+    /// [`Status::SECURITY_VIOLATION`]: crate::Status::SECURITY_VIOLATION
+    /// [`Status::ACCESS_DENIED`]: crate::Status::ACCESS_DENIED
+    pub fn file_authentication(
+        &self,
+        device_path: &DevicePath,
+        file_buffer: Option<&[u8]>,
+        boot_policy: bool,
+    ) -> Result {
+        // The buffer is not modified, despite the pointer not being const.
+        let (file_buffer, file_size) = match file_buffer {
+            Some(buffer) => (buffer.as_ptr().cast_mut().cast(), buffer.len()),
+            None => (ptr::null_mut(), 0),
+        };
+        unsafe {
+            (self.0.file_authentication)(
+                &self.0,
+                device_path.as_ffi_ptr().cast(),
+                file_buffer,
+                file_size,
+                boot_policy,
+            )
+        }
+        .to_result()
+    }
+}
@@ -1,4 +1,26 @@
 //! `ShellParams` protocol
+//!
+//! The UEFI shell installs [`ShellParameters`] on the image handle of each
+//! application it starts. It is not present if the application was started
+//! directly by the firmware (e.g. as a boot option), in which case opening it
+//! fails with [`Status::UNSUPPORTED`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::boot;
+//! use uefi::proto::shell_params::ShellParameters;
+//!
+//! # fn example() -> uefi::Result {
+//! let shell_params = boot::open_protocol_exclusive::<ShellParameters>(boot::image_handle())?;
+//! for arg in shell_params.args() {
+//!     log::info!("{arg}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Status::UNSUPPORTED`]: crate::Status::UNSUPPORTED
 
 use crate::proto::unsafe_protocol;
 use crate::{data_types, Char16};
@@ -7,6 +29,8 @@ use uefi_raw::protocol::shell_params::ShellParametersProtocol;
 
 use crate::CStr16;
 
+pub use uefi_raw::protocol::shell_params::ShellFileHandle;
+
 /// The ShellParameters protocol.
 #[derive(Debug)]
 #[repr(transparent)]
@@ -20,8 +44,11 @@ impl ShellParameters {
         self.0.argc
     }
 
-    /// Get an iterator of the shell parameter arguments
-    pub fn args(&self) -> impl Iterator<Item = &CStr16> {
+    /// Get an iterator of the shell parameter arguments. The first argument
+    /// is the path of the application, as typed in the shell.
+    ///
+    /// The arguments are borrowed from the protocol without copying.
+    pub fn args(&self) -> impl ExactSizeIterator<Item = &CStr16> {
         self.args_slice()
             .iter()
             .map(|x| unsafe { CStr16::from_ptr(*x) })
@@ -37,4 +64,39 @@ impl ShellParameters {
             )
         }
     }
+
+    /// Get the shell file handle of the standard input, which may be
+    /// redirected from a file.
+    ///
+    /// Shell file handles are opaque, and can only be used with the file
+    /// functions of the `EFI_SHELL_PROTOCOL`. They are not compatible with
+    /// [`FileHandle`].
+    ///
+    /// [`FileHandle`]: crate::proto::media::file::FileHandle
+    #[must_use]
+    pub const fn std_in(&self) -> ShellFileHandle {
+        self.0.std_in
+    }
+
+    /// Get the shell file handle of the standard output, which may be
+    /// redirected to a file.
+    ///
+    /// See [`std_in`] for how shell file handles can be used.
+    ///
+    /// [`std_in`]: Self::std_in
+    #[must_use]
+    pub const fn std_out(&self) -> ShellFileHandle {
+        self.0.std_out
+    }
+
+    /// Get the shell file handle of the standard error output, which may be
+    /// redirected to a file.
+    ///
+    /// See [`std_in`] for how shell file handles can be used.
+    ///
+    /// [`std_in`]: Self::std_in
+    #[must_use]
+    pub const fn std_err(&self) -> ShellFileHandle {
+        self.0.std_err
+    }
 }
@@ -54,18 +54,15 @@ impl UnicodeCollation {
     }
 
     /// Converts the characters in `s` to lower case characters.
+    ///
+    /// The result is written to `buf`, which must have room for `s` including
+    /// the trailing null.
     pub fn str_lwr<'a>(
         &self,
         s: &CStr16,
         buf: &'a mut [u16],
     ) -> Result<&'a CStr16, StrConversionError> {
-        let mut last_index = 0;
-        for (i, c) in s.iter().enumerate() {
-            *buf.get_mut(i).ok_or(StrConversionError::BufferTooSmall)? = (*c).into();
-            last_index = i;
-        }
-        *buf.get_mut(last_index + 1)
-            .ok_or(StrConversionError::BufferTooSmall)? = 0;
+        let buf = copy_with_nul(s, buf)?;
 
         unsafe { (self.0.str_lwr)(&self.0, buf.as_mut_ptr()) };
 
@@ -73,18 +70,15 @@ impl UnicodeCollation {
     }
 
     /// Converts the characters in `s` to upper case characters.
+    ///
+    /// The result is written to `buf`, which must have room for `s` including
+    /// the trailing null.
     pub fn str_upr<'a>(
         &self,
         s: &CStr16,
         buf: &'a mut [u16],
     ) -> Result<&'a CStr16, StrConversionError> {
-        let mut last_index = 0;
-        for (i, c) in s.iter().enumerate() {
-            *buf.get_mut(i).ok_or(StrConversionError::BufferTooSmall)? = (*c).into();
-            last_index = i;
-        }
-        *buf.get_mut(last_index + 1)
-            .ok_or(StrConversionError::BufferTooSmall)? = 0;
+        let buf = copy_with_nul(s, buf)?;
 
         unsafe { (self.0.str_upr)(&self.0, buf.as_mut_ptr()) };
 
@@ -108,7 +102,9 @@ impl UnicodeCollation {
                 buf.as_mut_ptr(),
             )
         };
-        Ok(unsafe { CStr16::from_u16_with_nul_unchecked(buf) })
+        // The converted string may be shorter than `buf`, so only return the
+        // part up to the null terminator.
+        CStr16::from_u16_until_nul(buf).map_err(|_| StrConversionError::ConversionFailed)
     }
 
     /// Converts the null terminated string `s` to legal characters in a FAT file name.
@@ -149,6 +145,17 @@ impl UnicodeCollation {
     }
 }
 
+/// Copy `s` including the trailing null into the start of `buf`, returning
+/// the part of `buf` that was written.
+fn copy_with_nul<'a>(s: &CStr16, buf: &'a mut [u16]) -> Result<&'a mut [u16], StrConversionError> {
+    let src = s.to_u16_slice_with_nul();
+    let buf = buf
+        .get_mut(..src.len())
+        .ok_or(StrConversionError::BufferTooSmall)?;
+    buf.copy_from_slice(src);
+    Ok(buf)
+}
+
 /// Errors returned by [`UnicodeCollation::str_lwr`] and [`UnicodeCollation::str_upr`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StrConversionError {
@@ -15,7 +15,7 @@ use crate::util::{ptr_write_unaligned_and_add, usize_from_u32};
 use crate::{Error, Result, Status, StatusExt};
 use core::fmt::{self, Debug, Formatter};
 use core::marker::PhantomData;
-use core::ptr;
+use core::{ptr, slice};
 use ptr_meta::Pointee;
 use uefi_raw::protocol::tcg::v1::{TcgBootServiceCapability, TcgProtocol};
 
@@ -293,6 +293,27 @@ impl EventLog<'_> {
     pub const fn is_truncated(&self) -> bool {
         self.is_truncated
     }
+
+    /// Raw bytes of the event log, from the start of the first event to the
+    /// end of the last event.
+    ///
+    /// This can be used to pass the log to an external parser, or to copy
+    /// it somewhere that outlives boot services.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.location.is_null() || self.last_entry.is_null() {
+            return &[];
+        }
+
+        // Safety: we trust that the protocol has given us a valid range
+        // of memory to read from.
+        unsafe {
+            let last_event = PcrEvent::from_ptr(self.last_entry);
+            let end = self.last_entry.add(size_of_val(last_event));
+            let len = end.offset_from(self.location);
+            slice::from_raw_parts(self.location, usize::try_from(len).unwrap())
+        }
+    }
 }
 
 /// Iterator for events in [`EventLog`].
@@ -573,6 +594,7 @@ mod tests {
         ];
 
         let log = unsafe { EventLog::new(bytes.as_ptr(), bytes.as_ptr().add(34), false) };
+        assert_eq!(log.as_bytes(), bytes);
         let mut iter = log.iter();
 
         // Entry 1
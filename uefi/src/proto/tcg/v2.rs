@@ -374,6 +374,36 @@ impl EventLog<'_> {
     pub const fn is_truncated(&self) -> bool {
         self.is_truncated
     }
+
+    /// Raw bytes of the event log, from the start of the header to the end
+    /// of the last event.
+    ///
+    /// This can be used to pass the log to an external parser, or to copy
+    /// it somewhere that outlives boot services. An empty slice is returned
+    /// if the log is empty or if the last event cannot be parsed.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        let Some(header) = self.header() else {
+            return &[];
+        };
+
+        let end = if self.last_entry.is_null() || self.last_entry == self.location {
+            // The log only contains the header.
+            unsafe { self.location.add(header.size_in_bytes) }
+        } else {
+            // Safety: we trust that the protocol has given us a valid range
+            // of memory to read from.
+            match unsafe { PcrEvent::from_ptr(self.last_entry, header) } {
+                Some(event) => event.next,
+                None => return &[],
+            }
+        };
+
+        unsafe {
+            let len = end.offset_from(self.location);
+            slice::from_raw_parts(self.location, usize::try_from(len).unwrap())
+        }
+    }
 }
 
 /// Digests in a PCR event.
@@ -930,6 +960,8 @@ mod tests {
             is_truncated: false,
         };
 
+        assert_eq!(log.as_bytes(), bytes);
+
         let header = log.header().unwrap();
         assert_eq!(header.platform_class, 0);
         assert_eq!(header.spec_version, (2, 0, 0));
@@ -0,0 +1,291 @@
+//! USB I/O protocol.
+
+use crate::proto::unsafe_protocol;
+use crate::{Error, Result, Status, StatusExt};
+use core::ffi::c_void;
+use core::ptr;
+use core::time::Duration;
+use uefi_raw::protocol::usb::UsbIoProtocol;
+
+pub use uefi_raw::protocol::usb::{
+    ConfigDescriptor, DeviceDescriptor, DeviceRequest, EndpointDescriptor, InterfaceDescriptor,
+    UsbDataDirection, UsbTransferStatus,
+};
+
+/// USB I/O protocol.
+///
+/// Each instance of this protocol is bound to one interface of a USB
+/// device, and gives access to the device's default control pipe and to
+/// the endpoints of that interface.
+///
+/// The transfer functions return an error carrying the
+/// [`UsbTransferStatus`] reported by the host controller, so that a
+/// stalled endpoint ([`UsbTransferStatus::STALL`]) can be told apart from
+/// a timeout ([`UsbTransferStatus::TIMEOUT`]) or a NAK
+/// ([`UsbTransferStatus::NAK`]).
+#[derive(Debug)]
+#[repr(transparent)]
+#[unsafe_protocol(UsbIoProtocol::GUID)]
+pub struct UsbIo(UsbIoProtocol);
+
+impl UsbIo {
+    /// Performs a control transfer on the default control pipe of the
+    /// device.
+    ///
+    /// `data` is the buffer for the data stage of the transfer. It must be
+    /// `None` if and only if `direction` is [`UsbDataDirection::NO_DATA`],
+    /// and must be at least `request.length` bytes long.
+    ///
+    /// A `timeout` of zero waits indefinitely. Otherwise it is rounded up
+    /// to the next millisecond.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `data` does not match `direction`
+    ///   or is shorter than `request.length`.
+    /// * [`Status::TIMEOUT`]: the transfer timed out.
+    /// * [`Status::DEVICE_ERROR`]: the transfer failed.
+    ///
+    /// On failure, the error data contains the [`UsbTransferStatus`] of
+    /// the transfer.
+    pub fn control_transfer(
+        &mut self,
+        request: &DeviceRequest,
+        direction: UsbDataDirection,
+        data: Option<&mut [u8]>,
+        timeout: Duration,
+    ) -> Result<(), UsbTransferStatus> {
+        let (data_ptr, data_len) = match data {
+            Some(data) => (data.as_mut_ptr().cast::<c_void>(), data.len()),
+            None => (ptr::null_mut(), 0),
+        };
+        if !control_transfer_args_valid(request, direction, data_ptr.is_null(), data_len) {
+            return Err(Error::new(
+                Status::INVALID_PARAMETER,
+                UsbTransferStatus::empty(),
+            ));
+        }
+
+        let mut request = *request;
+        let mut usb_status = UsbTransferStatus::empty();
+        unsafe {
+            (self.0.control_transfer)(
+                &mut self.0,
+                &mut request,
+                direction,
+                timeout_to_millis(timeout),
+                data_ptr,
+                data_len,
+                &mut usb_status,
+            )
+        }
+        .to_result_with_err(|_| usb_status)
+    }
+
+    /// Performs a bulk transfer on the bulk endpoint `endpoint` of the
+    /// device. The direction of the transfer is given by bit 7 of the
+    /// endpoint address.
+    ///
+    /// A `timeout` of zero waits indefinitely. Otherwise it is rounded up
+    /// to the next millisecond.
+    ///
+    /// Returns the number of bytes actually transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `endpoint` is not a valid bulk
+    ///   endpoint.
+    /// * [`Status::TIMEOUT`]: the transfer timed out.
+    /// * [`Status::DEVICE_ERROR`]: the transfer failed.
+    ///
+    /// On failure, the error data contains the [`UsbTransferStatus`] of
+    /// the transfer.
+    pub fn bulk_transfer(
+        &mut self,
+        endpoint: u8,
+        data: &mut [u8],
+        timeout: Duration,
+    ) -> Result<usize, UsbTransferStatus> {
+        let mut data_len = data.len();
+        let mut usb_status = UsbTransferStatus::empty();
+        unsafe {
+            (self.0.bulk_transfer)(
+                &mut self.0,
+                endpoint,
+                data.as_mut_ptr().cast(),
+                &mut data_len,
+                timeout_to_millis(timeout) as usize,
+                &mut usb_status,
+            )
+        }
+        .to_result_with(|| data_len, |_| usb_status)
+    }
+
+    /// Performs a synchronous interrupt transfer on the interrupt endpoint
+    /// `endpoint` of the device. The direction of the transfer is given by
+    /// bit 7 of the endpoint address.
+    ///
+    /// Returns the number of bytes actually transferred. See
+    /// [`bulk_transfer`] for the meaning of `timeout` and the errors.
+    ///
+    /// [`bulk_transfer`]: Self::bulk_transfer
+    pub fn sync_interrupt_transfer(
+        &mut self,
+        endpoint: u8,
+        data: &mut [u8],
+        timeout: Duration,
+    ) -> Result<usize, UsbTransferStatus> {
+        let mut data_len = data.len();
+        let mut usb_status = UsbTransferStatus::empty();
+        unsafe {
+            (self.0.sync_interrupt_transfer)(
+                &mut self.0,
+                endpoint,
+                data.as_mut_ptr().cast(),
+                &mut data_len,
+                timeout_to_millis(timeout) as usize,
+                &mut usb_status,
+            )
+        }
+        .to_result_with(|| data_len, |_| usb_status)
+    }
+
+    /// Returns the device descriptor of the device.
+    pub fn get_device_descriptor(&mut self) -> Result<DeviceDescriptor> {
+        let mut descriptor = DeviceDescriptor::default();
+        unsafe { (self.0.get_device_descriptor)(&mut self.0, &mut descriptor) }
+            .to_result_with_val(|| descriptor)
+    }
+
+    /// Returns the descriptor of the device's active configuration.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the device is not configured.
+    pub fn get_config_descriptor(&mut self) -> Result<ConfigDescriptor> {
+        let mut descriptor = ConfigDescriptor::default();
+        unsafe { (self.0.get_config_descriptor)(&mut self.0, &mut descriptor) }
+            .to_result_with_val(|| descriptor)
+    }
+
+    /// Returns the descriptor of the interface this protocol instance is
+    /// bound to. Its [`num_endpoints`] field gives the valid indices for
+    /// [`get_endpoint_descriptor`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the device is not configured.
+    ///
+    /// [`num_endpoints`]: InterfaceDescriptor::num_endpoints
+    /// [`get_endpoint_descriptor`]: Self::get_endpoint_descriptor
+    pub fn get_interface_descriptor(&mut self) -> Result<InterfaceDescriptor> {
+        let mut descriptor = InterfaceDescriptor::default();
+        unsafe { (self.0.get_interface_descriptor)(&mut self.0, &mut descriptor) }
+            .to_result_with_val(|| descriptor)
+    }
+
+    /// Returns the descriptor of the endpoint at `index` within the
+    /// interface this protocol instance is bound to.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: `index` is out of range, or the device is
+    ///   not configured.
+    pub fn get_endpoint_descriptor(&mut self, index: u8) -> Result<EndpointDescriptor> {
+        let mut descriptor = EndpointDescriptor::default();
+        unsafe { (self.0.get_endpoint_descriptor)(&mut self.0, index, &mut descriptor) }
+            .to_result_with_val(|| descriptor)
+    }
+
+    /// Resets the parent port of the device and restores its
+    /// configuration.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the device is a USB hub.
+    /// * [`Status::DEVICE_ERROR`]: the reset failed.
+    pub fn port_reset(&mut self) -> Result {
+        unsafe { (self.0.port_reset)(&mut self.0) }.to_result()
+    }
+}
+
+/// Checks that the data stage buffer of a control transfer is consistent
+/// with the transfer direction and large enough for the request.
+fn control_transfer_args_valid(
+    request: &DeviceRequest,
+    direction: UsbDataDirection,
+    data_is_null: bool,
+    data_len: usize,
+) -> bool {
+    data_is_null == (direction == UsbDataDirection::NO_DATA)
+        && usize::from(request.length) <= data_len
+}
+
+/// Converts a transfer timeout to milliseconds, rounding up so that a
+/// small non-zero timeout does not turn into an infinite wait.
+fn timeout_to_millis(timeout: Duration) -> u32 {
+    u32::try_from(timeout.as_nanos().div_ceil(1_000_000)).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_transfer_args_valid() {
+        let request = DeviceRequest {
+            length: 4,
+            ..Default::default()
+        };
+        assert!(control_transfer_args_valid(
+            &request,
+            UsbDataDirection::DATA_IN,
+            false,
+            4
+        ));
+        assert!(control_transfer_args_valid(
+            &request,
+            UsbDataDirection::DATA_OUT,
+            false,
+            8
+        ));
+        // Buffer too small for the request.
+        assert!(!control_transfer_args_valid(
+            &request,
+            UsbDataDirection::DATA_IN,
+            false,
+            2
+        ));
+        // Data stage without a buffer.
+        assert!(!control_transfer_args_valid(
+            &request,
+            UsbDataDirection::DATA_IN,
+            true,
+            0
+        ));
+
+        let request = DeviceRequest::default();
+        assert!(control_transfer_args_valid(
+            &request,
+            UsbDataDirection::NO_DATA,
+            true,
+            0
+        ));
+        // Buffer without a data stage.
+        assert!(!control_transfer_args_valid(
+            &request,
+            UsbDataDirection::NO_DATA,
+            false,
+            0
+        ));
+    }
+
+    #[test]
+    fn test_timeout_to_millis() {
+        assert_eq!(timeout_to_millis(Duration::ZERO), 0);
+        assert_eq!(timeout_to_millis(Duration::from_nanos(1)), 1);
+        assert_eq!(timeout_to_millis(Duration::from_millis(250)), 250);
+        assert_eq!(timeout_to_millis(Duration::from_micros(1500)), 2);
+        assert_eq!(timeout_to_millis(Duration::MAX), u32::MAX);
+    }
+}
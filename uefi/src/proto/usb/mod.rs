@@ -0,0 +1,7 @@
+//! USB protocols.
+
+mod io;
+pub use io::{
+    ConfigDescriptor, DeviceDescriptor, DeviceRequest, EndpointDescriptor, InterfaceDescriptor,
+    UsbDataDirection, UsbIo, UsbTransferStatus,
+};
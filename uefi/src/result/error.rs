@@ -1,7 +1,8 @@
 //! Module for UEFI-specific error encodings. See [`Error`].
 
 use super::Status;
-use core::fmt::{Debug, Display};
+use core::fmt::{self, Debug, Display};
+use core::panic::Location;
 
 /// An UEFI-related error with optionally additional payload data. The error
 /// kind is encoded in the `status` field (see [`Status`]). Additional payload
@@ -69,3 +70,137 @@ impl<Data: Debug> Error<Data> {
 }
 
 impl<Data: Debug> core::error::Error for Error<Data> {}
+
+/// An [`Error`] annotated with a static context string and the source
+/// location where the context was added.
+///
+/// This is created by [`ResultContextExt::with_context`]. The [`Display`] impl
+/// renders as `"<context>: <status> at <file>:<line>:<column>"`, which makes
+/// errors that bubble up through several layers easier to trace back. No
+/// allocation is required.
+///
+/// [`ResultContextExt::with_context`]: super::ResultContextExt::with_context
+#[derive(Clone, PartialEq, Eq)]
+pub struct ContextError<Data: Debug = ()> {
+    error: Error<Data>,
+    context: &'static str,
+    location: &'static Location<'static>,
+}
+
+impl<Data: Debug> ContextError<Data> {
+    /// Annotates `error` with `context` and the location of the caller.
+    #[must_use]
+    #[track_caller]
+    pub const fn new(error: Error<Data>, context: &'static str) -> Self {
+        Self {
+            error,
+            context,
+            location: Location::caller(),
+        }
+    }
+
+    /// Get error `Status`.
+    pub const fn status(&self) -> Status {
+        self.error.status()
+    }
+
+    /// Get the underlying error.
+    #[must_use]
+    pub const fn error(&self) -> &Error<Data> {
+        &self.error
+    }
+
+    /// Get the context string.
+    #[must_use]
+    pub const fn context(&self) -> &'static str {
+        self.context
+    }
+
+    /// Get the source location where the context was added.
+    #[must_use]
+    pub const fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Discards the context and returns the underlying error.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn into_inner(self) -> Error<Data> {
+        self.error
+    }
+}
+
+impl<Data: Debug> Debug for ContextError<Data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextError")
+            .field("context", &self.context)
+            .field("status", &self.error.status())
+            .field("data", self.error.data())
+            .field("location", &format_args!("{}", self.location))
+            .finish()
+    }
+}
+
+impl<Data: Debug> Display for ContextError<Data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} at {}",
+            self.context,
+            self.error.status(),
+            self.location
+        )
+    }
+}
+
+impl<Data: Debug> From<ContextError<Data>> for Error<Data> {
+    fn from(err: ContextError<Data>) -> Self {
+        err.into_inner()
+    }
+}
+
+impl<Data: Debug + 'static> core::error::Error for ContextError<Data> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResultContextExt;
+
+    fn not_found() -> crate::Result {
+        Err(Status::NOT_FOUND.into())
+    }
+
+    #[test]
+    fn test_with_context() {
+        let line = line!() + 1;
+        let err = not_found().with_context("open_protocol").unwrap_err();
+        assert_eq!(err.status(), Status::NOT_FOUND);
+        assert_eq!(err.context(), "open_protocol");
+        assert_eq!(err.location().file(), file!());
+        assert_eq!(err.location().line(), line);
+        assert_eq!(Error::from(err), Error::from(Status::NOT_FOUND));
+
+        assert_eq!(Ok::<_, Error>(3).with_context("unused"), Ok(3));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_context_error_display() {
+        use alloc::format;
+
+        let err = ContextError::new(Status::NOT_FOUND.into(), "open_protocol(SimpleFileSystem)");
+        let location = err.location();
+        assert_eq!(
+            format!("{err}"),
+            format!(
+                "open_protocol(SimpleFileSystem): NOT_FOUND at {}:{}:{}",
+                location.file(),
+                location.line(),
+                location.column()
+            )
+        );
+    }
+}
@@ -4,7 +4,7 @@ use core::fmt::Debug;
 
 /// The error type that we use, essentially a status code + optional additional data
 mod error;
-pub use error::Error;
+pub use error::{ContextError, Error};
 
 /// Definition of UEFI's standard status codes
 mod status;
@@ -96,3 +96,53 @@ impl<Output, ErrData: Debug> ResultExt<Output, ErrData> for Result<Output, ErrDa
         }
     }
 }
+
+/// Extension trait which adds [`with_context`] to [`Result`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// [`with_context`]: Self::with_context
+pub trait ResultContextExt<Output, ErrData: Debug>: sealed::Sealed {
+    /// Annotates the error, if any, with a static `context` string and the
+    /// location of the caller. See [`ContextError`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use uefi::boot;
+    /// use uefi::proto::media::fs::SimpleFileSystem;
+    /// use uefi::ResultContextExt;
+    ///
+    /// let handle = boot::get_handle_for_protocol::<SimpleFileSystem>()
+    ///     .with_context("get_handle_for_protocol(SimpleFileSystem)")
+    ///     // On failure, panics with a message like
+    ///     // "get_handle_for_protocol(SimpleFileSystem): NOT_FOUND at src/main.rs:6:6".
+    ///     .unwrap_or_else(|err| panic!("{err}"));
+    /// ```
+    #[track_caller]
+    fn with_context(
+        self,
+        context: &'static str,
+    ) -> core::result::Result<Output, ContextError<ErrData>>;
+}
+
+mod sealed {
+    /// Prevents [`ResultContextExt`](super::ResultContextExt) from being
+    /// implemented for other types, so that methods can be added to it.
+    pub trait Sealed {}
+}
+
+impl<Output, ErrData: Debug> sealed::Sealed for Result<Output, ErrData> {}
+
+impl<Output, ErrData: Debug> ResultContextExt<Output, ErrData> for Result<Output, ErrData> {
+    #[track_caller]
+    fn with_context(
+        self,
+        context: &'static str,
+    ) -> core::result::Result<Output, ContextError<ErrData>> {
+        match self {
+            Ok(output) => Ok(output),
+            Err(err) => Err(ContextError::new(err, context)),
+        }
+    }
+}
@@ -5,9 +5,13 @@
 //! functions after exiting boot services; see the "Calling Convention" section
 //! of the UEFI specification for details.
 
+#[cfg(feature = "alloc")]
+pub mod load_option;
+
 use crate::data_types::PhysicalAddress;
 use crate::table::{self, Revision};
 use crate::{CStr16, Error, Result, Status, StatusExt};
+use core::cmp::Ordering;
 use core::fmt::{self, Debug, Display, Formatter};
 use core::ptr::{self, NonNull};
 use uefi_raw::table::boot::MemoryDescriptor;
@@ -15,7 +19,7 @@ use uefi_raw::table::boot::MemoryDescriptor;
 #[cfg(feature = "alloc")]
 use {
     crate::mem::make_boxed, crate::CString16, crate::Guid, alloc::borrow::ToOwned,
-    alloc::boxed::Box, alloc::vec::Vec,
+    alloc::boxed::Box, alloc::vec, alloc::vec::Vec,
 };
 
 #[cfg(all(feature = "unstable", feature = "alloc"))]
@@ -64,7 +68,20 @@ pub fn get_time_and_caps() -> Result<(Time, TimeCapabilities)> {
 ///
 /// Undefined behavior could happen if multiple tasks try to
 /// use this function at the same time without synchronisation.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `time` is not valid, see
+///   [`Time::is_valid`]. This is checked before calling the firmware.
+/// * [`Status::DEVICE_ERROR`]: the time could not be set due to a hardware
+///   error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support setting the
+///   time at runtime.
 pub unsafe fn set_time(time: &Time) -> Result {
+    if time.is_valid().is_err() {
+        return Err(Status::INVALID_PARAMETER.into());
+    }
+
     let rt = runtime_services_raw_panicking();
     let rt = unsafe { rt.as_ref() };
 
@@ -191,6 +208,102 @@ pub fn get_variable_boxed(
     }
 }
 
+/// Gets a variable and parses its contents as `T`.
+///
+/// This is a convenience wrapper around [`get_variable_boxed`] and
+/// [`Variable::parse`]. For example, to check whether Secure Boot is enabled:
+///
+/// ```no_run
+/// use uefi::runtime::{self, VariableVendor};
+/// use uefi::cstr16;
+///
+/// let secure_boot: bool =
+///     runtime::get_typed(cstr16!("SecureBoot"), &VariableVendor::GLOBAL_VARIABLE)?;
+/// # Ok::<(), uefi::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: variable was not found.
+/// * [`Status::DEVICE_ERROR`]: variable could not be read due to a hardware error.
+/// * [`Status::SECURITY_VIOLATION`]: variable could not be read due to an
+///   authentication error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+/// * Any error returned by `T`'s [`Variable::parse`] implementation.
+#[cfg(feature = "alloc")]
+pub fn get_typed<T: Variable>(name: &CStr16, vendor: &VariableVendor) -> Result<T> {
+    let (data, attributes) = get_variable_boxed(name, vendor)?;
+    T::parse(&data, attributes)
+}
+
+/// A type that can be parsed from the raw contents of a UEFI variable.
+///
+/// See [`get_typed`] for reading a variable directly into a type that
+/// implements this trait.
+///
+/// Integers are stored little-endian and must exactly match the size of the
+/// type. `bool` is stored as a single byte that must be `0` or `1`, as used by
+/// global variables such as `SecureBoot` and `SetupMode`. [`CString16`] is
+/// stored as null-terminated UCS-2.
+pub trait Variable: Sized {
+    /// Parses the variable's `data`. The variable's `attributes` are provided
+    /// so that implementations can reject unexpected storage kinds.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::BAD_BUFFER_SIZE`]: `data` has the wrong size for the type.
+    /// * [`Status::INVALID_PARAMETER`]: `data` is not a valid value of the type.
+    fn parse(data: &[u8], attributes: VariableAttributes) -> Result<Self>;
+}
+
+macro_rules! impl_variable_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Variable for $t {
+                fn parse(data: &[u8], _attributes: VariableAttributes) -> Result<Self> {
+                    data.try_into()
+                        .map(Self::from_le_bytes)
+                        .map_err(|_| Status::BAD_BUFFER_SIZE.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_variable_for_int!(u8, u16, u32, u64);
+
+impl Variable for bool {
+    fn parse(data: &[u8], attributes: VariableAttributes) -> Result<Self> {
+        match u8::parse(data, attributes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Status::INVALID_PARAMETER.into()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Variable for CString16 {
+    fn parse(data: &[u8], _attributes: VariableAttributes) -> Result<Self> {
+        if data.len() % 2 != 0 {
+            return Err(Status::BAD_BUFFER_SIZE.into());
+        }
+        let chars = data
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect::<Vec<_>>();
+        Self::try_from(chars).map_err(|_| Status::INVALID_PARAMETER.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Variable for Box<[u8]> {
+    fn parse(data: &[u8], _attributes: VariableAttributes) -> Result<Self> {
+        Ok(data.into())
+    }
+}
+
 /// Gets each variable key (name and vendor) one at a time.
 ///
 /// This is used to iterate over variable keys. See [`variable_keys`] for a more
@@ -265,14 +378,17 @@ pub struct VariableKeys {
 
 #[cfg(feature = "alloc")]
 impl VariableKeys {
+    /// Initial length of the name buffer in `u16` characters.
+    const INITIAL_NAME_LEN: usize = 128;
+
     fn new() -> Self {
-        // Create a the name buffer with a reasonable default capacity, and
-        // initialize it to an empty null-terminated string.
-        let mut name = Vec::with_capacity(32);
-        name.push(0);
+        // Create the name buffer with a reasonable default size (256 bytes),
+        // and initialize it to an empty null-terminated string. The whole
+        // buffer is passed to the firmware, so most variable names fit
+        // without having to grow the buffer.
+        let name = vec![0; Self::INITIAL_NAME_LEN];
 
         Self {
-            // Give the name buffer a reasonable default capacity.
             name,
             // The initial vendor GUID is arbitrary.
             vendor: VariableVendor(Guid::default()),
@@ -402,7 +518,7 @@ pub fn query_variable_info(attributes: VariableAttributes) -> Result<VariableSto
     let rt = runtime_services_raw_panicking();
     let rt = unsafe { rt.as_ref() };
 
-    if rt.header.revision < Revision::EFI_2_00 {
+    if !rt.header.revision.supports_at_least(Revision::EFI_2_00) {
         return Err(Status::UNSUPPORTED.into());
     }
 
@@ -482,6 +598,30 @@ pub fn query_capsule_capabilities(capsule_header_array: &[&CapsuleHeader]) -> Re
     }
 }
 
+/// Get the high 32 bits of the platform's monotonic counter, incrementing
+/// it.
+///
+/// The high count is stored in nonvolatile storage and is also incremented
+/// on every reset, so it never repeats. Each call writes to nonvolatile
+/// storage, so this should be used sparingly.
+///
+/// While boot services are active, use [`boot::get_next_monotonic_count`]
+/// instead. It returns the full 64-bit count, with the high count in the
+/// upper 32 bits.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: the device is not functioning properly.
+///
+/// [`boot::get_next_monotonic_count`]: crate::boot::get_next_monotonic_count
+pub fn get_next_high_monotonic_count() -> Result<u32> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut high_count = 0;
+    unsafe { (rt.get_next_high_monotonic_count)(&mut high_count) }.to_result_with_val(|| high_count)
+}
+
 /// Resets the computer.
 ///
 /// See [`ResetType`] for details of the various reset types.
@@ -564,7 +704,8 @@ pub struct TimeParams {
     /// Month in the range `1..=12`.
     pub month: u8,
 
-    /// Day in the range `1..=31`.
+    /// Day in the range `1..=31`. The day must exist in the month, taking
+    /// leap years into account.
     pub day: u8,
 
     /// Hour in the range `0.=23`.
@@ -615,7 +756,7 @@ impl Display for TimeError {
             writeln!(f, "month not within `1..=12")?;
         }
         if self.day {
-            writeln!(f, "day not within `1..=31`")?;
+            writeln!(f, "day not within `1..=31` or past the end of the month")?;
         }
         if self.hour {
             writeln!(f, "hour not within `0..=23`")?;
@@ -678,6 +819,9 @@ impl Time {
     }
 
     /// `Ok()` if all fields are within valid ranges, `Err(TimeError)` otherwise.
+    ///
+    /// The day is also checked against the length of the month, so for
+    /// example February 29th is only valid in leap years.
     pub fn is_valid(&self) -> core::result::Result<(), TimeError> {
         let mut err = TimeError::default();
         if !(1900..=9999).contains(&self.year()) {
@@ -686,7 +830,9 @@ impl Time {
         if !(1..=12).contains(&self.month()) {
             err.month = true;
         }
-        if !(1..=31).contains(&self.day()) {
+        if !(1..=31).contains(&self.day())
+            || (!err.year && !err.month && self.day() > days_in_month(self.year(), self.month()))
+        {
             err.day = true;
         }
         if self.hour() > 23 {
@@ -704,6 +850,9 @@ impl Time {
         if self.time_zone().is_some() && !((-1440..=1440).contains(&self.time_zone().unwrap())) {
             err.timezone = true;
         }
+        if Daylight::from_bits(self.daylight().bits()).is_none() {
+            err.daylight = true;
+        }
         // All fields are false, i.e., within their valid range.
         if err == TimeError::default() {
             Ok(())
@@ -769,6 +918,97 @@ impl Time {
     pub const fn daylight(&self) -> Daylight {
         self.0.daylight
     }
+
+    /// Convert the time to the number of seconds since the Unix epoch
+    /// (1970-01-01 00:00:00 UTC). The nanosecond field is ignored.
+    ///
+    /// The time is converted to UTC by subtracting the
+    /// [`time_zone`] offset. A time in local time (an unspecified
+    /// time zone) is treated as if it were UTC, since the offset of
+    /// the local time zone is not known. Leap seconds are ignored.
+    ///
+    /// Returns `None` if the time is not valid, see [`Time::is_valid`].
+    ///
+    /// [`time_zone`]: Self::time_zone
+    #[must_use]
+    pub fn to_unix_secs(&self) -> Option<i64> {
+        self.is_valid().ok()?;
+
+        let days = days_since_unix_epoch(self.year(), self.month(), self.day());
+        let secs = days * 86400
+            + i64::from(self.hour()) * 3600
+            + i64::from(self.minute()) * 60
+            + i64::from(self.second());
+        let offset = i64::from(self.time_zone().unwrap_or(0)) * 60;
+        Some(secs - offset)
+    }
+
+    /// Key used for ordering. Times are ordered by their UTC instant, and
+    /// then by their fields so that the order is consistent with `Eq`.
+    fn cmp_key(&self) -> (Option<(i64, u32)>, [i64; 9]) {
+        let instant = self.to_unix_secs().map(|secs| (secs, self.nanosecond()));
+        let fields = [
+            i64::from(self.year()),
+            i64::from(self.month()),
+            i64::from(self.day()),
+            i64::from(self.hour()),
+            i64::from(self.minute()),
+            i64::from(self.second()),
+            i64::from(self.nanosecond()),
+            i64::from(self.0.time_zone),
+            i64::from(self.daylight().bits()),
+        ];
+        (instant, fields)
+    }
+}
+
+/// Whether `year` is a leap year in the Gregorian calendar.
+const fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in `month` (`1..=12`) of `year`.
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days between 1970-01-01 and the given date, which must be
+/// valid. Negative for dates before 1970.
+fn days_since_unix_epoch(year: u16, month: u8, day: u8) -> i64 {
+    /// Cumulative number of days before each month in a non-leap year.
+    const DAYS_BEFORE_MONTH: [i64; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+    // Number of leap years in `1..=y`.
+    let leap_years = |y: i64| y / 4 - y / 100 + y / 400;
+
+    let y = i64::from(year);
+    let mut days = (y - 1970) * 365 + leap_years(y - 1) - leap_years(1969);
+    days += DAYS_BEFORE_MONTH[usize::from(month - 1)];
+    if month > 2 && is_leap_year(year) {
+        days += 1;
+    }
+    days + i64::from(day) - 1
+}
+
+/// Times are ordered by the instant they represent, as returned by
+/// [`Time::to_unix_secs`] and [`Time::nanosecond`]. Times that represent
+/// the same instant in different time zones are ordered by their fields.
+/// Times that are not valid are ordered before all valid times.
+impl Ord for Time {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_key().cmp(&other.cmp_key())
+    }
+}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Debug for Time {
@@ -926,3 +1166,163 @@ pub struct CapsuleInfo {
     /// The type of reset required for the capsule update.
     pub reset_type: ResetType,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn try_time(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        time_zone: Option<i16>,
+    ) -> core::result::Result<Time, TimeError> {
+        Time::new(TimeParams {
+            year,
+            month,
+            day,
+            hour,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+            time_zone,
+            daylight: Daylight::empty(),
+        })
+    }
+
+    fn time(year: u16, month: u8, day: u8, hour: u8, time_zone: Option<i16>) -> Time {
+        try_time(year, month, day, hour, time_zone).unwrap()
+    }
+
+    #[test]
+    fn test_to_unix_secs() {
+        assert_eq!(time(1970, 1, 1, 0, None).to_unix_secs(), Some(0));
+        assert_eq!(time(1970, 1, 1, 0, Some(0)).to_unix_secs(), Some(0));
+        assert_eq!(
+            time(2000, 3, 1, 0, Some(0)).to_unix_secs(),
+            Some(951_868_800)
+        );
+        assert_eq!(
+            time(2024, 2, 29, 12, Some(0)).to_unix_secs(),
+            Some(1_709_208_000)
+        );
+        assert_eq!(
+            time(1900, 1, 1, 0, Some(0)).to_unix_secs(),
+            Some(-2_208_988_800)
+        );
+        assert_eq!(
+            time(9999, 12, 31, 0, Some(0)).to_unix_secs(),
+            Some(253_402_214_400)
+        );
+
+        // Time zone offsets are subtracted to get UTC.
+        assert_eq!(time(1970, 1, 1, 1, Some(60)).to_unix_secs(), Some(0));
+        assert_eq!(time(1970, 1, 1, 0, Some(-60)).to_unix_secs(), Some(3600));
+
+        assert_eq!(Time::invalid().to_unix_secs(), None);
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let day_err = TimeError {
+            day: true,
+            ..Default::default()
+        };
+        assert_eq!(try_time(2023, 2, 29, 0, None), Err(day_err));
+        assert_eq!(try_time(1900, 2, 29, 0, None), Err(day_err));
+        assert_eq!(try_time(2023, 4, 31, 0, None), Err(day_err));
+        assert_eq!(try_time(2023, 4, 32, 0, None), Err(day_err));
+        assert!(try_time(2000, 2, 29, 0, None).is_ok());
+        assert!(try_time(2023, 12, 31, 0, None).is_ok());
+
+        // An invalid month is not also reported as an invalid day.
+        assert_eq!(
+            try_time(2023, 13, 31, 0, None),
+            Err(TimeError {
+                month: true,
+                ..Default::default()
+            })
+        );
+
+        let mut time = time(2023, 1, 1, 0, None);
+        time.0.daylight = Daylight::from_bits_retain(0x80);
+        assert_eq!(
+            time.is_valid(),
+            Err(TimeError {
+                daylight: true,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_ord() {
+        let a = time(2024, 1, 1, 0, Some(0));
+        let b = time(2024, 1, 1, 1, Some(120));
+        let c = time(2024, 1, 1, 1, Some(60));
+        assert!(b < a);
+        assert!(a < time(2024, 1, 2, 0, Some(0)));
+        assert!(Time::invalid() < b);
+
+        // Same instant, different time zones.
+        assert_eq!(a.to_unix_secs(), c.to_unix_secs());
+        assert_ne!(a, c);
+        assert_ne!(a.cmp(&c), Ordering::Equal);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_variable_parse_ints() {
+        let attrs = VariableAttributes::empty();
+        assert_eq!(u8::parse(&[7], attrs), Ok(7));
+        assert_eq!(u16::parse(&[0x34, 0x12], attrs), Ok(0x1234));
+        assert_eq!(u32::parse(&[1, 0, 0, 0], attrs), Ok(1));
+        assert_eq!(u64::parse(&[0xff; 8], attrs), Ok(u64::MAX));
+        assert_eq!(
+            u32::parse(&[1, 0], attrs).unwrap_err().status(),
+            Status::BAD_BUFFER_SIZE
+        );
+        assert_eq!(
+            u8::parse(&[], attrs).unwrap_err().status(),
+            Status::BAD_BUFFER_SIZE
+        );
+    }
+
+    #[test]
+    fn test_variable_parse_bool() {
+        let attrs = VariableAttributes::empty();
+        assert_eq!(bool::parse(&[0], attrs), Ok(false));
+        assert_eq!(bool::parse(&[1], attrs), Ok(true));
+        assert_eq!(
+            bool::parse(&[2], attrs).unwrap_err().status(),
+            Status::INVALID_PARAMETER
+        );
+        assert_eq!(
+            bool::parse(&[1, 0], attrs).unwrap_err().status(),
+            Status::BAD_BUFFER_SIZE
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_variable_parse_string() {
+        let attrs = VariableAttributes::empty();
+        assert_eq!(
+            CString16::parse(&[b'h', 0, b'i', 0, 0, 0], attrs).unwrap(),
+            crate::cstr16!("hi")
+        );
+        assert_eq!(
+            CString16::parse(&[b'h', 0, b'i'], attrs)
+                .unwrap_err()
+                .status(),
+            Status::BAD_BUFFER_SIZE
+        );
+        // Missing null terminator.
+        assert_eq!(
+            CString16::parse(&[b'h', 0], attrs).unwrap_err().status(),
+            Status::INVALID_PARAMETER
+        );
+        assert_eq!(&*Box::<[u8]>::parse(&[1, 2, 3], attrs).unwrap(), &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,363 @@
+//! Boot manager load options.
+//!
+//! The boot manager is configured through a set of global variables. Each
+//! `Boot####` variable (where `####` is a four-digit uppercase hex number)
+//! contains an `EFI_LOAD_OPTION`, represented here by [`LoadOption`]. The
+//! `BootOrder` variable contains the list of option numbers in the order the
+//! boot manager should attempt them.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::runtime::load_option::{self, LoadOption};
+//! use uefi::runtime::{self, VariableVendor};
+//!
+//! # fn example() -> Result<(), Box<dyn core::error::Error>> {
+//! for number in load_option::boot_order()? {
+//!     let name = load_option::boot_option_name(number);
+//!     let (data, _) = runtime::get_variable_boxed(&name, &VariableVendor::GLOBAL_VARIABLE)?;
+//!     let option = LoadOption::try_from(&*data)?;
+//!     log::info!("{name}: {}", option.description);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{get_variable_boxed, set_variable, VariableAttributes, VariableVendor};
+use crate::proto::device_path::DevicePath;
+use crate::{cstr16, CStr16, CString16, Result, Status};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use bitflags::bitflags;
+use core::fmt::{self, Display, Formatter};
+
+bitflags! {
+    /// Attributes of a [`LoadOption`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct LoadOptionAttributes: u32 {
+        /// The boot manager will attempt to boot this option automatically.
+        const ACTIVE = 0x0000_0001;
+
+        /// All UEFI drivers in the system are disconnected and reconnected
+        /// after the last driver load option is processed.
+        const FORCE_RECONNECT = 0x0000_0002;
+
+        /// The option is not shown in menus provided by the boot manager.
+        const HIDDEN = 0x0000_0008;
+
+        /// The option is an application, such as a diagnostic tool, rather
+        /// than a boot option. If no category bits are set, the option is a
+        /// boot option.
+        const CATEGORY_APP = 0x0000_0100;
+
+        /// Mask of the bits used for the option category.
+        const CATEGORY = 0x0000_1f00;
+    }
+}
+
+/// Error returned when parsing a [`LoadOption`] from bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadOptionError {
+    /// The buffer is too small to hold the fixed-size header.
+    BufferTooSmall,
+
+    /// The description is not a valid null-terminated UCS-2 string.
+    InvalidDescription,
+
+    /// The file path list length extends past the end of the buffer.
+    InvalidFilePathListLength,
+
+    /// The file path list does not consist of valid device paths.
+    InvalidDevicePath,
+}
+
+impl Display for LoadOptionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let s = match self {
+            Self::BufferTooSmall => "buffer too small for load option header",
+            Self::InvalidDescription => "invalid load option description",
+            Self::InvalidFilePathListLength => "file path list length exceeds buffer",
+            Self::InvalidDevicePath => "invalid device path in load option",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for LoadOptionError {}
+
+/// Owned representation of an `EFI_LOAD_OPTION`.
+///
+/// The UEFI Specification allows the file path list to contain more than one
+/// device path. The first path is the image loaded by the boot manager; the
+/// meaning of any further paths is specific to the creator of the option.
+/// All paths are retained so that an option can be modified and written back
+/// without losing them.
+#[derive(Debug, Eq, PartialEq)]
+pub struct LoadOption {
+    /// Attributes of the load option.
+    pub attributes: LoadOptionAttributes,
+
+    /// Human-readable description of the load option.
+    pub description: CString16,
+
+    /// Device path of the image to load.
+    pub file_path: Box<DevicePath>,
+
+    /// Device paths following [`file_path`] in the file path list.
+    ///
+    /// [`file_path`]: Self::file_path
+    pub additional_file_paths: Vec<Box<DevicePath>>,
+
+    /// Data passed to the loaded image.
+    pub optional_data: Vec<u8>,
+}
+
+impl LoadOption {
+    /// Size of the fixed part of the load option: the attributes (`u32`) and
+    /// the file path list length (`u16`).
+    const HEADER_SIZE: usize = 6;
+
+    /// Serialize the load option to the `EFI_LOAD_OPTION` format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file path list is larger than `u16::MAX` bytes.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let file_paths = || {
+            core::iter::once(&self.file_path)
+                .chain(&self.additional_file_paths)
+                .map(|path| path.as_bytes())
+        };
+        let file_path_list_len = file_paths().map(<[u8]>::len).sum::<usize>();
+        let file_path_list_len_u16 =
+            u16::try_from(file_path_list_len).expect("file path list length must fit in a u16");
+
+        let mut bytes = Vec::with_capacity(
+            Self::HEADER_SIZE
+                + self.description.num_bytes()
+                + file_path_list_len
+                + self.optional_data.len(),
+        );
+        bytes.extend_from_slice(&self.attributes.bits().to_le_bytes());
+        bytes.extend_from_slice(&file_path_list_len_u16.to_le_bytes());
+        for c in self.description.to_u16_slice_with_nul() {
+            bytes.extend_from_slice(&c.to_le_bytes());
+        }
+        for path in file_paths() {
+            bytes.extend_from_slice(path);
+        }
+        bytes.extend_from_slice(&self.optional_data);
+        bytes
+    }
+}
+
+impl Clone for LoadOption {
+    fn clone(&self) -> Self {
+        Self {
+            attributes: self.attributes,
+            description: self.description.clone(),
+            file_path: self.file_path.to_boxed(),
+            additional_file_paths: self
+                .additional_file_paths
+                .iter()
+                .map(|path| path.to_boxed())
+                .collect(),
+            optional_data: self.optional_data.clone(),
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for LoadOption {
+    type Error = LoadOptionError;
+
+    fn try_from(bytes: &[u8]) -> core::result::Result<Self, Self::Error> {
+        if bytes.len() < Self::HEADER_SIZE {
+            return Err(LoadOptionError::BufferTooSmall);
+        }
+        let attributes = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let file_path_len = usize::from(u16::from_le_bytes(bytes[4..6].try_into().unwrap()));
+        let mut rest = &bytes[Self::HEADER_SIZE..];
+
+        // The description is a null-terminated UCS-2 string. It is not
+        // necessarily aligned, so decode it one character at a time.
+        let mut description = Vec::new();
+        loop {
+            let Some((c, tail)) = rest.split_first_chunk::<2>() else {
+                return Err(LoadOptionError::InvalidDescription);
+            };
+            rest = tail;
+            let c = u16::from_le_bytes(*c);
+            description.push(c);
+            if c == 0 {
+                break;
+            }
+        }
+        let description =
+            CString16::try_from(description).map_err(|_| LoadOptionError::InvalidDescription)?;
+
+        if file_path_len > rest.len() {
+            return Err(LoadOptionError::InvalidFilePathListLength);
+        }
+        let (file_path_list, optional_data) = rest.split_at(file_path_len);
+
+        let (file_path, mut rest) = split_device_path(file_path_list)?;
+        let mut additional_file_paths = Vec::new();
+        while !rest.is_empty() {
+            let (path, tail) = split_device_path(rest)?;
+            additional_file_paths.push(path.to_boxed());
+            rest = tail;
+        }
+
+        Ok(Self {
+            attributes: LoadOptionAttributes::from_bits_retain(attributes),
+            description,
+            file_path: file_path.to_boxed(),
+            additional_file_paths,
+            optional_data: optional_data.to_vec(),
+        })
+    }
+}
+
+/// Split the device path at the start of `bytes` from the rest. Each path in
+/// a file path list ends with an end-of-entire-path node.
+fn split_device_path(bytes: &[u8]) -> core::result::Result<(&DevicePath, &[u8]), LoadOptionError> {
+    let path = <&DevicePath>::try_from(bytes).map_err(|_| LoadOptionError::InvalidDevicePath)?;
+    Ok((path, &bytes[path.as_bytes().len()..]))
+}
+
+/// Name of the variable containing the boot order.
+const BOOT_ORDER: &CStr16 = cstr16!("BootOrder");
+
+/// Get the name of the `Boot####` variable for the load option `number`.
+#[must_use]
+pub fn boot_option_name(number: u16) -> CString16 {
+    CString16::try_from(format!("Boot{number:04X}").as_str())
+        .expect("boot option name is valid UCS-2")
+}
+
+/// Read the `BootOrder` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the `BootOrder` variable does not exist.
+/// * [`Status::BAD_BUFFER_SIZE`]: the variable size is not a multiple of two.
+/// * [`Status::DEVICE_ERROR`]: the variable could not be read due to a
+///   hardware error.
+pub fn boot_order() -> Result<Vec<u16>> {
+    let (data, _) = get_variable_boxed(BOOT_ORDER, &VariableVendor::GLOBAL_VARIABLE)?;
+    if data.len() % 2 != 0 {
+        return Err(Status::BAD_BUFFER_SIZE.into());
+    }
+    Ok(data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect())
+}
+
+/// Write the `BootOrder` variable.
+///
+/// # Errors
+///
+/// See [`set_variable`] for the possible errors.
+pub fn set_boot_order(order: &[u16]) -> Result {
+    let data: Vec<u8> = order.iter().flat_map(|n| n.to_le_bytes()).collect();
+    set_variable(
+        BOOT_ORDER,
+        &VariableVendor::GLOBAL_VARIABLE,
+        VariableAttributes::NON_VOLATILE
+            | VariableAttributes::BOOTSERVICE_ACCESS
+            | VariableAttributes::RUNTIME_ACCESS,
+        &data,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const END_ENTIRE: [u8; 4] = [0x7f, 0xff, 0x04, 0x00];
+
+    fn option_bytes(file_path_len: u16) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&file_path_len.to_le_bytes());
+        // "ab\0"
+        bytes.extend_from_slice(&[b'a', 0, b'b', 0, 0, 0]);
+        bytes.extend_from_slice(&END_ENTIRE);
+        bytes.extend_from_slice(&[1, 2, 3]);
+        bytes
+    }
+
+    #[test]
+    fn test_load_option_round_trip() {
+        let bytes = option_bytes(4);
+        let option = LoadOption::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(option.attributes, LoadOptionAttributes::ACTIVE);
+        assert_eq!(option.description, cstr16!("ab"));
+        assert_eq!(option.file_path.as_bytes(), END_ENTIRE);
+        assert_eq!(option.optional_data, [1, 2, 3]);
+        assert_eq!(option.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_load_option_multiple_file_paths() {
+        const END_INSTANCE: [u8; 4] = [0x7f, 0x01, 0x04, 0x00];
+
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&12u16.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        // A multi-instance path followed by a single-instance path.
+        bytes.extend_from_slice(&END_INSTANCE);
+        bytes.extend_from_slice(&END_ENTIRE);
+        bytes.extend_from_slice(&END_ENTIRE);
+
+        let option = LoadOption::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(
+            option.file_path.as_bytes(),
+            [END_INSTANCE, END_ENTIRE].concat()
+        );
+        assert_eq!(option.additional_file_paths.len(), 1);
+        assert_eq!(option.additional_file_paths[0].as_bytes(), END_ENTIRE);
+        assert!(option.optional_data.is_empty());
+        assert_eq!(option.to_bytes(), bytes);
+        assert_eq!(option.clone(), option);
+
+        // Trailing bytes that are not a device path are rejected.
+        bytes[4] = 14;
+        bytes.extend_from_slice(&[0x7f, 0xff]);
+        assert_eq!(
+            LoadOption::try_from(bytes.as_slice()),
+            Err(LoadOptionError::InvalidDevicePath)
+        );
+    }
+
+    #[test]
+    fn test_load_option_invalid() {
+        assert_eq!(
+            LoadOption::try_from([0u8; 5].as_slice()),
+            Err(LoadOptionError::BufferTooSmall)
+        );
+        assert_eq!(
+            LoadOption::try_from(&option_bytes(4)[..9]),
+            Err(LoadOptionError::InvalidDescription)
+        );
+        assert_eq!(
+            LoadOption::try_from(option_bytes(8).as_slice()),
+            Err(LoadOptionError::InvalidFilePathListLength)
+        );
+        assert_eq!(
+            LoadOption::try_from(option_bytes(2).as_slice()),
+            Err(LoadOptionError::InvalidDevicePath)
+        );
+    }
+
+    #[test]
+    fn test_boot_option_name() {
+        assert_eq!(boot_option_name(0x1a), cstr16!("Boot001A"));
+    }
+}
@@ -10,7 +10,9 @@
 use crate::proto::console::text::{Input, Output};
 use crate::table::cfg::ConfigTableEntry;
 use crate::table::{self, Revision};
-use crate::{CStr16, Char16};
+use crate::{CStr16, Char16, Guid};
+use core::ffi::c_void;
+use core::fmt::{self, Display, Formatter};
 use core::slice;
 
 /// Get the firmware vendor string.
@@ -28,12 +30,54 @@ pub fn firmware_vendor() -> &'static CStr16 {
 
 /// Get the firmware revision.
 #[must_use]
-pub fn firmware_revision() -> u32 {
+pub fn firmware_revision() -> FirmwareRevision {
     let st = table::system_table_raw_panicking();
     // SAFETY: valid per requirements of `set_system_table`.
     let st = unsafe { st.as_ref() };
 
-    st.firmware_revision
+    FirmwareRevision(st.firmware_revision)
+}
+
+/// Revision of the firmware, as returned by [`firmware_revision`].
+///
+/// The meaning of the revision is defined by the firmware vendor. By
+/// convention, the upper 16 bits are the major revision and the lower 16
+/// bits are the minor revision, and the revision is displayed as
+/// `major.minor`:
+///
+/// ```
+/// # use uefi::system::FirmwareRevision;
+/// assert_eq!(FirmwareRevision(0x0001_0000).to_string(), "1.0");
+/// assert_eq!(FirmwareRevision(0x0005_0012).to_string(), "5.18");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct FirmwareRevision(pub u32);
+
+impl FirmwareRevision {
+    /// Returns the major revision.
+    #[must_use]
+    pub const fn major(self) -> u16 {
+        (self.0 >> 16) as u16
+    }
+
+    /// Returns the minor revision.
+    #[must_use]
+    pub const fn minor(self) -> u16 {
+        self.0 as u16
+    }
+
+    /// Returns the revision as the raw value from the system table.
+    #[must_use]
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for FirmwareRevision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major(), self.minor())
+    }
 }
 
 /// Get the revision of the system table, which is defined to be the revision of
@@ -67,6 +111,22 @@ where
     f(slice)
 }
 
+/// Get the address of the vendor-specific table identified by `guid`, or
+/// `None` if the table is not present or its address is null. See
+/// [`table::cfg`] for the GUIDs of common tables such as ACPI and SMBIOS.
+///
+/// Use [`with_config_table`] to iterate over all entries.
+#[must_use]
+pub fn find_config_table(guid: &Guid) -> Option<*const c_void> {
+    let address = with_config_table(|entries| {
+        entries
+            .iter()
+            .find(|entry| entry.guid == *guid)
+            .map(|entry| entry.address)
+    })?;
+    (!address.is_null()).then_some(address)
+}
+
 /// Call `f` with the [`Input`] protocol attached to stdin.
 ///
 /// # Panics
@@ -7,9 +7,11 @@
 //! This module contains the actual entries of the configuration table,
 //! as well as GUIDs for many known vendor tables.
 
-use crate::{guid, Guid};
+use crate::{guid, system, Guid};
 use bitflags::bitflags;
 use core::ffi::c_void;
+use core::fmt::{self, Debug, Formatter};
+use core::slice;
 
 /// Contains a set of GUID / pointer for a vendor-specific table.
 ///
@@ -36,6 +38,393 @@ pub const SMBIOS_GUID: Guid = guid!("eb9d2d31-2d88-11d3-9a16-0090273fc14d");
 /// Entry pointing to the SMBIOS 3.0 table.
 pub const SMBIOS3_GUID: Guid = guid!("f2fd1544-9794-4a2c-992e-e5bbcf20e394");
 
+/// SMBIOS 3.0 entry point structure, found via the [`SMBIOS3_GUID`]
+/// configuration table.
+///
+/// Use [`smbios3_table`] to get the entry point, and [`Smbios3::structures`]
+/// to iterate over the structures in the table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Smbios3 {
+    major_version: u8,
+    minor_version: u8,
+    docrev: u8,
+    table_max_size: u32,
+    table_address: u64,
+}
+
+impl Smbios3 {
+    /// Anchor string at the start of the entry point structure.
+    const ANCHOR: &'static [u8; 5] = b"_SM3_";
+
+    /// Size of the entry point structure in SMBIOS 3.0.
+    const MIN_LEN: usize = 0x18;
+
+    /// Parse an entry point structure, validating the anchor string and the
+    /// checksum. `bytes` must be the length given by the structure itself.
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::MIN_LEN || !bytes.starts_with(Self::ANCHOR) {
+            return None;
+        }
+        if usize::from(bytes[6]) != bytes.len() {
+            return None;
+        }
+        if !checksum_is_valid(bytes) {
+            return None;
+        }
+
+        Some(Self {
+            major_version: bytes[7],
+            minor_version: bytes[8],
+            docrev: bytes[9],
+            table_max_size: u32::from_le_bytes(bytes[0xc..0x10].try_into().unwrap()),
+            table_address: u64::from_le_bytes(bytes[0x10..0x18].try_into().unwrap()),
+        })
+    }
+
+    /// Major version of the SMBIOS specification implemented by the table.
+    #[must_use]
+    pub const fn major_version(&self) -> u8 {
+        self.major_version
+    }
+
+    /// Minor version of the SMBIOS specification implemented by the table.
+    #[must_use]
+    pub const fn minor_version(&self) -> u8 {
+        self.minor_version
+    }
+
+    /// Revision of the SMBIOS specification implemented by the table.
+    #[must_use]
+    pub const fn docrev(&self) -> u8 {
+        self.docrev
+    }
+
+    /// Physical address of the structure table.
+    #[must_use]
+    pub const fn table_address(&self) -> u64 {
+        self.table_address
+    }
+
+    /// Maximum size of the structure table in bytes. The actual table may be
+    /// smaller; it ends with an end-of-table structure (type 127).
+    #[must_use]
+    pub const fn table_max_size(&self) -> u32 {
+        self.table_max_size
+    }
+
+    /// Returns an iterator over the structures in the structure table.
+    ///
+    /// # Safety
+    ///
+    /// The structure table must be readable at [`table_address`] for
+    /// [`table_max_size`] bytes, and must not be modified while the iterator
+    /// or the structures it returns are in use. This holds for an entry point
+    /// returned by [`smbios3_table`] as long as the firmware's identity
+    /// mapping of memory is in place and nothing writes to the table.
+    ///
+    /// [`table_address`]: Self::table_address
+    /// [`table_max_size`]: Self::table_max_size
+    #[must_use]
+    pub const unsafe fn structures(&self) -> SmbiosStructures<'static> {
+        // SAFETY: the caller guarantees that the table is readable and not
+        // modified.
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                self.table_address as *const u8,
+                self.table_max_size as usize,
+            )
+        };
+        SmbiosStructures { bytes }
+    }
+}
+
+/// Get the SMBIOS 3.0 entry point from the configuration table.
+///
+/// Returns `None` if there is no SMBIOS 3.0 table, or if its anchor string,
+/// length, or checksum is invalid.
+#[must_use]
+pub fn smbios3_table() -> Option<Smbios3> {
+    let address = system::find_config_table(&SMBIOS3_GUID)?.cast::<u8>();
+
+    // The structure's length is stored at offset 6. Read the minimum size
+    // first to validate the anchor, then the full structure.
+    let header = unsafe { slice::from_raw_parts(address, Smbios3::MIN_LEN) };
+    if !header.starts_with(Smbios3::ANCHOR) {
+        return None;
+    }
+    let len = usize::from(header[6]);
+    Smbios3::parse(unsafe { slice::from_raw_parts(address, len) })
+}
+
+/// A structure in the SMBIOS structure table.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SmbiosStructure<'a> {
+    formatted: &'a [u8],
+    strings: &'a [u8],
+}
+
+impl<'a> SmbiosStructure<'a> {
+    /// Structure type indicating the end of the structure table.
+    pub const END_OF_TABLE: u8 = 127;
+
+    /// Type of the structure, e.g. 0 for BIOS information.
+    #[must_use]
+    pub const fn header_type(&self) -> u8 {
+        self.formatted[0]
+    }
+
+    /// Handle of the structure, which other structures can use to refer to
+    /// it.
+    #[must_use]
+    pub const fn handle(&self) -> u16 {
+        u16::from_le_bytes([self.formatted[2], self.formatted[3]])
+    }
+
+    /// The formatted area of the structure, including the 4-byte header.
+    #[must_use]
+    pub const fn formatted(&self) -> &'a [u8] {
+        self.formatted
+    }
+
+    /// The string set following the formatted area, without the
+    /// terminating double null. Each string is null-terminated.
+    #[must_use]
+    pub const fn string_set(&self) -> &'a [u8] {
+        self.strings
+    }
+
+    /// Get a string from the string set by its index, as used in the
+    /// formatted area. Indices start at 1; 0 means no string.
+    #[must_use]
+    pub fn string(&self, index: u8) -> Option<&'a [u8]> {
+        let index = usize::from(index).checked_sub(1)?;
+        self.strings.split(|b| *b == 0).nth(index)
+    }
+}
+
+impl Debug for SmbiosStructure<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmbiosStructure")
+            .field("header_type", &self.header_type())
+            .field("handle", &self.handle())
+            .field("formatted_len", &self.formatted.len())
+            .field("string_set_len", &self.strings.len())
+            .finish()
+    }
+}
+
+/// Iterator over the structures in an SMBIOS structure table.
+///
+/// Iteration stops after the end-of-table structure, at the end of the
+/// table, or at the first malformed structure.
+#[derive(Clone, Debug)]
+pub struct SmbiosStructures<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for SmbiosStructures<'a> {
+    type Item = SmbiosStructure<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = core::mem::take(&mut self.bytes);
+
+        // The header is the type, the length of the formatted area, and the
+        // handle.
+        let len = usize::from(*bytes.get(1)?);
+        if len < 4 || len > bytes.len() {
+            return None;
+        }
+        let (formatted, rest) = bytes.split_at(len);
+
+        // The string set ends with a double null. If the structure has no
+        // strings, the set is just the double null.
+        let end = rest.windows(2).position(|w| w == [0, 0])?;
+        let structure = SmbiosStructure {
+            formatted,
+            strings: &rest[..end],
+        };
+
+        if structure.header_type() != SmbiosStructure::END_OF_TABLE {
+            self.bytes = &rest[end + 2..];
+        }
+        Some(structure)
+    }
+}
+
+impl core::iter::FusedIterator for SmbiosStructures<'_> {}
+
+/// ACPI Root System Description Pointer (RSDP), found via the [`ACPI2_GUID`]
+/// configuration table.
+///
+/// Use [`acpi2_rsdp`] to get the RSDP, and [`Rsdp::xsdt`] to iterate over the
+/// addresses of the ACPI tables.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rsdp {
+    oem_id: [u8; 6],
+    revision: u8,
+    rsdt_address: u32,
+    xsdt_address: u64,
+}
+
+impl Rsdp {
+    /// Signature at the start of the RSDP.
+    const SIGNATURE: &'static [u8; 8] = b"RSD PTR ";
+
+    /// Size of the ACPI 1.0 part of the RSDP, covered by the first checksum.
+    const V1_LEN: usize = 20;
+
+    /// Size of the RSDP in ACPI 2.0 and later.
+    const V2_LEN: usize = 36;
+
+    /// Parse an ACPI 2.0 RSDP, validating the signature and both checksums.
+    /// `bytes` must be the length given by the structure itself.
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::V2_LEN || !bytes.starts_with(Self::SIGNATURE) {
+            return None;
+        }
+        let revision = bytes[15];
+        let length = u32::from_le_bytes(bytes[20..24].try_into().unwrap());
+        if revision < 2 || length as usize != bytes.len() {
+            return None;
+        }
+        if !checksum_is_valid(&bytes[..Self::V1_LEN]) || !checksum_is_valid(bytes) {
+            return None;
+        }
+
+        Some(Self {
+            oem_id: bytes[9..15].try_into().unwrap(),
+            revision,
+            rsdt_address: u32::from_le_bytes(bytes[16..20].try_into().unwrap()),
+            xsdt_address: u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+        })
+    }
+
+    /// OEM-supplied identifier.
+    #[must_use]
+    pub const fn oem_id(&self) -> [u8; 6] {
+        self.oem_id
+    }
+
+    /// Revision of the RSDP. ACPI 2.0 and later use revision 2.
+    #[must_use]
+    pub const fn revision(&self) -> u8 {
+        self.revision
+    }
+
+    /// Physical address of the 32-bit Root System Description Table (RSDT).
+    #[must_use]
+    pub const fn rsdt_address(&self) -> u32 {
+        self.rsdt_address
+    }
+
+    /// Physical address of the Extended System Description Table (XSDT).
+    #[must_use]
+    pub const fn xsdt_address(&self) -> u64 {
+        self.xsdt_address
+    }
+
+    /// Returns an iterator over the physical addresses of the tables listed
+    /// in the XSDT, such as the MADT and FADT.
+    ///
+    /// Returns `None` if the XSDT address is null, or if the XSDT's signature
+    /// or checksum is invalid.
+    ///
+    /// # Safety
+    ///
+    /// If [`xsdt_address`] is not null, a complete XSDT must be readable at
+    /// that address, and must not be modified while the iterator is in use.
+    /// This holds for an RSDP returned by [`acpi2_rsdp`] as long as the
+    /// firmware's identity mapping of memory is in place and nothing writes
+    /// to the table.
+    ///
+    /// [`xsdt_address`]: Self::xsdt_address
+    #[must_use]
+    pub unsafe fn xsdt(&self) -> Option<XsdtEntries<'static>> {
+        let address = self.xsdt_address as *const u8;
+        if address.is_null() {
+            return None;
+        }
+
+        // Read the header first to get the length of the whole table.
+        let header = unsafe { slice::from_raw_parts(address, XsdtEntries::HEADER_LEN) };
+        let length = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        let bytes = unsafe { slice::from_raw_parts(address, length as usize) };
+        XsdtEntries::parse(bytes)
+    }
+}
+
+/// Get the ACPI 2.0 RSDP from the configuration table.
+///
+/// Returns `None` if there is no ACPI 2.0 table, or if the RSDP's signature,
+/// revision, length, or either checksum is invalid.
+#[must_use]
+pub fn acpi2_rsdp() -> Option<Rsdp> {
+    let address = system::find_config_table(&ACPI2_GUID)?.cast::<u8>();
+
+    // Read the ACPI 1.0 part first to validate the signature, then the full
+    // structure using the length at offset 20.
+    let header = unsafe { slice::from_raw_parts(address, Rsdp::V2_LEN) };
+    if !header.starts_with(Rsdp::SIGNATURE) {
+        return None;
+    }
+    let length = u32::from_le_bytes(header[20..24].try_into().unwrap());
+    Rsdp::parse(unsafe { slice::from_raw_parts(address, length as usize) })
+}
+
+/// Iterator over the table addresses in the ACPI Extended System Description
+/// Table (XSDT).
+#[derive(Clone, Debug)]
+pub struct XsdtEntries<'a> {
+    entries: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> XsdtEntries<'a> {
+    /// Size of the system description table header.
+    const HEADER_LEN: usize = 36;
+
+    /// Parse an XSDT, validating the signature, length, and checksum.
+    /// `bytes` must be the length given by the table header.
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < Self::HEADER_LEN || !bytes.starts_with(b"XSDT") {
+            return None;
+        }
+        let length = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if length as usize != bytes.len() || !checksum_is_valid(bytes) {
+            return None;
+        }
+
+        // The entries are not necessarily 8-byte aligned. A trailing partial
+        // entry is ignored.
+        Some(Self {
+            entries: bytes[Self::HEADER_LEN..].chunks_exact(8),
+        })
+    }
+}
+
+impl Iterator for XsdtEntries<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.entries
+            .next()
+            .map(|entry| u64::from_le_bytes(entry.try_into().unwrap()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl ExactSizeIterator for XsdtEntries<'_> {}
+
+impl core::iter::FusedIterator for XsdtEntries<'_> {}
+
+/// Check that all bytes of a table add up to zero, as required for SMBIOS and
+/// ACPI checksums.
+fn checksum_is_valid(bytes: &[u8]) -> bool {
+    bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) == 0
+}
+
 /// Entry pointing to the EFI System Resource table (ESRT).
 pub const ESRT_GUID: Guid = guid!("b122a263-3661-4f68-9929-78f8b0d62180");
 
@@ -95,3 +484,125 @@ pub const TIANO_COMPRESS_GUID: Guid = guid!("a31280ad-481e-41b6-95e8-127f4c98477
 
 /// Pointer to the debug image info table.
 pub const DEBUG_IMAGE_INFO_GUID: Guid = guid!("49152e77-1ada-4764-b7a2-7afefed95e8b");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_smbios3_parse() {
+        let mut entry = [0u8; 0x18];
+        entry[..5].copy_from_slice(b"_SM3_");
+        entry[6] = 0x18;
+        entry[7] = 3;
+        entry[8] = 4;
+        entry[0xc..0x10].copy_from_slice(&0x100u32.to_le_bytes());
+        entry[0x10..0x18].copy_from_slice(&0x1234_5000u64.to_le_bytes());
+        entry[5] = 0u8.wrapping_sub(entry.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)));
+
+        let smbios = Smbios3::parse(&entry).unwrap();
+        assert_eq!(smbios.major_version(), 3);
+        assert_eq!(smbios.minor_version(), 4);
+        assert_eq!(smbios.table_max_size(), 0x100);
+        assert_eq!(smbios.table_address(), 0x1234_5000);
+
+        // Bad checksum.
+        entry[5] = entry[5].wrapping_add(1);
+        assert!(Smbios3::parse(&entry).is_none());
+    }
+
+    /// Set the checksum byte at `index` so that `bytes` sums to zero.
+    fn fix_checksum(bytes: &mut [u8], index: usize) {
+        bytes[index] = 0;
+        bytes[index] = 0u8.wrapping_sub(bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)));
+    }
+
+    #[test]
+    fn test_rsdp_parse() {
+        let mut rsdp = [0u8; 36];
+        rsdp[..8].copy_from_slice(b"RSD PTR ");
+        rsdp[9..15].copy_from_slice(b"OEMID ");
+        rsdp[15] = 2;
+        rsdp[16..20].copy_from_slice(&0x1000u32.to_le_bytes());
+        rsdp[20..24].copy_from_slice(&36u32.to_le_bytes());
+        rsdp[24..32].copy_from_slice(&0x2000u64.to_le_bytes());
+        fix_checksum(&mut rsdp[..20], 8);
+        fix_checksum(&mut rsdp, 32);
+
+        let parsed = Rsdp::parse(&rsdp).unwrap();
+        assert_eq!(parsed.oem_id(), *b"OEMID ");
+        assert_eq!(parsed.revision(), 2);
+        assert_eq!(parsed.rsdt_address(), 0x1000);
+        assert_eq!(parsed.xsdt_address(), 0x2000);
+
+        // Bad extended checksum.
+        let mut bad = rsdp;
+        bad[32] = bad[32].wrapping_add(1);
+        assert!(Rsdp::parse(&bad).is_none());
+
+        // Bad ACPI 1.0 checksum, with the extended checksum still valid.
+        let mut bad = rsdp;
+        bad[8] = bad[8].wrapping_add(1);
+        bad[32] = bad[32].wrapping_sub(1);
+        assert!(Rsdp::parse(&bad).is_none());
+
+        // Bad signature.
+        let mut bad = rsdp;
+        bad[0] = b'X';
+        assert!(Rsdp::parse(&bad).is_none());
+    }
+
+    #[test]
+    fn test_xsdt_entries() {
+        let mut xsdt = vec![0u8; 36];
+        xsdt[..4].copy_from_slice(b"XSDT");
+        xsdt.extend_from_slice(&0x1000u64.to_le_bytes());
+        xsdt.extend_from_slice(&0x2000u64.to_le_bytes());
+        let len = xsdt.len() as u32;
+        xsdt[4..8].copy_from_slice(&len.to_le_bytes());
+        fix_checksum(&mut xsdt, 9);
+
+        let entries = XsdtEntries::parse(&xsdt).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries.collect::<Vec<_>>(), [0x1000, 0x2000]);
+
+        xsdt[9] = xsdt[9].wrapping_add(1);
+        assert!(XsdtEntries::parse(&xsdt).is_none());
+    }
+
+    #[test]
+    fn test_smbios_structures() {
+        #[rustfmt::skip]
+        let table: &[u8] = &[
+            // Type 0, length 5, handle 0x0001, one formatted byte, two strings.
+            0, 5, 1, 0, 1, b'a', b'b', 0, b'c', 0, 0,
+            // Type 1, length 4, handle 0x0002, no strings.
+            1, 4, 2, 0, 0, 0,
+            // End of table.
+            127, 4, 3, 0, 0, 0,
+            // Trailing bytes that must not be parsed.
+            0xff, 0xff,
+        ];
+        let structures: Vec<_> = SmbiosStructures { bytes: table }.collect();
+        assert_eq!(structures.len(), 3);
+
+        assert_eq!(structures[0].header_type(), 0);
+        assert_eq!(structures[0].handle(), 1);
+        assert_eq!(structures[0].formatted(), [0, 5, 1, 0, 1]);
+        assert_eq!(structures[0].string(1), Some(b"ab".as_slice()));
+        assert_eq!(structures[0].string(2), Some(b"c".as_slice()));
+        assert_eq!(structures[0].string(0), None);
+
+        assert_eq!(structures[1].header_type(), 1);
+        assert_eq!(structures[1].handle(), 2);
+        assert!(structures[1].string_set().is_empty());
+
+        assert_eq!(structures[2].header_type(), SmbiosStructure::END_OF_TABLE);
+
+        // A structure whose string set is not terminated stops iteration.
+        let truncated: &[u8] = &[0, 4, 1, 0, b'a', 0];
+        assert_eq!(SmbiosStructures { bytes: truncated }.count(), 0);
+    }
+}